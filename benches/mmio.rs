@@ -0,0 +1,226 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Benchmarks of MMIO access latency and code size, comparing the backend in use with plain
+//! volatile accesses, and measuring the cost of the projection macros.
+//!
+//! The accesses are to fake registers in ordinary memory, so these measure the overhead added by
+//! the crate rather than the latency of a real device.
+//!
+//! Run with:
+//!
+//! ```sh
+//! cargo bench
+//! ```
+//!
+//! By default latency is measured with `std::time::Instant`. On aarch64 the `cycle-count` feature
+//! instead measures it in ticks of the generic timer's virtual count, which is also available on
+//! bare metal and gives more stable results. Code size is only measured on aarch64.
+
+use safe_mmio::{
+    UniqueMmioPointer, field,
+    fields::{ReadPure, ReadWrite},
+    split_fields,
+};
+use std::hint::black_box;
+
+const ITERATIONS: u64 = 10_000_000;
+
+#[repr(C)]
+struct Registers {
+    control: ReadWrite<u32>,
+    status: ReadPure<u32>,
+    data: [ReadWrite<u64>; 4],
+}
+
+/// Returns the current time in nanoseconds, or ticks of the virtual counter with `cycle-count`.
+#[cfg(not(all(target_arch = "aarch64", feature = "cycle-count")))]
+fn now() -> u64 {
+    use std::{sync::OnceLock, time::Instant};
+
+    static START: OnceLock<Instant> = OnceLock::new();
+    START.get_or_init(Instant::now).elapsed().as_nanos() as u64
+}
+
+/// Returns the current time in nanoseconds, or ticks of the virtual counter with `cycle-count`.
+#[cfg(all(target_arch = "aarch64", feature = "cycle-count"))]
+fn now() -> u64 {
+    let count: u64;
+    // SAFETY: Reading CNTVCT_EL0 has no side-effects, and is allowed at EL0 on Linux.
+    unsafe {
+        core::arch::asm!(
+            "isb",
+            "mrs {count}, cntvct_el0",
+            count = out(reg) count,
+            options(nomem, nostack, preserves_flags),
+        );
+    }
+    count
+}
+
+const UNIT: &str = if cfg!(all(target_arch = "aarch64", feature = "cycle-count")) {
+    "ticks"
+} else {
+    "ns"
+};
+
+/// Runs `f` for `ITERATIONS` iterations and prints the average time taken per iteration.
+fn bench(name: &str, mut f: impl FnMut()) {
+    // Warm up caches and branch predictors.
+    for _ in 0..ITERATIONS / 10 {
+        f();
+    }
+    let start = now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let elapsed = now() - start;
+    println!(
+        "{name:40} {:>10.3} {UNIT}/iter",
+        elapsed as f64 / ITERATIONS as f64
+    );
+}
+
+#[inline(never)]
+fn read_volatile_u32(register: &ReadWrite<u32>) -> u32 {
+    // SAFETY: The pointer comes from a reference, so is valid and aligned.
+    unsafe { (&raw const register.0).read_volatile() }
+}
+
+#[inline(never)]
+fn read_backend_u32(mut register: UniqueMmioPointer<ReadWrite<u32>>) -> u32 {
+    register.read()
+}
+
+#[inline(never)]
+fn write_volatile_u32(register: &mut ReadWrite<u32>, value: u32) {
+    // SAFETY: The pointer comes from a reference, so is valid and aligned.
+    unsafe { (&raw mut register.0).write_volatile(value) }
+}
+
+#[inline(never)]
+fn write_backend_u32(mut register: UniqueMmioPointer<ReadWrite<u32>>, value: u32) {
+    register.write(value);
+}
+
+#[inline(never)]
+fn read_volatile_u64(register: &ReadWrite<u64>) -> u64 {
+    // SAFETY: The pointer comes from a reference, so is valid and aligned.
+    unsafe { (&raw const register.0).read_volatile() }
+}
+
+#[inline(never)]
+fn read_backend_u64(mut register: UniqueMmioPointer<ReadWrite<u64>>) -> u64 {
+    register.read()
+}
+
+#[inline(never)]
+fn read_field(mut regs: UniqueMmioPointer<Registers>) -> u32 {
+    field!(regs, control).read()
+}
+
+#[inline(never)]
+fn read_array_element(mut regs: UniqueMmioPointer<Registers>, index: usize) -> Option<u64> {
+    Some(field!(regs, data).get(index)?.read())
+}
+
+#[inline(never)]
+fn read_split_fields(regs: UniqueMmioPointer<Registers>) -> u32 {
+    // SAFETY: Each field is only passed once.
+    let (mut control, status) = unsafe { split_fields!(regs, control, status) };
+    control.read() ^ status.read()
+}
+
+/// Returns the number of instructions in the function at `f`, up to and including the first `ret`.
+///
+/// # Safety
+///
+/// `f` must be the address of a function, which must end with a `ret` instruction.
+#[cfg(target_arch = "aarch64")]
+unsafe fn instruction_count(f: *const u32) -> usize {
+    const RET: u32 = 0xd65f03c0;
+
+    for i in 0..256 {
+        // SAFETY: The caller promised that `f` points to a function ending with `ret`, and we stop
+        // reading once we find it.
+        if unsafe { f.add(i).read() } == RET {
+            return i + 1;
+        }
+    }
+    panic!("No ret instruction found in function at {f:?}");
+}
+
+#[cfg(target_arch = "aarch64")]
+fn code_size() {
+    println!("\nCode size (instructions, up to the first ret):");
+    let functions: [(&str, *const u32); 9] = [
+        ("read_volatile_u32", read_volatile_u32 as *const u32),
+        ("read_backend_u32", read_backend_u32 as *const u32),
+        ("write_volatile_u32", write_volatile_u32 as *const u32),
+        ("write_backend_u32", write_backend_u32 as *const u32),
+        ("read_volatile_u64", read_volatile_u64 as *const u32),
+        ("read_backend_u64", read_backend_u64 as *const u32),
+        ("read_field", read_field as *const u32),
+        ("read_array_element", read_array_element as *const u32),
+        ("read_split_fields", read_split_fields as *const u32),
+    ];
+    for (name, f) in functions {
+        // SAFETY: We pass the addresses of functions.
+        println!("{name:40} {:>10}", unsafe { instruction_count(f) });
+    }
+}
+
+fn main() {
+    let mut registers = Registers {
+        control: ReadWrite(1),
+        status: ReadPure(2),
+        data: [const { ReadWrite(3) }; 4],
+    };
+
+    println!("Latency:");
+    bench("read_volatile_u32", || {
+        black_box(read_volatile_u32(black_box(&registers.control)));
+    });
+    bench("read_backend_u32", || {
+        black_box(read_backend_u32(black_box(UniqueMmioPointer::from(
+            &mut registers.control,
+        ))));
+    });
+    bench("write_volatile_u32", || {
+        write_volatile_u32(black_box(&mut registers.control), black_box(42));
+    });
+    bench("write_backend_u32", || {
+        write_backend_u32(
+            black_box(UniqueMmioPointer::from(&mut registers.control)),
+            black_box(42),
+        );
+    });
+    bench("read_volatile_u64", || {
+        black_box(read_volatile_u64(black_box(&registers.data[0])));
+    });
+    bench("read_backend_u64", || {
+        black_box(read_backend_u64(black_box(UniqueMmioPointer::from(
+            &mut registers.data[0],
+        ))));
+    });
+    bench("read_field", || {
+        black_box(read_field(black_box(UniqueMmioPointer::from(
+            &mut registers,
+        ))));
+    });
+    bench("read_array_element", || {
+        black_box(read_array_element(
+            black_box(UniqueMmioPointer::from(&mut registers)),
+            black_box(2),
+        ));
+    });
+    bench("read_split_fields", || {
+        black_box(read_split_fields(black_box(UniqueMmioPointer::from(
+            &mut registers,
+        ))));
+    });
+
+    #[cfg(target_arch = "aarch64")]
+    code_size();
+}
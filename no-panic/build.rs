@@ -0,0 +1,11 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+fn main() {
+    // Shared libraries may have undefined symbols by default on Linux, so make sure that a reference
+    // to the missing symbol in the panic handler is an error.
+    if std::env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("linux") {
+        println!("cargo:rustc-cdylib-link-arg=-Wl,--no-undefined");
+    }
+}
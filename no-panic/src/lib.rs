@@ -0,0 +1,99 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Exports functions using the non-panicking parts of the safe-mmio API, with a panic handler which
+//! fails to link. A release build of this crate therefore only succeeds if the optimiser can remove
+//! every panic path from them.
+//!
+//! This must be built with `--release`, as unoptimised builds keep unreachable panics.
+
+#![no_std]
+
+use core::panic::PanicInfo;
+use safe_mmio::{
+    SharedMmioPointer, UniqueMmioPointer, field, field_shared,
+    fields::{ReadPure, ReadPureWrite, ReadWrite, WriteOnly},
+    split_fields,
+};
+
+#[repr(C)]
+pub struct Uart {
+    data: ReadWrite<u32>,
+    status: ReadPure<u32>,
+    control: [WriteOnly<u8>; 4],
+    fifo: [ReadPureWrite<u16>; 8],
+    queues: [[ReadWrite<u32>; 4]; 2],
+}
+
+#[unsafe(no_mangle)]
+pub fn fields(uart: UniqueMmioPointer<Uart>) -> u32 {
+    let mut uart = uart;
+    field!(uart, data).write(42);
+    let (mut data, status) = unsafe { split_fields!(uart, data, status) };
+    data.write(status.read());
+    data.read()
+}
+
+#[unsafe(no_mangle)]
+pub fn shared_fields(uart: SharedMmioPointer<Uart>) -> u32 {
+    field_shared!(uart, status).read()
+}
+
+#[unsafe(no_mangle)]
+pub fn arrays(mut uart: UniqueMmioPointer<Uart>, index: usize) -> Option<u16> {
+    if let Some(mut control) = field!(uart, control).get(index) {
+        control.write(1);
+    }
+    let [mut first, _, mut third, _] = field!(uart, control).split();
+    first.write(2);
+    third.write(3);
+    for mut control in field!(uart, control) {
+        control.write(0);
+    }
+    let mut fifo = field!(uart, fifo);
+    let mut range = fifo.get_range(index..index + 2)?;
+    range.first()?.write(4);
+    for mut chunk in fifo.as_mut_slice().chunks_exact(3) {
+        chunk.last()?.write(5);
+    }
+    let value = fifo.reborrow().take(index)?.read();
+    let [mut a, b] = fifo.try_split_some([index, 7])?;
+    a.write(b.read());
+    Some(value)
+}
+
+/// This should compile to a bounds check on each index and a single address computation.
+#[unsafe(no_mangle)]
+pub fn matrix(mut uart: UniqueMmioPointer<Uart>, port: usize, queue: usize) -> Option<u32> {
+    Some(field!(uart, queues).get2(port, queue)?.read())
+}
+
+#[unsafe(no_mangle)]
+pub fn shared_arrays(uart: SharedMmioPointer<Uart>, index: usize) -> Option<u16> {
+    let fifo = field_shared!(uart, fifo);
+    let [a, b] = fifo.as_slice().try_split_some([index, 1])?;
+    let sum = fifo.iter().fold(0, |sum, element| sum ^ element.read());
+    Some(sum ^ a.read() ^ b.read() ^ fifo.get(index)?.read())
+}
+
+#[unsafe(no_mangle)]
+pub fn from_address(address: usize) -> Option<u32> {
+    // SAFETY: The caller must pass the address of a UART.
+    let mut uart = unsafe { UniqueMmioPointer::<Uart>::try_from_exposed_addr(address) }?;
+    Some(field!(uart, data).read())
+}
+
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    unsafe extern "C" {
+        /// This doesn't exist, so linking fails if any panic path remains.
+        fn safe_mmio_panic_path_not_optimised_away() -> !;
+    }
+    // SAFETY: This is never called, as the build would fail to link.
+    unsafe { safe_mmio_panic_path_not_optimised_away() }
+}
+
+/// The prebuilt `core` is compiled with unwinding, so refers to this even though we abort on panic.
+#[unsafe(no_mangle)]
+extern "C" fn rust_eh_personality() {}
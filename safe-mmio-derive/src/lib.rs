@@ -0,0 +1,655 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Derive macros for [safe-mmio](https://crates.io/crates/safe-mmio). Use them through the `derive`
+//! feature of safe-mmio rather than depending on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    Attribute, Data, DeriveInput, Error, Expr, ExprLit, ExprRange, Fields, Ident, Lit, RangeLimits,
+    Result, Token, Type, parse_macro_input,
+};
+
+/// The names of the field wrappers which can be written, and so are included in the builder
+/// generated by `MmioInit`.
+const WRITABLE_WRAPPERS: &[&str] = &[
+    "ReadPureWrite",
+    "ReadWrite",
+    "ReadWrite64HiLo",
+    "ReadWrite64LoHi",
+    "WriteOnly",
+];
+
+/// Derives conversions and bit accessors for a `repr(transparent)` newtype over an unsigned
+/// integer, to use as a strongly typed register value.
+///
+/// This implements `From` in both directions between the newtype and the integer, so it can be
+/// used with
+/// [`Converted`](https://docs.rs/safe-mmio/latest/safe_mmio/access/struct.Converted.html), and
+/// checks at compile time that the integer is a type which safe-mmio can access atomically. It also
+/// implements
+/// [`AtomicNewtype`](https://docs.rs/safe-mmio/latest/safe_mmio/access/trait.AtomicNewtype.html),
+/// so that a register of the newtype, such as `ReadWrite<Control>`, can be read, written and
+/// modified in place if it also implements the zerocopy traits. To derive those too, use the
+/// [`macro@mmio_value`] attribute instead of deriving this directly.
+///
+/// Each field declared in a `#[bits(...)]` attribute, either as a range of bits `name = low..high`
+/// (or `low..=high`) or as a single bit `name = bit`, gets three methods:
+///
+/// - `name(self)` returns the field, shifted down to bit 0, or a `bool` for a single bit.
+/// - `with_name(self, value)` returns a copy of the value with the field replaced.
+/// - `set_name(&mut self, value)` replaces the field in place.
+///
+/// `with_name` and `set_name` panic if the new value doesn't fit in the field.
+///
+/// A multi-bit field can instead be given an enum type which implements
+/// [`FieldEnum`](https://docs.rs/safe-mmio/latest/safe_mmio/value/trait.FieldEnum.html), usually by
+/// deriving [`MmioEnum`], with `name = low..high: Type`. Its getter then returns
+/// `Result<Type, InvalidFieldValue>`, and `with_name` and `set_name` take a `Type`. Every variant
+/// is checked at compile time to fit in the field, so they can't panic.
+///
+/// # Example
+///
+/// ```
+/// use safe_mmio::MmioValue;
+///
+/// #[derive(Clone, Copy, Debug, Default, Eq, MmioValue, PartialEq)]
+/// #[repr(transparent)]
+/// #[bits(enable = 0, divider = 4..8)]
+/// struct Control(u32);
+///
+/// let control = Control::default().with_enable(true).with_divider(3);
+/// assert_eq!(u32::from(control), 0x31);
+/// assert_eq!(Control::from(0x50).divider(), 5);
+/// ```
+///
+/// With an enum field:
+///
+/// ```
+/// use safe_mmio::{MmioEnum, MmioValue};
+///
+/// #[derive(Clone, Copy, Debug, Eq, MmioEnum, PartialEq)]
+/// enum Parity {
+///     None = 0,
+///     Odd = 1,
+///     Even = 3,
+/// }
+///
+/// #[derive(Clone, Copy, Debug, Default, Eq, MmioValue, PartialEq)]
+/// #[repr(transparent)]
+/// #[bits(parity = 1..3: Parity)]
+/// struct LineControl(u8);
+///
+/// let line_control = LineControl::default().with_parity(Parity::Even);
+/// assert_eq!(u8::from(line_control), 0b110);
+/// assert_eq!(line_control.parity(), Ok(Parity::Even));
+/// assert!(LineControl::from(0b100).parity().is_err());
+/// ```
+///
+/// The register itself keeps its integer type, and is read and written as the newtype through
+/// `Converted`:
+///
+/// ```
+/// use safe_mmio::{
+///     MmioValue, UniqueMmioPointer,
+///     access::{Converted, SideEffectReadable, Writable},
+///     fields::ReadWrite,
+/// };
+///
+/// #[derive(Clone, Copy, Debug, Default, Eq, MmioValue, PartialEq)]
+/// #[repr(transparent)]
+/// #[bits(enable = 0, divider = 4..8)]
+/// struct Control(u32);
+///
+/// let control: UniqueMmioPointer<ReadWrite<u32>>;
+/// # let mut fake = ReadWrite(0x30);
+/// # control = UniqueMmioPointer::from(&mut fake);
+/// let mut control = Converted::<_, Control>::new(control);
+/// let value = control.read();
+/// control.write(value.with_enable(true));
+/// ```
+#[proc_macro_derive(MmioValue, attributes(bits))]
+pub fn derive_mmio_value(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    value(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Derives [`MmioValue`] along with the zerocopy `FromBytes`, `Immutable`, `IntoBytes` and
+/// `KnownLayout` traits.
+///
+/// The newtype can then be used in place of its integer in a register struct which derives the
+/// zerocopy traits. As with deriving them on the register struct, the crate using this must depend
+/// on zerocopy with its `derive` feature.
+///
+/// # Example
+///
+/// ```
+/// use safe_mmio::{fields::ReadPure, mmio_value};
+/// use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+///
+/// #[mmio_value]
+/// #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+/// #[repr(transparent)]
+/// #[bits(ready = 0, level = 8..16)]
+/// struct Status(u32);
+///
+/// #[derive(FromBytes, Immutable, IntoBytes, KnownLayout)]
+/// #[repr(C)]
+/// struct Registers {
+///     status: ReadPure<Status>,
+/// }
+///
+/// let status = Status::read_from_bytes(&[0x01, 0x2a, 0x00, 0x00]).unwrap();
+/// assert!(status.ready());
+/// assert_eq!(status.level(), 42);
+/// ```
+#[proc_macro_attribute]
+pub fn mmio_value(attribute: TokenStream, item: TokenStream) -> TokenStream {
+    let attribute = TokenStream2::from(attribute);
+    if !attribute.is_empty() {
+        return Error::new_spanned(attribute, "mmio_value doesn't take any arguments")
+            .into_compile_error()
+            .into();
+    }
+    let item = TokenStream2::from(item);
+    quote! {
+        #[derive(
+            ::safe_mmio::MmioValue,
+            ::zerocopy::FromBytes,
+            ::zerocopy::Immutable,
+            ::zerocopy::IntoBytes,
+            ::zerocopy::KnownLayout,
+        )]
+        #item
+    }
+    .into()
+}
+
+/// A field of a register value, declared in a `#[bits(...)]` attribute.
+struct BitField {
+    name: Ident,
+    /// The lowest bit of the field.
+    low: u32,
+    /// The number of bits in the field.
+    width: u32,
+    /// The enum type of the field, if it has one.
+    ty: Option<Type>,
+    span: Span,
+}
+
+fn value(input: &DeriveInput) -> Result<TokenStream2> {
+    let name = &input.ident;
+    let raw = raw_type(input)?;
+    if !input.attrs.iter().any(is_repr_transparent) {
+        return Err(Error::new_spanned(
+            name,
+            "MmioValue can only be derived for #[repr(transparent)] types",
+        ));
+    }
+    let mut fields = Vec::new();
+    for attribute in input.attrs.iter().filter(|a| a.path().is_ident("bits")) {
+        parse_bits(attribute, &mut fields)?;
+    }
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    let accessors = fields.iter().map(|field| accessors(field, raw));
+    let width_checks = fields.iter().map(|field| {
+        let end = field.low + field.width;
+        let width = field.width;
+        let message = format!("Field `{}` doesn't fit in `{}`", field.name, quote!(#raw));
+        let enum_check = field.ty.as_ref().map(|ty| {
+            let message = format!(
+                "Not every variant of `{}` fits in field `{}`",
+                quote!(#ty),
+                field.name
+            );
+            quote::quote_spanned! {field.span=>
+                ::core::assert!(
+                    <#ty as ::safe_mmio::value::FieldEnum>::MAX <= u64::MAX >> (64 - #width),
+                    #message
+                );
+            }
+        });
+        quote::quote_spanned! {field.span=>
+            ::core::assert!(#end <= <#raw>::BITS, #message);
+            #enum_check
+        }
+    });
+
+    // The `AtomicNewtype` impl is sound because we checked above that the type is
+    // `repr(transparent)` over `raw`.
+    Ok(quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            #(#accessors)*
+        }
+
+        impl #impl_generics ::core::convert::From<#raw> for #name #type_generics #where_clause {
+            fn from(value: #raw) -> Self {
+                Self(value)
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<#name #type_generics> for #raw #where_clause {
+            fn from(value: #name #type_generics) -> Self {
+                value.0
+            }
+        }
+
+        unsafe impl #impl_generics ::safe_mmio::access::AtomicNewtype for #name #type_generics
+            #where_clause
+        {
+            type Inner = #raw;
+        }
+
+        const _: () = {
+            const fn assert_atomic_access<T: ::safe_mmio::access::AtomicAccess>() {}
+            assert_atomic_access::<#raw>();
+            ::core::assert!(<#raw>::MIN == 0, "MmioValue types must wrap an unsigned integer");
+            #(#width_checks)*
+        };
+    })
+}
+
+/// Returns the type of the single field of the given tuple struct.
+fn raw_type(input: &DeriveInput) -> Result<&Type> {
+    if let Data::Struct(data) = &input.data {
+        if let Fields::Unnamed(fields) = &data.fields {
+            if fields.unnamed.len() == 1 {
+                return Ok(&fields.unnamed[0].ty);
+            }
+        }
+    }
+    Err(Error::new_spanned(
+        &input.ident,
+        "MmioValue can only be derived for tuple structs with a single field",
+    ))
+}
+
+fn is_repr_transparent(attribute: &Attribute) -> bool {
+    let mut transparent = false;
+    if attribute.path().is_ident("repr") {
+        let _ = attribute.parse_nested_meta(|meta| {
+            transparent |= meta.path.is_ident("transparent");
+            Ok(())
+        });
+    }
+    transparent
+}
+
+/// Parses a `#[bits(name = low..high, ...)]` attribute, and adds the fields it declares to
+/// `fields`.
+fn parse_bits(attribute: &Attribute, fields: &mut Vec<BitField>) -> Result<()> {
+    attribute.parse_nested_meta(|meta| {
+        let Some(name) = meta.path.get_ident().cloned() else {
+            return Err(meta.error("Expected a field name"));
+        };
+        if fields.iter().any(|field| field.name == name) {
+            return Err(meta.error(format!("Duplicate field `{name}`")));
+        }
+        let expr: Expr = meta.value()?.parse()?;
+        let (low, width) = match &expr {
+            Expr::Range(range) => parse_range(range)?,
+            expr => (parse_bit(expr)?, 1),
+        };
+        let ty = if meta.input.peek(Token![:]) {
+            meta.input.parse::<Token![:]>()?;
+            Some(meta.input.parse()?)
+        } else {
+            None
+        };
+        if width > 64 {
+            return Err(meta.error("Fields may be at most 64 bits wide"));
+        }
+        fields.push(BitField {
+            span: name.span(),
+            name,
+            low,
+            width,
+            ty,
+        });
+        Ok(())
+    })
+}
+
+/// Parses a range of bits, and returns its lowest bit and width.
+fn parse_range(range: &ExprRange) -> Result<(u32, u32)> {
+    let (Some(start), Some(end)) = (&range.start, &range.end) else {
+        return Err(Error::new_spanned(range, "Expected a range with both ends"));
+    };
+    let low = parse_bit(start)?;
+    let high = match range.limits {
+        RangeLimits::HalfOpen(_) => parse_bit(end)?,
+        RangeLimits::Closed(_) => parse_bit(end)? + 1,
+    };
+    if high <= low {
+        return Err(Error::new_spanned(range, "Range of bits must not be empty"));
+    }
+    Ok((low, high - low))
+}
+
+fn parse_bit(expr: &Expr) -> Result<u32> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(int), ..
+        }) => int.base10_parse(),
+        expr => Err(Error::new_spanned(expr, "Expected a bit number")),
+    }
+}
+
+/// Returns the getter, `with_` and `set_` methods for the given field.
+fn accessors(field: &BitField, raw: &Type) -> TokenStream2 {
+    let BitField {
+        name,
+        low,
+        width,
+        ty,
+        ..
+    } = field;
+    let with = format_ident!("with_{}", name);
+    let set = format_ident!("set_{}", name);
+    let high = low + width;
+    if let Some(ty) = ty {
+        let get_doc = format!(
+            "Returns bits {low}..{high} as a `{}`, or an error if they don't match any variant.",
+            quote!(#ty)
+        );
+        let with_doc =
+            format!("Returns a copy of the value with bits {low}..{high} replaced by `value`.");
+        let set_doc = format!("Replaces bits {low}..{high} with `value`.");
+        quote! {
+            #[doc = #get_doc]
+            pub fn #name(self) -> ::core::result::Result<#ty, ::safe_mmio::value::InvalidFieldValue> {
+                let bits = ((self.0 >> #low) & (<#raw>::MAX >> (<#raw>::BITS - #width))) as u64;
+                <#ty as ::safe_mmio::value::FieldEnum>::from_bits(bits)
+                    .ok_or(::safe_mmio::value::InvalidFieldValue(bits))
+            }
+
+            #[doc = #with_doc]
+            #[must_use]
+            pub fn #with(self, value: #ty) -> Self {
+                let mask = <#raw>::MAX >> (<#raw>::BITS - #width);
+                let bits = ::safe_mmio::value::FieldEnum::into_bits(value) as #raw;
+                Self(self.0 & !(mask << #low) | (bits << #low))
+            }
+
+            #[doc = #set_doc]
+            pub fn #set(&mut self, value: #ty) {
+                *self = self.#with(value);
+            }
+        }
+    } else if *width == 1 {
+        let get_doc = format!("Returns whether bit {low} is set.");
+        let with_doc =
+            format!("Returns a copy of the value with bit {low} set to the given value.");
+        let set_doc = format!("Sets bit {low} to the given value.");
+        quote! {
+            #[doc = #get_doc]
+            pub const fn #name(self) -> bool {
+                self.0 & (1 << #low) != 0
+            }
+
+            #[doc = #with_doc]
+            #[must_use]
+            pub const fn #with(self, value: bool) -> Self {
+                Self(self.0 & !(1 << #low) | ((value as #raw) << #low))
+            }
+
+            #[doc = #set_doc]
+            pub const fn #set(&mut self, value: bool) {
+                *self = self.#with(value);
+            }
+        }
+    } else {
+        let get_doc = format!("Returns bits {low}..{high}, shifted down to bit 0.");
+        let with_doc =
+            format!("Returns a copy of the value with bits {low}..{high} replaced by `value`.");
+        let set_doc = format!("Replaces bits {low}..{high} with `value`.");
+        let panics = format!("\n\n# Panics\n\nPanics if `value` doesn't fit in {width} bits.");
+        let message = format!("Value doesn't fit in field `{name}`");
+        quote! {
+            #[doc = #get_doc]
+            pub const fn #name(self) -> #raw {
+                (self.0 >> #low) & (<#raw>::MAX >> (<#raw>::BITS - #width))
+            }
+
+            #[doc = #with_doc]
+            #[doc = #panics]
+            #[must_use]
+            pub const fn #with(self, value: #raw) -> Self {
+                let mask = <#raw>::MAX >> (<#raw>::BITS - #width);
+                ::core::assert!(value <= mask, #message);
+                Self(self.0 & !(mask << #low) | (value << #low))
+            }
+
+            #[doc = #set_doc]
+            #[doc = #panics]
+            pub const fn #set(&mut self, value: #raw) {
+                *self = self.#with(value);
+            }
+        }
+    }
+}
+
+/// Derives [`FieldEnum`](https://docs.rs/safe-mmio/latest/safe_mmio/value/trait.FieldEnum.html)
+/// for a fieldless enum, so that it can be used as the type of a field of an [`MmioValue`] type.
+///
+/// Each variant's value is its discriminant.
+#[proc_macro_derive(MmioEnum)]
+pub fn derive_mmio_enum(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    mmio_enum(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn mmio_enum(input: &DeriveInput) -> Result<TokenStream2> {
+    let name = &input.ident;
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new_spanned(
+            name,
+            "MmioEnum can only be derived for enums",
+        ));
+    };
+    let mut variants = Vec::new();
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                variant,
+                "MmioEnum can only be derived for enums without fields",
+            ));
+        }
+        variants.push(&variant.ident);
+    }
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::safe_mmio::value::FieldEnum for #name #type_generics #where_clause {
+            const MAX: u64 = {
+                let mut max = 0;
+                #(
+                    if Self::#variants as u64 > max {
+                        max = Self::#variants as u64;
+                    }
+                )*
+                max
+            };
+
+            fn from_bits(bits: u64) -> ::core::option::Option<Self> {
+                #(
+                    if bits == Self::#variants as u64 {
+                        return ::core::option::Option::Some(Self::#variants);
+                    }
+                )*
+                ::core::option::Option::None
+            }
+
+            fn into_bits(self) -> u64 {
+                self as u64
+            }
+        }
+    })
+}
+
+/// Derives a builder for the initial values of the writable registers of a register struct, so
+/// that the values can be computed (and tested) separately from writing them to the device.
+///
+/// For a struct `Regs` this generates a `RegsInit` builder, returned by `Regs::init()`. It has a
+/// method for each field whose type is one of the writable wrappers (`WriteOnly`, `ReadWrite`,
+/// `ReadPureWrite`, `ReadWrite64LoHi` or `ReadWrite64HiLo`), named after the field, which sets the
+/// value to write to it. The values are also available as public `Option` fields of the builder.
+/// Other fields, such as read-only registers and reserved space, are skipped.
+///
+/// `apply` then performs an MMIO write of each register which was given a value, in the order that
+/// the fields are declared.
+///
+/// # Example
+///
+/// ```
+/// use safe_mmio::{MmioInit, UniqueMmioPointer, fields::{ReadPure, ReadWrite, WriteOnly}};
+///
+/// #[derive(MmioInit)]
+/// #[repr(C)]
+/// struct UartRegisters {
+///     data: ReadWrite<u32>,
+///     status: ReadPure<u32>,
+///     baud_divisor: WriteOnly<u32>,
+///     control: ReadWrite<u32>,
+/// }
+///
+/// fn uart_init(clock: u32, baud: u32) -> UartRegistersInit {
+///     UartRegisters::init().baud_divisor(clock / (16 * baud)).control(0x301)
+/// }
+///
+/// // The values can be checked without any device.
+/// let init = uart_init(24_000_000, 115_200);
+/// assert_eq!(init.baud_divisor, Some(13));
+/// assert_eq!(init.data, None);
+///
+/// let mut regs: UniqueMmioPointer<UartRegisters>;
+/// # let mut fake = UartRegisters {
+/// #     data: ReadWrite(0),
+/// #     status: ReadPure(0),
+/// #     baud_divisor: WriteOnly(0),
+/// #     control: ReadWrite(0),
+/// # };
+/// # regs = UniqueMmioPointer::from(&mut fake);
+/// // Writes `baud_divisor` and then `control`.
+/// init.apply(&mut regs);
+/// ```
+#[proc_macro_derive(MmioInit)]
+pub fn derive_mmio_init(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    mmio_init(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn mmio_init(input: &DeriveInput) -> Result<TokenStream2> {
+    let name = &input.ident;
+    let vis = &input.vis;
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            name,
+            "MmioInit can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new_spanned(
+            name,
+            "MmioInit can only be derived for structs with named fields",
+        ));
+    };
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "MmioInit can't be derived for generic structs",
+        ));
+    }
+    let builder = format_ident!("{}Init", name);
+    let builder_doc = format!(
+        "Initial values for the writable registers of [`{name}`], to be written by \
+        [`apply`](Self::apply)."
+    );
+    let init_doc = format!("Returns a [`{builder}`] with no register values set.");
+
+    let writable = fields
+        .named
+        .iter()
+        .filter(|field| is_writable(&field.ty))
+        .map(|field| (field.ident.as_ref().unwrap(), &field.ty))
+        .collect::<Vec<_>>();
+    let names = writable.iter().map(|(name, _)| name).collect::<Vec<_>>();
+    let values = writable
+        .iter()
+        .map(|(_, ty)| {
+            quote! {
+                <::safe_mmio::UniqueMmioPointer<'static, #ty> as ::safe_mmio::access::Writable>::Value
+            }
+        })
+        .collect::<Vec<_>>();
+    let field_docs = names
+        .iter()
+        .map(|field| format!("The value to write to `{field}`, if any."));
+    let setter_docs = names
+        .iter()
+        .map(|field| format!("Sets the value to write to `{field}`."));
+
+    Ok(quote! {
+        #[doc = #builder_doc]
+        #[derive(Clone, Copy, Default)]
+        #vis struct #builder {
+            #(
+                #[doc = #field_docs]
+                pub #names: ::core::option::Option<#values>,
+            )*
+        }
+
+        impl #name {
+            #[doc = #init_doc]
+            #vis fn init() -> #builder {
+                #builder::default()
+            }
+        }
+
+        impl #builder {
+            #(
+                #[doc = #setter_docs]
+                #[must_use]
+                pub fn #names(mut self, value: #values) -> Self {
+                    self.#names = ::core::option::Option::Some(value);
+                    self
+                }
+            )*
+
+            /// Performs an MMIO write of each register which has been given a value, in the order
+            /// that they are declared.
+            pub fn apply(self, regs: &mut ::safe_mmio::UniqueMmioPointer<#name>) {
+                #(
+                    if let ::core::option::Option::Some(value) = self.#names {
+                        ::safe_mmio::access::Writable::write(
+                            &mut ::safe_mmio::field!(*regs, #names),
+                            value,
+                        );
+                    }
+                )*
+            }
+        }
+    })
+}
+
+/// Returns whether the given field type is one of the writable field wrappers.
+fn is_writable(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+    path.path.segments.last().is_some_and(|segment| {
+        WRITABLE_WRAPPERS
+            .iter()
+            .any(|wrapper| segment.ident == wrapper)
+    })
+}
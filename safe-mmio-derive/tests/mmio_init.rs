@@ -0,0 +1,81 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+use safe_mmio::{
+    MmioInit, MmioValue, UniqueMmioPointer,
+    fields::{ReadPure, ReadPureWrite, ReadWrite, ReadWrite64LoHi, WriteOnly},
+};
+
+#[derive(Clone, Copy, Debug, Default, Eq, MmioValue, PartialEq)]
+#[repr(transparent)]
+#[bits(enable = 0, divider = 4..8)]
+struct Control(u32);
+
+#[derive(MmioInit)]
+#[repr(C)]
+struct Registers {
+    data: ReadWrite<u8>,
+    status: ReadPure<u8>,
+    mode: ReadPureWrite<u16>,
+    control: WriteOnly<u32>,
+    reserved: [u32; 1],
+    counter: ReadWrite64LoHi,
+}
+
+fn fake() -> Registers {
+    Registers {
+        data: ReadWrite(1),
+        status: ReadPure(2),
+        mode: ReadPureWrite(3),
+        control: WriteOnly(4),
+        reserved: [5],
+        counter: ReadWrite64LoHi::default(),
+    }
+}
+
+#[test]
+fn builder_values() {
+    let init = Registers::init();
+    assert_eq!(init.data, None);
+    assert_eq!(init.mode, None);
+    assert_eq!(init.control, None);
+    assert_eq!(init.counter, None);
+
+    let init = init
+        .mode(7)
+        .control(Control::default().with_enable(true).with_divider(3).into())
+        .mode(8);
+    assert_eq!(init.data, None);
+    assert_eq!(init.mode, Some(8));
+    assert_eq!(init.control, Some(0x31));
+}
+
+#[test]
+fn apply_writes_set_registers() {
+    let mut fake = fake();
+    let mut regs = UniqueMmioPointer::from(&mut fake);
+    Registers::init()
+        .data(42)
+        .control(Control::default().with_divider(15).into())
+        .counter(0x1234_5678_9abc_def0)
+        .apply(&mut regs);
+    assert_eq!(fake.data.0, 42);
+    assert_eq!(fake.status.0, 2);
+    assert_eq!(fake.mode.0, 3);
+    assert_eq!(fake.control.0, 0xf0);
+    assert_eq!(fake.reserved, [5]);
+    assert_eq!(
+        UniqueMmioPointer::from(&mut fake.counter).read(),
+        0x1234_5678_9abc_def0
+    );
+}
+
+#[test]
+fn apply_nothing() {
+    let mut fake = fake();
+    Registers::init().apply(&mut UniqueMmioPointer::from(&mut fake));
+    assert_eq!(fake.data.0, 1);
+    assert_eq!(fake.mode.0, 3);
+    assert_eq!(fake.control.0, 4);
+}
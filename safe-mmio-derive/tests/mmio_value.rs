@@ -0,0 +1,125 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+use safe_mmio::{
+    MmioEnum, MmioValue, UniqueMmioPointer,
+    access::{Converted, SideEffectReadable, Writable},
+    field,
+    fields::{ReadPure, ReadWrite},
+    mmio_value,
+    value::{FieldEnum, InvalidFieldValue},
+};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+#[mmio_value]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[repr(transparent)]
+#[bits(enable = 0, mode = 1..=2)]
+#[bits(divider = 4..8, top = 31)]
+struct Control(u32);
+
+#[derive(Clone, Copy, Debug, Default, Eq, MmioValue, PartialEq)]
+#[repr(transparent)]
+#[bits(whole = 0..8)]
+struct Byte(u8);
+
+#[derive(FromBytes, Immutable, IntoBytes, KnownLayout)]
+#[repr(C)]
+struct Registers {
+    control: ReadWrite<Control>,
+    status: ReadPure<u32>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, MmioEnum, PartialEq)]
+#[repr(u8)]
+enum Mode {
+    Off = 0,
+    Slow = 2,
+    Fast = 5,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, MmioValue, PartialEq)]
+#[repr(transparent)]
+#[bits(enable = 0, mode = 4..7: Mode, level = 8..16)]
+struct Config(u16);
+
+#[test]
+fn accessors() {
+    let mut control = Control::default();
+    assert!(!control.enable());
+    control.set_enable(true);
+    control.set_mode(3);
+    control.set_top(true);
+    assert_eq!(control.with_divider(0xa).0, 0x8000_00a7);
+    assert_eq!(control.0, 0x8000_0007);
+    assert!(control.top());
+    assert_eq!(control.mode(), 3);
+    assert_eq!(control.divider(), 0);
+    assert!(!control.with_enable(false).enable());
+    assert_eq!(control.with_mode(1).0, 0x8000_0003);
+
+    let byte = Byte(0x12).with_whole(0xff);
+    assert_eq!(byte.whole(), 0xff);
+    assert_eq!(u8::from(byte), 0xff);
+}
+
+#[test]
+fn field_enum() {
+    assert_eq!(Mode::MAX, 5);
+    assert_eq!(Mode::from_bits(2), Some(Mode::Slow));
+    assert_eq!(Mode::from_bits(3), None);
+    assert_eq!(Mode::Fast.into_bits(), 5);
+}
+
+#[test]
+fn enum_accessors() {
+    let mut config = Config(0xffff).with_mode(Mode::Slow);
+    assert_eq!(config.0, 0xffaf);
+    assert_eq!(config.mode(), Ok(Mode::Slow));
+    config.set_mode(Mode::Off);
+    assert_eq!(config.0, 0xff8f);
+    assert_eq!(config.mode(), Ok(Mode::Off));
+    assert!(config.enable());
+    assert_eq!(config.level(), 0xff);
+    assert_eq!(Config(0x0070).mode(), Err(InvalidFieldValue(7)));
+}
+
+#[test]
+#[should_panic]
+fn too_large() {
+    let _ = Control::default().with_divider(16);
+}
+
+#[test]
+fn zerocopy() {
+    let registers = Registers::read_from_bytes(&[0x31, 0, 0, 0x80, 0x42, 0, 0, 0]).unwrap();
+    assert_eq!(registers.control.0, Control(0x8000_0031));
+    assert!(registers.control.0.top());
+    assert_eq!(registers.status.0, 0x42);
+    assert_eq!(Control(0x12).as_bytes(), &[0x12, 0, 0, 0]);
+}
+
+#[test]
+fn converted_register() {
+    let mut fake = ReadWrite(0x31);
+    let mut register = Converted::<_, Control>::new(UniqueMmioPointer::from(&mut fake));
+    let control = register.read();
+    assert!(control.enable());
+    assert_eq!(control.divider(), 3);
+    register.write(control.with_enable(false));
+    assert_eq!(fake.0, 0x30);
+}
+
+#[test]
+fn register_of_value() {
+    let mut fake = Registers {
+        control: ReadWrite(Control(0x31)),
+        status: ReadPure(0),
+    };
+    let mut regs = UniqueMmioPointer::from(&mut fake);
+    field!(regs, control).modify(|control| control.with_enable(false).with_mode(2));
+    assert_eq!(field!(regs, control).read(), Control(0x34));
+    field!(regs, control).write(Control::default().with_top(true));
+    assert_eq!(fake.control.0, Control(0x8000_0000));
+}
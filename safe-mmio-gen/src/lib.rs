@@ -0,0 +1,532 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Generates [safe-mmio](https://crates.io/crates/safe-mmio) register structs from
+//! [CMSIS-SVD](https://open-cmsis-pack.github.io/svd-spec/main/index.html) files.
+//!
+//! Each peripheral becomes a `#[repr(C)]` struct defined with `safe_mmio::register_map!`, with the
+//! field wrapper for each register chosen from its SVD metadata:
+//!
+//! | `access`     | `readAction` on register or any field | Wrapper            |
+//! | ------------ | ------------------------------------- | ------------------ |
+//! | `read-only`  | no                                    | `ReadPure<T>`      |
+//! | `read-only`  | yes                                   | `ReadOnly<T>`      |
+//! | `write-only` | -                                     | `WriteOnly<T>`     |
+//! | `read-write` | no                                    | `ReadPureWrite<T>` |
+//! | `read-write` | yes                                   | `ReadWrite<T>`     |
+//!
+//! `modifiedWriteValues`, such as `oneToClear`, is described in the register's doc comment.
+//! Registers with `dim` become arrays if their name contains `[%s]`, or are repeated otherwise.
+//! Peripherals which are `derivedFrom` another become type aliases for its struct.
+//!
+//! Clusters, and registers which overlap an earlier register (e.g. `alternateRegister`s) or aren't
+//! naturally aligned, aren't supported. They are skipped with a warning.
+//!
+//! # Example
+//!
+//! From a build script:
+//!
+//! ```no_run
+//! use std::{env, fs, path::Path};
+//!
+//! let svd = fs::read_to_string("device.svd").unwrap();
+//! let generated = safe_mmio_gen::generate(&svd).unwrap();
+//! for warning in &generated.warnings {
+//!     println!("cargo::warning={warning}");
+//! }
+//! let out = Path::new(&env::var("OUT_DIR").unwrap()).join("registers.rs");
+//! fs::write(out, generated.code).unwrap();
+//! println!("cargo::rerun-if-changed=device.svd");
+//! ```
+//!
+//! and then `include!(concat!(env!("OUT_DIR"), "/registers.rs"));` in a module of the crate.
+
+mod svd;
+mod xml;
+
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Display, Formatter, Write},
+};
+use svd::{Access, Device, Peripheral, Register};
+
+/// An error parsing an SVD file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// The file isn't well-formed XML.
+    Xml {
+        /// The line on which the error was found.
+        line: usize,
+        /// A description of the error.
+        message: String,
+    },
+    /// The file is well-formed XML, but not a valid SVD device description.
+    Svd(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Xml { line, message } => write!(f, "Invalid XML on line {line}: {message}"),
+            Self::Svd(message) => write!(f, "Invalid SVD: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Rust source generated from an SVD file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Generated {
+    /// The generated items, to be included in a module of a crate which depends on `safe-mmio`.
+    pub code: String,
+    /// Descriptions of the parts of the SVD file which couldn't be represented, and so were
+    /// skipped.
+    pub warnings: Vec<String>,
+}
+
+/// Generates register structs for all peripherals in the given SVD file.
+pub fn generate(svd: &str) -> Result<Generated, Error> {
+    let device = Device::parse(&xml::parse(svd)?)?;
+    let mut generator = Generator::default();
+    let mut items = String::new();
+    for peripheral in &device.peripherals {
+        generator.peripheral(&device, peripheral, &mut items)?;
+    }
+
+    let mut code = format!(
+        "// Generated by safe-mmio-gen from the SVD file for {}. Do not edit.\n\n",
+        device.name
+    );
+    if !generator.wrappers.is_empty() {
+        let wrappers = generator
+            .wrappers
+            .into_iter()
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(
+            code,
+            "use safe_mmio::{{\n    fields::{{{wrappers}}},\n    register_map,\n}};"
+        )
+        .unwrap();
+    }
+    code.push_str(&items);
+    Ok(Generated {
+        code,
+        warnings: generator.warnings,
+    })
+}
+
+#[derive(Default)]
+struct Generator {
+    /// The names of the field wrappers used so far.
+    wrappers: BTreeSet<&'static str>,
+    /// The names of the peripherals for which structs have been generated so far.
+    structs: BTreeSet<String>,
+    warnings: Vec<String>,
+}
+
+/// A single field of a generated struct.
+struct Field {
+    name: String,
+    doc: String,
+    offset: u64,
+    /// The size of each element, in bytes.
+    element_size: u64,
+    /// The number of elements, if the field is an array.
+    count: Option<usize>,
+    ty: String,
+}
+
+impl Generator {
+    fn peripheral(
+        &mut self,
+        device: &Device,
+        peripheral: &Peripheral,
+        out: &mut String,
+    ) -> Result<(), Error> {
+        let struct_name = format!("{}Registers", camel_case(&peripheral.name));
+        if let Some(base) = &peripheral.derived_from {
+            if !device.peripherals.iter().any(|other| &other.name == base) {
+                return Err(Error::Svd(format!(
+                    "peripheral {} is derived from unknown peripheral {base}",
+                    peripheral.name
+                )));
+            }
+            if !self.structs.contains(base) {
+                self.warnings.push(format!(
+                    "Skipped peripheral {}, as {base} which it is derived from wasn't generated \
+                     before it.",
+                    peripheral.name
+                ));
+                return Ok(());
+            }
+        }
+        out.push('\n');
+        writeln!(
+            out,
+            "/// The base address of the {} peripheral.\npub const {}_BASE_ADDRESS: usize = {:#x};",
+            peripheral.name,
+            screaming_snake_case(&peripheral.name),
+            peripheral.base_address
+        )
+        .unwrap();
+
+        if let Some(base) = &peripheral.derived_from {
+            writeln!(
+                out,
+                "\n{}pub type {struct_name} = {}Registers;",
+                doc_comment(
+                    "",
+                    &format!("The registers of the {} peripheral.", peripheral.name)
+                ),
+                camel_case(base)
+            )
+            .unwrap();
+            return Ok(());
+        }
+
+        for cluster in &peripheral.clusters {
+            self.warnings.push(format!(
+                "Skipped cluster {cluster} in {}, as clusters aren't supported.",
+                peripheral.name
+            ));
+        }
+        let fields = self.fields(peripheral);
+        if fields.is_empty() {
+            self.warnings.push(format!(
+                "Skipped peripheral {}, which has no supported registers.",
+                peripheral.name
+            ));
+            return Ok(());
+        }
+
+        let description = peripheral
+            .description
+            .clone()
+            .unwrap_or_else(|| format!("The registers of the {} peripheral.", peripheral.name));
+        writeln!(out, "\nregister_map! {{").unwrap();
+        out.push_str(&doc_comment("    ", &description));
+        writeln!(out, "    #[derive(Debug)]\n    #[repr(C)]").unwrap();
+        writeln!(out, "    pub struct {struct_name} {{").unwrap();
+        let mut end = 0;
+        for field in &fields {
+            if field.offset > end {
+                let gap = field.offset - end;
+                if end % 4 == 0 && gap % 4 == 0 {
+                    writeln!(out, "        reserved_{end:x}: [u32; {}],", gap / 4).unwrap();
+                } else {
+                    writeln!(out, "        reserved_{end:x}: [u8; {gap}],").unwrap();
+                }
+            }
+            out.push_str(&doc_comment("        ", &field.doc));
+            writeln!(out, "        pub {}: {},", field.name, field.ty).unwrap();
+            end = field.offset + field.element_size * field.count.unwrap_or(1) as u64;
+        }
+        writeln!(out, "    }}\n}}").unwrap();
+
+        writeln!(out, "\nconst _: () = {{").unwrap();
+        for field in &fields {
+            writeln!(
+                out,
+                "    assert!(core::mem::offset_of!({struct_name}, {}) == {:#x});",
+                field.name, field.offset
+            )
+            .unwrap();
+        }
+        writeln!(out, "}};").unwrap();
+        self.structs.insert(peripheral.name.clone());
+        Ok(())
+    }
+
+    /// Returns the fields for the registers of the given peripheral, sorted by offset, skipping
+    /// any which can't be represented.
+    fn fields(&mut self, peripheral: &Peripheral) -> Vec<Field> {
+        let mut fields = Vec::new();
+        for register in &peripheral.registers {
+            let Some(wrapper) = self.wrapper(peripheral, register) else {
+                continue;
+            };
+            let element_size = register.size / 8;
+            let doc = register_doc(register);
+            match &register.dim {
+                Some(dim) if register.name.contains("[%s]") => {
+                    if dim.increment != element_size {
+                        self.warnings.push(format!(
+                            "Skipped {}.{}, as its dimIncrement isn't the same as its size.",
+                            peripheral.name, register.name
+                        ));
+                        continue;
+                    }
+                    fields.push(Field {
+                        name: snake_case(&register.name.replace("[%s]", "")),
+                        doc,
+                        offset: register.address_offset,
+                        element_size,
+                        count: Some(dim.indices.len()),
+                        ty: format!("[{wrapper}; {}]", dim.indices.len()),
+                    });
+                }
+                Some(dim) => {
+                    for (i, index) in dim.indices.iter().enumerate() {
+                        fields.push(Field {
+                            name: snake_case(&register.name.replace("%s", index)),
+                            doc: doc.replace("%s", index),
+                            offset: register.address_offset + i as u64 * dim.increment,
+                            element_size,
+                            count: None,
+                            ty: wrapper.clone(),
+                        });
+                    }
+                }
+                None => fields.push(Field {
+                    name: snake_case(&register.name),
+                    doc,
+                    offset: register.address_offset,
+                    element_size,
+                    count: None,
+                    ty: wrapper,
+                }),
+            }
+        }
+        fields.sort_by_key(|field| field.offset);
+
+        let mut end = 0;
+        let mut names = BTreeSet::new();
+        fields.retain(|field| {
+            let problem = if field.offset < end {
+                "overlaps an earlier register"
+            } else if field.offset % field.element_size != 0 {
+                "isn't naturally aligned"
+            } else if !names.insert(field.name.clone()) {
+                "has the same name as an earlier register"
+            } else {
+                end = field.offset + field.element_size * field.count.unwrap_or(1) as u64;
+                return true;
+            };
+            self.warnings.push(format!(
+                "Skipped {}.{} at offset {:#x}, which {problem}.",
+                peripheral.name, field.name, field.offset
+            ));
+            false
+        });
+        fields
+    }
+
+    /// Returns the field wrapper type to use for the given register, or `None` if it has an
+    /// unsupported size.
+    fn wrapper(&mut self, peripheral: &Peripheral, register: &Register) -> Option<String> {
+        let ty = match register.size {
+            8 => "u8",
+            16 => "u16",
+            32 => "u32",
+            64 => "u64",
+            size => {
+                self.warnings.push(format!(
+                    "Skipped {}.{}, as its size of {size} bits isn't supported.",
+                    peripheral.name, register.name
+                ));
+                return None;
+            }
+        };
+        let wrapper = match (register.access, register.read_action) {
+            (Access::ReadOnly, false) => "ReadPure",
+            (Access::ReadOnly, true) => "ReadOnly",
+            (Access::WriteOnly, _) => "WriteOnly",
+            (Access::ReadWrite, false) => "ReadPureWrite",
+            (Access::ReadWrite, true) => "ReadWrite",
+        };
+        self.wrappers.insert(wrapper);
+        Some(format!("{wrapper}<{ty}>"))
+    }
+}
+
+/// Returns the doc comment text for the given register, including any side-effects of reads and
+/// writes.
+fn register_doc(register: &Register) -> String {
+    let mut doc = register
+        .description
+        .clone()
+        .unwrap_or_else(|| format!("The {} register.", register.name));
+    if !doc.ends_with('.') {
+        doc.push('.');
+    }
+    if register.read_action {
+        doc.push_str(" Reading has side-effects.");
+    }
+    if let Some(effect) = register
+        .modified_write_values
+        .as_deref()
+        .and_then(write_effect)
+    {
+        write!(doc, " Writing {effect}.").unwrap();
+    }
+    for (field, value) in &register.field_modified_write_values {
+        if let Some(effect) = write_effect(value) {
+            write!(doc, " `{field}`: writing {effect}.").unwrap();
+        }
+    }
+    doc
+}
+
+/// Describes the effect of writes for the given `modifiedWriteValues`.
+fn write_effect(modified_write_values: &str) -> Option<&'static str> {
+    match modified_write_values {
+        "oneToClear" => Some("1 to a bit clears it"),
+        "oneToSet" => Some("1 to a bit sets it"),
+        "oneToToggle" => Some("1 to a bit toggles it"),
+        "zeroToClear" => Some("0 to a bit clears it"),
+        "zeroToSet" => Some("0 to a bit sets it"),
+        "zeroToToggle" => Some("0 to a bit toggles it"),
+        "clear" => Some("any value clears all bits"),
+        "set" => Some("any value sets all bits"),
+        "modify" => Some("any value modifies the bits in a device-specific way"),
+        _ => None,
+    }
+}
+
+/// Formats the given text as a doc comment with the given indentation, wrapped to 100 columns.
+fn doc_comment(indent: &str, text: &str) -> String {
+    let mut out = String::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && indent.len() + 4 + line.len() + 1 + word.len() > 100 {
+            writeln!(out, "{indent}/// {line}").unwrap();
+            line.clear();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    writeln!(out, "{indent}/// {line}").unwrap();
+    out
+}
+
+/// Splits the given SVD name into words, at underscores and other non-alphanumeric characters.
+fn words(name: &str) -> impl Iterator<Item = &str> {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+}
+
+/// Converts the given SVD name to a Rust type name, e.g. `GPIO_A` to `GpioA`.
+fn camel_case(name: &str) -> String {
+    let mut result = String::new();
+    for word in words(name) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            result.push(first.to_ascii_uppercase());
+            result.extend(chars.map(|c| c.to_ascii_lowercase()));
+        }
+    }
+    if !result.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        result.insert(0, 'P');
+    }
+    result
+}
+
+/// Converts the given SVD name to a Rust field name, e.g. `CR1` to `cr1`.
+fn snake_case(name: &str) -> String {
+    let mut result = words(name)
+        .map(|word| word.to_ascii_lowercase())
+        .collect::<Vec<_>>()
+        .join("_");
+    if !result.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        result.insert(0, 'r');
+    }
+    if is_keyword(&result) {
+        result.push('_');
+    }
+    result
+}
+
+/// Converts the given SVD name to a Rust constant name, e.g. `gpioA` to `GPIOA`.
+fn screaming_snake_case(name: &str) -> String {
+    snake_case(name).trim_end_matches('_').to_ascii_uppercase()
+}
+
+fn is_keyword(name: &str) -> bool {
+    matches!(
+        name,
+        "as" | "async"
+            | "await"
+            | "break"
+            | "const"
+            | "continue"
+            | "crate"
+            | "dyn"
+            | "else"
+            | "enum"
+            | "extern"
+            | "false"
+            | "fn"
+            | "for"
+            | "gen"
+            | "if"
+            | "impl"
+            | "in"
+            | "let"
+            | "loop"
+            | "match"
+            | "mod"
+            | "move"
+            | "mut"
+            | "pub"
+            | "ref"
+            | "return"
+            | "self"
+            | "static"
+            | "struct"
+            | "super"
+            | "trait"
+            | "true"
+            | "type"
+            | "unsafe"
+            | "use"
+            | "where"
+            | "while"
+            | "yield"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names() {
+        assert_eq!(camel_case("GPIO_A"), "GpioA");
+        assert_eq!(camel_case("UART0"), "Uart0");
+        assert_eq!(camel_case("2D"), "P2d");
+        assert_eq!(snake_case("CR1"), "cr1");
+        assert_eq!(snake_case("TX-DATA"), "tx_data");
+        assert_eq!(snake_case("TYPE"), "type_");
+        assert_eq!(snake_case("0x10"), "r0x10");
+        assert_eq!(screaming_snake_case("gpioA"), "GPIOA");
+    }
+
+    #[test]
+    fn doc_wrapping() {
+        let text = "word ".repeat(30);
+        let doc = doc_comment("    ", &text);
+        assert_eq!(doc.lines().count(), 2);
+        assert!(doc.lines().all(|line| line.len() <= 100));
+        assert!(doc.lines().all(|line| line.starts_with("    /// word")));
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            generate("<peripherals/>"),
+            Err(Error::Svd(
+                "expected root element `device`, found `peripherals`".to_string()
+            ))
+        );
+        assert_eq!(
+            generate("<device></device>"),
+            Err(Error::Svd("`device` is missing `name`".to_string()))
+        );
+        assert!(matches!(generate("<device>"), Err(Error::Xml { .. })));
+    }
+}
@@ -0,0 +1,36 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Generates safe-mmio register structs from the SVD file given as an argument, and writes them to
+//! standard output.
+
+use std::{env, fs, process::ExitCode};
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().collect();
+    let [_, path] = args.as_slice() else {
+        eprintln!("Usage: safe-mmio-gen <device.svd>");
+        return ExitCode::FAILURE;
+    };
+    let svd = match fs::read_to_string(path) {
+        Ok(svd) => svd,
+        Err(e) => {
+            eprintln!("Failed to read {path}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    match safe_mmio_gen::generate(&svd) {
+        Ok(generated) => {
+            for warning in &generated.warnings {
+                eprintln!("Warning: {warning}");
+            }
+            print!("{}", generated.code);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{path}: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,316 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! The subset of the CMSIS-SVD device description model needed to generate register structs.
+
+use crate::{Error, xml::Element};
+
+/// Which accesses a register allows.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Access {
+    ReadOnly,
+    WriteOnly,
+    ReadWrite,
+}
+
+impl Access {
+    fn parse(value: &str) -> Result<Self, Error> {
+        match value {
+            "read-only" => Ok(Self::ReadOnly),
+            "write-only" | "writeOnce" => Ok(Self::WriteOnly),
+            "read-write" | "read-writeOnce" => Ok(Self::ReadWrite),
+            _ => Err(Error::Svd(format!("invalid access `{value}`"))),
+        }
+    }
+}
+
+/// The register properties which are inherited from the device and peripheral by registers.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Properties {
+    /// The register size in bits.
+    size: u64,
+    access: Access,
+}
+
+impl Properties {
+    fn inherit(self, element: &Element) -> Result<Self, Error> {
+        Ok(Self {
+            size: element
+                .child_text("size")
+                .map_or(Ok(self.size), parse_number)?,
+            access: element
+                .child_text("access")
+                .map_or(Ok(self.access), Access::parse)?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Device {
+    pub name: String,
+    pub peripherals: Vec<Peripheral>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Peripheral {
+    pub name: String,
+    pub description: Option<String>,
+    pub base_address: u64,
+    pub derived_from: Option<String>,
+    pub registers: Vec<Register>,
+    /// The names of clusters, which aren't supported.
+    pub clusters: Vec<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Register {
+    pub name: String,
+    pub description: Option<String>,
+    pub address_offset: u64,
+    /// The register size in bits.
+    pub size: u64,
+    pub access: Access,
+    /// Whether the register or any of its fields has a `readAction`, i.e. reading it has
+    /// side-effects.
+    pub read_action: bool,
+    /// The `modifiedWriteValues` of the register itself.
+    pub modified_write_values: Option<String>,
+    /// The names and `modifiedWriteValues` of fields which have their own.
+    pub field_modified_write_values: Vec<(String, String)>,
+    pub dim: Option<Dim>,
+}
+
+/// How a register is repeated.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Dim {
+    /// The index of each element, to substitute for `%s` in the name.
+    pub indices: Vec<String>,
+    /// The address increment between elements, in bytes.
+    pub increment: u64,
+}
+
+impl Device {
+    /// Extracts the device model from the root `device` element of an SVD file.
+    pub fn parse(root: &Element) -> Result<Self, Error> {
+        if root.name != "device" {
+            return Err(Error::Svd(format!(
+                "expected root element `device`, found `{}`",
+                root.name
+            )));
+        }
+        let properties = Properties {
+            size: 32,
+            access: Access::ReadWrite,
+        }
+        .inherit(root)?;
+        let peripherals = match root.child("peripherals") {
+            Some(peripherals) => peripherals
+                .children_named("peripheral")
+                .map(|peripheral| Peripheral::parse(peripheral, properties))
+                .collect::<Result<_, _>>()?,
+            None => Vec::new(),
+        };
+        Ok(Self {
+            name: required_text(root, "name")?.to_string(),
+            peripherals,
+        })
+    }
+}
+
+impl Peripheral {
+    fn parse(element: &Element, properties: Properties) -> Result<Self, Error> {
+        let name = required_text(element, "name")?.to_string();
+        let properties = properties.inherit(element)?;
+        let mut registers = Vec::new();
+        let mut clusters = Vec::new();
+        if let Some(block) = element.child("registers") {
+            for child in &block.children {
+                match child.name.as_str() {
+                    "register" => registers.push(Register::parse(child, properties)?),
+                    "cluster" => clusters.push(required_text(child, "name")?.to_string()),
+                    _ => {}
+                }
+            }
+        }
+        Ok(Self {
+            base_address: parse_number(required_text(element, "baseAddress")?)?,
+            description: description(element),
+            derived_from: element.attribute("derivedFrom").map(str::to_string),
+            registers,
+            clusters,
+            name,
+        })
+    }
+}
+
+impl Register {
+    fn parse(element: &Element, properties: Properties) -> Result<Self, Error> {
+        let properties = properties.inherit(element)?;
+        let fields = element
+            .child("fields")
+            .into_iter()
+            .flat_map(|fields| fields.children_named("field"));
+        let mut read_action = element.child("readAction").is_some();
+        let mut field_modified_write_values = Vec::new();
+        for field in fields {
+            read_action |= field.child("readAction").is_some();
+            if let Some(value) = field.child_text("modifiedWriteValues") {
+                field_modified_write_values
+                    .push((required_text(field, "name")?.to_string(), value.to_string()));
+            }
+        }
+        let dim = match element.child_text("dim") {
+            Some(count) => Some(Dim::parse(element, parse_number(count)?)?),
+            None => None,
+        };
+        Ok(Self {
+            name: required_text(element, "name")?.to_string(),
+            description: description(element),
+            address_offset: parse_number(required_text(element, "addressOffset")?)?,
+            size: properties.size,
+            access: properties.access,
+            read_action,
+            modified_write_values: element
+                .child_text("modifiedWriteValues")
+                .map(str::to_string),
+            field_modified_write_values,
+            dim,
+        })
+    }
+}
+
+impl Dim {
+    fn parse(element: &Element, count: u64) -> Result<Self, Error> {
+        let increment = parse_number(required_text(element, "dimIncrement")?)?;
+        let indices: Vec<String> = match element.child_text("dimIndex") {
+            Some(list) if list.contains(',') => list
+                .split(',')
+                .map(|index| index.trim().to_string())
+                .collect(),
+            Some(range) => {
+                let Some((start, end)) = range.split_once('-') else {
+                    return Err(Error::Svd(format!("invalid dimIndex `{range}`")));
+                };
+                if let (Ok(start), Ok(end)) = (start.parse::<u64>(), end.parse::<u64>()) {
+                    (start..=end).map(|index| index.to_string()).collect()
+                } else if let (Some(start), Some(end)) = (single_char(start), single_char(end)) {
+                    (start..=end).map(String::from).collect()
+                } else {
+                    return Err(Error::Svd(format!("invalid dimIndex `{range}`")));
+                }
+            }
+            None => (0..count).map(|index| index.to_string()).collect(),
+        };
+        if indices.len() as u64 != count {
+            return Err(Error::Svd(format!(
+                "dimIndex has {} entries but dim is {count}",
+                indices.len()
+            )));
+        }
+        Ok(Self { indices, increment })
+    }
+}
+
+fn single_char(s: &str) -> Option<char> {
+    let mut chars = s.chars();
+    let c = chars.next()?;
+    chars.next().is_none().then_some(c)
+}
+
+fn required_text<'a>(element: &'a Element, name: &str) -> Result<&'a str, Error> {
+    element
+        .child_text(name)
+        .ok_or_else(|| Error::Svd(format!("`{}` is missing `{name}`", element.name)))
+}
+
+/// Returns the description of the given element with whitespace collapsed, if it has a non-empty
+/// one.
+fn description(element: &Element) -> Option<String> {
+    let description = element
+        .child_text("description")?
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!description.is_empty()).then_some(description)
+}
+
+/// Parses an SVD `scaledNonNegativeInteger`, which may be decimal, hexadecimal with a `0x` prefix
+/// or binary with a `#` prefix, optionally followed by a `k`, `M`, `G` or `T` scale.
+pub fn parse_number(text: &str) -> Result<u64, Error> {
+    let invalid = || Error::Svd(format!("invalid number `{text}`"));
+    let (digits, scale) = match text.char_indices().last() {
+        Some((index, 'k' | 'K')) => (&text[..index], 1 << 10),
+        Some((index, 'm' | 'M')) => (&text[..index], 1 << 20),
+        Some((index, 'g' | 'G')) => (&text[..index], 1 << 30),
+        Some((index, 't' | 'T')) => (&text[..index], 1 << 40),
+        _ => (text, 1),
+    };
+    let value = if let Some(hex) = digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+    {
+        u64::from_str_radix(hex, 16)
+    } else if let Some(binary) = digits.strip_prefix('#') {
+        u64::from_str_radix(binary, 2)
+    } else {
+        digits.parse()
+    }
+    .map_err(|_| invalid())?;
+    value.checked_mul(scale).ok_or_else(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers() {
+        assert_eq!(parse_number("42"), Ok(42));
+        assert_eq!(parse_number("0x40000000"), Ok(0x4000_0000));
+        assert_eq!(parse_number("0X1f"), Ok(0x1f));
+        assert_eq!(parse_number("#101"), Ok(5));
+        assert_eq!(parse_number("4k"), Ok(4096));
+        assert_eq!(parse_number("0x1M"), Ok(1 << 20));
+        assert!(parse_number("").is_err());
+        assert!(parse_number("twelve").is_err());
+    }
+
+    #[test]
+    fn dim_indices() {
+        let dim = |count: &str, index: Option<&str>| {
+            let mut register = Element {
+                name: "register".to_string(),
+                ..Default::default()
+            };
+            for (name, text) in [
+                ("dim", Some(count)),
+                ("dimIncrement", Some("4")),
+                ("dimIndex", index),
+            ] {
+                if let Some(text) = text {
+                    register.children.push(Element {
+                        name: name.to_string(),
+                        text: text.to_string(),
+                        ..Default::default()
+                    });
+                }
+            }
+            Dim::parse(&register, parse_number(count).unwrap()).map(|dim| dim.indices)
+        };
+        assert_eq!(dim("2", None), Ok(vec!["0".to_string(), "1".to_string()]));
+        assert_eq!(
+            dim("2", Some("3-4")),
+            Ok(vec!["3".to_string(), "4".to_string()])
+        );
+        assert_eq!(
+            dim("2", Some("A-B")),
+            Ok(vec!["A".to_string(), "B".to_string()])
+        );
+        assert_eq!(
+            dim("2", Some("TX, RX")),
+            Ok(vec!["TX".to_string(), "RX".to_string()])
+        );
+        assert!(dim("3", Some("A-B")).is_err());
+    }
+}
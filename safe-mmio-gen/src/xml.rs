@@ -0,0 +1,270 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! A minimal XML parser, just enough for SVD files.
+//!
+//! Namespaces, DTDs and entities other than the predefined ones aren't supported.
+
+use crate::Error;
+
+/// An XML element, with its attributes, child elements and text content.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Element>,
+    pub text: String,
+}
+
+impl Element {
+    /// Returns the first child element with the given name, if any.
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    /// Returns all child elements with the given name.
+    pub fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    /// Returns the trimmed text content of the first child element with the given name, if any.
+    pub fn child_text(&self, name: &str) -> Option<&str> {
+        self.child(name).map(|child| child.text.trim())
+    }
+
+    /// Returns the value of the attribute with the given name, if any.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Parses the given XML document and returns its root element.
+pub fn parse(input: &str) -> Result<Element, Error> {
+    let mut parser = Parser { input, pos: 0 };
+    parser.skip_misc()?;
+    let root = parser.element()?;
+    parser.skip_misc()?;
+    if parser.pos != input.len() {
+        return Err(parser.error("unexpected content after root element"));
+    }
+    Ok(root)
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn rest(&self) -> &str {
+        &self.input[self.pos..]
+    }
+
+    fn error(&self, message: &str) -> Error {
+        Error::Xml {
+            line: self.input[..self.pos].matches('\n').count() + 1,
+            message: message.to_string(),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.pos = self.input.len() - self.rest().trim_start().len();
+    }
+
+    /// Skips past the next occurrence of `end`.
+    fn skip_past(&mut self, end: &str) -> Result<(), Error> {
+        let Some(index) = self.rest().find(end) else {
+            return Err(self.error(&format!("missing `{end}`")));
+        };
+        self.pos += index + end.len();
+        Ok(())
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), Error> {
+        if !self.rest().starts_with(token) {
+            return Err(self.error(&format!("expected `{token}`")));
+        }
+        self.pos += token.len();
+        Ok(())
+    }
+
+    /// Skips whitespace, comments, processing instructions and document type declarations.
+    fn skip_misc(&mut self) -> Result<(), Error> {
+        loop {
+            self.skip_whitespace();
+            if self.rest().starts_with("<?") {
+                self.skip_past("?>")?;
+            } else if self.rest().starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if self.rest().starts_with("<!") {
+                self.skip_past(">")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn name(&mut self) -> Result<String, Error> {
+        let length = self
+            .rest()
+            .find(|c: char| c.is_whitespace() || matches!(c, '/' | '>' | '='))
+            .unwrap_or(self.rest().len());
+        if length == 0 {
+            return Err(self.error("expected name"));
+        }
+        let name = self.rest()[..length].to_string();
+        self.pos += length;
+        Ok(name)
+    }
+
+    fn element(&mut self) -> Result<Element, Error> {
+        self.expect("<")?;
+        let mut element = Element {
+            name: self.name()?,
+            ..Default::default()
+        };
+        loop {
+            self.skip_whitespace();
+            if self.rest().starts_with("/>") {
+                self.pos += 2;
+                return Ok(element);
+            } else if self.rest().starts_with('>') {
+                self.pos += 1;
+                break;
+            }
+            let name = self.name()?;
+            self.skip_whitespace();
+            self.expect("=")?;
+            self.skip_whitespace();
+            let quote = if self.rest().starts_with('"') {
+                "\""
+            } else {
+                "'"
+            };
+            self.expect(quote)?;
+            let Some(length) = self.rest().find(quote) else {
+                return Err(self.error("unterminated attribute value"));
+            };
+            let value = self.unescape(&self.rest()[..length])?;
+            self.pos += length + 1;
+            element.attributes.push((name, value));
+        }
+        loop {
+            if self.rest().starts_with("</") {
+                self.pos += 2;
+                if self.name()? != element.name {
+                    return Err(self.error(&format!("expected `</{}>`", element.name)));
+                }
+                self.skip_whitespace();
+                self.expect(">")?;
+                return Ok(element);
+            } else if self.rest().starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if let Some(rest) = self.rest().strip_prefix("<![CDATA[") {
+                let Some(length) = rest.find("]]>") else {
+                    return Err(self.error("unterminated CDATA section"));
+                };
+                element.text.push_str(&rest[..length]);
+                self.pos += "<![CDATA[".len() + length + "]]>".len();
+            } else if self.rest().starts_with("<?") {
+                self.skip_past("?>")?;
+            } else if self.rest().starts_with('<') {
+                element.children.push(self.element()?);
+            } else if self.rest().is_empty() {
+                return Err(self.error(&format!("missing `</{}>`", element.name)));
+            } else {
+                let length = self.rest().find('<').unwrap_or(self.rest().len());
+                let text = self.unescape(&self.rest()[..length])?;
+                element.text.push_str(&text);
+                self.pos += length;
+            }
+        }
+    }
+
+    /// Replaces entity and character references in the given text.
+    fn unescape(&self, text: &str) -> Result<String, Error> {
+        let mut result = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find('&') {
+            result.push_str(&rest[..start]);
+            let Some(end) = rest[start..].find(';') else {
+                return Err(self.error("unterminated entity reference"));
+            };
+            let entity = &rest[start + 1..start + end];
+            let c = match entity {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ => {
+                    if let Some(hex) = entity.strip_prefix("#x") {
+                        u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+                    } else if let Some(decimal) = entity.strip_prefix('#') {
+                        decimal.parse().ok().and_then(char::from_u32)
+                    } else {
+                        None
+                    }
+                }
+            };
+            let Some(c) = c else {
+                return Err(self.error(&format!("unknown entity `&{entity};`")));
+            };
+            result.push(c);
+            rest = &rest[start + end + 1..];
+        }
+        result.push_str(rest);
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_document() {
+        let root = parse(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+            <!-- A comment. -->
+            <device schemaVersion='1.1' xmlns:xs="http://www.w3.org/2001/XMLSchema-instance">
+              <name>Test &amp; device</name>
+              <empty/>
+              <description><![CDATA[<raw>]]> &#x41;&#66;</description>
+            </device>
+            "#,
+        )
+        .unwrap();
+        assert_eq!(root.name, "device");
+        assert_eq!(root.attribute("schemaVersion"), Some("1.1"));
+        assert_eq!(root.child_text("name"), Some("Test & device"));
+        assert_eq!(root.child_text("empty"), Some(""));
+        assert_eq!(root.child_text("description"), Some("<raw> AB"));
+        assert_eq!(root.children_named("name").count(), 1);
+        assert_eq!(root.child("missing"), None);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            parse("<a>\n<b></a>"),
+            Err(Error::Xml {
+                line: 2,
+                message: "expected `</b>`".to_string()
+            })
+        );
+        assert_eq!(
+            parse("<a>&nbsp;</a>"),
+            Err(Error::Xml {
+                line: 1,
+                message: "unknown entity `&nbsp;`".to_string()
+            })
+        );
+        assert!(parse("<a></a><b/>").is_err());
+        assert!(parse("<a>").is_err());
+    }
+}
@@ -0,0 +1,56 @@
+// Generated by safe-mmio-gen from the SVD file for EXAMPLE. Do not edit.
+
+use safe_mmio::{
+    fields::{ReadOnly, ReadPure, ReadPureWrite, ReadWrite, WriteOnly},
+    register_map,
+};
+
+/// The base address of the UART0 peripheral.
+pub const UART0_BASE_ADDRESS: usize = 0x40001000;
+
+register_map! {
+    /// A UART, with a FIFO for each direction.
+    #[derive(Debug)]
+    #[repr(C)]
+    pub struct Uart0Registers {
+        /// Data register. Reading has side-effects.
+        pub dr: ReadWrite<u32>,
+        reserved_4: [u32; 5],
+        /// Flag register.
+        pub fr: ReadPure<u32>,
+        /// Receive status, cleared when read. Reading has side-effects.
+        pub rxstat: ReadOnly<u16>,
+        reserved_1e: [u8; 2],
+        /// Control register.
+        pub ctrl: ReadPureWrite<u32>,
+        /// Interrupt mask set/clear registers.
+        pub imsc: [ReadPureWrite<u32>; 2],
+        /// Interrupt clear register. Writing 1 to a bit clears it.
+        pub icr: WriteOnly<u32>,
+        /// The STATUS register. `OVERRUN`: writing 1 to a bit clears it.
+        pub status: ReadPureWrite<u32>,
+        reserved_34: [u32; 3],
+        /// TX timeout.
+        pub tx_timeout: ReadPureWrite<u64>,
+        /// RX timeout.
+        pub rx_timeout: ReadPureWrite<u64>,
+    }
+}
+
+const _: () = {
+    assert!(core::mem::offset_of!(Uart0Registers, dr) == 0x0);
+    assert!(core::mem::offset_of!(Uart0Registers, fr) == 0x18);
+    assert!(core::mem::offset_of!(Uart0Registers, rxstat) == 0x1c);
+    assert!(core::mem::offset_of!(Uart0Registers, ctrl) == 0x20);
+    assert!(core::mem::offset_of!(Uart0Registers, imsc) == 0x24);
+    assert!(core::mem::offset_of!(Uart0Registers, icr) == 0x2c);
+    assert!(core::mem::offset_of!(Uart0Registers, status) == 0x30);
+    assert!(core::mem::offset_of!(Uart0Registers, tx_timeout) == 0x40);
+    assert!(core::mem::offset_of!(Uart0Registers, rx_timeout) == 0x48);
+};
+
+/// The base address of the UART1 peripheral.
+pub const UART1_BASE_ADDRESS: usize = 0x40002000;
+
+/// The registers of the UART1 peripheral.
+pub type Uart1Registers = Uart0Registers;
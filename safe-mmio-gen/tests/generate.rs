@@ -0,0 +1,39 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Tests that the code generated for an example SVD file is as expected, and compiles.
+
+mod example {
+    include!("example.rs");
+}
+
+use example::{Uart0Registers, Uart1Registers};
+use safe_mmio::register_map::{Access, RegisterMap};
+use safe_mmio_gen::generate;
+
+#[test]
+fn matches_expected() {
+    let generated = generate(include_str!("example.svd")).unwrap();
+    assert_eq!(generated.code, include_str!("example.rs"));
+    assert_eq!(
+        generated.warnings,
+        [
+            "Skipped cluster DMA in UART0, as clusters aren't supported.",
+            "Skipped UART0.ctrl_alt at offset 0x20, which overlaps an earlier register.",
+        ]
+    );
+}
+
+#[test]
+fn wrappers() {
+    let access = |offset| Uart0Registers::lookup(offset).unwrap().access;
+    assert_eq!(access(0x0), Access::ReadWrite);
+    assert_eq!(access(0x18), Access::ReadPure);
+    assert_eq!(access(0x1c), Access::ReadOnly);
+    assert_eq!(access(0x20), Access::ReadPureWrite);
+    assert_eq!(access(0x2c), Access::WriteOnly);
+    assert_eq!(Uart1Registers::lookup(0x48).unwrap().name, "rx_timeout");
+    assert_eq!(example::UART0_BASE_ADDRESS, 0x4000_1000);
+    assert_eq!(example::UART1_BASE_ADDRESS, 0x4000_2000);
+}
@@ -2,12 +2,32 @@
 // This project is dual-licensed under Apache 2.0 and MIT terms.
 // See LICENSE-APACHE and LICENSE-MIT for details.
 
-use crate::{SharedMmioPointer, UniqueMmioPointer};
+use crate::{SharedMmioPointer, UniqueMmioPointer, access_width};
 use core::ptr::NonNull;
 use zerocopy::{FromBytes, Immutable, IntoBytes};
 
+/// Defines functions to read and write a `$t` with a single load or store instruction.
+///
+/// None of the `asm!` blocks have the `nomem` or `readonly` options, so the compiler must assume
+/// that they read and write arbitrary memory. This keeps MMIO accesses in program order relative to
+/// ordinary memory accesses, such as to DMA buffers, and prevents them from being merged or
+/// removed. They don't touch the stack or the condition flags, so are marked `nostack` and
+/// `preserves_flags`.
+///
+/// `$read_pure_name` is instead `pure` and `readonly`, so that the compiler may merge repeated
+/// reads or remove unused ones. This is only correct for registers which can be read without
+/// side-effects, and whose value the program doesn't expect to change between reads without an
+/// intervening write.
 macro_rules! asm_mmio {
-    ($t:ty, $read_name:ident, $read_assembly:literal, $write_name:ident, $write_assembly:literal) => {
+    (
+        $t:ty,
+        $read_name:ident,
+        $read_pure_name:ident,
+        $read_assembly:literal,
+        $write_name:ident,
+        $write_assembly:literal
+    ) => {
+        #[inline(always)]
         unsafe fn $read_name(ptr: *const $t) -> $t {
             let value;
             unsafe {
@@ -15,17 +35,34 @@ macro_rules! asm_mmio {
                     $read_assembly,
                     value = out(reg) value,
                     ptr = in(reg) ptr,
+                    options(nostack, preserves_flags),
                 );
             }
             value
         }
 
+        #[inline(always)]
+        unsafe fn $read_pure_name(ptr: *const $t) -> $t {
+            let value;
+            unsafe {
+                core::arch::asm!(
+                    $read_assembly,
+                    value = out(reg) value,
+                    ptr = in(reg) ptr,
+                    options(pure, readonly, nostack, preserves_flags),
+                );
+            }
+            value
+        }
+
+        #[inline(always)]
         unsafe fn $write_name(ptr: *mut $t, value: $t) {
             unsafe {
                 core::arch::asm!(
                     $write_assembly,
                     value = in(reg) value,
                     ptr = in(reg) ptr,
+                    options(nostack, preserves_flags),
                 );
             }
         }
@@ -35,6 +72,7 @@ macro_rules! asm_mmio {
 asm_mmio!(
     u8,
     read_u8,
+    read_pure_u8,
     "ldrb {value:w}, [{ptr}]",
     write_u8,
     "strb {value:w}, [{ptr}]"
@@ -42,6 +80,7 @@ asm_mmio!(
 asm_mmio!(
     u16,
     read_u16,
+    read_pure_u16,
     "ldrh {value:w}, [{ptr}]",
     write_u16,
     "strh {value:w}, [{ptr}]"
@@ -49,6 +88,7 @@ asm_mmio!(
 asm_mmio!(
     u32,
     read_u32,
+    read_pure_u32,
     "ldr {value:w}, [{ptr}]",
     write_u32,
     "str {value:w}, [{ptr}]"
@@ -56,6 +96,7 @@ asm_mmio!(
 asm_mmio!(
     u64,
     read_u64,
+    read_pure_u64,
     "ldr {value:x}, [{ptr}]",
     write_u64,
     "str {value:x}, [{ptr}]"
@@ -64,8 +105,9 @@ asm_mmio!(
 impl<T: FromBytes + IntoBytes> UniqueMmioPointer<'_, T> {
     /// Performs an MMIO read and returns the value.
     ///
-    /// If `T` is exactly 1, 2, 4 or 8 bytes long then this will be a single operation. Otherwise
-    /// it will be split into several, reading chunks as large as possible.
+    /// If `T` is 1, 2, 4 or 8 bytes long and aligned to its size then this will be a single
+    /// operation. Otherwise it will be split into several, in order of increasing address, each as
+    /// wide as the alignment of `T` up to a maximum of 8 bytes.
     ///
     /// Note that this takes `&mut self` rather than `&self` because an MMIO read may cause
     /// side-effects that change the state of the device.
@@ -73,27 +115,33 @@ impl<T: FromBytes + IntoBytes> UniqueMmioPointer<'_, T> {
     /// # Safety
     ///
     /// This field must be safe to perform an MMIO read from.
+    #[inline(always)]
     pub unsafe fn read_unsafe(&mut self) -> T {
-        unsafe { mmio_read(self.regs) }
+        let mut value = unsafe { mmio_read(self.regs) };
+        self.record_read(value.as_mut_bytes());
+        value
     }
 }
 
 impl<T: Immutable + IntoBytes> UniqueMmioPointer<'_, T> {
     /// Performs an MMIO write of the given value.
     ///
-    /// If `T` is exactly 1, 2, 4 or 8 bytes long then this will be a single operation. Otherwise
-    /// it will be split into several, writing chunks as large as possible.
+    /// If `T` is 1, 2, 4 or 8 bytes long and aligned to its size then this will be a single
+    /// operation. Otherwise it will be split into several, in order of increasing address, each as
+    /// wide as the alignment of `T` up to a maximum of 8 bytes.
     ///
     /// # Safety
     ///
     /// This field must be safe to perform an MMIO write to.
+    #[inline(always)]
     pub unsafe fn write_unsafe(&mut self, value: T) {
-        match size_of::<T>() {
-            1 => unsafe { write_u8(self.regs.cast().as_ptr(), value.as_bytes()[0]) },
-            2 => unsafe { write_u16(self.regs.cast().as_ptr(), convert(value)) },
-            4 => unsafe { write_u32(self.regs.cast().as_ptr(), convert(value)) },
-            8 => unsafe { write_u64(self.regs.cast().as_ptr(), convert(value)) },
-            _ => unsafe { write_slice(self.regs.cast(), value.as_bytes()) },
+        self.record_write(value.as_bytes());
+        match (size_of::<T>(), access_width::<T>()) {
+            (1, _) => unsafe { write_u8(self.regs.cast().as_ptr(), value.as_bytes()[0]) },
+            (2, 2) => unsafe { write_u16(self.regs.cast().as_ptr(), convert(value)) },
+            (4, 4) => unsafe { write_u32(self.regs.cast().as_ptr(), convert(value)) },
+            (8, 8) => unsafe { write_u64(self.regs.cast().as_ptr(), convert(value)) },
+            (_, width) => unsafe { write_slice(self.regs.cast(), value.as_bytes(), width) },
         }
     }
 }
@@ -101,15 +149,42 @@ impl<T: Immutable + IntoBytes> UniqueMmioPointer<'_, T> {
 impl<T: FromBytes + IntoBytes> SharedMmioPointer<'_, T> {
     /// Performs an MMIO read and returns the value.
     ///
-    /// If `T` is exactly 1, 2, 4 or 8 bytes long then this will be a single operation. Otherwise
-    /// it will be split into several, reading chunks as large as possible.
+    /// If `T` is 1, 2, 4 or 8 bytes long and aligned to its size then this will be a single
+    /// operation. Otherwise it will be split into several, in order of increasing address, each as
+    /// wide as the alignment of `T` up to a maximum of 8 bytes.
     ///
     /// # Safety
     ///
     /// This field must be safe to perform an MMIO read from, and doing so must not cause any
     /// side-effects.
+    #[inline(always)]
     pub unsafe fn read_unsafe(&self) -> T {
-        unsafe { mmio_read(self.regs) }
+        let mut value = unsafe { mmio_read(self.regs) };
+        self.record_read(value.as_mut_bytes());
+        value
+    }
+}
+
+impl<T: FromBytes + IntoBytes> SharedMmioPointer<'_, T> {
+    /// Performs an MMIO read which the compiler may merge with other reads of the same register or
+    /// remove if the value is unused, as if it were an ordinary memory read.
+    ///
+    /// # Safety
+    ///
+    /// This field must be safe to perform an MMIO read from, doing so must not cause any
+    /// side-effects, and its value must not change other than by writes from this program.
+    #[inline(always)]
+    pub(crate) unsafe fn read_constant_unsafe(&self) -> T {
+        let ptr = self.regs;
+        let mut value = match (size_of::<T>(), access_width::<T>()) {
+            (1, _) => convert(unsafe { read_pure_u8(ptr.cast().as_ptr()) }),
+            (2, 2) => convert(unsafe { read_pure_u16(ptr.cast().as_ptr()) }),
+            (4, 4) => convert(unsafe { read_pure_u32(ptr.cast().as_ptr()) }),
+            (8, 8) => convert(unsafe { read_pure_u64(ptr.cast().as_ptr()) }),
+            _ => unsafe { mmio_read(ptr) },
+        };
+        self.record_read(value.as_mut_bytes());
+        value
     }
 }
 
@@ -118,68 +193,403 @@ impl<T: FromBytes + IntoBytes> SharedMmioPointer<'_, T> {
 /// # Safety
 ///
 /// The pointer must be valid to perform an MMIO read from.
+#[inline(always)]
 unsafe fn mmio_read<T: FromBytes + IntoBytes>(ptr: NonNull<T>) -> T {
-    match size_of::<T>() {
-        1 => convert(unsafe { read_u8(ptr.cast().as_ptr()) }),
-        2 => convert(unsafe { read_u16(ptr.cast().as_ptr()) }),
-        4 => convert(unsafe { read_u32(ptr.cast().as_ptr()) }),
-        8 => convert(unsafe { read_u64(ptr.cast().as_ptr()) }),
-        _ => {
+    match (size_of::<T>(), access_width::<T>()) {
+        (1, _) => convert(unsafe { read_u8(ptr.cast().as_ptr()) }),
+        (2, 2) => convert(unsafe { read_u16(ptr.cast().as_ptr()) }),
+        (4, 4) => convert(unsafe { read_u32(ptr.cast().as_ptr()) }),
+        (8, 8) => convert(unsafe { read_u64(ptr.cast().as_ptr()) }),
+        (_, width) => {
             let mut value = T::new_zeroed();
-            unsafe { read_slice(ptr.cast(), value.as_mut_bytes()) };
+            unsafe { read_slice(ptr.cast(), value.as_mut_bytes(), width) };
             value
         }
     }
 }
 
+#[inline(always)]
 fn convert<T: Immutable + IntoBytes, U: FromBytes>(value: T) -> U {
     U::read_from_bytes(value.as_bytes()).unwrap()
 }
 
-unsafe fn write_slice(ptr: NonNull<u8>, slice: &[u8]) {
-    if let Some((first, rest)) = slice.split_at_checked(8) {
-        unsafe {
-            write_u64(ptr.cast().as_ptr(), u64::read_from_bytes(first).unwrap());
-            write_slice(ptr.add(8), rest);
+/// Writes the given bytes with MMIO accesses of the given width, which must divide the length of
+/// the slice and the alignment of `ptr`.
+unsafe fn write_slice(ptr: NonNull<u8>, slice: &[u8], width: usize) {
+    match width {
+        8 => {
+            for (i, chunk) in slice.chunks_exact(8).enumerate() {
+                let value = u64::read_from_bytes(chunk).unwrap();
+                unsafe { write_u64(ptr.add(i * 8).cast().as_ptr(), value) };
+            }
+        }
+        4 => {
+            for (i, chunk) in slice.chunks_exact(4).enumerate() {
+                let value = u32::read_from_bytes(chunk).unwrap();
+                unsafe { write_u32(ptr.add(i * 4).cast().as_ptr(), value) };
+            }
+        }
+        2 => {
+            for (i, chunk) in slice.chunks_exact(2).enumerate() {
+                let value = u16::read_from_bytes(chunk).unwrap();
+                unsafe { write_u16(ptr.add(i * 2).cast().as_ptr(), value) };
+            }
+        }
+        _ => {
+            for (i, byte) in slice.iter().enumerate() {
+                unsafe { write_u8(ptr.add(i).as_ptr(), *byte) };
+            }
+        }
+    }
+}
+
+/// Reads into the given slice with MMIO accesses of the given width, which must divide the length
+/// of the slice and the alignment of `ptr`.
+unsafe fn read_slice(ptr: NonNull<u8>, slice: &mut [u8], width: usize) {
+    match width {
+        8 => {
+            for (i, chunk) in slice.chunks_exact_mut(8).enumerate() {
+                unsafe { read_u64(ptr.add(i * 8).cast().as_ptr()) }
+                    .write_to(chunk)
+                    .unwrap();
+            }
         }
-    } else if let Some((first, rest)) = slice.split_at_checked(4) {
-        unsafe {
-            write_u32(ptr.cast().as_ptr(), u32::read_from_bytes(first).unwrap());
-            write_slice(ptr.add(4), rest);
+        4 => {
+            for (i, chunk) in slice.chunks_exact_mut(4).enumerate() {
+                unsafe { read_u32(ptr.add(i * 4).cast().as_ptr()) }
+                    .write_to(chunk)
+                    .unwrap();
+            }
         }
-    } else if let Some((first, rest)) = slice.split_at_checked(2) {
-        unsafe {
-            write_u16(ptr.cast().as_ptr(), u16::read_from_bytes(first).unwrap());
-            write_slice(ptr.add(2), rest);
+        2 => {
+            for (i, chunk) in slice.chunks_exact_mut(2).enumerate() {
+                unsafe { read_u16(ptr.add(i * 2).cast().as_ptr()) }
+                    .write_to(chunk)
+                    .unwrap();
+            }
         }
-    } else if let [first, rest @ ..] = slice {
-        unsafe {
-            write_u8(ptr.as_ptr(), *first);
-            write_slice(ptr.add(1), rest);
+        _ => {
+            for (i, byte) in slice.iter_mut().enumerate() {
+                *byte = unsafe { read_u8(ptr.add(i).as_ptr()) };
+            }
         }
     }
 }
 
-unsafe fn read_slice(ptr: NonNull<u8>, slice: &mut [u8]) {
-    if let Some((first, rest)) = slice.split_at_mut_checked(8) {
-        unsafe {
-            read_u64(ptr.cast().as_ptr()).write_to(first).unwrap();
-            read_slice(ptr.add(8), rest);
-        }
-    } else if let Some((first, rest)) = slice.split_at_mut_checked(4) {
-        unsafe {
-            read_u32(ptr.cast().as_ptr()).write_to(first).unwrap();
-            read_slice(ptr.add(4), rest);
-        }
-    } else if let Some((first, rest)) = slice.split_at_mut_checked(2) {
-        unsafe {
-            read_u16(ptr.cast().as_ptr()).write_to(first).unwrap();
-            read_slice(ptr.add(2), rest);
-        }
-    } else if let [first, rest @ ..] = slice {
-        unsafe {
-            *first = read_u8(ptr.as_ptr());
-            read_slice(ptr.add(1), rest);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The encoding of `ret`, which marks the end of the functions we scan.
+    const RET: u32 = 0xd65f_03c0;
+
+    /// Returns the only load or store in the function at `f` which doesn't use the stack pointer or
+    /// frame pointer as its base register, panicking if there is not exactly one.
+    ///
+    /// Accesses relative to `sp` or `x29` are stack frame setup or spills in unoptimised builds, so
+    /// are ignored.
+    ///
+    /// # Safety
+    ///
+    /// `f` must be the address of a function, which must end with a `ret` instruction.
+    unsafe fn single_mmio_access(f: *const u32) -> u32 {
+        let mut access = None;
+        for i in 0..256 {
+            // SAFETY: The caller promised that `f` points to a function ending with `ret`, and we
+            // stop reading once we find it.
+            let instruction = unsafe { f.add(i).read() };
+            if instruction == RET {
+                return access.unwrap_or_else(|| panic!("No MMIO access found in {f:?}"));
+            }
+            // Loads and stores are encoded with op0 = x1x0.
+            let is_load_store = instruction & 0x0a00_0000 == 0x0800_0000;
+            let rn = (instruction >> 5) & 0x1f;
+            if is_load_store && rn != 29 && rn != 31 {
+                if let Some(previous) = access {
+                    panic!(
+                        "Found multiple MMIO accesses in {f:?}: {previous:#010x}, {instruction:#010x}"
+                    );
+                }
+                access = Some(instruction);
+            }
+        }
+        panic!("No ret instruction found in function at {f:?}");
+    }
+
+    /// Returns the number of instructions in the function at `f`, including the final `ret`.
+    ///
+    /// # Safety
+    ///
+    /// `f` must be the address of a function, which must end with a `ret` instruction.
+    #[cfg(not(debug_assertions))]
+    unsafe fn instruction_count(f: *const u32) -> usize {
+        for i in 0..256 {
+            // SAFETY: The caller promised that `f` points to a function ending with `ret`, and we
+            // stop reading once we find it.
+            if unsafe { f.add(i).read() } == RET {
+                return i + 1;
+            }
+        }
+        panic!("No ret instruction found in function at {f:?}");
+    }
+
+    /// Asserts that the function at `f` performs exactly one MMIO access, and that it is a single
+    /// register load or store of the given size with no writeback, so that the exception syndrome
+    /// can be decoded by a hypervisor.
+    fn assert_single_iss_access(f: *const u32, size_bytes: u32, load: bool) {
+        // SAFETY: All callers pass the address of a function.
+        let instruction = unsafe { single_mmio_access(f) };
+        // Load/store register (unsigned immediate) with V = 0, i.e. not a SIMD&FP register.
+        assert_eq!(
+            instruction & 0x3f00_0000,
+            0x3900_0000,
+            "Unexpected instruction form {instruction:#010x}"
+        );
+        // No offset, as the address is computed before the asm block.
+        assert_eq!(
+            (instruction >> 10) & 0xfff,
+            0,
+            "Unexpected offset in {instruction:#010x}"
+        );
+        assert_eq!(
+            1 << (instruction >> 30),
+            size_bytes,
+            "Wrong size for {instruction:#010x}"
+        );
+        // opc is 0b00 for stores and 0b01 for zero-extending loads.
+        assert_eq!(
+            (instruction >> 22) & 0b11,
+            u32::from(load),
+            "Wrong direction for {instruction:#010x}"
+        );
+    }
+
+    #[test]
+    fn asm_reads() {
+        assert_single_iss_access(read_u8 as *const u32, 1, true);
+        assert_single_iss_access(read_u16 as *const u32, 2, true);
+        assert_single_iss_access(read_u32 as *const u32, 4, true);
+        assert_single_iss_access(read_u64 as *const u32, 8, true);
+    }
+
+    #[test]
+    fn asm_writes() {
+        assert_single_iss_access(write_u8 as *const u32, 1, false);
+        assert_single_iss_access(write_u16 as *const u32, 2, false);
+        assert_single_iss_access(write_u32 as *const u32, 4, false);
+        assert_single_iss_access(write_u64 as *const u32, 8, false);
+    }
+
+    /// These only compile to a single instruction once everything is inlined, so are only checked
+    /// in optimised builds.
+    #[cfg(not(debug_assertions))]
+    mod inlined {
+        use super::*;
+        #[cfg(safe_mmio_pure_reads)]
+        use crate::fields::ReadPure;
+        use crate::fields::{ReadConstant, ReadOnly, ReadWrite, WriteOnly};
+
+        #[inline(never)]
+        fn read_read_write_u32(mut regs: UniqueMmioPointer<ReadWrite<u32>>) -> u32 {
+            regs.read()
+        }
+
+        #[inline(never)]
+        fn read_read_only_u64(mut regs: UniqueMmioPointer<ReadOnly<u64>>) -> u64 {
+            regs.read()
+        }
+
+        #[inline(never)]
+        fn read_shared_u16(regs: SharedMmioPointer<u16>) -> u16 {
+            // SAFETY: The pointer is never called in this test, only disassembled.
+            unsafe { regs.read_unsafe() }
+        }
+
+        #[inline(never)]
+        fn write_read_write_u32(mut regs: UniqueMmioPointer<ReadWrite<u32>>, value: u32) {
+            regs.write(value);
+        }
+
+        #[inline(never)]
+        fn write_write_only_u8(mut regs: UniqueMmioPointer<WriteOnly<u8>>, value: u8) {
+            regs.write(value);
+        }
+
+        #[inline(never)]
+        fn read_volatile_u32(regs: *const u32) -> u32 {
+            // SAFETY: The pointer is never called in this test, only disassembled.
+            unsafe { regs.read_volatile() }
+        }
+
+        #[inline(never)]
+        fn write_volatile_u32(regs: *mut u32, value: u32) {
+            // SAFETY: The pointer is never called in this test, only disassembled.
+            unsafe { regs.write_volatile(value) }
+        }
+
+        #[test]
+        fn inlined_reads() {
+            assert_single_iss_access(read_read_write_u32 as *const u32, 4, true);
+            assert_single_iss_access(read_read_only_u64 as *const u32, 8, true);
+            assert_single_iss_access(read_shared_u16 as *const u32, 2, true);
+        }
+
+        #[test]
+        fn inlined_writes() {
+            assert_single_iss_access(write_read_write_u32 as *const u32, 4, false);
+            assert_single_iss_access(write_write_only_u8 as *const u32, 1, false);
+        }
+
+        #[inline(never)]
+        fn read_constant_twice_u16(regs: SharedMmioPointer<ReadConstant<u16>>) -> u16 {
+            regs.read().wrapping_add(regs.read())
+        }
+
+        /// Repeated reads of a `ReadConstant` register should always be merged.
+        #[test]
+        fn constant_reads_merged() {
+            assert_single_iss_access(read_constant_twice_u16 as *const u32, 2, true);
+        }
+
+        #[cfg(safe_mmio_pure_reads)]
+        #[inline(never)]
+        fn read_pure_twice_u32(regs: SharedMmioPointer<ReadPure<u32>>) -> u32 {
+            regs.read().wrapping_add(regs.read())
+        }
+
+        /// With `cfg(safe_mmio_pure_reads)` the compiler should merge repeated reads of a
+        /// `ReadPure` register.
+        #[cfg(safe_mmio_pure_reads)]
+        #[test]
+        fn pure_reads_merged() {
+            assert_single_iss_access(read_pure_twice_u32 as *const u32, 4, true);
+        }
+
+        /// Checks that the safe API is no larger than a hand-written volatile access, other than
+        /// possibly a `mov` to get the value in or out of the register chosen for the inline
+        /// assembly.
+        #[test]
+        fn parity_with_volatile() {
+            // SAFETY: We pass the addresses of functions.
+            unsafe {
+                assert!(
+                    instruction_count(read_read_write_u32 as *const u32)
+                        <= instruction_count(read_volatile_u32 as *const u32) + 1
+                );
+                assert!(
+                    instruction_count(write_read_write_u32 as *const u32)
+                        <= instruction_count(write_volatile_u32 as *const u32) + 1
+                );
+            }
+        }
+    }
+
+    /// Runs the same accesses through this backend and the volatile backend against ordinary
+    /// memory, to catch mistakes in the assembly templates such as the wrong register class or a
+    /// missing clobber.
+    mod differential {
+        use super::*;
+        use crate::fields::{ReadOnly, ReadPure, ReadWrite, WriteOnly};
+        use crate::volatile_mmio;
+        use core::fmt::Debug;
+
+        /// The offset of the value under test within the backing memory, leaving guard bytes either
+        /// side of it to catch accesses which are too wide or at the wrong address.
+        const OFFSET: usize = 16;
+
+        /// Arbitrary bytes to write, with no two the same so that a misplaced byte is noticed.
+        const WRITTEN: [u8; 16] = [
+            0x81, 0x12, 0xf3, 0x24, 0xa5, 0x36, 0xd7, 0x48, 0x69, 0x5a, 0xcb, 0x6c, 0x3d, 0x7e,
+            0xef, 0x90,
+        ];
+
+        /// Returns backing memory filled with a pattern which differs from `WRITTEN`.
+        fn backing() -> [u64; 6] {
+            let mut memory = [0; 6];
+            for (i, byte) in memory.as_mut_bytes().iter_mut().enumerate() {
+                *byte = i as u8 ^ 0x5a;
+            }
+            memory
+        }
+
+        /// Returns a pointer to the value under test in the given backing memory.
+        fn value_in<T>(memory: &mut [u64; 6]) -> NonNull<T> {
+            assert!(OFFSET + size_of::<T>() <= size_of_val(memory) - OFFSET);
+            assert!(align_of::<T>() <= align_of::<u64>());
+            // SAFETY: The offset is within the backing memory, as checked above.
+            unsafe { NonNull::from(memory).cast::<u8>().add(OFFSET).cast() }
+        }
+
+        /// Checks that reads and writes of a `T` through both backends give the same results.
+        fn check<T: Debug + FromBytes + Immutable + IntoBytes + PartialEq>() {
+            let mut asm_memory = backing();
+            let mut volatile_memory = backing();
+            let asm_ptr = value_in::<T>(&mut asm_memory);
+            let volatile_ptr = value_in::<T>(&mut volatile_memory);
+
+            // SAFETY: Both pointers are to the value under test in ordinary memory, which we don't
+            // otherwise access while they are in use.
+            unsafe {
+                let asm_value = UniqueMmioPointer::new(asm_ptr).read_unsafe();
+                let shared_value =
+                    SharedMmioPointer::from(UniqueMmioPointer::new(asm_ptr)).read_unsafe();
+                let constant_value =
+                    SharedMmioPointer::from(UniqueMmioPointer::new(asm_ptr)).read_constant_unsafe();
+                let volatile_value = volatile_mmio::mmio_read(volatile_ptr);
+                assert_eq!(asm_value, volatile_value);
+                assert_eq!(shared_value, volatile_value);
+                assert_eq!(constant_value, volatile_value);
+
+                let written = &WRITTEN[..size_of::<T>()];
+                UniqueMmioPointer::new(asm_ptr).write_unsafe(T::read_from_bytes(written).unwrap());
+                volatile_mmio::mmio_write(volatile_ptr, T::read_from_bytes(written).unwrap());
+            }
+            assert_eq!(asm_memory, volatile_memory);
+            assert_eq!(
+                &asm_memory.as_bytes()[OFFSET..OFFSET + size_of::<T>()],
+                &WRITTEN[..size_of::<T>()]
+            );
+        }
+
+        #[repr(C, align(4))]
+        #[derive(Debug, Eq, FromBytes, Immutable, IntoBytes, PartialEq)]
+        struct Mixed {
+            a: u8,
+            b: u8,
+            c: u16,
+            d: u32,
+        }
+
+        #[test]
+        fn integers() {
+            check::<u8>();
+            check::<u16>();
+            check::<u32>();
+            check::<u64>();
+            check::<usize>();
+            check::<i8>();
+            check::<i16>();
+            check::<i32>();
+            check::<i64>();
+        }
+
+        #[test]
+        fn arrays_and_structs() {
+            check::<[u8; 3]>();
+            check::<[u8; 16]>();
+            check::<[u16; 3]>();
+            check::<[u32; 3]>();
+            check::<[u64; 2]>();
+            check::<Mixed>();
+        }
+
+        #[test]
+        fn fields() {
+            check::<ReadOnly<u8>>();
+            check::<ReadPure<u16>>();
+            check::<ReadWrite<u32>>();
+            check::<WriteOnly<u64>>();
+            check::<ReadWrite<[u32; 2]>>();
         }
     }
 }
@@ -0,0 +1,474 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Traits for reading and writing registers, to allow code to be generic over field wrappers.
+//!
+//! The `read` and `write` methods on [`UniqueMmioPointer`] and [`SharedMmioPointer`] are defined
+//! separately for each field wrapper, so these traits let drivers and HAL layers accept any pointer
+//! which can be read or written.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_mmio::{
+//!     UniqueMmioPointer,
+//!     access::{SideEffectReadable, Writable},
+//!     fields::{ReadOnly, ReadWrite, WriteOnly},
+//! };
+//!
+//! fn copy_register(
+//!     from: &mut impl SideEffectReadable<Value = u32>,
+//!     to: &mut impl Writable<Value = u32>,
+//! ) {
+//!     to.write(from.read());
+//! }
+//!
+//! let mut source = ReadOnly(42);
+//! let mut destination = WriteOnly(0);
+//! copy_register(
+//!     &mut UniqueMmioPointer::from(&mut source),
+//!     &mut UniqueMmioPointer::from(&mut destination),
+//! );
+//! # assert_eq!(destination.0, 42);
+//! ```
+
+use crate::{
+    SharedMmioPointer, UniqueMmioPointer,
+    fields::{
+        ReadConstant, ReadOnly, ReadPure, ReadPureWrite, ReadWrite, ReadWrite64HiLo,
+        ReadWrite64LoHi, WriteOnly,
+    },
+};
+use core::marker::PhantomData;
+use zerocopy::{FromBytes, Immutable, IntoBytes};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A type which is read and written with a single-copy atomic MMIO access on the current target.
+///
+/// A single-copy atomic access is performed as a single transaction, so the device can't observe
+/// or produce a partially-written value. The safe `read` and `write` methods of
+/// [`UniqueMmioPointer`] and [`SharedMmioPointer`] are only available for field wrappers of types
+/// implementing this. Larger types such as structs or arrays of registers can't be accessed as a
+/// whole, so that a read or write can't be silently torn into several accesses; use field
+/// projection to access each register separately, or the `unsafe` `read_unsafe` and
+/// `write_unsafe` methods.
+///
+/// This is implemented for 8, 16 and 32-bit integers on all targets, and 64-bit integers on 64-bit
+/// targets. On 32-bit targets, use [`ReadWrite64LoHi`] or [`ReadWrite64HiLo`] for 64-bit
+/// registers. It is also implemented for newtypes which implement [`AtomicNewtype`].
+///
+/// This trait is sealed, so can't be implemented outside this crate.
+pub trait AtomicAccess: sealed::Sealed + Copy + FromBytes + Immutable + IntoBytes {}
+
+/// A `repr(transparent)` newtype over an [`AtomicAccess`] integer, such as a set of flags, which
+/// can be read and written in place with the safe `read` and `write` methods if it also implements
+/// `Copy` and the zerocopy `FromBytes`, `Immutable` and `IntoBytes` traits.
+///
+/// The `MmioValue` derive macro implements this, so it doesn't usually need to be implemented by
+/// hand.
+///
+/// # Example
+///
+/// ```
+/// use safe_mmio::{UniqueMmioPointer, access::AtomicNewtype, fields::ReadWrite};
+/// use zerocopy::{FromBytes, Immutable, IntoBytes};
+///
+/// #[derive(Clone, Copy, Debug, Eq, FromBytes, Immutable, IntoBytes, PartialEq)]
+/// #[repr(transparent)]
+/// struct Flags(u32);
+///
+/// // SAFETY: `Flags` is `repr(transparent)` over a `u32`.
+/// unsafe impl AtomicNewtype for Flags {
+///     type Inner = u32;
+/// }
+///
+/// let mut fake = ReadWrite(Flags(0));
+/// let mut register = UniqueMmioPointer::from(&mut fake);
+/// register.write(Flags(3));
+/// assert_eq!(register.read(), Flags(3));
+/// ```
+///
+/// # Safety
+///
+/// The type must be `repr(transparent)` over `Inner`, so that it is accessed with the same single
+/// single-copy atomic access.
+pub unsafe trait AtomicNewtype {
+    /// The integer type which this is a newtype over.
+    type Inner: AtomicAccess;
+}
+
+impl<T: AtomicNewtype + Copy + FromBytes + Immutable + IntoBytes> sealed::Sealed for T {}
+impl<T: AtomicNewtype + Copy + FromBytes + Immutable + IntoBytes> AtomicAccess for T {}
+
+macro_rules! impl_atomic_access {
+    ($($t:ty),*) => {
+        $(
+            impl sealed::Sealed for $t {}
+            impl AtomicAccess for $t {}
+        )*
+    };
+}
+
+impl_atomic_access!(u8, i8, u16, i16, u32, i32, usize, isize);
+#[cfg(target_pointer_width = "64")]
+impl_atomic_access!(u64, i64);
+
+/// A pointer to a register which can be read through a shared reference because reading it has no
+/// side-effects.
+pub trait PureReadable: sealed::Sealed {
+    /// The type of the register value.
+    type Value;
+
+    /// Performs an MMIO read of the entire register.
+    fn read(&self) -> Self::Value;
+}
+
+/// A pointer to a register which can be read through a unique reference, possibly with
+/// side-effects.
+///
+/// This is implemented for every readable register, including those which are also
+/// [`PureReadable`].
+pub trait SideEffectReadable: sealed::Sealed {
+    /// The type of the register value.
+    type Value;
+
+    /// Performs an MMIO read of the entire register.
+    fn read(&mut self) -> Self::Value;
+}
+
+/// A pointer to a register which can be written.
+pub trait Writable: sealed::Sealed {
+    /// The type of the register value.
+    type Value;
+
+    /// Performs an MMIO write of the entire register.
+    fn write(&mut self, value: Self::Value);
+}
+
+impl<T> sealed::Sealed for SharedMmioPointer<'_, ReadConstant<T>> {}
+impl<T> sealed::Sealed for SharedMmioPointer<'_, ReadPure<T>> {}
+impl<T> sealed::Sealed for SharedMmioPointer<'_, ReadPureWrite<T>> {}
+impl<T> sealed::Sealed for UniqueMmioPointer<'_, ReadConstant<T>> {}
+impl<T> sealed::Sealed for UniqueMmioPointer<'_, ReadOnly<T>> {}
+impl<T> sealed::Sealed for UniqueMmioPointer<'_, ReadPure<T>> {}
+impl<T> sealed::Sealed for UniqueMmioPointer<'_, ReadPureWrite<T>> {}
+impl<T> sealed::Sealed for UniqueMmioPointer<'_, ReadWrite<T>> {}
+impl<T> sealed::Sealed for UniqueMmioPointer<'_, WriteOnly<T>> {}
+impl sealed::Sealed for UniqueMmioPointer<'_, ReadWrite64HiLo> {}
+impl sealed::Sealed for UniqueMmioPointer<'_, ReadWrite64LoHi> {}
+
+impl<T: AtomicAccess> PureReadable for SharedMmioPointer<'_, ReadConstant<T>> {
+    type Value = T;
+
+    fn read(&self) -> T {
+        SharedMmioPointer::<ReadConstant<T>>::read(self)
+    }
+}
+
+impl<T: AtomicAccess> PureReadable for SharedMmioPointer<'_, ReadPure<T>> {
+    type Value = T;
+
+    fn read(&self) -> T {
+        SharedMmioPointer::<ReadPure<T>>::read(self)
+    }
+}
+
+impl<T: AtomicAccess> PureReadable for SharedMmioPointer<'_, ReadPureWrite<T>> {
+    type Value = T;
+
+    fn read(&self) -> T {
+        SharedMmioPointer::<ReadPureWrite<T>>::read(self)
+    }
+}
+
+impl<T: AtomicAccess> PureReadable for UniqueMmioPointer<'_, ReadConstant<T>> {
+    type Value = T;
+
+    fn read(&self) -> T {
+        SharedMmioPointer::<ReadConstant<T>>::read(self)
+    }
+}
+
+impl<T: AtomicAccess> PureReadable for UniqueMmioPointer<'_, ReadPure<T>> {
+    type Value = T;
+
+    fn read(&self) -> T {
+        SharedMmioPointer::<ReadPure<T>>::read(self)
+    }
+}
+
+impl<T: AtomicAccess> PureReadable for UniqueMmioPointer<'_, ReadPureWrite<T>> {
+    type Value = T;
+
+    fn read(&self) -> T {
+        SharedMmioPointer::<ReadPureWrite<T>>::read(self)
+    }
+}
+
+impl<T: AtomicAccess> SideEffectReadable for UniqueMmioPointer<'_, ReadOnly<T>> {
+    type Value = T;
+
+    fn read(&mut self) -> T {
+        UniqueMmioPointer::<ReadOnly<T>>::read(self)
+    }
+}
+
+impl<T: AtomicAccess> SideEffectReadable for UniqueMmioPointer<'_, ReadWrite<T>> {
+    type Value = T;
+
+    fn read(&mut self) -> T {
+        UniqueMmioPointer::<ReadWrite<T>>::read(self)
+    }
+}
+
+impl<T: AtomicAccess> SideEffectReadable for UniqueMmioPointer<'_, ReadConstant<T>> {
+    type Value = T;
+
+    fn read(&mut self) -> T {
+        SharedMmioPointer::<ReadConstant<T>>::read(self)
+    }
+}
+
+impl<T: AtomicAccess> SideEffectReadable for UniqueMmioPointer<'_, ReadPure<T>> {
+    type Value = T;
+
+    fn read(&mut self) -> T {
+        SharedMmioPointer::<ReadPure<T>>::read(self)
+    }
+}
+
+impl<T: AtomicAccess> SideEffectReadable for UniqueMmioPointer<'_, ReadPureWrite<T>> {
+    type Value = T;
+
+    fn read(&mut self) -> T {
+        SharedMmioPointer::<ReadPureWrite<T>>::read(self)
+    }
+}
+
+impl SideEffectReadable for UniqueMmioPointer<'_, ReadWrite64LoHi> {
+    type Value = u64;
+
+    fn read(&mut self) -> u64 {
+        UniqueMmioPointer::<ReadWrite64LoHi>::read(self)
+    }
+}
+
+impl SideEffectReadable for UniqueMmioPointer<'_, ReadWrite64HiLo> {
+    type Value = u64;
+
+    fn read(&mut self) -> u64 {
+        UniqueMmioPointer::<ReadWrite64HiLo>::read(self)
+    }
+}
+
+impl<T: AtomicAccess> Writable for UniqueMmioPointer<'_, WriteOnly<T>> {
+    type Value = T;
+
+    fn write(&mut self, value: T) {
+        UniqueMmioPointer::<WriteOnly<T>>::write(self, value);
+    }
+}
+
+impl<T: AtomicAccess> Writable for UniqueMmioPointer<'_, ReadWrite<T>> {
+    type Value = T;
+
+    fn write(&mut self, value: T) {
+        UniqueMmioPointer::<ReadWrite<T>>::write(self, value);
+    }
+}
+
+impl<T: AtomicAccess> Writable for UniqueMmioPointer<'_, ReadPureWrite<T>> {
+    type Value = T;
+
+    fn write(&mut self, value: T) {
+        UniqueMmioPointer::<ReadPureWrite<T>>::write(self, value);
+    }
+}
+
+impl Writable for UniqueMmioPointer<'_, ReadWrite64LoHi> {
+    type Value = u64;
+
+    fn write(&mut self, value: u64) {
+        UniqueMmioPointer::<ReadWrite64LoHi>::write(self, value);
+    }
+}
+
+impl Writable for UniqueMmioPointer<'_, ReadWrite64HiLo> {
+    type Value = u64;
+
+    fn write(&mut self, value: u64) {
+        UniqueMmioPointer::<ReadWrite64HiLo>::write(self, value);
+    }
+}
+
+/// A pointer to a register whose values are converted to and from a domain type `V` when it is
+/// read or written, while the register itself keeps its plain integer type.
+///
+/// Reads convert the register value with `V::from`, and writes convert `V` back with `Into`. This
+/// implements whichever of [`PureReadable`], [`SideEffectReadable`] and [`Writable`] the wrapped
+/// pointer does.
+///
+/// # Example
+///
+/// ```
+/// use safe_mmio::{
+///     UniqueMmioPointer,
+///     access::{Converted, SideEffectReadable, Writable},
+///     fields::ReadWrite,
+/// };
+///
+/// #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// struct Hertz(u32);
+///
+/// impl From<u32> for Hertz {
+///     fn from(value: u32) -> Self {
+///         Self(value)
+///     }
+/// }
+///
+/// impl From<Hertz> for u32 {
+///     fn from(value: Hertz) -> Self {
+///         value.0
+///     }
+/// }
+///
+/// let frequency: UniqueMmioPointer<ReadWrite<u32>>;
+/// # let mut fake = ReadWrite(0);
+/// # frequency = UniqueMmioPointer::from(&mut fake);
+/// let mut frequency = Converted::<_, Hertz>::new(frequency);
+/// frequency.write(Hertz(24_000_000));
+/// assert_eq!(frequency.read(), Hertz(24_000_000));
+/// ```
+#[derive(Debug)]
+pub struct Converted<P, V> {
+    register: P,
+    value: PhantomData<fn(V) -> V>,
+}
+
+impl<P, V> Converted<P, V> {
+    /// Wraps the given register pointer.
+    pub const fn new(register: P) -> Self {
+        Self {
+            register,
+            value: PhantomData,
+        }
+    }
+
+    /// Returns the original register pointer.
+    pub fn into_inner(self) -> P {
+        self.register
+    }
+}
+
+impl<P, V> sealed::Sealed for Converted<P, V> {}
+
+impl<P: PureReadable, V: From<P::Value>> PureReadable for Converted<P, V> {
+    type Value = V;
+
+    fn read(&self) -> V {
+        self.register.read().into()
+    }
+}
+
+impl<P: SideEffectReadable, V: From<P::Value>> SideEffectReadable for Converted<P, V> {
+    type Value = V;
+
+    fn read(&mut self) -> V {
+        self.register.read().into()
+    }
+}
+
+impl<P: Writable, V: Into<P::Value>> Writable for Converted<P, V> {
+    type Value = V;
+
+    fn write(&mut self, value: V) {
+        self.register.write(value.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_pure(register: &impl PureReadable<Value = u32>) -> u32 {
+        register.read()
+    }
+
+    fn read(register: &mut impl SideEffectReadable<Value = u32>) -> u32 {
+        register.read()
+    }
+
+    fn write(register: &mut impl Writable<Value = u32>, value: u32) {
+        register.write(value);
+    }
+
+    #[test]
+    fn pure_readable() {
+        let mut pure = ReadPure(1);
+        let mut pure_write = ReadPureWrite(2);
+        assert_eq!(read_pure(&SharedMmioPointer::from(&pure)), 1);
+        assert_eq!(read_pure(&SharedMmioPointer::from(&pure_write)), 2);
+        assert_eq!(read_pure(&UniqueMmioPointer::from(&mut pure)), 1);
+        assert_eq!(read_pure(&UniqueMmioPointer::from(&mut pure_write)), 2);
+    }
+
+    #[test]
+    fn side_effect_readable() {
+        let mut read_only = ReadOnly(1);
+        let mut read_write = ReadWrite(2);
+        let mut pure = ReadPure(3);
+        let mut pure_write = ReadPureWrite(4);
+        assert_eq!(read(&mut UniqueMmioPointer::from(&mut read_only)), 1);
+        assert_eq!(read(&mut UniqueMmioPointer::from(&mut read_write)), 2);
+        assert_eq!(read(&mut UniqueMmioPointer::from(&mut pure)), 3);
+        assert_eq!(read(&mut UniqueMmioPointer::from(&mut pure_write)), 4);
+    }
+
+    #[test]
+    fn writable() {
+        let mut write_only = WriteOnly(0);
+        let mut read_write = ReadWrite(0);
+        let mut pure_write = ReadPureWrite(0);
+        write(&mut UniqueMmioPointer::from(&mut write_only), 1);
+        write(&mut UniqueMmioPointer::from(&mut read_write), 2);
+        write(&mut UniqueMmioPointer::from(&mut pure_write), 3);
+        assert_eq!(write_only.0, 1);
+        assert_eq!(read_write.0, 2);
+        assert_eq!(pure_write.0, 3);
+    }
+
+    #[test]
+    fn converted() {
+        #[derive(Debug, Eq, PartialEq)]
+        struct Enabled(bool);
+
+        impl From<u8> for Enabled {
+            fn from(value: u8) -> Self {
+                Self(value != 0)
+            }
+        }
+
+        impl From<Enabled> for u8 {
+            fn from(value: Enabled) -> Self {
+                value.0.into()
+            }
+        }
+
+        let mut pure_write = ReadPureWrite(0u8);
+        {
+            let mut converted =
+                Converted::<_, Enabled>::new(UniqueMmioPointer::from(&mut pure_write));
+            assert_eq!(PureReadable::read(&converted), Enabled(false));
+            converted.write(Enabled(true));
+            assert_eq!(SideEffectReadable::read(&mut converted), Enabled(true));
+        }
+        assert_eq!(pure_write.0, 1);
+
+        let converted = Converted::<_, Enabled>::new(SharedMmioPointer::from(&pure_write));
+        assert_eq!(converted.read(), Enabled(true));
+    }
+}
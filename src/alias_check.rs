@@ -0,0 +1,308 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! A global registry of the address ranges of `UniqueMmioPointer`s, to detect overlapping aliases.
+//!
+//! Wrapping a pointer in [`Checked`] records its address range until the `Checked` is dropped, and
+//! panics if the range overlaps that of another `Checked` pointer which still exists. This is meant
+//! for debug builds and tests, to catch drivers which accidentally create two unique pointers to the
+//! same registers.
+//!
+//! With this feature enabled, the entry points for platform code in the
+//! [`platform`](crate::platform) module return `Checked` pointers, so every device pointer which
+//! the platform creates is registered. This changes their return types, so the feature should only
+//! be enabled by the final binary. [`UniqueMmioPointer::new`] itself stays `const` and doesn't
+//! register anything, so pointers created with it directly, e.g. in tests, must be wrapped in
+//! `Checked` explicitly to be checked. A [`DeviceRegistry`](crate::registry::DeviceRegistry) hands
+//! out each physical region only once by itself; the pointer which the platform then creates for
+//! the mapping of the region is registered here.
+//!
+//! Pointers projected from a `Checked` pointer borrow it, with [`Checked::reborrow`], so the whole
+//! range stays registered until they have all been dropped.
+//!
+//! The registry is protected by a spinlock. If interrupt hooks have been set with
+//! [`set_interrupt_hooks`](crate::critical::set_interrupt_hooks) then interrupts are masked while
+//! the lock is held. Otherwise `Checked` pointers must not be created or dropped from interrupt
+//! handlers, as the handler could interrupt code on the same CPU which holds the lock and then
+//! spin forever.
+//!
+//! # Example
+//!
+//! ```
+//! use core::ptr::NonNull;
+//! use safe_mmio::{UniqueMmioPointer, alias_check::Checked, fields::ReadWrite};
+//!
+//! # let mut fake = [ReadWrite(0u32), ReadWrite(0)];
+//! let regs: NonNull<[ReadWrite<u32>; 2]>;
+//! # regs = NonNull::from(&mut fake);
+//! // SAFETY: `regs` is the address of the device's registers, which are mapped as device memory,
+//! // and nothing else accesses them.
+//! let mut device = Checked::new(unsafe { UniqueMmioPointer::new(regs) });
+//! device.reborrow().get(1).unwrap().write(42);
+//! // Creating another `Checked` pointer to the same registers here would panic.
+//! let device: UniqueMmioPointer<_> = device.into_inner();
+//! ```
+
+use crate::{SharedMmioPointer, UniqueMmioPointer, critical, header_slice::HeaderSlice};
+use core::{
+    cell::UnsafeCell,
+    hint::spin_loop,
+    ops::Deref,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// The maximum number of address ranges which can be registered at once. Pointers checked while
+/// the registry is full aren't registered.
+const CAPACITY: usize = 256;
+
+/// A half-open range of addresses, `start..end`.
+type Range = (usize, usize);
+
+/// The address ranges of all currently registered pointers, protected by a spinlock.
+struct Ranges {
+    locked: AtomicBool,
+    ranges: UnsafeCell<[Option<Range>; CAPACITY]>,
+}
+
+// SAFETY: `ranges` is only accessed while holding `locked`.
+unsafe impl Sync for Ranges {}
+
+impl Ranges {
+    /// Calls `f` with the ranges while holding the lock, with interrupts masked if possible.
+    fn with<R>(&self, f: impl FnOnce(&mut [Option<Range>; CAPACITY]) -> R) -> R {
+        critical::with_interrupts_masked(|| {
+            while self
+                .locked
+                .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+                .is_err()
+            {
+                spin_loop();
+            }
+            // SAFETY: We hold the lock, so nothing else can access `ranges` until we release it.
+            let result = f(unsafe { &mut *self.ranges.get() });
+            self.locked.store(false, Ordering::Release);
+            result
+        })
+    }
+}
+
+static ACTIVE: Ranges = Ranges {
+    locked: AtomicBool::new(false),
+    ranges: UnsafeCell::new([None; CAPACITY]),
+};
+
+/// The registration of an address range, which is removed again when it is dropped.
+struct Registration {
+    slot: Option<usize>,
+}
+
+impl Registration {
+    /// Registers the `len` bytes starting at `start`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range overlaps a range which is already registered.
+    #[track_caller]
+    fn register(start: usize, len: usize) -> Self {
+        if len == 0 {
+            return Self { slot: None };
+        }
+        let end = start.saturating_add(len);
+        let result = ACTIVE.with(|ranges| {
+            if let Some(&existing) = ranges
+                .iter()
+                .flatten()
+                .find(|&&(other_start, other_end)| start < other_end && other_start < end)
+            {
+                return Err(existing);
+            }
+            let slot = ranges.iter().position(Option::is_none);
+            if let Some(slot) = slot {
+                ranges[slot] = Some((start, end));
+            }
+            Ok(slot)
+        });
+        match result {
+            Ok(slot) => Self { slot },
+            Err((other_start, other_end)) => panic!(
+                "UniqueMmioPointer to {start:#x}..{end:#x} overlaps existing UniqueMmioPointer to \
+                 {other_start:#x}..{other_end:#x}"
+            ),
+        }
+    }
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        if let Some(slot) = self.slot {
+            ACTIVE.with(|ranges| ranges[slot] = None);
+        }
+    }
+}
+
+/// A type of register block whose size can be found from a pointer to it, without accessing the
+/// registers.
+///
+/// This is implemented for all sized types, slices, and [`HeaderSlice`]s with a slice tail.
+///
+/// This trait is sealed, so can't be implemented outside this crate.
+pub trait ByteLen: sealed::Sealed {
+    /// Returns the size in bytes of the registers which `pointer` points to.
+    #[doc(hidden)]
+    fn byte_len(pointer: &SharedMmioPointer<Self>) -> usize;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+impl<T> sealed::Sealed for T {}
+impl<T> sealed::Sealed for [T] {}
+impl<H, E> sealed::Sealed for HeaderSlice<H, [E]> {}
+
+impl<T> ByteLen for T {
+    fn byte_len(_pointer: &SharedMmioPointer<Self>) -> usize {
+        size_of::<T>()
+    }
+}
+
+impl<T> ByteLen for [T] {
+    fn byte_len(pointer: &SharedMmioPointer<Self>) -> usize {
+        pointer.len() * size_of::<T>()
+    }
+}
+
+impl<H, E> ByteLen for HeaderSlice<H, [E]> {
+    fn byte_len(pointer: &SharedMmioPointer<Self>) -> usize {
+        pointer.byte_len()
+    }
+}
+
+/// A `UniqueMmioPointer` whose address range is registered in the global registry until it is
+/// dropped.
+///
+/// It derefs to the `UniqueMmioPointer` for shared access, and [`reborrow`](Self::reborrow) gives a
+/// `UniqueMmioPointer` for exclusive access which borrows it.
+pub struct Checked<'a, T: ?Sized> {
+    pointer: UniqueMmioPointer<'a, T>,
+    _registration: Registration,
+}
+
+impl<'a, T: ?Sized + ByteLen> Checked<'a, T> {
+    /// Registers the address range of `pointer`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range overlaps that of another `Checked` pointer which still exists.
+    #[track_caller]
+    pub fn new(pointer: UniqueMmioPointer<'a, T>) -> Self {
+        let len = T::byte_len(&pointer);
+        let registration = Registration::register(pointer.0.regs.addr().get(), len);
+        Self {
+            pointer,
+            _registration: registration,
+        }
+    }
+}
+
+impl<'a, T: ?Sized> Checked<'a, T> {
+    /// Returns a `UniqueMmioPointer` to the registers, which borrows this one so that the
+    /// registration lasts at least as long as it does.
+    pub const fn reborrow(&mut self) -> UniqueMmioPointer<'_, T> {
+        self.pointer.reborrow()
+    }
+
+    /// Removes the registration and returns the pointer, which is no longer checked.
+    pub fn into_inner(self) -> UniqueMmioPointer<'a, T> {
+        self.pointer
+    }
+}
+
+impl<'a, T: ?Sized> Deref for Checked<'a, T> {
+    type Target = UniqueMmioPointer<'a, T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.pointer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fields::ReadWrite, platform};
+    use core::ptr::NonNull;
+
+    #[test]
+    #[should_panic(expected = "overlaps existing UniqueMmioPointer")]
+    fn overlap() {
+        let mut fake = [ReadWrite(0u32), ReadWrite(0)];
+        let regs = NonNull::from(&mut fake);
+        // SAFETY: `fake` is valid, and the second pointer is rejected before it can be used.
+        let _all = Checked::new(unsafe { UniqueMmioPointer::new(regs) });
+        // SAFETY: As above.
+        let _second = Checked::new(unsafe { UniqueMmioPointer::new(regs.cast::<u32>().add(1)) });
+    }
+
+    #[test]
+    fn disjoint_and_dropped() {
+        let mut fake = [ReadWrite(0u32), ReadWrite(0)];
+        let regs = NonNull::from(&mut fake).cast::<ReadWrite<u32>>();
+        // SAFETY: `fake` is valid, and the two pointers are to different elements.
+        let (mut first, mut second) = unsafe {
+            (
+                Checked::new(UniqueMmioPointer::new(regs)),
+                Checked::new(UniqueMmioPointer::new(regs.add(1))),
+            )
+        };
+        first.reborrow().write(1);
+        second.reborrow().write(2);
+        drop(first);
+        // SAFETY: `first` has been dropped, so nothing else accesses the first element.
+        let mut again = Checked::new(unsafe { UniqueMmioPointer::new(regs) });
+        again.reborrow().write(3);
+        let mut unchecked = second.into_inner();
+        // The registration has been removed, so a borrow of the pointer can be checked again.
+        Checked::new(unchecked.reborrow()).reborrow().write(4);
+        drop(again);
+        assert_eq!(fake[0].0, 3);
+        assert_eq!(fake[1].0, 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "overlaps existing UniqueMmioPointer")]
+    fn children_keep_registration() {
+        let mut fake = [ReadWrite(0u32), ReadWrite(0)];
+        let regs = NonNull::from(&mut fake);
+        // SAFETY: `fake` is valid, and the second pointer is rejected before it can be used.
+        let mut all = Checked::new(unsafe { UniqueMmioPointer::new(regs) });
+        let [_first, _second] = all.reborrow().split();
+        // SAFETY: As above.
+        let _again = Checked::new(unsafe { UniqueMmioPointer::new(regs.cast::<u32>().add(1)) });
+    }
+
+    #[test]
+    #[should_panic(expected = "overlaps existing UniqueMmioPointer")]
+    fn platform_slice() {
+        let mut fake = [ReadWrite(0u32), ReadWrite(0)];
+        let regs = NonNull::from(&mut fake[..]);
+        // SAFETY: `fake` is valid, and the second pointer is rejected before it can be used.
+        let _all = unsafe { platform::unique(regs) };
+        // SAFETY: As above.
+        let _second = unsafe { platform::unique(regs.cast::<ReadWrite<u32>>().add(1)) };
+    }
+
+    #[test]
+    fn header_slice_len() {
+        let mut fake = HeaderSlice {
+            header: ReadWrite(0u8),
+            tail: [ReadWrite(0u32), ReadWrite(0)],
+        };
+        let header = NonNull::from(&mut fake).cast::<ReadWrite<u8>>();
+        // SAFETY: `fake` is valid, and only accessed through this pointer while it exists.
+        let pointer = unsafe {
+            UniqueMmioPointer::<HeaderSlice<_, [ReadWrite<u32>]>>::new_with_len(header, 2)
+        };
+        assert_eq!(ByteLen::byte_len(&pointer), 12);
+    }
+}
@@ -0,0 +1,302 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Type-level tags for the memory attributes with which registers are mapped.
+//!
+//! The barriers needed around MMIO accesses depend on how the registers are mapped. Device
+//! nGnRnE memory keeps accesses in program order and only acknowledges writes once they reach the
+//! device, so no barriers are needed. Device nGnRE memory allows writes to be acknowledged early,
+//! and Normal Non-cacheable memory (as used for write-combining buffers) allows accesses to be
+//! reordered and merged.
+//!
+//! A [`Mapped`] pointer wraps a [`UniqueMmioPointer`] along with a [`MemoryAttribute`] tag, and
+//! issues the barriers which the tag needs around each access. This lets a single driver use both
+//! a strongly-ordered register block and a write-combining buffer correctly.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_mmio::{
+//!     UniqueMmioPointer,
+//!     attributes::{DeviceStrict, Mapped, NormalNC},
+//!     fields::{ReadWrite, WriteOnly},
+//! };
+//!
+//! let doorbell: UniqueMmioPointer<WriteOnly<u32>>;
+//! let buffer: UniqueMmioPointer<[ReadWrite<u32>]>;
+//! # let mut fake_doorbell = WriteOnly(0);
+//! # let mut fake_buffer = [const { ReadWrite(0) }; 4];
+//! # doorbell = UniqueMmioPointer::from(&mut fake_doorbell);
+//! # buffer = UniqueMmioPointer::from(fake_buffer.as_mut_slice());
+//! let mut doorbell = Mapped::<DeviceStrict, _>::new(doorbell);
+//! let mut buffer = Mapped::<NormalNC, _>::new(buffer);
+//!
+//! buffer.access(|mut buffer| buffer.write_slice(&[1, 2, 3, 4]));
+//! // The writes to the buffer are ordered before the doorbell write.
+//! doorbell.write(1);
+//! ```
+//!
+//! To write several registers with the same memory attributes, such as when initialising a device,
+//! [`batch`] issues the barriers once around all of the writes rather than around each one.
+
+use crate::{
+    UniqueMmioPointer,
+    access::{SideEffectReadable, Writable},
+};
+use core::marker::PhantomData;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// The memory attributes with which some registers are mapped.
+///
+/// This trait is sealed, so can't be implemented outside this crate.
+pub trait MemoryAttribute: sealed::Sealed {
+    /// Issues whatever barrier is needed before an access, to keep it in program order with
+    /// respect to previous accesses.
+    fn before_access();
+
+    /// Issues whatever barrier is needed after a write, so that it is complete or at least ordered
+    /// before subsequent accesses.
+    fn after_write();
+}
+
+/// Device nGnRnE memory, which is strongly ordered and doesn't allow early write acknowledgement.
+///
+/// No barriers are needed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DeviceStrict;
+
+/// Device nGnRE memory, which allows writes to be acknowledged before they reach the device.
+///
+/// A store barrier is issued after each write, so that it has completed before any subsequent
+/// access.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DeviceRelaxed;
+
+/// Normal Non-cacheable memory, such as a write-combining frame buffer or mailbox window, where
+/// accesses may be reordered and merged.
+///
+/// A full barrier is issued before each access, and a store barrier after each write.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct NormalNC;
+
+impl sealed::Sealed for DeviceStrict {}
+impl sealed::Sealed for DeviceRelaxed {}
+impl sealed::Sealed for NormalNC {}
+
+impl MemoryAttribute for DeviceStrict {
+    #[inline(always)]
+    fn before_access() {}
+
+    #[inline(always)]
+    fn after_write() {}
+}
+
+impl MemoryAttribute for DeviceRelaxed {
+    #[inline(always)]
+    fn before_access() {}
+
+    #[inline(always)]
+    fn after_write() {
+        #[cfg(all(target_arch = "aarch64", not(miri)))]
+        // SAFETY: A barrier has no effect on memory safety.
+        unsafe {
+            core::arch::asm!("dsb oshst", options(nostack, preserves_flags));
+        }
+        #[cfg(any(not(target_arch = "aarch64"), miri))]
+        core::sync::atomic::fence(core::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+impl MemoryAttribute for NormalNC {
+    #[inline(always)]
+    fn before_access() {
+        #[cfg(all(target_arch = "aarch64", not(miri)))]
+        // SAFETY: A barrier has no effect on memory safety.
+        unsafe {
+            core::arch::asm!("dmb osh", options(nostack, preserves_flags));
+        }
+        #[cfg(any(not(target_arch = "aarch64"), miri))]
+        core::sync::atomic::fence(core::sync::atomic::Ordering::SeqCst);
+    }
+
+    #[inline(always)]
+    fn after_write() {
+        #[cfg(all(target_arch = "aarch64", not(miri)))]
+        // SAFETY: A barrier has no effect on memory safety.
+        unsafe {
+            core::arch::asm!("dmb oshst", options(nostack, preserves_flags));
+        }
+        #[cfg(any(not(target_arch = "aarch64"), miri))]
+        core::sync::atomic::fence(core::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+/// A pointer to registers of type `T` which are mapped with the memory attributes `A`.
+#[derive(Debug)]
+pub struct Mapped<'a, A: MemoryAttribute, T: ?Sized> {
+    regs: UniqueMmioPointer<'a, T>,
+    attribute: PhantomData<A>,
+}
+
+impl<'a, A: MemoryAttribute, T: ?Sized> Mapped<'a, A, T> {
+    /// Wraps the given pointer to registers which are mapped with the memory attributes `A`.
+    ///
+    /// If the registers are actually mapped with weaker attributes than `A` then accesses may not
+    /// be ordered as expected.
+    pub const fn new(regs: UniqueMmioPointer<'a, T>) -> Self {
+        Self {
+            regs,
+            attribute: PhantomData,
+        }
+    }
+
+    /// Performs an MMIO read of the register, with any barrier needed before it.
+    pub fn read<V>(&mut self) -> V
+    where
+        for<'b> UniqueMmioPointer<'b, T>: SideEffectReadable<Value = V>,
+    {
+        A::before_access();
+        self.regs.reborrow().read()
+    }
+
+    /// Performs an MMIO write of the register, with any barriers needed around it.
+    pub fn write<V>(&mut self, value: V)
+    where
+        for<'b> UniqueMmioPointer<'b, T>: Writable<Value = V>,
+    {
+        A::before_access();
+        self.regs.reborrow().write(value);
+        A::after_write();
+    }
+
+    /// Calls `f` with a pointer to the registers, with the barriers needed before an access and
+    /// after a write issued before and after it respectively.
+    ///
+    /// Barriers aren't issued between the accesses which `f` makes, so this is useful for bulk
+    /// accesses to a buffer, where only the ordering with respect to other accesses matters.
+    pub fn access<R>(&mut self, f: impl FnOnce(UniqueMmioPointer<'_, T>) -> R) -> R {
+        A::before_access();
+        let result = f(self.regs.reborrow());
+        A::after_write();
+        result
+    }
+
+    /// Returns the original pointer to the registers, discarding the memory attributes.
+    pub fn into_inner(self) -> UniqueMmioPointer<'a, T> {
+        self.regs
+    }
+}
+
+/// Writes made within a call to [`batch`], without barriers between them.
+#[derive(Debug)]
+pub struct Batch<A: MemoryAttribute> {
+    attribute: PhantomData<A>,
+}
+
+impl<A: MemoryAttribute> Batch<A> {
+    /// Performs an MMIO write of the given register, without any barriers.
+    ///
+    /// The write is ordered before any accesses after the end of the batch.
+    pub fn write<P: Writable>(&mut self, mut register: P, value: P::Value) {
+        register.write(value);
+    }
+}
+
+/// Calls `f` to make a batch of writes to registers which are mapped with the memory attributes
+/// `A`, issuing the barrier needed before an access once before it and the barrier needed after a
+/// write once after it.
+///
+/// For [`DeviceRelaxed`] memory the writes within the batch still reach each device in program
+/// order, so this is sound whenever the device only needs them to be complete before whatever
+/// follows the batch. For [`NormalNC`] memory the writes within the batch may be reordered or
+/// merged with each other, so it should only be used for writes whose relative order doesn't
+/// matter.
+///
+/// # Example
+///
+/// ```
+/// use safe_mmio::{
+///     UniqueMmioPointer,
+///     attributes::{DeviceRelaxed, batch},
+///     field,
+///     fields::ReadWrite,
+/// };
+///
+/// #[repr(C)]
+/// struct Registers {
+///     control: ReadWrite<u32>,
+///     baud_divisor: ReadWrite<u32>,
+///     interrupt_mask: ReadWrite<u32>,
+/// }
+///
+/// let mut regs: UniqueMmioPointer<Registers>;
+/// # let mut fake = Registers {
+/// #     control: ReadWrite(0),
+/// #     baud_divisor: ReadWrite(0),
+/// #     interrupt_mask: ReadWrite(0),
+/// # };
+/// # regs = UniqueMmioPointer::from(&mut fake);
+/// batch::<DeviceRelaxed, _>(|b| {
+///     b.write(field!(regs, baud_divisor), 26);
+///     b.write(field!(regs, interrupt_mask), 0x7ff);
+///     b.write(field!(regs, control), 0x301);
+/// });
+/// // All three writes have completed by this point.
+/// ```
+pub fn batch<A: MemoryAttribute, R>(f: impl FnOnce(&mut Batch<A>) -> R) -> R {
+    A::before_access();
+    let result = f(&mut Batch {
+        attribute: PhantomData,
+    });
+    A::after_write();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fields::{ReadOnly, ReadWrite};
+
+    #[test]
+    fn read_write() {
+        let mut fake = ReadWrite(1u32);
+        {
+            let mut register = Mapped::<DeviceRelaxed, _>::new(UniqueMmioPointer::from(&mut fake));
+            assert_eq!(register.read(), 1);
+            register.write(2);
+            assert_eq!(register.read(), 2);
+        }
+        assert_eq!(fake.0, 2);
+
+        let mut fake = ReadOnly(42u8);
+        let mut register = Mapped::<DeviceStrict, _>::new(UniqueMmioPointer::from(&mut fake));
+        assert_eq!(register.read(), 42);
+    }
+
+    #[test]
+    fn bulk_access() {
+        let mut fake = [const { ReadWrite(0u16) }; 3];
+        let mut buffer = Mapped::<NormalNC, _>::new(UniqueMmioPointer::from(fake.as_mut_slice()));
+        buffer.access(|mut buffer| buffer.write_slice(&[1, 2, 3]));
+        let mut buffer = buffer.into_inner();
+        assert_eq!(buffer.get(2).unwrap().read(), 3);
+    }
+
+    #[test]
+    fn batch_writes() {
+        let mut fake = [const { ReadWrite(0u32) }; 2];
+        let mut regs = UniqueMmioPointer::from(&mut fake);
+        let count = batch::<NormalNC, _>(|b| {
+            b.write(regs.get(0).unwrap(), 1);
+            b.write(regs.get(1).unwrap(), 2);
+            2
+        });
+        assert_eq!(count, 2);
+        assert_eq!(fake[0].0, 1);
+        assert_eq!(fake[1].0, 2);
+    }
+}
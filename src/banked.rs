@@ -0,0 +1,112 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Helper for devices which multiplex several pages of registers through a single window.
+
+use crate::{UniqueMmioPointer, access::AtomicAccess};
+use core::sync::atomic::{Ordering, fence};
+
+/// A window of registers, the contents of which are selected by writing a page number to a select
+/// register.
+///
+/// This owns both the select register and the window, so the page can't be changed by anything
+/// else while the window is being accessed.
+///
+/// # Example
+///
+/// ```
+/// use safe_mmio::{UniqueMmioPointer, banked::BankedRegisters, field, fields::ReadWrite};
+///
+/// #[repr(C)]
+/// struct Window {
+///     data: [ReadWrite<u32>; 4],
+/// }
+///
+/// let select: UniqueMmioPointer<u8>;
+/// let window: UniqueMmioPointer<Window>;
+/// # let mut fake_select = 0;
+/// # let mut fake_window = Window { data: [const { ReadWrite(0) }; 4] };
+/// # select = UniqueMmioPointer::from(&mut fake_select);
+/// # window = UniqueMmioPointer::from(&mut fake_window);
+/// // SAFETY: Writing the select register only changes which page the window shows, and all pages
+/// // have the same layout.
+/// let mut banked = unsafe { BankedRegisters::new(select, window) };
+/// banked.with_page(2, |mut window| {
+///     field!(window, data).get(0).unwrap().write(42);
+/// });
+/// ```
+#[derive(Debug)]
+pub struct BankedRegisters<'a, S, W: ?Sized> {
+    select: UniqueMmioPointer<'a, S>,
+    window: UniqueMmioPointer<'a, W>,
+}
+
+impl<'a, S: AtomicAccess, W: ?Sized> BankedRegisters<'a, S, W> {
+    /// Creates a new `BankedRegisters` from pointers to the page select register and the window.
+    ///
+    /// # Safety
+    ///
+    /// It must be safe to write any page number passed to [`with_page`](Self::with_page) to
+    /// `select`, and doing so must have no side-effects other than changing which registers the
+    /// window shows. For every such page, the registers shown in the window must be correctly
+    /// described by `W`, including the safety requirements of any field wrappers it contains.
+    pub const unsafe fn new(
+        select: UniqueMmioPointer<'a, S>,
+        window: UniqueMmioPointer<'a, W>,
+    ) -> Self {
+        Self { select, window }
+    }
+
+    /// Selects the given page, and then calls `f` with a pointer to the window.
+    ///
+    /// A memory barrier is issued after writing the select register, before `f` is called.
+    pub fn with_page<R>(&mut self, page: S, f: impl FnOnce(UniqueMmioPointer<'_, W>) -> R) -> R {
+        // SAFETY: The caller of `new` promised that writing any page number to the select register
+        // is safe.
+        unsafe {
+            self.select.write_unsafe(page);
+        }
+        fence(Ordering::SeqCst);
+        f(self.window.reborrow())
+    }
+
+    /// Returns the original pointers to the page select register and the window.
+    pub fn into_inner(self) -> (UniqueMmioPointer<'a, S>, UniqueMmioPointer<'a, W>) {
+        (self.select, self.window)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fields::ReadWrite;
+
+    #[test]
+    fn with_page() {
+        let mut select = 0u32;
+        let mut window = ReadWrite(0u32);
+
+        {
+            // SAFETY: These are just fakes, so writing the select register has no side-effects.
+            let mut banked = unsafe {
+                BankedRegisters::new(
+                    UniqueMmioPointer::from(&mut select),
+                    UniqueMmioPointer::from(&mut window),
+                )
+            };
+            let value = banked.with_page(3, |mut window| {
+                window.write(42);
+                window.read()
+            });
+            assert_eq!(value, 42);
+
+            let (mut select, _) = banked.into_inner();
+            // SAFETY: This is just a fake.
+            assert_eq!(unsafe { select.read_unsafe() }, 3);
+        }
+
+        assert_eq!(select, 3);
+        assert_eq!(window.0, 42);
+    }
+}
@@ -0,0 +1,289 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Access to individual register bits through the bit-band alias regions of Cortex-M3 and Cortex-M4
+//! processors.
+//!
+//! On these cores each bit of the first megabyte of the SRAM and peripheral regions is also mapped
+//! to a word in a corresponding alias region. Writing 1 or 0 to the alias word sets or clears just
+//! that bit, and reading it returns the bit as 0 or 1. The read-modify-write is done by the bus
+//! matrix as a single locked transaction, so unlike a read-modify-write in software it can't be
+//! interrupted or race with an interrupt handler modifying other bits of the same register.
+//!
+//! The read-modify-write still reads and writes back the whole register, so it isn't suitable for
+//! registers with bits that are cleared by writing 1, or which have side-effects when read. Bits
+//! can therefore only be set and cleared for [`ReadPureWrite`] registers, and read for
+//! [`ReadPure`] and [`ReadPureWrite`] registers.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use safe_mmio::{
+//!     UniqueMmioPointer,
+//!     bitband::{BitBand, BitBandRegion},
+//!     fields::ReadPureWrite,
+//! };
+//!
+//! // SAFETY: 0x4001_100c is the address of a GPIO output data register, which isn't accessed any
+//! // other way.
+//! let odr = unsafe { UniqueMmioPointer::<ReadPureWrite<u32>>::from_exposed_addr(0x4001_100c) };
+//! // SAFETY: This is a Cortex-M4, which implements the standard peripheral bit-band region, and
+//! // nothing else accesses the alias of the register.
+//! let mut odr = unsafe { BitBand::new(odr, &BitBandRegion::PERIPHERAL) }.unwrap();
+//! odr.set_bit(13);
+//! ```
+
+use crate::{
+    UniqueMmioPointer,
+    access::AtomicAccess,
+    fields::{ReadPure, ReadPureWrite, ReadWrite},
+};
+use core::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    ptr::{self, NonNull},
+};
+
+/// A region of memory whose bits are each aliased to a word in a corresponding alias region.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BitBandRegion {
+    base: usize,
+    len: usize,
+    alias: usize,
+}
+
+impl BitBandRegion {
+    /// The standard SRAM bit-band region of Cortex-M3 and Cortex-M4.
+    pub const SRAM: Self = Self::new(0x2000_0000, 0x10_0000, 0x2200_0000);
+
+    /// The standard peripheral bit-band region of Cortex-M3 and Cortex-M4.
+    pub const PERIPHERAL: Self = Self::new(0x4000_0000, 0x10_0000, 0x4200_0000);
+
+    /// Returns a bit-band region covering the `len` bytes starting at `base`, whose alias region
+    /// starts at `alias`.
+    pub const fn new(base: usize, len: usize, alias: usize) -> Self {
+        Self { base, len, alias }
+    }
+
+    /// Returns the address of the alias word for bit 0 of the `len` bytes starting at `address`,
+    /// or `None` if they aren't all within the region.
+    const fn alias_of(&self, address: usize, len: usize) -> Option<usize> {
+        let Some(offset) = address.checked_sub(self.base) else {
+            return None;
+        };
+        if offset > self.len || len > self.len - offset {
+            return None;
+        }
+        let Some(alias_offset) = offset.checked_mul(32) else {
+            return None;
+        };
+        self.alias.checked_add(alias_offset)
+    }
+}
+
+/// An error creating a [`BitBand`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BitBandError {
+    /// The register isn't entirely within the bit-band region.
+    NotInRegion {
+        /// The address of the register.
+        address: usize,
+    },
+}
+
+impl Display for BitBandError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::NotInRegion { address } => {
+                write!(f, "Register at {address:#x} is not in the bit-band region")
+            }
+        }
+    }
+}
+
+impl Error for BitBandError {}
+
+/// A register in a bit-band region, whose bits can be set, cleared and read individually through
+/// the alias region.
+#[derive(Debug)]
+pub struct BitBand<'a, T> {
+    regs: UniqueMmioPointer<'a, T>,
+    alias: UniqueMmioPointer<'a, [ReadWrite<u32>]>,
+}
+
+impl<'a, T> BitBand<'a, T> {
+    /// Wraps the given register, after checking that it is within the given bit-band region.
+    ///
+    /// # Safety
+    ///
+    /// The CPU must implement `region`, as Cortex-M3 and Cortex-M4 do for the standard regions,
+    /// and the alias words of `regs` must not be accessed other than through the returned
+    /// `BitBand` while it exists.
+    pub unsafe fn new(
+        regs: UniqueMmioPointer<'a, T>,
+        region: &BitBandRegion,
+    ) -> Result<Self, BitBandError> {
+        let address = regs.addr();
+        let alias = region
+            .alias_of(address, size_of::<T>())
+            .ok_or(BitBandError::NotInRegion { address })?;
+        let alias = NonNull::slice_from_raw_parts(
+            NonNull::new(ptr::with_exposed_provenance_mut(alias))
+                .ok_or(BitBandError::NotInRegion { address })?,
+            size_of::<T>() * 8,
+        );
+        // SAFETY: The caller promised that the CPU implements the region, so the alias words of
+        // `regs` are valid MMIO address space, and that nothing else accesses them.
+        let alias = unsafe { UniqueMmioPointer::new(alias) };
+        Ok(Self { regs, alias })
+    }
+
+    /// Returns the wrapped register.
+    pub fn into_inner(self) -> UniqueMmioPointer<'a, T> {
+        self.regs
+    }
+
+    /// Returns the alias word of bit `n`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is not less than the number of bits in the register.
+    #[track_caller]
+    fn alias_word(&mut self, n: usize) -> UniqueMmioPointer<'_, ReadWrite<u32>> {
+        let bits = self.alias.len();
+        self.alias
+            .get(n)
+            .unwrap_or_else(|| panic!("Bit {n} out of range for {bits}-bit register"))
+    }
+}
+
+macro_rules! impl_read_bit {
+    ($wrapper:ident) => {
+        impl<T: AtomicAccess> BitBand<'_, $wrapper<T>> {
+            /// Reads bit `n` of the register with a single word read of its alias.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `n` is not less than the number of bits in the register.
+            #[track_caller]
+            pub fn read_bit(&mut self, n: usize) -> bool {
+                self.alias_word(n).read() & 1 != 0
+            }
+        }
+    };
+}
+
+macro_rules! impl_write_bit {
+    ($wrapper:ident) => {
+        impl<T: AtomicAccess> BitBand<'_, $wrapper<T>> {
+            /// Sets bit `n` of the register, leaving the other bits unchanged, with a single word
+            /// write to its alias.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `n` is not less than the number of bits in the register.
+            #[track_caller]
+            pub fn set_bit(&mut self, n: usize) {
+                self.alias_word(n).write(1);
+            }
+
+            /// Clears bit `n` of the register, leaving the other bits unchanged, with a single word
+            /// write to its alias.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `n` is not less than the number of bits in the register.
+            #[track_caller]
+            pub fn clear_bit(&mut self, n: usize) {
+                self.alias_word(n).write(0);
+            }
+        }
+    };
+}
+
+impl_read_bit!(ReadPure);
+impl_read_bit!(ReadPureWrite);
+impl_write_bit!(ReadPureWrite);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alias_address() {
+        let region = BitBandRegion::PERIPHERAL;
+        assert_eq!(region.alias_of(0x4000_0000, 4), Some(0x4200_0000));
+        assert_eq!(region.alias_of(0x4001_100c, 4), Some(0x4222_0180));
+        assert_eq!(region.alias_of(0x400f_fffc, 4), Some(0x43ff_ff80));
+        assert_eq!(region.alias_of(0x400f_fffe, 4), None);
+        assert_eq!(region.alias_of(0x3fff_fffc, 4), None);
+        assert_eq!(region.alias_of(0x4010_0000, 1), None);
+
+        let region = BitBandRegion::new(0x1000, 0x100, usize::MAX - 0x7ff);
+        assert_eq!(region.alias_of(0x1000, 4), Some(usize::MAX - 0x7ff));
+        assert_eq!(region.alias_of(0x1040, 4), None);
+        let region = BitBandRegion::new(0, usize::MAX, 0);
+        assert_eq!(region.alias_of(usize::MAX / 16, 4), None);
+    }
+
+    #[test]
+    fn not_in_region() {
+        let mut fake = ReadPureWrite(0u32);
+        let regs = UniqueMmioPointer::from(&mut fake);
+        let address = regs.addr();
+        let region = BitBandRegion::new(address + 4, 0x100, 0x1000);
+        // SAFETY: The region doesn't contain the register, so the alias is never accessed.
+        let result = unsafe { BitBand::new(regs, &region) };
+        assert_eq!(
+            result.map(|_| ()),
+            Err(BitBandError::NotInRegion { address })
+        );
+    }
+
+    /// Uses ordinary memory for the alias words, so checks which words are accessed rather than
+    /// their effect on the register.
+    #[test]
+    #[cfg_attr(miri, ignore = "Uses exposed provenance")]
+    fn alias_words() {
+        let mut fake = ReadPureWrite(0u16);
+        let mut alias = [const { ReadWrite(0u32) }; 16];
+        alias[9].0 = 1;
+        let region = BitBandRegion::new(
+            UniqueMmioPointer::from(&mut fake).addr(),
+            2,
+            UniqueMmioPointer::from(alias.as_mut_slice()).expose_provenance(),
+        );
+        {
+            // SAFETY: The fake alias words aren't accessed any other way while `bits` exists.
+            let mut bits =
+                unsafe { BitBand::new(UniqueMmioPointer::from(&mut fake), &region) }.unwrap();
+            bits.set_bit(3);
+            bits.clear_bit(9);
+            bits.set_bit(15);
+            assert!(bits.read_bit(3));
+            assert!(!bits.read_bit(4));
+        }
+        assert_eq!(
+            alias.map(|word| word.0),
+            [0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "Uses exposed provenance")]
+    #[should_panic(expected = "Bit 16 out of range for 16-bit register")]
+    fn bit_out_of_range() {
+        let mut fake = ReadPureWrite(0u16);
+        let mut alias = [const { ReadWrite(0u32) }; 16];
+        let region = BitBandRegion::new(
+            UniqueMmioPointer::from(&mut fake).addr(),
+            2,
+            UniqueMmioPointer::from(alias.as_mut_slice()).expose_provenance(),
+        );
+        // SAFETY: The fake alias words aren't accessed any other way while `bits` exists.
+        let mut bits =
+            unsafe { BitBand::new(UniqueMmioPointer::from(&mut fake), &region) }.unwrap();
+        bits.set_bit(16);
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Cached reads of registers whose value doesn't change.
+
+use crate::{SharedMmioPointer, access::AtomicAccess, fields::ReadPure};
+use core::{
+    cell::Cell,
+    fmt::{self, Debug, Formatter},
+};
+
+/// A pointer to a pure read-only register, along with a copy of the value read from it.
+///
+/// This is useful for ID and capability registers which never change, or only change when the
+/// device is reset, so that they aren't read again over a slow bus each time they are needed. The
+/// register is read the first time [`read`](Self::read) is called, and later calls return the
+/// cached value until [`invalidate`](Self::invalidate) is called.
+///
+/// # Example
+///
+/// ```
+/// use safe_mmio::{SharedMmioPointer, cached::CachedRead, fields::ReadPure};
+///
+/// let id: SharedMmioPointer<ReadPure<u32>>;
+/// # let fake = ReadPure(0x0034_1011);
+/// # id = SharedMmioPointer::from(&fake);
+/// let id = CachedRead::new(id);
+/// // Only the first call reads the register.
+/// assert_eq!(id.read(), 0x0034_1011);
+/// assert_eq!(id.read(), 0x0034_1011);
+/// // After the device is reset, make sure the register is read again.
+/// id.invalidate();
+/// ```
+pub struct CachedRead<'a, T> {
+    register: SharedMmioPointer<'a, ReadPure<T>>,
+    cached: Cell<Option<T>>,
+}
+
+impl<'a, T: AtomicAccess> CachedRead<'a, T> {
+    /// Wraps the given pointer to a pure read-only register.
+    ///
+    /// This doesn't read from the register.
+    pub const fn new(register: SharedMmioPointer<'a, ReadPure<T>>) -> Self {
+        Self {
+            register,
+            cached: Cell::new(None),
+        }
+    }
+
+    /// Returns the cached value of the register, or performs an MMIO read of the entire `T` and
+    /// caches it if there is no cached value.
+    pub fn read(&self) -> T {
+        if let Some(value) = self.cached.get() {
+            return value;
+        }
+        let value = self.register.read();
+        self.cached.set(Some(value));
+        value
+    }
+
+    /// Returns the cached value of the register without reading it, or `None` if it hasn't been
+    /// read since the cache was last invalidated.
+    pub fn cached(&self) -> Option<T> {
+        self.cached.get()
+    }
+
+    /// Discards the cached value, so that the next call to [`read`](Self::read) reads the register
+    /// again.
+    ///
+    /// Call this after anything which might change the value of the register, such as a device
+    /// reset.
+    pub fn invalidate(&self) {
+        self.cached.set(None);
+    }
+
+    /// Returns the original pointer to the register.
+    pub fn into_inner(self) -> SharedMmioPointer<'a, ReadPure<T>> {
+        self.register
+    }
+}
+
+impl<T: AtomicAccess + Debug> Debug for CachedRead<'_, T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("CachedRead")
+            .field("register", &self.register)
+            .field("cached", &self.cached.get())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UniqueMmioPointer;
+    use core::ptr::NonNull;
+
+    #[test]
+    fn read_once() {
+        let mut fake = ReadPure(42u32);
+        let fake_ptr = NonNull::from(&mut fake);
+        // SAFETY: This is just a fake, and is only otherwise accessed through `fake_ptr`.
+        let register = unsafe { UniqueMmioPointer::new(fake_ptr) };
+        let cached = CachedRead::new(*register);
+        assert_eq!(cached.cached(), None);
+        assert_eq!(cached.read(), 42);
+        assert_eq!(cached.cached(), Some(42));
+
+        // SAFETY: `fake_ptr` is valid, and nothing else is accessing the fake at the moment.
+        unsafe { fake_ptr.write_volatile(ReadPure(66)) };
+        assert_eq!(cached.read(), 42);
+        cached.invalidate();
+        assert_eq!(cached.cached(), None);
+        assert_eq!(cached.read(), 66);
+    }
+}
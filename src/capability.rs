@@ -0,0 +1,263 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Helper for walking linked lists of capability structures within an MMIO region, such as PCI
+//! capabilities or xHCI extended capabilities.
+
+use crate::UniqueMmioPointer;
+use core::{
+    fmt::{self, Display, Formatter},
+    marker::PhantomData,
+};
+
+/// An error walking a capability list.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CapabilityError {
+    /// A capability header at the given byte offset would extend past the end of the region, or
+    /// wouldn't be properly aligned.
+    InvalidOffset(usize),
+    /// The list has more capabilities than could fit in the region without overlapping, so it must
+    /// contain a loop.
+    Loop,
+}
+
+impl Display for CapabilityError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidOffset(offset) => {
+                write!(
+                    f,
+                    "Capability offset {offset:#x} is outside region or misaligned"
+                )
+            }
+            Self::Loop => write!(f, "Capability list contains a loop"),
+        }
+    }
+}
+
+impl core::error::Error for CapabilityError {}
+
+/// Follows a chain of capability headers of type `H` within an MMIO region.
+///
+/// The offset of each capability after the first is found by calling a function with the offset
+/// and a pointer to the header of the previous capability, so any encoding of the "next" pointer
+/// can be supported. Each offset is checked to be within the region and properly aligned, and the
+/// number of capabilities is limited so that a loop in the list is detected rather than walked
+/// forever.
+///
+/// Each capability header returned by [`next_capability`](Self::next_capability) borrows the
+/// walker, as a device could give the same offset more than once.
+///
+/// # Example
+///
+/// ```
+/// use safe_mmio::{
+///     UniqueMmioPointer,
+///     capability::CapabilityWalker,
+///     field, field_shared,
+///     fields::ReadPure,
+/// };
+/// use zerocopy::IntoBytes;
+///
+/// #[repr(C)]
+/// struct PciCapabilityHeader {
+///     id: ReadPure<u8>,
+///     next: ReadPure<u8>,
+/// }
+///
+/// let mut config: UniqueMmioPointer<[u8]>;
+/// # let mut fake = [0u32; 64];
+/// # fake[13] = 0x40;
+/// # fake[16] = 0x05;
+/// # config = UniqueMmioPointer::from(fake.as_mut_bytes());
+/// // SAFETY: The capabilities pointer of a PCI configuration header is a pure read.
+/// let first = unsafe { config.offset_as::<ReadPure<u8>>(0x34) }.unwrap().read();
+/// let next = |_, header: &mut UniqueMmioPointer<PciCapabilityHeader>| {
+///     match field!(*header, next).read() {
+///         0 => None,
+///         next => Some(next.into()),
+///     }
+/// };
+/// // SAFETY: Each offset in a PCI capability list points to a capability header.
+/// let mut walker = unsafe { CapabilityWalker::new(config, Some(first.into()), next) };
+/// while let Some(capability) = walker.next_capability() {
+///     let (offset, header) = capability.unwrap();
+///     if field_shared!(header, id).read() == 0x05 {
+///         // Found the MSI capability at `offset`.
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct CapabilityWalker<'a, H, F> {
+    region: UniqueMmioPointer<'a, [u8]>,
+    phantom: PhantomData<H>,
+    /// The offset of the capability most recently returned, if any.
+    current: Option<usize>,
+    /// The offset of the first capability, until it has been returned.
+    first: Option<usize>,
+    next: F,
+    /// The number of capabilities which may still be returned before assuming there is a loop.
+    remaining: usize,
+}
+
+impl<'a, H, F: FnMut(usize, &mut UniqueMmioPointer<H>) -> Option<usize>>
+    CapabilityWalker<'a, H, F>
+{
+    /// Creates a new `CapabilityWalker` over the given region, starting from the capability at
+    /// offset `first` if there is one.
+    ///
+    /// `next` is called with the offset and header of each capability to find the offset of the
+    /// next one, or `None` at the end of the list. Offsets are in bytes from the start of the
+    /// region.
+    ///
+    /// # Safety
+    ///
+    /// For every offset in the list, i.e. `first` and each offset returned by `next`, the MMIO
+    /// address space at that offset within the region must be valid registers of type `H`, if it
+    /// is within the region.
+    pub unsafe fn new(region: UniqueMmioPointer<'a, [u8]>, first: Option<usize>, next: F) -> Self {
+        let remaining = region.len()
+            / if size_of::<H>() == 0 {
+                1
+            } else {
+                size_of::<H>()
+            };
+        Self {
+            region,
+            phantom: PhantomData,
+            current: None,
+            first,
+            next,
+            remaining,
+        }
+    }
+
+    /// Returns the offset and a pointer to the header of the next capability in the list, or
+    /// `None` at the end of the list.
+    ///
+    /// Once an error has been returned, this returns `None`.
+    pub fn next_capability(
+        &mut self,
+    ) -> Option<Result<(usize, UniqueMmioPointer<'_, H>), CapabilityError>> {
+        let offset = if let Some(current) = self.current.take() {
+            // SAFETY: `current` was already checked to be valid when it was returned, and the
+            // caller of `new` promised that it points to valid registers of type `H`.
+            let mut header = unsafe { self.region.offset_as::<H>(current) }.unwrap();
+            (self.next)(current, &mut header)?
+        } else {
+            self.first.take()?
+        };
+        if self.remaining == 0 {
+            return Some(Err(CapabilityError::Loop));
+        }
+        self.remaining -= 1;
+        // SAFETY: The caller of `new` promised that every offset in the list points to valid
+        // registers of type `H`, if it is within the region, which `offset_as` checks.
+        let Some(header) = (unsafe { self.region.offset_as::<H>(offset) }) else {
+            return Some(Err(CapabilityError::InvalidOffset(offset)));
+        };
+        self.current = Some(offset);
+        Some(Ok((offset, header)))
+    }
+
+    /// Returns the original pointer to the region.
+    pub fn into_inner(self) -> UniqueMmioPointer<'a, [u8]> {
+        self.region
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{field, fields::ReadPure};
+    use zerocopy::IntoBytes;
+
+    #[repr(C)]
+    struct Header {
+        id: ReadPure<u8>,
+        next: ReadPure<u8>,
+    }
+
+    fn next_pointer(_: usize, header: &mut UniqueMmioPointer<Header>) -> Option<usize> {
+        match field!(*header, next).read() {
+            0 => None,
+            next => Some(next.into()),
+        }
+    }
+
+    #[test]
+    fn walk() {
+        let mut fake = [0u8; 16];
+        fake[4..6].copy_from_slice(&[1, 8]);
+        fake[8..10].copy_from_slice(&[2, 0]);
+        // SAFETY: These are just fakes, so every offset is a valid `Header`.
+        let mut walker = unsafe {
+            CapabilityWalker::new(
+                UniqueMmioPointer::from(fake.as_mut_bytes()),
+                Some(4),
+                next_pointer,
+            )
+        };
+        let (offset, mut header) = walker.next_capability().unwrap().unwrap();
+        assert_eq!(offset, 4);
+        assert_eq!(field!(header, id).read(), 1);
+        let (offset, mut header) = walker.next_capability().unwrap().unwrap();
+        assert_eq!(offset, 8);
+        assert_eq!(field!(header, id).read(), 2);
+        assert!(walker.next_capability().is_none());
+        assert!(walker.next_capability().is_none());
+
+        // SAFETY: These are just fakes, so every offset is a valid `Header`.
+        let mut walker = unsafe {
+            CapabilityWalker::new(
+                UniqueMmioPointer::from(fake.as_mut_bytes()),
+                None,
+                next_pointer,
+            )
+        };
+        assert!(walker.next_capability().is_none());
+    }
+
+    #[test]
+    fn invalid_offset() {
+        let mut fake = [0u8; 16];
+        fake[0..2].copy_from_slice(&[1, 15]);
+        // SAFETY: These are just fakes, so every offset is a valid `Header`.
+        let mut walker = unsafe {
+            CapabilityWalker::new(
+                UniqueMmioPointer::from(fake.as_mut_bytes()),
+                Some(0),
+                next_pointer,
+            )
+        };
+        assert!(walker.next_capability().unwrap().is_ok());
+        assert_eq!(
+            walker.next_capability().unwrap().unwrap_err(),
+            CapabilityError::InvalidOffset(15)
+        );
+        assert!(walker.next_capability().is_none());
+    }
+
+    #[test]
+    fn detect_loop() {
+        let mut fake = [0u8; 8];
+        fake[2..4].copy_from_slice(&[1, 2]);
+        // SAFETY: These are just fakes, so every offset is a valid `Header`.
+        let mut walker = unsafe {
+            CapabilityWalker::new(
+                UniqueMmioPointer::from(fake.as_mut_bytes()),
+                Some(2),
+                next_pointer,
+            )
+        };
+        for _ in 0..4 {
+            assert!(walker.next_capability().unwrap().is_ok());
+        }
+        assert_eq!(
+            walker.next_capability().unwrap().unwrap_err(),
+            CapabilityError::Loop
+        );
+        assert!(walker.next_capability().is_none());
+    }
+}
@@ -0,0 +1,258 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Helpers for reading free-running counters, including 64-bit counters which are exposed as a pair
+//! of 32-bit registers.
+
+use crate::{SharedMmioPointer, UniqueMmioPointer, access::AtomicAccess, fields::ReadPure};
+
+/// How a consistent value is read from a [`SplitCounter64`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SplitCounterProtocol {
+    /// Read the high half, then the low half, then the high half again, and retry if the high half
+    /// changed in between.
+    HighLowHigh,
+    /// Reading the low half latches the high half, so read the low half and then the high half.
+    LatchOnLowRead,
+}
+
+/// A 64-bit counter exposed as separate registers for its low and high 32 bits.
+///
+/// # Example
+///
+/// ```
+/// use safe_mmio::{
+///     UniqueMmioPointer,
+///     counter::{SplitCounter64, SplitCounterProtocol},
+/// };
+///
+/// let low: UniqueMmioPointer<u32>;
+/// let high: UniqueMmioPointer<u32>;
+/// # let mut fake_low = 0x5678;
+/// # let mut fake_high = 0x1234;
+/// # low = UniqueMmioPointer::from(&mut fake_low);
+/// # high = UniqueMmioPointer::from(&mut fake_high);
+/// // SAFETY: Reading the timer count registers has no side-effects.
+/// let mut counter = unsafe { SplitCounter64::new(low, high, SplitCounterProtocol::HighLowHigh) };
+/// assert_eq!(counter.read(), 0x1234_0000_5678);
+/// ```
+#[derive(Debug)]
+pub struct SplitCounter64<'a> {
+    low: UniqueMmioPointer<'a, u32>,
+    high: UniqueMmioPointer<'a, u32>,
+    protocol: SplitCounterProtocol,
+}
+
+impl<'a> SplitCounter64<'a> {
+    /// Creates a new `SplitCounter64` from pointers to the registers for the low and high halves of
+    /// the counter, to be read with the given protocol.
+    ///
+    /// # Safety
+    ///
+    /// It must be safe to read both registers any number of times, and doing so must have no
+    /// side-effects other than latching the high half if `protocol` is
+    /// [`LatchOnLowRead`](SplitCounterProtocol::LatchOnLowRead).
+    pub const unsafe fn new(
+        low: UniqueMmioPointer<'a, u32>,
+        high: UniqueMmioPointer<'a, u32>,
+        protocol: SplitCounterProtocol,
+    ) -> Self {
+        Self {
+            low,
+            high,
+            protocol,
+        }
+    }
+
+    /// Reads a consistent 64-bit value from the counter.
+    pub fn read(&mut self) -> u64 {
+        // SAFETY: The caller of `new` promised that reading the registers has no side-effects other
+        // than latching.
+        unsafe {
+            match self.protocol {
+                SplitCounterProtocol::HighLowHigh => {
+                    let mut high = self.high.read_unsafe();
+                    loop {
+                        let low = self.low.read_unsafe();
+                        let high_again = self.high.read_unsafe();
+                        if high_again == high {
+                            return u64::from(high) << 32 | u64::from(low);
+                        }
+                        high = high_again;
+                    }
+                }
+                SplitCounterProtocol::LatchOnLowRead => {
+                    let low = self.low.read_unsafe();
+                    let high = self.high.read_unsafe();
+                    u64::from(high) << 32 | u64::from(low)
+                }
+            }
+        }
+    }
+
+    /// Returns the original pointers to the registers for the low and high halves of the counter.
+    pub fn into_inner(self) -> (UniqueMmioPointer<'a, u32>, UniqueMmioPointer<'a, u32>) {
+        (self.low, self.high)
+    }
+}
+
+/// An unsigned integer type which can be used for a counter register.
+pub trait CounterValue: AtomicAccess {
+    /// The number of bits in the type.
+    const BITS: u32;
+
+    /// Returns the low `bits` bits of `self`.
+    fn truncate(self, bits: u32) -> Self;
+
+    /// Returns `self - earlier`, wrapping around at `bits` bits.
+    fn wrapping_delta(self, earlier: Self, bits: u32) -> Self;
+}
+
+macro_rules! impl_counter_value {
+    ($($t:ty),*) => {
+        $(
+            impl CounterValue for $t {
+                const BITS: u32 = <$t>::BITS;
+
+                fn truncate(self, bits: u32) -> Self {
+                    if bits >= Self::BITS { self } else { self & ((1 << bits) - 1) }
+                }
+
+                fn wrapping_delta(self, earlier: Self, bits: u32) -> Self {
+                    self.wrapping_sub(earlier).truncate(bits)
+                }
+            }
+        )*
+    };
+}
+
+impl_counter_value!(u8, u16, u32, usize);
+#[cfg(target_pointer_width = "64")]
+impl_counter_value!(u64);
+
+/// A counter register which counts up continuously and wraps around to zero, such as a timer count.
+///
+/// The counter may be narrower than its register, in which case the remaining high bits are
+/// ignored. Differences between counter values are calculated modulo the width of the counter, so
+/// they are correct across a wraparound as long as less than one full period has elapsed.
+///
+/// # Example
+///
+/// ```
+/// use safe_mmio::{SharedMmioPointer, counter::FreeRunningCounter, fields::ReadPure};
+///
+/// let count: SharedMmioPointer<ReadPure<u32>>;
+/// # let fake = ReadPure(0x0000_0010);
+/// # count = SharedMmioPointer::from(&fake);
+/// // The timer has a 24-bit counter.
+/// let counter = FreeRunningCounter::with_bits(count, 24);
+/// let start = 0x00ff_ffe0;
+/// // If the counter has since wrapped around to 0x10, the elapsed time is still correct.
+/// assert_eq!(counter.elapsed_since(start), 0x30);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct FreeRunningCounter<'a, T> {
+    counter: SharedMmioPointer<'a, ReadPure<T>>,
+    bits: u32,
+}
+
+impl<'a, T: CounterValue> FreeRunningCounter<'a, T> {
+    /// Creates a new `FreeRunningCounter` for a counter which uses every bit of its register.
+    pub const fn new(counter: SharedMmioPointer<'a, ReadPure<T>>) -> Self {
+        Self {
+            counter,
+            bits: T::BITS,
+        }
+    }
+
+    /// Creates a new `FreeRunningCounter` for a counter which uses only the low `bits` bits of its
+    /// register.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is 0 or more than the number of bits in `T`.
+    pub const fn with_bits(counter: SharedMmioPointer<'a, ReadPure<T>>, bits: u32) -> Self {
+        assert!(
+            bits > 0 && bits <= T::BITS,
+            "Counter width must be between 1 and the width of its register"
+        );
+        Self { counter, bits }
+    }
+
+    /// Returns the number of bits in the counter.
+    pub const fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// Reads the current value of the counter.
+    pub fn read(&self) -> T {
+        self.counter.read().truncate(self.bits)
+    }
+
+    /// Returns how much the counter has advanced since it had the value `previous`, taking into
+    /// account any wraparound.
+    pub fn elapsed_since(&self, previous: T) -> T {
+        self.read().wrapping_delta(previous, self.bits)
+    }
+
+    /// Returns the original pointer to the counter register.
+    pub const fn into_inner(self) -> SharedMmioPointer<'a, ReadPure<T>> {
+        self.counter
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read() {
+        for protocol in [
+            SplitCounterProtocol::HighLowHigh,
+            SplitCounterProtocol::LatchOnLowRead,
+        ] {
+            let mut low = 0xdead_beef;
+            let mut high = 0x1234_5678;
+            // SAFETY: These are just fakes, so reading them has no side-effects.
+            let mut counter = unsafe {
+                SplitCounter64::new(
+                    UniqueMmioPointer::from(&mut low),
+                    UniqueMmioPointer::from(&mut high),
+                    protocol,
+                )
+            };
+            assert_eq!(counter.read(), 0x1234_5678_dead_beef);
+        }
+    }
+
+    #[test]
+    fn free_running_wraparound() {
+        let count = ReadPure(5u32);
+        let counter = FreeRunningCounter::new(SharedMmioPointer::from(&count));
+        assert_eq!(counter.read(), 5);
+        assert_eq!(counter.elapsed_since(2), 3);
+        assert_eq!(counter.elapsed_since(u32::MAX - 1), 7);
+
+        let count = ReadPure(0xff00_0005u32);
+        let counter = FreeRunningCounter::with_bits(SharedMmioPointer::from(&count), 24);
+        assert_eq!(counter.bits(), 24);
+        assert_eq!(counter.read(), 5);
+        assert_eq!(counter.elapsed_since(0x00ff_fffe), 7);
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn free_running_u64() {
+        let count = ReadPure(1u64);
+        let counter = FreeRunningCounter::new(SharedMmioPointer::from(&count));
+        assert_eq!(counter.elapsed_since(u64::MAX), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Counter width must be between 1 and the width of its register")]
+    fn free_running_too_wide() {
+        let count = ReadPure(0u16);
+        FreeRunningCounter::with_bits(SharedMmioPointer::from(&count), 17);
+    }
+}
@@ -0,0 +1,178 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Sequences of MMIO accesses which mustn't be interleaved with accesses from interrupt handlers.
+//!
+//! Some device protocols need several accesses in a row, such as writing an index register and
+//! then a data register, or a sequence of unlock writes. If an interrupt handler accesses the same
+//! device in the middle of such a sequence then both will go wrong. A [`UniqueMmioPointer`]
+//! prevents other code from accessing the device through safe code, but a driver which shares a
+//! device with its interrupt handler has to mask interrupts around each sequence.
+//!
+//! This crate doesn't know how to mask interrupts on every platform, so the platform must provide
+//! functions to do so with [`set_interrupt_hooks`]. Then
+//! [`with_atomic_sequence`](UniqueMmioPointer::with_atomic_sequence) masks interrupts, runs the
+//! sequence, and restores them afterwards, even if the sequence panics.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_mmio::{
+//!     UniqueMmioPointer,
+//!     critical::{InterruptHooks, set_interrupt_hooks},
+//!     field,
+//!     fields::ReadWrite,
+//! };
+//!
+//! fn mask_interrupts() -> usize {
+//!     // Save the current interrupt mask and mask all interrupts, e.g. with DAIF on aarch64.
+//!     0
+//! }
+//!
+//! fn restore_interrupts(_state: usize) {
+//!     // Restore the saved interrupt mask.
+//! }
+//!
+//! static HOOKS: InterruptHooks = InterruptHooks {
+//!     mask: mask_interrupts,
+//!     restore: restore_interrupts,
+//! };
+//! set_interrupt_hooks(&HOOKS);
+//!
+//! #[repr(C)]
+//! struct Registers {
+//!     index: ReadWrite<u32>,
+//!     data: ReadWrite<u32>,
+//! }
+//!
+//! let mut regs: UniqueMmioPointer<Registers>;
+//! # let mut fake = Registers { index: ReadWrite(0), data: ReadWrite(0) };
+//! # regs = UniqueMmioPointer::from(&mut fake);
+//! let value = regs.with_atomic_sequence(|mut regs| {
+//!     field!(regs, index).write(3);
+//!     field!(regs, data).read()
+//! });
+//! ```
+
+use crate::UniqueMmioPointer;
+use core::{
+    ptr::{self, null_mut},
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+static HOOKS: AtomicPtr<InterruptHooks> = AtomicPtr::new(null_mut());
+
+/// Platform functions to mask and restore interrupts on the current CPU.
+#[derive(Clone, Copy, Debug)]
+pub struct InterruptHooks {
+    /// Masks all interrupts which might access devices, and returns the previous state.
+    pub mask: fn() -> usize,
+    /// Restores the state returned by `mask`.
+    pub restore: fn(usize),
+}
+
+/// Sets the functions used to mask and restore interrupts around
+/// [`with_atomic_sequence`](UniqueMmioPointer::with_atomic_sequence).
+pub fn set_interrupt_hooks(hooks: &'static InterruptHooks) {
+    HOOKS.store(ptr::from_ref(hooks).cast_mut(), Ordering::Release);
+}
+
+/// Restores interrupts when dropped, including while unwinding.
+struct Restore {
+    hooks: &'static InterruptHooks,
+    state: usize,
+}
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        (self.hooks.restore)(self.state);
+    }
+}
+
+/// Returns the hooks set with [`set_interrupt_hooks`], if any.
+fn hooks() -> Option<&'static InterruptHooks> {
+    let hooks = HOOKS.load(Ordering::Acquire);
+    // SAFETY: `HOOKS` is only ever set from a `&'static InterruptHooks`.
+    unsafe { hooks.as_ref() }
+}
+
+/// Calls `f` with interrupts masked if hooks have been set with [`set_interrupt_hooks`], or just
+/// calls it otherwise.
+#[cfg(feature = "alias-check")]
+pub(crate) fn with_interrupts_masked<R>(f: impl FnOnce() -> R) -> R {
+    let _restore = hooks().map(|hooks| Restore {
+        hooks,
+        state: (hooks.mask)(),
+    });
+    f()
+}
+
+impl<T: ?Sized> UniqueMmioPointer<'_, T> {
+    /// Calls `f` with a pointer to the registers while interrupts are masked, so that the accesses
+    /// it makes can't be interleaved with accesses from an interrupt handler on the same CPU.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no hooks have been set with [`set_interrupt_hooks`].
+    pub fn with_atomic_sequence<R>(&mut self, f: impl FnOnce(UniqueMmioPointer<'_, T>) -> R) -> R {
+        let hooks =
+            hooks().expect("Interrupt hooks must be set before with_atomic_sequence is used");
+        let _restore = Restore {
+            hooks,
+            state: (hooks.mask)(),
+        };
+        f(self.reborrow())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fields::ReadWrite;
+    use core::cell::Cell;
+
+    extern crate std;
+
+    std::thread_local! {
+        // Other tests may mask interrupts on their own threads once the hooks are set, so count
+        // per thread.
+        static MASKED: Cell<usize> = const { Cell::new(0) };
+    }
+
+    fn masked() -> usize {
+        MASKED.with(Cell::get)
+    }
+
+    fn mask() -> usize {
+        let state = masked();
+        MASKED.with(|masked| masked.set(state + 1));
+        state + 42
+    }
+
+    fn restore(state: usize) {
+        MASKED.with(|masked| masked.set(masked.get() - 1));
+        assert_eq!(state, masked() + 42);
+    }
+
+    static HOOKS: InterruptHooks = InterruptHooks { mask, restore };
+
+    #[test]
+    fn masked_sequence() {
+        set_interrupt_hooks(&HOOKS);
+        let mut fake = [const { ReadWrite(0u32) }; 2];
+        let mut regs = UniqueMmioPointer::from(&mut fake);
+        let value = regs.with_atomic_sequence(|mut regs| {
+            assert_eq!(masked(), 1);
+            regs.get(0).unwrap().write(1);
+            // Nested sequences should work too.
+            regs.with_atomic_sequence(|mut regs| {
+                assert_eq!(masked(), 2);
+                regs.get(1).unwrap().write(2);
+            });
+            regs.get(1).unwrap().read()
+        });
+        assert_eq!(value, 2);
+        assert_eq!(masked(), 0);
+    }
+}
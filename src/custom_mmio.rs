@@ -47,7 +47,7 @@
 
 use zerocopy::{FromBytes, Immutable, IntoBytes};
 
-use crate::{SharedMmioPointer, UniqueMmioPointer};
+use crate::{SharedMmioPointer, UniqueMmioPointer, access_width};
 use core::mem::size_of;
 use core::ptr::NonNull;
 
@@ -201,8 +201,9 @@ macro_rules! set_mmio_ops {
 impl<T: FromBytes + IntoBytes> UniqueMmioPointer<'_, T> {
     /// Performs an MMIO read and returns the value.
     ///
-    /// If `T` is exactly 1, 2, 4 or 8 bytes long then this will be a single operation. Otherwise
-    /// it will be split into several, reading chunks as large as possible.
+    /// If `T` is 1, 2, 4 or 8 bytes long and aligned to its size then this will be a single
+    /// operation. Otherwise it will be split into several, in order of increasing address, each as
+    /// wide as the alignment of `T` up to a maximum of 8 bytes.
     ///
     /// Note that this takes `&mut self` rather than `&self` because an MMIO read may cause
     /// side-effects that change the state of the device.
@@ -210,31 +211,37 @@ impl<T: FromBytes + IntoBytes> UniqueMmioPointer<'_, T> {
     /// # Safety
     ///
     /// This field must be safe to perform an MMIO read from.
+    #[inline(always)]
     pub unsafe fn read_unsafe(&mut self) -> T {
         // SAFETY: self.regs is always a valid and unique pointer to MMIO address space.
-        unsafe { mmio_read(self.regs) }
+        let mut value = unsafe { mmio_read(self.regs) };
+        self.record_read(value.as_mut_bytes());
+        value
     }
 }
 
 impl<T: Immutable + IntoBytes> UniqueMmioPointer<'_, T> {
     /// Performs an MMIO write of the given value.
     ///
-    /// If `T` is exactly 1, 2, 4 or 8 bytes long then this will be a single operation. Otherwise
-    /// it will be split into several, writing chunks as large as possible.
+    /// If `T` is 1, 2, 4 or 8 bytes long and aligned to its size then this will be a single
+    /// operation. Otherwise it will be split into several, in order of increasing address, each as
+    /// wide as the alignment of `T` up to a maximum of 8 bytes.
     ///
     /// # Safety
     ///
     /// This field must be safe to perform an MMIO write to.
+    #[inline(always)]
     pub unsafe fn write_unsafe(&mut self, value: T) {
+        self.record_write(value.as_bytes());
         // SAFETY: self.regs is always a valid and unique pointer to MMIO address space. The
         // extern functions are provided by the consumer via set_mmio_ops!().
         unsafe {
-            match size_of::<T>() {
-                1 => __safe_mmio_write_u8(self.regs.cast().as_ptr(), value.as_bytes()[0]),
-                2 => __safe_mmio_write_u16(self.regs.cast().as_ptr(), convert(value)),
-                4 => __safe_mmio_write_u32(self.regs.cast().as_ptr(), convert(value)),
-                8 => __safe_mmio_write_u64(self.regs.cast().as_ptr(), convert(value)),
-                _ => write_slice(self.regs.cast(), value.as_bytes()),
+            match (size_of::<T>(), access_width::<T>()) {
+                (1, _) => __safe_mmio_write_u8(self.regs.cast().as_ptr(), value.as_bytes()[0]),
+                (2, 2) => __safe_mmio_write_u16(self.regs.cast().as_ptr(), convert(value)),
+                (4, 4) => __safe_mmio_write_u32(self.regs.cast().as_ptr(), convert(value)),
+                (8, 8) => __safe_mmio_write_u64(self.regs.cast().as_ptr(), convert(value)),
+                (_, width) => write_slice(self.regs.cast(), value.as_bytes(), width),
             }
         }
     }
@@ -243,16 +250,20 @@ impl<T: Immutable + IntoBytes> UniqueMmioPointer<'_, T> {
 impl<T: FromBytes + IntoBytes> SharedMmioPointer<'_, T> {
     /// Performs an MMIO read and returns the value.
     ///
-    /// If `T` is exactly 1, 2, 4 or 8 bytes long then this will be a single operation. Otherwise
-    /// it will be split into several, reading chunks as large as possible.
+    /// If `T` is 1, 2, 4 or 8 bytes long and aligned to its size then this will be a single
+    /// operation. Otherwise it will be split into several, in order of increasing address, each as
+    /// wide as the alignment of `T` up to a maximum of 8 bytes.
     ///
     /// # Safety
     ///
     /// This field must be safe to perform an MMIO read from, and doing so must not cause any
     /// side-effects.
+    #[inline(always)]
     pub unsafe fn read_unsafe(&self) -> T {
         // SAFETY: self.regs is always a valid pointer to MMIO address space.
-        unsafe { mmio_read(self.regs) }
+        let mut value = unsafe { mmio_read(self.regs) };
+        self.record_read(value.as_mut_bytes());
+        value
     }
 }
 
@@ -261,94 +272,102 @@ impl<T: FromBytes + IntoBytes> SharedMmioPointer<'_, T> {
 /// # Safety
 ///
 /// The pointer must be valid to perform an MMIO read from.
+#[inline(always)]
 unsafe fn mmio_read<T: FromBytes + IntoBytes>(ptr: NonNull<T>) -> T {
     // SAFETY: ptr is a valid, aligned pointer to MMIO address space. The extern functions are
-    // provided by the consumer via set_mmio_ops!(). For sizes 1/2/4/8 we perform a single
-    // access; for larger sizes we split into chunks. The MaybeUninit is fully initialized before
-    // calling assume_init().
+    // provided by the consumer via set_mmio_ops!(). For sizes 1/2/4/8 which are naturally aligned
+    // we perform a single access; otherwise we split into chunks no wider than the alignment.
     unsafe {
-        match size_of::<T>() {
-            1 => convert(__safe_mmio_read_u8(ptr.cast().as_ptr())),
-            2 => convert(__safe_mmio_read_u16(ptr.cast().as_ptr())),
-            4 => convert(__safe_mmio_read_u32(ptr.cast().as_ptr())),
-            8 => convert(__safe_mmio_read_u64(ptr.cast().as_ptr())),
-            _ => {
+        match (size_of::<T>(), access_width::<T>()) {
+            (1, _) => convert(__safe_mmio_read_u8(ptr.cast().as_ptr())),
+            (2, 2) => convert(__safe_mmio_read_u16(ptr.cast().as_ptr())),
+            (4, 4) => convert(__safe_mmio_read_u32(ptr.cast().as_ptr())),
+            (8, 8) => convert(__safe_mmio_read_u64(ptr.cast().as_ptr())),
+            (_, width) => {
                 let mut value = T::new_zeroed();
-                read_slice(ptr.cast(), value.as_mut_bytes());
+                read_slice(ptr.cast(), value.as_mut_bytes(), width);
                 value
             }
         }
     }
 }
 
+#[inline(always)]
 fn convert<T: Immutable + IntoBytes, U: FromBytes>(value: T) -> U {
     U::read_from_bytes(value.as_bytes()).unwrap()
 }
 
 /// # Safety
 ///
-/// `ptr` must be valid for MMIO writes spanning `slice.len()` bytes.
-unsafe fn write_slice(ptr: NonNull<u8>, slice: &[u8]) {
-    if let Some((first, rest)) = slice.split_at_checked(8) {
-        // SAFETY: Caller guarantees ptr is valid for the full slice length.
-        unsafe {
-            __safe_mmio_write_u64(ptr.cast().as_ptr(), u64::read_from_bytes(first).unwrap());
-            write_slice(ptr.add(8), rest);
-        }
-    } else if let Some((first, rest)) = slice.split_at_checked(4) {
-        // SAFETY: Caller guarantees ptr is valid for the full slice length.
-        unsafe {
-            __safe_mmio_write_u32(ptr.cast().as_ptr(), u32::read_from_bytes(first).unwrap());
-            write_slice(ptr.add(4), rest);
-        }
-    } else if let Some((first, rest)) = slice.split_at_checked(2) {
-        // SAFETY: Caller guarantees ptr is valid for the full slice length.
-        unsafe {
-            __safe_mmio_write_u16(ptr.cast().as_ptr(), u16::read_from_bytes(first).unwrap());
-            write_slice(ptr.add(2), rest);
-        }
-    } else if let [first, rest @ ..] = slice {
-        // SAFETY: Caller guarantees ptr is valid for the full slice length.
-        unsafe {
-            __safe_mmio_write_u8(ptr.as_ptr(), *first);
-            write_slice(ptr.add(1), rest);
+/// `ptr` must be valid for MMIO writes spanning `slice.len()` bytes, and `width` must divide both
+/// `slice.len()` and the alignment of `ptr`.
+unsafe fn write_slice(ptr: NonNull<u8>, slice: &[u8], width: usize) {
+    // SAFETY: Caller guarantees ptr is valid for the full slice length, and that each chunk is
+    // aligned to the width of the access.
+    unsafe {
+        match width {
+            8 => {
+                for (i, chunk) in slice.chunks_exact(8).enumerate() {
+                    let value = u64::read_from_bytes(chunk).unwrap();
+                    __safe_mmio_write_u64(ptr.add(i * 8).cast().as_ptr(), value);
+                }
+            }
+            4 => {
+                for (i, chunk) in slice.chunks_exact(4).enumerate() {
+                    let value = u32::read_from_bytes(chunk).unwrap();
+                    __safe_mmio_write_u32(ptr.add(i * 4).cast().as_ptr(), value);
+                }
+            }
+            2 => {
+                for (i, chunk) in slice.chunks_exact(2).enumerate() {
+                    let value = u16::read_from_bytes(chunk).unwrap();
+                    __safe_mmio_write_u16(ptr.add(i * 2).cast().as_ptr(), value);
+                }
+            }
+            _ => {
+                for (i, byte) in slice.iter().enumerate() {
+                    __safe_mmio_write_u8(ptr.add(i).as_ptr(), *byte);
+                }
+            }
         }
     }
 }
 
 /// # Safety
 ///
-/// `ptr` must be valid for MMIO reads spanning `slice.len()` bytes.
-unsafe fn read_slice(ptr: NonNull<u8>, slice: &mut [u8]) {
-    if let Some((first, rest)) = slice.split_at_mut_checked(8) {
-        // SAFETY: Caller guarantees ptr is valid for the full slice length.
-        unsafe {
-            __safe_mmio_read_u64(ptr.cast().as_ptr())
-                .write_to(first)
-                .unwrap();
-            read_slice(ptr.add(8), rest);
-        }
-    } else if let Some((first, rest)) = slice.split_at_mut_checked(4) {
-        // SAFETY: Caller guarantees ptr is valid for the full slice length.
-        unsafe {
-            __safe_mmio_read_u32(ptr.cast().as_ptr())
-                .write_to(first)
-                .unwrap();
-            read_slice(ptr.add(4), rest);
-        }
-    } else if let Some((first, rest)) = slice.split_at_mut_checked(2) {
-        // SAFETY: Caller guarantees ptr is valid for the full slice length.
-        unsafe {
-            __safe_mmio_read_u16(ptr.cast().as_ptr())
-                .write_to(first)
-                .unwrap();
-            read_slice(ptr.add(2), rest);
-        }
-    } else if let [first, rest @ ..] = slice {
-        // SAFETY: Caller guarantees ptr is valid for the full slice length.
-        unsafe {
-            *first = __safe_mmio_read_u8(ptr.as_ptr());
-            read_slice(ptr.add(1), rest);
+/// `ptr` must be valid for MMIO reads spanning `slice.len()` bytes, and `width` must divide both
+/// `slice.len()` and the alignment of `ptr`.
+unsafe fn read_slice(ptr: NonNull<u8>, slice: &mut [u8], width: usize) {
+    // SAFETY: Caller guarantees ptr is valid for the full slice length, and that each chunk is
+    // aligned to the width of the access.
+    unsafe {
+        match width {
+            8 => {
+                for (i, chunk) in slice.chunks_exact_mut(8).enumerate() {
+                    __safe_mmio_read_u64(ptr.add(i * 8).cast().as_ptr())
+                        .write_to(chunk)
+                        .unwrap();
+                }
+            }
+            4 => {
+                for (i, chunk) in slice.chunks_exact_mut(4).enumerate() {
+                    __safe_mmio_read_u32(ptr.add(i * 4).cast().as_ptr())
+                        .write_to(chunk)
+                        .unwrap();
+                }
+            }
+            2 => {
+                for (i, chunk) in slice.chunks_exact_mut(2).enumerate() {
+                    __safe_mmio_read_u16(ptr.add(i * 2).cast().as_ptr())
+                        .write_to(chunk)
+                        .unwrap();
+                }
+            }
+            _ => {
+                for (i, byte) in slice.iter_mut().enumerate() {
+                    *byte = __safe_mmio_read_u8(ptr.add(i).as_ptr());
+                }
+            }
         }
     }
 }
@@ -0,0 +1,262 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Device names, and paths of fields and elements recorded by projections, for `Debug` output.
+
+use core::fmt::{self, Debug, Display, Formatter};
+
+/// The maximum number of segments recorded in a path. Deeper paths are truncated.
+const MAX_SEGMENTS: usize = 8;
+
+#[derive(Clone, Copy, Debug)]
+enum Segment {
+    /// The device name given to the pointer which the path starts from.
+    Name(&'static str),
+    /// The expression for the pointer which the path starts from, if it wasn't given a name.
+    Root(&'static str),
+    /// A field of a struct.
+    Field(&'static str),
+    /// An element of an array or slice.
+    Index(usize),
+}
+
+/// The path by which a pointer was projected from the original pointer, such as `uart.ctrl` or
+/// `gic.priority[3]`.
+#[derive(Clone, Copy)]
+pub(crate) struct DebugPath {
+    segments: [Segment; MAX_SEGMENTS],
+    len: usize,
+    truncated: bool,
+}
+
+impl DebugPath {
+    /// A path with no segments, for a pointer which wasn't projected from another.
+    pub const EMPTY: Self = Self {
+        segments: [Segment::Index(0); MAX_SEGMENTS],
+        len: 0,
+        truncated: false,
+    };
+
+    /// Returns a path starting from a pointer to the device with the given name.
+    pub const fn named(name: &'static str) -> Self {
+        Self::EMPTY.push(Segment::Name(name))
+    }
+
+    /// Returns the device name which the path starts from, if any.
+    pub const fn name(&self) -> Option<&'static str> {
+        match self.segments[0] {
+            Segment::Name(name) if self.len > 0 => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Returns whether no segments have been recorded.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the path to the given field of the pointer with this path.
+    ///
+    /// If this path is empty, it is first started from `parent`, which is the expression for the
+    /// parent pointer passed to the field macro.
+    pub const fn field(self, parent: &'static str, field: &'static str) -> Self {
+        let path = if self.is_empty() {
+            self.push(Segment::Root(parent))
+        } else {
+            self
+        };
+        path.push(Segment::Field(field))
+    }
+
+    /// Returns the path to the given element of the array or slice with this path.
+    pub const fn index(self, index: usize) -> Self {
+        self.push(Segment::Index(index))
+    }
+
+    const fn push(mut self, segment: Segment) -> Self {
+        if self.len < MAX_SEGMENTS {
+            self.segments[self.len] = segment;
+            self.len += 1;
+        } else {
+            self.truncated = true;
+        }
+        self
+    }
+}
+
+impl Display for DebugPath {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for (i, segment) in self.segments[..self.len].iter().enumerate() {
+            match segment {
+                Segment::Name(name) | Segment::Root(name) => write!(f, "{name}")?,
+                Segment::Field(field) if i == 0 => write!(f, "{field}")?,
+                Segment::Field(field) => write!(f, ".{field}")?,
+                Segment::Index(index) => write!(f, "[{index}]")?,
+            }
+        }
+        if self.truncated {
+            write!(f, "...")?;
+        }
+        Ok(())
+    }
+}
+
+impl Debug for DebugPath {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "\"{self}\"")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        UniqueMmioPointer, field,
+        fields::ReadWrite,
+        versioned::{Revision, VersionedMmio},
+    };
+    use core::fmt::Write;
+
+    /// A fixed-size buffer to format into.
+    struct Buffer {
+        bytes: [u8; 64],
+        len: usize,
+    }
+
+    impl Buffer {
+        fn format(args: fmt::Arguments) -> Self {
+            let mut buffer = Self {
+                bytes: [0; 64],
+                len: 0,
+            };
+            buffer.write_fmt(args).unwrap();
+            buffer
+        }
+
+        fn as_str(&self) -> &str {
+            core::str::from_utf8(&self.bytes[..self.len]).unwrap()
+        }
+    }
+
+    impl Write for Buffer {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let end = self.len + s.len();
+            self.bytes
+                .get_mut(self.len..end)
+                .ok_or(fmt::Error)?
+                .copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            Buffer::format(format_args!("{}", DebugPath::EMPTY)).as_str(),
+            ""
+        );
+        let path = DebugPath::EMPTY.field("uart", "ctrl");
+        assert_eq!(Buffer::format(format_args!("{path}")).as_str(), "uart.ctrl");
+        assert_eq!(
+            Buffer::format(format_args!("{path:?}")).as_str(),
+            "\"uart.ctrl\""
+        );
+        let path = DebugPath::EMPTY
+            .index(2)
+            .field("ignored", "priority")
+            .index(3);
+        assert_eq!(
+            Buffer::format(format_args!("{path}")).as_str(),
+            "[2].priority[3]"
+        );
+        assert_eq!(path.name(), None);
+        let path = DebugPath::named("gic@8000000")
+            .field("ignored", "priority")
+            .index(3);
+        assert_eq!(
+            Buffer::format(format_args!("{path}")).as_str(),
+            "gic@8000000.priority[3]"
+        );
+        assert_eq!(path.name(), Some("gic@8000000"));
+        let mut path = DebugPath::EMPTY;
+        for i in 0..10 {
+            path = path.index(i);
+        }
+        assert_eq!(
+            Buffer::format(format_args!("{path}")).as_str(),
+            "[0][1][2][3][4][5][6][7]..."
+        );
+    }
+
+    #[test]
+    fn projection() {
+        #[repr(C)]
+        struct Uart {
+            data: ReadWrite<u32>,
+            ctrl: [ReadWrite<u32>; 2],
+        }
+
+        let mut fake = Uart {
+            data: ReadWrite(0),
+            ctrl: [ReadWrite(0), ReadWrite(0)],
+        };
+        let mut uart = UniqueMmioPointer::from(&mut fake);
+        assert!(uart.0.path.is_empty());
+        assert_eq!(uart.name(), None);
+        let data = field!(uart, data);
+        assert_eq!(
+            Buffer::format(format_args!("{}", data.0.path)).as_str(),
+            "uart.data"
+        );
+        let mut ctrl = field!(uart, ctrl);
+        let ctrl = ctrl.get(1).unwrap();
+        assert_eq!(
+            Buffer::format(format_args!("{}", ctrl.0.path)).as_str(),
+            "uart.ctrl[1]"
+        );
+        assert!(
+            Buffer::format(format_args!("{ctrl:?}"))
+                .as_str()
+                .ends_with(", \"uart.ctrl[1]\")")
+        );
+
+        let mut uart = UniqueMmioPointer::from(&mut fake).with_name("pl011@9000000");
+        assert_eq!(uart.name(), Some("pl011@9000000"));
+        let data = field!(uart, data);
+        assert_eq!(data.name(), Some("pl011@9000000"));
+        assert_eq!(
+            Buffer::format(format_args!("{}", data.0.path)).as_str(),
+            "pl011@9000000.data"
+        );
+    }
+
+    #[test]
+    fn converted() {
+        let mut fake = [ReadWrite(0u32), ReadWrite(0)];
+        let mut iter = UniqueMmioPointer::from(&mut fake)
+            .with_name("timer@1000")
+            .into_iter();
+        iter.next();
+        let second = iter.next().unwrap();
+        assert_eq!(second.name(), Some("timer@1000"));
+        assert!(
+            Buffer::format(format_args!("{second:?}"))
+                .as_str()
+                .ends_with(", \"timer@1000\")")
+        );
+
+        // SAFETY: `fake` is valid for the layouts of both revisions.
+        let versioned = unsafe {
+            VersionedMmio::<[ReadWrite<u32>; 2], ReadWrite<u32>>::probe(
+                UniqueMmioPointer::from(&mut fake).with_name("timer@1000"),
+                |_| Some(Revision::V2),
+            )
+        };
+        let Some(VersionedMmio::V2(regs)) = versioned else {
+            panic!("Expected revision 2");
+        };
+        assert_eq!(regs.name(), Some("timer@1000"));
+    }
+}
@@ -0,0 +1,173 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! A macro to declare a fixed table of devices, with their regions checked at compile time.
+
+/// Declares a struct with a [`PhysicalInstance`](crate::PhysicalInstance) field for each of a
+/// fixed set of devices, each with a given base address and register type.
+///
+/// The region of each device is the size of its register type, and the regions are checked at
+/// compile time to make sure that no two of them overlap and none of them overflows the address
+/// space. This catches copy-paste mistakes in base addresses before anything boots.
+///
+/// The struct gets an unsafe `const fn new()` to construct it.
+///
+/// # Example
+///
+/// ```
+/// use safe_mmio::{device_table, fields::ReadWrite};
+///
+/// #[repr(C)]
+/// struct UartRegisters {
+///     data: ReadWrite<u32>,
+///     flags: ReadWrite<u32>,
+/// }
+///
+/// #[repr(C)]
+/// struct RtcRegisters {
+///     counter: ReadWrite<u32>,
+/// }
+///
+/// device_table! {
+///     /// The devices on our platform.
+///     pub struct Peripherals {
+///         pub uart0: UartRegisters = 0x900_0000,
+///         pub uart1: UartRegisters = 0x900_1000,
+///         pub rtc: RtcRegisters = 0x901_0000,
+///     }
+/// }
+///
+/// // SAFETY: The addresses are those of the devices on our platform, and we only call this once.
+/// let peripherals = unsafe { Peripherals::new() };
+/// assert_eq!(peripherals.uart1.pa(), 0x900_1000);
+/// ```
+///
+/// Overlapping regions fail to compile:
+///
+/// ```compile_fail
+/// use safe_mmio::{device_table, fields::ReadWrite};
+///
+/// device_table! {
+///     struct Peripherals {
+///         a: [ReadWrite<u32>; 4] = 0x1000,
+///         b: ReadWrite<u32> = 0x100c,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! device_table {
+    (
+        $(#[$attribute:meta])*
+        $struct_vis:vis struct $name:ident {
+            $(
+                $(#[$field_attribute:meta])*
+                $field_vis:vis $field:ident: $type:ty = $base:expr
+            ),+ $(,)?
+        }
+    ) => {
+        $(#[$attribute])*
+        $struct_vis struct $name {
+            $(
+                $(#[$field_attribute])*
+                $field_vis $field: $crate::PhysicalInstance<$type>,
+            )+
+        }
+
+        const _: () = assert!(
+            $crate::device_table::regions_valid(&[$(($base, size_of::<$type>())),+]),
+            concat!("Regions in device table ", stringify!($name), " overlap or overflow"),
+        );
+
+        impl $name {
+            /// Constructs the table of devices.
+            ///
+            /// # Safety
+            ///
+            /// Each address in the table must be the physical address of a real set of device
+            /// registers of the corresponding type, and there must only ever be a single
+            /// `PhysicalInstance` created for each of them. In particular, this must not be called
+            /// more than once.
+            #[allow(dead_code)]
+            $struct_vis const unsafe fn new() -> Self {
+                Self {
+                    $(
+                        // SAFETY: Our caller promised that the address is correct and that no
+                        // other `PhysicalInstance` exists for it.
+                        $field: unsafe { $crate::PhysicalInstance::new_u64($base) },
+                    )+
+                }
+            }
+        }
+    };
+}
+
+/// Returns whether none of the given regions overlap each other or extend past the end of the
+/// address space.
+///
+/// Each region is given as its physical base address and size in bytes. Zero-sized regions never
+/// overlap anything. This is used by [`device_table!`].
+#[doc(hidden)]
+pub const fn regions_valid(regions: &[(u64, usize)]) -> bool {
+    let mut i = 0;
+    while i < regions.len() {
+        let (base, size) = regions[i];
+        if base.checked_add(size as u64).is_none() {
+            return false;
+        }
+        i += 1;
+    }
+    let mut i = 0;
+    while i < regions.len() {
+        let (base, size) = regions[i];
+        let mut j = i + 1;
+        while j < regions.len() {
+            let (other_base, other_size) = regions[j];
+            if size != 0
+                && other_size != 0
+                && base < other_base + other_size as u64
+                && other_base < base + size as u64
+            {
+                return false;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fields::ReadWrite;
+
+    device_table! {
+        struct Peripherals {
+            a: [ReadWrite<u32>; 4] = 0x1000,
+            b: ReadWrite<u32> = 0x1010,
+            empty: () = 0x1004,
+        }
+    }
+
+    #[test]
+    fn new() {
+        // SAFETY: These instances are never used to access any device.
+        let peripherals = unsafe { Peripherals::new() };
+        assert_eq!(peripherals.a.pa(), 0x1000);
+        assert_eq!(peripherals.b.pa(), 0x1010);
+        assert_eq!(peripherals.empty.pa(), 0x1004);
+    }
+
+    #[test]
+    fn valid() {
+        assert!(regions_valid(&[]));
+        assert!(regions_valid(&[(0x1000, 0x10), (0x1010, 4), (0x1004, 0)]));
+        assert!(regions_valid(&[(u64::MAX - 3, 3)]));
+        assert!(!regions_valid(&[(0x1000, 0x10), (0x100c, 4)]));
+        assert!(!regions_valid(&[(0x100c, 4), (0x1000, 0x10)]));
+        assert!(!regions_valid(&[(0x1000, 0x10), (0x1000, 0x10)]));
+        assert!(!regions_valid(&[(u64::MAX - 3, 4)]));
+        assert!(!regions_valid(&[(0x1000, 0x10), (u64::MAX - 3, 4)]));
+    }
+}
@@ -0,0 +1,309 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! An MMIO backend for driver tests which can inject faults into accesses to fake registers.
+//!
+//! With the `fault-injection` feature enabled, [`FaultInjectingBackend`] can be registered as the
+//! [`custom_mmio`] backend. It performs volatile accesses to the fake registers as usual, except
+//! while [`with_faults`] is running, when the given [`Faults`] are injected. This lets tests
+//! exercise a driver's timeout and error handling deterministically.
+//!
+//! Faults apply to all accesses made through the backend from any thread while they are active,
+//! so concurrent calls to [`with_faults`] are serialised.
+//!
+//! [`custom_mmio`]: crate::custom_mmio
+//!
+//! # Example
+//!
+//! ```standalone_crate
+//! use safe_mmio::{
+//!     SharedMmioPointer, UniqueMmioPointer,
+//!     fault::{FaultInjectingBackend, Faults, StuckBits, with_faults},
+//!     fields::ReadPure,
+//!     set_mmio_ops,
+//! };
+//!
+//! set_mmio_ops!(FaultInjectingBackend);
+//!
+//! const READY: u32 = 1 << 3;
+//!
+//! fn wait_ready(status: SharedMmioPointer<ReadPure<u32>>) -> Result<(), ()> {
+//!     for _ in 0..100 {
+//!         if status.read() & READY != 0 {
+//!             return Ok(());
+//!         }
+//!     }
+//!     Err(())
+//! }
+//!
+//! let mut fake = ReadPure(READY);
+//! let status = UniqueMmioPointer::from(&mut fake);
+//! assert_eq!(wait_ready(*status), Ok(()));
+//!
+//! let faults = Faults {
+//!     stuck_bits: Some(StuckBits::new(&status, READY.into(), 0)),
+//!     ..Default::default()
+//! };
+//! assert_eq!(with_faults(faults, || wait_ready(*status)), Err(()));
+//! ```
+
+use crate::{SharedMmioPointer, custom_mmio::MmioOps};
+use core::{
+    hint::spin_loop,
+    sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+};
+
+static IN_USE: AtomicBool = AtomicBool::new(false);
+static ACCESSES: AtomicUsize = AtomicUsize::new(0);
+static BUS_ERROR_AFTER: AtomicUsize = AtomicUsize::new(usize::MAX);
+static STUCK_ADDRESS: AtomicUsize = AtomicUsize::new(0);
+static STUCK_MASK: AtomicU64 = AtomicU64::new(0);
+static STUCK_VALUE: AtomicU64 = AtomicU64::new(0);
+static FLIP_STATE: AtomicU64 = AtomicU64::new(0);
+
+/// Faults to inject into MMIO accesses made through [`FaultInjectingBackend`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Faults {
+    /// After this many MMIO accesses, all reads return all ones as for a bus error, and writes are
+    /// ignored.
+    pub bus_error_after: Option<usize>,
+    /// Bits of a register which are stuck at a particular value, such as a ready bit which never
+    /// gets set.
+    pub stuck_bits: Option<StuckBits>,
+    /// If set, one pseudo-random bit of every value read is flipped, using a generator with the
+    /// given seed.
+    pub flip_bits_seed: Option<u64>,
+}
+
+/// Bits of a register which always read as a particular value.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct StuckBits {
+    address: usize,
+    mask: u64,
+    value: u64,
+}
+
+impl StuckBits {
+    /// Makes the bits set in `mask` always read as the corresponding bits of `value`, for reads of
+    /// the given register.
+    pub fn new<T: ?Sized>(register: &SharedMmioPointer<T>, mask: u64, value: u64) -> Self {
+        Self {
+            address: register.addr(),
+            mask,
+            value,
+        }
+    }
+}
+
+/// Calls `f` with the given faults injected into all MMIO accesses done via
+/// [`FaultInjectingBackend`].
+///
+/// Accesses are counted for [`Faults::bus_error_after`] from the start of `f`. If another thread is
+/// already running `with_faults` then this waits for it to finish first.
+pub fn with_faults<R>(faults: Faults, f: impl FnOnce() -> R) -> R {
+    while IN_USE
+        .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+        .is_err()
+    {
+        spin_loop();
+    }
+    let _guard = FaultsGuard;
+    if let Some(stuck_bits) = faults.stuck_bits {
+        STUCK_MASK.store(stuck_bits.mask, Ordering::Relaxed);
+        STUCK_VALUE.store(stuck_bits.value, Ordering::Relaxed);
+        STUCK_ADDRESS.store(stuck_bits.address, Ordering::Relaxed);
+    }
+    if let Some(seed) = faults.flip_bits_seed {
+        // The xorshift generator gets stuck at 0, so avoid that.
+        FLIP_STATE.store(seed.max(1), Ordering::Relaxed);
+    }
+    ACCESSES.store(0, Ordering::Relaxed);
+    BUS_ERROR_AFTER.store(
+        faults.bus_error_after.unwrap_or(usize::MAX),
+        Ordering::Relaxed,
+    );
+    f()
+}
+
+/// Returns the number of MMIO accesses made through [`FaultInjectingBackend`] since the start of
+/// the current or most recent call to [`with_faults`].
+pub fn access_count() -> usize {
+    ACCESSES.load(Ordering::Relaxed)
+}
+
+/// Removes all faults when dropped, including if the function passed to [`with_faults`] panics.
+struct FaultsGuard;
+
+impl Drop for FaultsGuard {
+    fn drop(&mut self) {
+        BUS_ERROR_AFTER.store(usize::MAX, Ordering::Relaxed);
+        STUCK_ADDRESS.store(0, Ordering::Relaxed);
+        FLIP_STATE.store(0, Ordering::Relaxed);
+        IN_USE.store(false, Ordering::Release);
+    }
+}
+
+/// Counts an MMIO access, and returns whether it should fail with a bus error.
+fn bus_error() -> bool {
+    ACCESSES.fetch_add(1, Ordering::Relaxed) >= BUS_ERROR_AFTER.load(Ordering::Relaxed)
+}
+
+/// Applies stuck bits and bit flips to a value of `width` bits read from the given address.
+fn corrupt(address: usize, width: u32, mut value: u64) -> u64 {
+    if STUCK_ADDRESS.load(Ordering::Relaxed) == address {
+        let mask = STUCK_MASK.load(Ordering::Relaxed);
+        value = (value & !mask) | (STUCK_VALUE.load(Ordering::Relaxed) & mask);
+    }
+    if let Ok(state) = FLIP_STATE.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |state| {
+        (state != 0).then(|| xorshift(state))
+    }) {
+        value ^= 1 << (xorshift(state) % u64::from(width));
+    }
+    value
+}
+
+fn xorshift(mut state: u64) -> u64 {
+    state ^= state << 13;
+    state ^= state >> 7;
+    state ^= state << 17;
+    state
+}
+
+/// An [`MmioOps`] implementation which performs volatile accesses, with faults injected by
+/// [`with_faults`].
+///
+/// Register it with [`set_mmio_ops!`](crate::set_mmio_ops) in the test binary.
+#[derive(Debug)]
+pub struct FaultInjectingBackend;
+
+// SAFETY: Each method performs at most a single volatile access of the indicated width.
+unsafe impl MmioOps for FaultInjectingBackend {
+    unsafe fn read_u8(src: *const u8) -> u8 {
+        if bus_error() {
+            return u8::MAX;
+        }
+        // SAFETY: Caller guarantees src is valid and aligned.
+        let value = unsafe { src.read_volatile() };
+        corrupt(src.addr(), u8::BITS, value.into()) as u8
+    }
+
+    unsafe fn read_u16(src: *const u16) -> u16 {
+        if bus_error() {
+            return u16::MAX;
+        }
+        // SAFETY: Caller guarantees src is valid and aligned.
+        let value = unsafe { src.read_volatile() };
+        corrupt(src.addr(), u16::BITS, value.into()) as u16
+    }
+
+    unsafe fn read_u32(src: *const u32) -> u32 {
+        if bus_error() {
+            return u32::MAX;
+        }
+        // SAFETY: Caller guarantees src is valid and aligned.
+        let value = unsafe { src.read_volatile() };
+        corrupt(src.addr(), u32::BITS, value.into()) as u32
+    }
+
+    unsafe fn read_u64(src: *const u64) -> u64 {
+        if bus_error() {
+            return u64::MAX;
+        }
+        // SAFETY: Caller guarantees src is valid and aligned.
+        let value = unsafe { src.read_volatile() };
+        corrupt(src.addr(), u64::BITS, value)
+    }
+
+    unsafe fn write_u8(dst: *mut u8, value: u8) {
+        if !bus_error() {
+            // SAFETY: Caller guarantees dst is valid and aligned.
+            unsafe { dst.write_volatile(value) }
+        }
+    }
+
+    unsafe fn write_u16(dst: *mut u16, value: u16) {
+        if !bus_error() {
+            // SAFETY: Caller guarantees dst is valid and aligned.
+            unsafe { dst.write_volatile(value) }
+        }
+    }
+
+    unsafe fn write_u32(dst: *mut u32, value: u32) {
+        if !bus_error() {
+            // SAFETY: Caller guarantees dst is valid and aligned.
+            unsafe { dst.write_volatile(value) }
+        }
+    }
+
+    unsafe fn write_u64(dst: *mut u64, value: u64) {
+        if !bus_error() {
+            // SAFETY: Caller guarantees dst is valid and aligned.
+            unsafe { dst.write_volatile(value) }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{UniqueMmioPointer, fields::ReadWrite};
+
+    #[test]
+    fn bus_error_after() {
+        let mut register = 42u32;
+        let faults = Faults {
+            bus_error_after: Some(2),
+            ..Default::default()
+        };
+        with_faults(faults, || {
+            // SAFETY: The pointer is to a local variable.
+            unsafe {
+                assert_eq!(FaultInjectingBackend::read_u32(&register), 42);
+                FaultInjectingBackend::write_u32(&mut register, 66);
+                assert_eq!(FaultInjectingBackend::read_u32(&register), u32::MAX);
+                FaultInjectingBackend::write_u32(&mut register, 100);
+            }
+            assert_eq!(access_count(), 4);
+        });
+        assert_eq!(register, 66);
+        // SAFETY: The pointer is to a local variable.
+        assert_eq!(unsafe { FaultInjectingBackend::read_u32(&register) }, 66);
+    }
+
+    #[test]
+    fn stuck_bits() {
+        let mut fake = [ReadWrite(0xf0u8), ReadWrite(0xf0)];
+        let regs = UniqueMmioPointer::from(&mut fake);
+        let [stuck, other] = regs.split();
+        let faults = Faults {
+            stuck_bits: Some(StuckBits::new(&stuck, 0x11, 0x01)),
+            ..Default::default()
+        };
+        with_faults(faults, || {
+            // SAFETY: The pointers are to a local variable.
+            unsafe {
+                assert_eq!(FaultInjectingBackend::read_u8(stuck.ptr().cast()), 0xe1);
+                assert_eq!(FaultInjectingBackend::read_u8(other.ptr().cast()), 0xf0);
+            }
+        });
+    }
+
+    #[test]
+    fn flip_bits() {
+        let register = 0u64;
+        let faults = Faults {
+            flip_bits_seed: Some(1234),
+            ..Default::default()
+        };
+        let read = || {
+            // SAFETY: The pointer is to a local variable.
+            let value = unsafe { FaultInjectingBackend::read_u64(&register) };
+            assert_eq!(value.count_ones(), 1);
+            value
+        };
+        let first = with_faults(faults, || [read(), read(), read()]);
+        let second = with_faults(faults, || [read(), read(), read()]);
+        assert_eq!(first, second);
+    }
+}
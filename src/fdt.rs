@@ -0,0 +1,124 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Helpers for constructing [`PhysicalInstance`]s from devicetree nodes, using the [`fdt`] crate.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_mmio::{fdt::physical_instance_from_reg, fields::ReadWrite, PhysicalInstance};
+//!
+//! #[repr(C)]
+//! struct UartRegisters {
+//!     data: ReadWrite<u32>,
+//!     status: ReadWrite<u32>,
+//! }
+//!
+//! fn find_uart(fdt: &fdt::Fdt) -> Option<PhysicalInstance<UartRegisters>> {
+//!     let node = fdt.find_compatible(&["arm,pl011"])?;
+//!     // SAFETY: We only call this once for the UART node, and the devicetree describes real
+//!     // devices.
+//!     unsafe { physical_instance_from_reg(node, 0).ok() }
+//! }
+//! ```
+
+use crate::PhysicalInstance;
+use ::fdt::node::FdtNode;
+use core::fmt::{self, Display, Formatter};
+
+/// An error getting a device region from the `reg` property of a devicetree node.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FdtRegError {
+    /// The node has no `reg` property.
+    NoReg,
+    /// The parent node's `#address-cells` or `#size-cells` is greater than 2.
+    UnsupportedCells,
+    /// The `reg` property doesn't have an entry with the given index.
+    IndexOutOfRange,
+    /// The parent node's `#size-cells` is 0, so the region has no size.
+    NoSize,
+    /// The size doesn't fit in a `usize`.
+    TooLarge,
+    /// The region is smaller than the type being requested.
+    TooSmall {
+        /// The size in bytes of the type being requested.
+        expected: usize,
+        /// The size in bytes of the region in the devicetree.
+        actual: usize,
+    },
+}
+
+impl Display for FdtRegError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::NoReg => write!(f, "Node has no reg property"),
+            Self::UnsupportedCells => write!(f, "Unsupported #address-cells or #size-cells"),
+            Self::IndexOutOfRange => write!(f, "reg property has no entry with the given index"),
+            Self::NoSize => write!(f, "reg property has no size"),
+            Self::TooLarge => write!(f, "reg size doesn't fit in usize"),
+            Self::TooSmall { expected, actual } => write!(
+                f,
+                "reg size {actual:#x} is smaller than requested size {expected:#x}"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for FdtRegError {}
+
+/// Returns the physical base address and size in bytes of the entry with the given index in the
+/// `reg` property of the given devicetree node.
+///
+/// The number of cells for the address and size is taken from the parent node's `#address-cells`
+/// and `#size-cells` properties.
+pub fn reg_entry(node: FdtNode, index: usize) -> Result<(u64, usize), FdtRegError> {
+    let entry = node
+        .raw_reg()
+        .ok_or(FdtRegError::NoReg)?
+        .nth(index)
+        .ok_or(FdtRegError::IndexOutOfRange)?;
+    if entry.address.len() > 8 || entry.size.len() > 8 {
+        return Err(FdtRegError::UnsupportedCells);
+    }
+    if entry.size.is_empty() {
+        return Err(FdtRegError::NoSize);
+    }
+    let size = parse_cells(entry.size)
+        .try_into()
+        .map_err(|_| FdtRegError::TooLarge)?;
+    Ok((parse_cells(entry.address), size))
+}
+
+/// Constructs a `PhysicalInstance` for the entry with the given index in the `reg` property of the
+/// given devicetree node.
+///
+/// Returns an error if the region is smaller than `T`.
+///
+/// # Safety
+///
+/// The devicetree must accurately describe the device, the registers of which must have type `T`,
+/// and there must only ever be a single `PhysicalInstance` created for those device registers.
+/// In particular, this must not be called more than once for the same node and index.
+pub unsafe fn physical_instance_from_reg<T>(
+    node: FdtNode,
+    index: usize,
+) -> Result<PhysicalInstance<T>, FdtRegError> {
+    let (pa, size) = reg_entry(node, index)?;
+    if size < size_of::<T>() {
+        return Err(FdtRegError::TooSmall {
+            expected: size_of::<T>(),
+            actual: size,
+        });
+    }
+    // SAFETY: The caller promised that the devicetree describes real device registers of type `T`,
+    // and that no other `PhysicalInstance` exists for them.
+    Ok(unsafe { PhysicalInstance::new_u64(pa) })
+}
+
+/// Parses up to 2 big-endian cells into a `u64`.
+fn parse_cells(cells: &[u8]) -> u64 {
+    cells
+        .iter()
+        .fold(0u64, |value, byte| (value << 8) | u64::from(*byte))
+}
@@ -3,7 +3,14 @@
 // See LICENSE-APACHE and LICENSE-MIT for details.
 
 //! Wrapper types for MMIO fields.
+//!
+//! Every wrapper except [`Overlay`] is `repr(transparent)`, so it has the same size, alignment and
+//! layout as the type it wraps, and can be used in place of it in a `repr(C)` register struct
+//! without changing any offsets. `Overlay` is a `repr(C)` union of two layouts, as large as the
+//! larger of them. To expose a register to driver code as a domain type rather than an integer, use
+//! [`Converted`](crate::access::Converted).
 
+use core::{marker::PhantomData, mem::ManuallyDrop};
 use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
 
 /// Wrapper for a field which may safely be read but not written. Reading may cause side-effects,
@@ -17,6 +24,15 @@ pub struct ReadOnly<T>(pub T);
 #[repr(transparent)]
 pub struct ReadPure<T>(pub T);
 
+/// Wrapper for a field which may safely be read with no side-effects but not written, and whose
+/// value never changes, such as an ID or feature register.
+///
+/// Unlike [`ReadPure`], the compiler may merge repeated reads of the field or remove reads whose
+/// value is unused, so reading it in a loop costs no more than reading it once.
+#[derive(Clone, Debug, Default, Eq, FromBytes, Immutable, IntoBytes, KnownLayout, PartialEq)]
+#[repr(transparent)]
+pub struct ReadConstant<T>(pub T);
+
 /// Wrapper for a field which may safely be written but not read.
 #[derive(Clone, Debug, Default, Eq, FromBytes, Immutable, IntoBytes, KnownLayout, PartialEq)]
 #[repr(transparent)]
@@ -33,3 +49,128 @@ pub struct ReadWrite<T>(pub T);
 #[derive(Clone, Debug, Default, Eq, FromBytes, Immutable, IntoBytes, KnownLayout, PartialEq)]
 #[repr(transparent)]
 pub struct ReadPureWrite<T>(pub T);
+
+/// Wrapper for a 64-bit field which may safely be written and read, but which must be accessed as
+/// two 32-bit halves, with the low half first.
+///
+/// The low half is at the lower address, as for a little-endian device. Reading may cause
+/// side-effects, such as latching the high half when the low half is read.
+#[derive(Clone, Debug, Default, Eq, FromBytes, Immutable, IntoBytes, KnownLayout, PartialEq)]
+#[repr(transparent)]
+pub struct ReadWrite64LoHi(pub u64);
+
+/// Wrapper for a 64-bit field which may safely be written and read, but which must be accessed as
+/// two 32-bit halves, with the high half first.
+///
+/// The low half is at the lower address, as for a little-endian device. Reading may cause
+/// side-effects, such as latching the low half when the high half is read.
+#[derive(Clone, Debug, Default, Eq, FromBytes, Immutable, IntoBytes, KnownLayout, PartialEq)]
+#[repr(transparent)]
+pub struct ReadWrite64HiLo(pub u64);
+
+/// Wrapper for a command or doorbell field which may safely be written with any value of `C`, but
+/// not with arbitrary integers, and not read.
+///
+/// `C` is typically a fieldless enum with a `repr` of `u8`, `u16`, `u32` or `u64` and a variant for
+/// each valid command, deriving `IntoBytes` and `Immutable`. It must be a single naturally aligned
+/// 1, 2, 4 or 8 byte value.
+#[derive(Clone, Debug, Default, Eq, FromBytes, Immutable, IntoBytes, KnownLayout, PartialEq)]
+#[repr(transparent)]
+pub struct Command<C>(pub C);
+
+/// Wrapper for a command field which may safely be written with any value of `C`, and read with no
+/// side-effects to find the last command written.
+///
+/// Reading checks that the register contains a valid value of `C`, so `C` must also derive
+/// `TryFromBytes`. See [`Command`] for the other requirements on `C`.
+#[derive(Clone, Debug, Default, Eq, FromBytes, Immutable, IntoBytes, KnownLayout, PartialEq)]
+#[repr(transparent)]
+pub struct ReadPureCommand<C>(pub C);
+
+/// Wrapper for a field which may safely be read with no side-effects, but which only accepts writes
+/// after an unlock sequence of keys of type `U` has been written to another register.
+///
+/// Writes are made through the guard returned by
+/// [`unlock_with`](crate::UniqueMmioPointer::unlock_with).
+#[derive(Clone, Debug, Default, Eq, FromBytes, Immutable, IntoBytes, KnownLayout, PartialEq)]
+#[repr(transparent)]
+pub struct Protected<T, U>(pub T, pub PhantomData<U>);
+
+/// Wrapper for a field which may safely be read with no side-effects, but which may only be written
+/// by code holding a reference to a capability token of type `Token`.
+///
+/// `Token` is typically a zero-sized type with a private field, defined by the platform module which
+/// is allowed to configure the register. See the [`privileged`](crate::privileged) module.
+#[derive(Clone, Debug, Default, Eq, FromBytes, Immutable, IntoBytes, KnownLayout, PartialEq)]
+#[repr(transparent)]
+pub struct Privileged<T, Token>(pub T, pub PhantomData<Token>);
+
+/// Wrapper for a register `W`, such as [`ReadWrite`] or [`ReadPureWrite`], of which only the bits
+/// set in `MASK` may be written. The other bits are reserved, and written according to the policy
+/// `R`: [`PreserveReserved`] writes back their current value, and [`ZeroReserved`] writes zeroes.
+///
+/// Writes through the wrapper apply the mask, so a driver can't accidentally change the reserved
+/// bits. See the [`masked`](crate::masked) module.
+#[derive(Clone, Debug, Default, Eq, FromBytes, Immutable, IntoBytes, KnownLayout, PartialEq)]
+#[repr(transparent)]
+pub struct Masked<W, const MASK: u64, R = PreserveReserved>(pub W, pub PhantomData<R>);
+
+/// Policy for a [`Masked`] register to write back the current value of its reserved bits. This
+/// needs an extra read before each write.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PreserveReserved;
+
+/// Policy for a [`Masked`] register to write zeroes to its reserved bits.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ZeroReserved;
+
+/// A field whose registers have layout `A` or layout `B` depending on the mode of the device.
+///
+/// It is as large as the larger of `A` and `B`, and aligned for both. Use
+/// [`as_a`](crate::UniqueMmioPointer::as_a) or [`as_b`](crate::UniqueMmioPointer::as_b) to access
+/// the registers. As with any other field wrapper, every register in both layouts must be safe to
+/// access as its wrapper says whatever mode the device is in; it is up to the driver to pick the
+/// layout which is meaningful. See the [`overlay`](crate::overlay) module.
+#[repr(C)]
+pub union Overlay<A, B> {
+    pub(crate) a: ManuallyDrop<A>,
+    pub(crate) b: ManuallyDrop<B>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::mem::align_of;
+
+    macro_rules! assert_transparent {
+        ($($wrapper:ty => $inner:ty),* $(,)?) => {
+            $(
+                assert_eq!(size_of::<$wrapper>(), size_of::<$inner>());
+                assert_eq!(align_of::<$wrapper>(), align_of::<$inner>());
+            )*
+        };
+    }
+
+    #[test]
+    fn transparent() {
+        assert_transparent!(
+            ReadOnly<u8> => u8,
+            ReadOnly<u64> => u64,
+            ReadPure<u16> => u16,
+            ReadPure<[u32; 3]> => [u32; 3],
+            WriteOnly<u32> => u32,
+            ReadWrite<u64> => u64,
+            ReadPureWrite<u8> => u8,
+            ReadWrite64LoHi => u64,
+            ReadWrite64HiLo => u64,
+            Command<u16> => u16,
+            ReadPureCommand<u32> => u32,
+            Protected<u32, u8> => u32,
+            Protected<u8, u64> => u8,
+            Privileged<u32, ()> => u32,
+            Privileged<u64, [u8; 0]> => u64,
+            Masked<ReadWrite<u32>, 0xff> => u32,
+            Masked<ReadPureWrite<u16>, 0x3, ZeroReserved> => u16,
+        );
+    }
+}
@@ -0,0 +1,231 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Helper for devices with a FIFO accessed through a data register and a status register.
+
+use crate::{UniqueMmioPointer, access::AtomicAccess};
+use core::ops::BitAnd;
+
+/// A condition indicated by one or more bits of a status register.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Flag<S> {
+    mask: S,
+    active_low: bool,
+}
+
+impl<S: AtomicAccess + BitAnd<Output = S> + Default + Eq> Flag<S> {
+    /// Returns a flag for a condition which holds when any of the bits in `mask` are set.
+    pub const fn when_set(mask: S) -> Self {
+        Self {
+            mask,
+            active_low: false,
+        }
+    }
+
+    /// Returns a flag for a condition which holds when all of the bits in `mask` are clear, e.g.
+    /// "full" for a "transmit holding register empty" bit.
+    pub const fn when_clear(mask: S) -> Self {
+        Self {
+            mask,
+            active_low: true,
+        }
+    }
+
+    /// Returns whether the condition holds for the given status register value.
+    pub fn holds(self, status: S) -> bool {
+        (status & self.mask != S::default()) != self.active_low
+    }
+}
+
+/// A FIFO accessed by writing or reading a data register, with flags in a status register to say
+/// whether it is full or empty.
+///
+/// The status register is read before each access to the data register, so this never writes to a
+/// full FIFO or reads from an empty one.
+///
+/// # Example
+///
+/// ```
+/// use safe_mmio::{
+///     UniqueMmioPointer,
+///     fifo::{Fifo, Flag},
+/// };
+///
+/// const TXFF: u32 = 1 << 5;
+/// const RXFE: u32 = 1 << 4;
+///
+/// let data: UniqueMmioPointer<u32>;
+/// let flags: UniqueMmioPointer<u32>;
+/// # let mut fake_data = 0;
+/// # let mut fake_flags = 0;
+/// # data = UniqueMmioPointer::from(&mut fake_data);
+/// # flags = UniqueMmioPointer::from(&mut fake_flags);
+/// // SAFETY: Writing to the PL011 data register transmits a byte, reading from it receives a byte,
+/// // and reading the flag register has no side-effects.
+/// let mut fifo = unsafe { Fifo::new(data, flags, Flag::when_set(TXFF), Flag::when_set(RXFE)) };
+/// fifo.try_push(u32::from(b'x')).unwrap();
+/// let mut received = [0; 16];
+/// let count = fifo.drain_into(&mut received);
+/// ```
+#[derive(Debug)]
+pub struct Fifo<'a, D, S> {
+    data: UniqueMmioPointer<'a, D>,
+    status: UniqueMmioPointer<'a, S>,
+    full: Flag<S>,
+    empty: Flag<S>,
+}
+
+impl<'a, D: AtomicAccess, S: AtomicAccess + BitAnd<Output = S> + Default + Eq> Fifo<'a, D, S> {
+    /// Creates a new `Fifo` from pointers to its data and status registers, and the flags in the
+    /// status register which say whether the FIFO is full or empty.
+    ///
+    /// # Safety
+    ///
+    /// It must be safe to read the status register at any time, to write any value of type `D` to
+    /// the data register when the FIFO isn't full, and to read the data register when the FIFO
+    /// isn't empty.
+    pub const unsafe fn new(
+        data: UniqueMmioPointer<'a, D>,
+        status: UniqueMmioPointer<'a, S>,
+        full: Flag<S>,
+        empty: Flag<S>,
+    ) -> Self {
+        Self {
+            data,
+            status,
+            full,
+            empty,
+        }
+    }
+
+    /// Reads the status register and returns whether the FIFO is full.
+    pub fn is_full(&mut self) -> bool {
+        self.full.holds(self.read_status())
+    }
+
+    /// Reads the status register and returns whether the FIFO is empty.
+    pub fn is_empty(&mut self) -> bool {
+        self.empty.holds(self.read_status())
+    }
+
+    /// Writes the given value to the data register, unless the FIFO is full in which case it is
+    /// returned as an error.
+    pub fn try_push(&mut self, value: D) -> Result<(), D> {
+        if self.is_full() {
+            return Err(value);
+        }
+        // SAFETY: The caller of `new` promised that writing any value to the data register is safe
+        // when the FIFO isn't full.
+        unsafe {
+            self.data.write_unsafe(value);
+        }
+        Ok(())
+    }
+
+    /// Reads a value from the data register, unless the FIFO is empty.
+    pub fn try_pop(&mut self) -> Option<D> {
+        if self.is_empty() {
+            return None;
+        }
+        // SAFETY: The caller of `new` promised that reading the data register is safe when the FIFO
+        // isn't empty.
+        Some(unsafe { self.data.read_unsafe() })
+    }
+
+    /// Writes values from the start of the given slice until it is exhausted or the FIFO is full.
+    ///
+    /// Returns the number of values written.
+    pub fn fill_from(&mut self, values: &[D]) -> usize {
+        values
+            .iter()
+            .take_while(|value| self.try_push(**value).is_ok())
+            .count()
+    }
+
+    /// Reads values into the start of the given slice until it is full or the FIFO is empty.
+    ///
+    /// Returns the number of values read.
+    pub fn drain_into(&mut self, buffer: &mut [D]) -> usize {
+        let mut count = 0;
+        for slot in buffer {
+            let Some(value) = self.try_pop() else {
+                break;
+            };
+            *slot = value;
+            count += 1;
+        }
+        count
+    }
+
+    /// Returns the original pointers to the data and status registers.
+    pub fn into_inner(self) -> (UniqueMmioPointer<'a, D>, UniqueMmioPointer<'a, S>) {
+        (self.data, self.status)
+    }
+
+    fn read_status(&mut self) -> S {
+        // SAFETY: The caller of `new` promised that reading the status register is always safe.
+        unsafe { self.status.read_unsafe() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags() {
+        assert!(Flag::when_set(0b110u8).holds(0b010));
+        assert!(!Flag::when_set(0b110u8).holds(0b001));
+        assert!(Flag::when_clear(0b100u8).holds(0b011));
+        assert!(!Flag::when_clear(0b100u8).holds(0b100));
+    }
+
+    #[test]
+    fn push_pop() {
+        const FULL: u16 = 1 << 0;
+        const NOT_EMPTY: u16 = 1 << 1;
+
+        let mut data = 0u8;
+        let mut status = FULL;
+        {
+            // SAFETY: These are just fakes, so accessing them has no side-effects.
+            let mut fifo = unsafe {
+                Fifo::new(
+                    UniqueMmioPointer::from(&mut data),
+                    UniqueMmioPointer::from(&mut status),
+                    Flag::when_set(FULL),
+                    Flag::when_clear(NOT_EMPTY),
+                )
+            };
+            assert!(fifo.is_full());
+            assert!(fifo.is_empty());
+            assert_eq!(fifo.try_push(42), Err(42));
+            assert_eq!(fifo.fill_from(&[1, 2]), 0);
+            assert_eq!(fifo.try_pop(), None);
+            let mut buffer = [0; 4];
+            assert_eq!(fifo.drain_into(&mut buffer), 0);
+
+            let (_, mut status) = fifo.into_inner();
+            // SAFETY: This is just a fake.
+            unsafe { status.write_unsafe(NOT_EMPTY) };
+        }
+        assert_eq!(data, 0);
+        {
+            // SAFETY: These are just fakes, so accessing them has no side-effects.
+            let mut fifo = unsafe {
+                Fifo::new(
+                    UniqueMmioPointer::from(&mut data),
+                    UniqueMmioPointer::from(&mut status),
+                    Flag::when_set(FULL),
+                    Flag::when_clear(NOT_EMPTY),
+                )
+            };
+            assert_eq!(fifo.fill_from(&[1, 2, 3]), 3);
+            assert_eq!(fifo.try_pop(), Some(3));
+            let mut buffer = [0; 4];
+            assert_eq!(fifo.drain_into(&mut buffer), 4);
+            assert_eq!(buffer, [3; 4]);
+        }
+    }
+}
@@ -0,0 +1,200 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! An MMIO backend for fuzzing drivers against arbitrary device behaviour.
+//!
+//! With the `fuzz` feature enabled, [`FuzzBackend`] can be registered as the [`custom_mmio`]
+//! backend. Every MMIO read then takes its value from the fuzz input passed to [`with_input`],
+//! rather than from memory, and writes are discarded. This lets a fuzzer explore how a driver
+//! copes with a device returning nonsensical values, without needing a fake device at all.
+//!
+//! Once the input is exhausted, reads return zero.
+//!
+//! [`custom_mmio`]: crate::custom_mmio
+//!
+//! # Example
+//!
+//! ```standalone_crate
+//! use core::ptr::NonNull;
+//! use safe_mmio::{UniqueMmioPointer, fields::ReadOnly, fuzz, set_mmio_ops};
+//!
+//! set_mmio_ops!(fuzz::FuzzBackend);
+//!
+//! fn wait_ready(mut status: UniqueMmioPointer<ReadOnly<u32>>) -> Result<(), ()> {
+//!     for _ in 0..10 {
+//!         match status.read() {
+//!             0 => {}
+//!             1 => return Ok(()),
+//!             _ => return Err(()),
+//!         }
+//!     }
+//!     Err(())
+//! }
+//!
+//! // This would usually be called from a fuzz target, e.g. with `libfuzzer-sys`.
+//! fn fuzz_target(data: &[u8]) {
+//!     fuzz::with_input(data, || {
+//!         // SAFETY: The fuzz backend never accesses the memory which the pointer refers to.
+//!         let status = unsafe { UniqueMmioPointer::new(NonNull::dangling()) };
+//!         let _ = wait_ready(status);
+//!     });
+//! }
+//!
+//! fuzz_target(&[0, 0, 0, 0, 1, 0, 0, 0]);
+//! ```
+
+use crate::custom_mmio::MmioOps;
+use core::{
+    cell::UnsafeCell,
+    hint::spin_loop,
+    ptr::NonNull,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// The remaining fuzz input, protected by a spinlock which is held for the whole of each read so
+/// that the input can't be cleared while it is being read.
+struct Input {
+    locked: AtomicBool,
+    remaining: UnsafeCell<Option<NonNull<[u8]>>>,
+}
+
+// SAFETY: `remaining` is only accessed while holding `locked`.
+unsafe impl Sync for Input {}
+
+impl Input {
+    /// Calls `f` with the remaining input while holding the lock.
+    fn with<R>(&self, f: impl FnOnce(&mut Option<NonNull<[u8]>>) -> R) -> R {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            spin_loop();
+        }
+        let _unlock = Unlock(&self.locked);
+        // SAFETY: We hold the lock, so nothing else can access `remaining` until we release it.
+        f(unsafe { &mut *self.remaining.get() })
+    }
+}
+
+/// Releases the lock when dropped, including if the function passed to [`Input::with`] panics.
+struct Unlock<'a>(&'a AtomicBool);
+
+impl Drop for Unlock<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+static INPUT: Input = Input {
+    locked: AtomicBool::new(false),
+    remaining: UnsafeCell::new(None),
+};
+
+/// Calls `f` with the given fuzz input used for all MMIO reads done via [`FuzzBackend`].
+///
+/// Panics if called again from within `f`, or while another thread is calling it.
+pub fn with_input<R>(input: &[u8], f: impl FnOnce() -> R) -> R {
+    INPUT.with(|remaining| {
+        assert!(
+            remaining.is_none(),
+            "fuzz::with_input is already in progress"
+        );
+        *remaining = Some(NonNull::from(input));
+    });
+    let _guard = InputGuard;
+    f()
+}
+
+/// Clears the fuzz input when dropped, including if the function passed to [`with_input`] panics.
+struct InputGuard;
+
+impl Drop for InputGuard {
+    fn drop(&mut self) {
+        INPUT.with(|remaining| *remaining = None);
+    }
+}
+
+/// Fills `bytes` from the front of the current fuzz input, or with zeroes once it is exhausted.
+///
+/// Panics if there is no current fuzz input.
+fn take_input(bytes: &mut [u8]) {
+    INPUT.with(|remaining| {
+        let remaining = remaining
+            .as_mut()
+            .expect("MMIO read with FuzzBackend outside of fuzz::with_input");
+        // SAFETY: `with_input` stored a pointer to a slice which remains borrowed until the guard
+        // clears it, which can't happen while we hold the lock. We only ever replace it with a
+        // subslice.
+        let input = unsafe { remaining.as_ref() };
+        let (head, tail) = input.split_at(bytes.len().min(input.len()));
+        bytes[..head.len()].copy_from_slice(head);
+        bytes[head.len()..].fill(0);
+        *remaining = NonNull::from(tail);
+    });
+}
+
+/// An [`MmioOps`] implementation which returns values from the fuzz input for reads, and discards
+/// writes.
+///
+/// Register it with [`set_mmio_ops!`](crate::set_mmio_ops) in the fuzz target, and run the driver
+/// code within [`with_input`].
+#[derive(Debug)]
+pub struct FuzzBackend;
+
+// SAFETY: None of the methods access memory through the given pointers, so they are trivially
+// sound for any pointer.
+unsafe impl MmioOps for FuzzBackend {
+    unsafe fn read_u8(_src: *const u8) -> u8 {
+        let mut bytes = [0; 1];
+        take_input(&mut bytes);
+        u8::from_le_bytes(bytes)
+    }
+
+    unsafe fn read_u16(_src: *const u16) -> u16 {
+        let mut bytes = [0; 2];
+        take_input(&mut bytes);
+        u16::from_le_bytes(bytes)
+    }
+
+    unsafe fn read_u32(_src: *const u32) -> u32 {
+        let mut bytes = [0; 4];
+        take_input(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    unsafe fn read_u64(_src: *const u64) -> u64 {
+        let mut bytes = [0; 8];
+        take_input(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    unsafe fn write_u8(_dst: *mut u8, _value: u8) {}
+
+    unsafe fn write_u16(_dst: *mut u16, _value: u16) {}
+
+    unsafe fn write_u32(_dst: *mut u32, _value: u32) {}
+
+    unsafe fn write_u64(_dst: *mut u64, _value: u64) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ptr::{NonNull, null};
+
+    #[test]
+    fn reads_from_input() {
+        with_input(&[0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc], || {
+            // SAFETY: FuzzBackend doesn't access memory.
+            unsafe {
+                assert_eq!(FuzzBackend::read_u8(null()), 0x12);
+                FuzzBackend::write_u32(NonNull::dangling().as_ptr(), 42);
+                assert_eq!(FuzzBackend::read_u16(null()), 0x5634);
+                assert_eq!(FuzzBackend::read_u32(null()), 0xbc9a78);
+                assert_eq!(FuzzBackend::read_u64(null()), 0);
+            }
+        });
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! RAII guard to enable a device for a scope and disable it again afterwards.
+
+use crate::UniqueMmioPointer;
+
+/// A pointer to device registers, along with a closure to disable the device which is called when
+/// the guard is dropped.
+///
+/// This is useful for power or clock gating around some accesses, to make sure that the device is
+/// disabled again on every return path.
+///
+/// # Example
+///
+/// ```
+/// use safe_mmio::{UniqueMmioPointer, field, fields::ReadWrite, guard::Guarded};
+///
+/// #[repr(C)]
+/// struct Registers {
+///     clock_enable: ReadWrite<u32>,
+///     data: ReadWrite<u32>,
+/// }
+///
+/// let mut regs: UniqueMmioPointer<Registers>;
+/// # let mut fake = Registers { clock_enable: ReadWrite(0), data: ReadWrite(0) };
+/// # regs = UniqueMmioPointer::from(&mut fake);
+/// {
+///     let mut guard = Guarded::new(
+///         regs.reborrow(),
+///         |regs| field!(*regs, clock_enable).write(1),
+///         |regs| field!(*regs, clock_enable).write(0),
+///     );
+///     let mut regs = guard.regs();
+///     field!(regs, data).write(42);
+/// }
+/// assert_eq!(field!(regs, clock_enable).read(), 0);
+/// ```
+pub struct Guarded<'a, T: ?Sized, D: FnOnce(&mut UniqueMmioPointer<T>)> {
+    regs: UniqueMmioPointer<'a, T>,
+    disable: Option<D>,
+}
+
+impl<'a, T: ?Sized, D: FnOnce(&mut UniqueMmioPointer<T>)> Guarded<'a, T, D> {
+    /// Calls `enable` with the given pointer to the device registers, and returns a guard which will
+    /// call `disable` with it when it is dropped.
+    pub fn new(
+        mut regs: UniqueMmioPointer<'a, T>,
+        enable: impl FnOnce(&mut UniqueMmioPointer<T>),
+        disable: D,
+    ) -> Self {
+        enable(&mut regs);
+        Self {
+            regs,
+            disable: Some(disable),
+        }
+    }
+
+    /// Returns a pointer to the device registers.
+    pub const fn regs(&mut self) -> UniqueMmioPointer<'_, T> {
+        self.regs.reborrow()
+    }
+}
+
+impl<T: ?Sized, D: FnOnce(&mut UniqueMmioPointer<T>)> Drop for Guarded<'_, T, D> {
+    fn drop(&mut self) {
+        if let Some(disable) = self.disable.take() {
+            disable(&mut self.regs);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fields::ReadWrite;
+
+    fn write_while_enabled(regs: UniqueMmioPointer<[ReadWrite<u32>; 2]>, fail: bool) -> bool {
+        let mut guard = Guarded::new(
+            regs,
+            |regs| regs.get(0).unwrap().write(1),
+            |regs| regs.get(0).unwrap().write(0),
+        );
+        let mut regs = guard.regs();
+        assert_eq!(regs.get(0).unwrap().read(), 1);
+        if fail {
+            return false;
+        }
+        regs.get(1).unwrap().write(42);
+        true
+    }
+
+    #[test]
+    fn disable_on_drop() {
+        let mut fake = [ReadWrite(0), ReadWrite(0)];
+        let mut regs = UniqueMmioPointer::from(&mut fake);
+
+        assert!(!write_while_enabled(regs.reborrow(), true));
+        assert_eq!(regs.get(0).unwrap().read(), 0);
+        assert_eq!(regs.get(1).unwrap().read(), 0);
+
+        assert!(write_while_enabled(regs.reborrow(), false));
+        assert_eq!(regs.get(0).unwrap().read(), 0);
+        assert_eq!(regs.get(1).unwrap().read(), 42);
+    }
+}
@@ -0,0 +1,354 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! A versioned binary format for handing a device map over from one boot stage to the next, and a
+//! raw handle for passing a single device across an ABI boundary.
+//!
+//! A bootloader which has already worked out where its devices are, e.g. from a devicetree, can
+//! give up its [`PhysicalInstance`]s and write them to a [`DeviceMapHandoff`] in memory. The next
+//! stage can then check the handoff and take the instances back out of it, rather than deriving
+//! them again. The format is plain little-endian `repr(C)` data, so it can be read and written
+//! with [`zerocopy`] and doesn't depend on both stages being built with the same compiler.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_mmio::{PhysicalInstance, fields::ReadWrite, handoff::DeviceMapHandoff};
+//! use zerocopy::{FromBytes, IntoBytes};
+//!
+//! #[repr(C)]
+//! struct UartRegisters {
+//!     data: ReadWrite<u32>,
+//!     status: ReadWrite<u32>,
+//! }
+//!
+//! // In the bootloader:
+//! let uart: PhysicalInstance<UartRegisters>;
+//! # uart = unsafe { PhysicalInstance::new(0x900_0000) };
+//! let mut handoff = DeviceMapHandoff::<4>::new();
+//! handoff.push(uart).unwrap();
+//! let bytes = handoff.as_bytes();
+//!
+//! // In the next stage:
+//! let handoff = DeviceMapHandoff::<4>::read_from_bytes(bytes).unwrap();
+//! handoff.check().unwrap();
+//! // SAFETY: The bootloader put the UART at index 0, and we only take it once.
+//! let uart = unsafe { handoff.take::<UartRegisters>(0) }.unwrap();
+//! assert_eq!(uart.pa(), 0x900_0000);
+//! ```
+//!
+//! Once a device has been mapped, a [`UniqueMmioPointer`] to it can instead be converted to a
+//! [`RawMmioHandle`] with [`UniqueMmioPointer::into_handle`], passed to C code or to a payload at
+//! another exception level which shares the mapping, and converted back with
+//! [`UniqueMmioPointer::from_handle`].
+
+use crate::{PhysicalInstance, UniqueMmioPointer};
+use core::{
+    fmt::{self, Display, Formatter},
+    ptr::{self, NonNull},
+};
+use zerocopy::{
+    FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned,
+    little_endian::{U32, U64},
+};
+
+/// An error writing or reading a [`DeviceMapHandoff`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HandoffError {
+    /// The handoff doesn't start with [`DeviceMapHandoff::MAGIC`].
+    BadMagic,
+    /// The handoff has a format version which isn't supported.
+    UnsupportedVersion(u32),
+    /// The handoff has no space for any more entries, or claims to have more entries than fit.
+    Full,
+    /// There is no entry with the given index.
+    IndexOutOfRange,
+    /// The region of the entry is smaller than the type being requested.
+    TooSmall {
+        /// The size in bytes of the type being requested.
+        expected: usize,
+        /// The size in bytes of the region in the handoff.
+        actual: u64,
+    },
+    /// The address of a [`RawMmioHandle`] is null or isn't properly aligned for the type being
+    /// requested.
+    InvalidAddress(usize),
+}
+
+impl Display for HandoffError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::BadMagic => write!(f, "Device map handoff has the wrong magic number"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "Unsupported device map handoff version {version}")
+            }
+            Self::Full => write!(f, "Device map handoff is full"),
+            Self::IndexOutOfRange => write!(f, "Device map handoff has no entry with that index"),
+            Self::TooSmall { expected, actual } => write!(
+                f,
+                "Handoff region size {actual:#x} is smaller than requested size {expected:#x}"
+            ),
+            Self::InvalidAddress(address) => {
+                write!(f, "Invalid or misaligned handle address {address:#x}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for HandoffError {}
+
+/// The physical address and size of a single device region in a [`DeviceMapHandoff`].
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    FromBytes,
+    Immutable,
+    IntoBytes,
+    KnownLayout,
+    PartialEq,
+    Unaligned,
+)]
+#[repr(C)]
+pub struct HandoffEntry {
+    /// The physical base address of the region.
+    pub pa: U64,
+    /// The size of the region in bytes.
+    pub size: U64,
+}
+
+/// A device map of up to `N` regions, to be passed from one boot stage to the next.
+#[derive(Clone, Debug, Eq, FromBytes, Immutable, IntoBytes, KnownLayout, PartialEq, Unaligned)]
+#[repr(C)]
+pub struct DeviceMapHandoff<const N: usize> {
+    magic: U32,
+    version: U32,
+    count: U32,
+    reserved: U32,
+    entries: [HandoffEntry; N],
+}
+
+impl<const N: usize> DeviceMapHandoff<N> {
+    /// The magic number at the start of every handoff, `"MMIO"` in ASCII.
+    pub const MAGIC: u32 = u32::from_le_bytes(*b"MMIO");
+
+    /// The version of the format written by this version of the crate.
+    pub const VERSION: u32 = 1;
+
+    /// Creates a new empty handoff.
+    pub const fn new() -> Self {
+        Self {
+            magic: U32::new(Self::MAGIC),
+            version: U32::new(Self::VERSION),
+            count: U32::ZERO,
+            reserved: U32::ZERO,
+            entries: [HandoffEntry {
+                pa: U64::ZERO,
+                size: U64::ZERO,
+            }; N],
+        }
+    }
+
+    /// Checks that the handoff has the right magic number and a supported version, and that its
+    /// entry count is valid.
+    pub fn check(&self) -> Result<(), HandoffError> {
+        if self.magic.get() != Self::MAGIC {
+            return Err(HandoffError::BadMagic);
+        }
+        if self.version.get() != Self::VERSION {
+            return Err(HandoffError::UnsupportedVersion(self.version.get()));
+        }
+        if self.count.get() as usize > N {
+            return Err(HandoffError::Full);
+        }
+        Ok(())
+    }
+
+    /// Returns the entries in the handoff.
+    ///
+    /// If the entry count is invalid then this returns all `N` entries, so call
+    /// [`check`](Self::check) first.
+    pub fn entries(&self) -> &[HandoffEntry] {
+        &self.entries[..N.min(self.count.get() as usize)]
+    }
+
+    /// Adds the given physical instance to the end of the handoff, giving up ownership of it.
+    ///
+    /// Returns the index of the new entry.
+    pub fn push<T>(&mut self, instance: PhysicalInstance<T>) -> Result<usize, HandoffError> {
+        let index = self.count.get() as usize;
+        let entry = self.entries.get_mut(index).ok_or(HandoffError::Full)?;
+        *entry = HandoffEntry {
+            pa: U64::new(instance.pa_u64()),
+            size: U64::new(size_of::<T>() as u64),
+        };
+        self.count = U32::new(self.count.get() + 1);
+        Ok(index)
+    }
+
+    /// Takes the physical instance for the entry with the given index.
+    ///
+    /// # Safety
+    ///
+    /// The handoff must have been written by a trusted earlier boot stage which gave up ownership
+    /// of the instance, the registers at the entry's address must have type `T`, and this must not
+    /// be called more than once for the same entry.
+    pub unsafe fn take<T>(&self, index: usize) -> Result<PhysicalInstance<T>, HandoffError> {
+        let entry = self
+            .entries()
+            .get(index)
+            .ok_or(HandoffError::IndexOutOfRange)?;
+        if entry.size.get() < size_of::<T>() as u64 {
+            return Err(HandoffError::TooSmall {
+                expected: size_of::<T>(),
+                actual: entry.size.get(),
+            });
+        }
+        // SAFETY: The caller promised that the entry describes registers of type `T`, and that no
+        // other `PhysicalInstance` exists for them.
+        Ok(unsafe { PhysicalInstance::new_u64(entry.pa.get()) })
+    }
+}
+
+impl<const N: usize> Default for DeviceMapHandoff<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The virtual address and size of a device region which is being handed over, with a stable
+/// layout so that it can be passed across an ABI boundary.
+#[derive(Clone, Copy, Debug, Eq, FromBytes, Immutable, IntoBytes, KnownLayout, PartialEq)]
+#[repr(C)]
+pub struct RawMmioHandle {
+    /// The virtual address of the region.
+    pub address: usize,
+    /// The size of the region in bytes.
+    pub size: usize,
+}
+
+impl<T> UniqueMmioPointer<'_, T> {
+    /// Gives up this pointer, and returns a handle for it which can be passed across an ABI
+    /// boundary.
+    ///
+    /// The provenance of the pointer is exposed, so that [`from_handle`](Self::from_handle) can
+    /// use it again.
+    pub fn into_handle(mut self) -> RawMmioHandle {
+        RawMmioHandle {
+            address: self.ptr_mut().expose_provenance(),
+            size: size_of::<T>(),
+        }
+    }
+
+    /// Creates a new `UniqueMmioPointer` from a handle passed across an ABI boundary.
+    ///
+    /// Returns an error if the handle's address is null or misaligned for `T`, or its size is
+    /// smaller than `T`.
+    ///
+    /// # Safety
+    ///
+    /// The handle must have been created by [`into_handle`](Self::into_handle) or equivalent code
+    /// in a trusted component which gave up ownership of the region, for registers of type `T` in
+    /// the same address space. The address must satisfy the requirements of
+    /// [`UniqueMmioPointer::new`] for the lifetime of the returned pointer, and this must not be
+    /// called more than once for the same handle. If the mapping was created by Rust code then its
+    /// provenance must have been exposed, as `into_handle` does.
+    pub unsafe fn from_handle(handle: RawMmioHandle) -> Result<Self, HandoffError> {
+        if handle.size < size_of::<T>() {
+            return Err(HandoffError::TooSmall {
+                expected: size_of::<T>(),
+                actual: handle.size as u64,
+            });
+        }
+        let Some(regs) = NonNull::new(ptr::with_exposed_provenance_mut::<T>(handle.address))
+            .filter(|regs| regs.is_aligned())
+        else {
+            return Err(HandoffError::InvalidAddress(handle.address));
+        };
+        // SAFETY: The caller promised that the handle's address satisfies the requirements of
+        // `new`, and that nothing else owns it.
+        Ok(unsafe { Self::new(regs) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut handoff = DeviceMapHandoff::<2>::new();
+        // SAFETY: These instances are never used to access any device.
+        let (a, b, c) = unsafe {
+            (
+                PhysicalInstance::<[u32; 4]>::new(0x1000),
+                PhysicalInstance::<u32>::new_u64(0x1_0000_0000),
+                PhysicalInstance::<u32>::new(0x3000),
+            )
+        };
+        assert_eq!(handoff.push(a), Ok(0));
+        assert_eq!(handoff.push(b), Ok(1));
+        assert_eq!(handoff.push(c), Err(HandoffError::Full));
+
+        let handoff = DeviceMapHandoff::<2>::read_from_bytes(handoff.as_bytes()).unwrap();
+        assert_eq!(handoff.check(), Ok(()));
+        assert_eq!(handoff.entries().len(), 2);
+        // SAFETY: Each entry is only taken once, and never used to access any device.
+        unsafe {
+            assert_eq!(
+                handoff.take::<[u32; 8]>(0),
+                Err(HandoffError::TooSmall {
+                    expected: 32,
+                    actual: 16
+                })
+            );
+            assert_eq!(handoff.take::<[u32; 4]>(0).unwrap().pa_u64(), 0x1000);
+            assert_eq!(handoff.take::<u32>(1).unwrap().pa_u64(), 0x1_0000_0000);
+            assert_eq!(handoff.take::<u32>(2), Err(HandoffError::IndexOutOfRange));
+        }
+    }
+
+    #[test]
+    fn check() {
+        let mut handoff = DeviceMapHandoff::<1>::new();
+        handoff.version = U32::new(2);
+        assert_eq!(handoff.check(), Err(HandoffError::UnsupportedVersion(2)));
+        handoff.magic = U32::ZERO;
+        assert_eq!(handoff.check(), Err(HandoffError::BadMagic));
+
+        let mut handoff = DeviceMapHandoff::<1>::new();
+        handoff.count = U32::new(2);
+        assert_eq!(handoff.check(), Err(HandoffError::Full));
+        assert_eq!(handoff.entries().len(), 1);
+    }
+
+    #[test]
+    fn raw_handle() {
+        let mut fake = [1u32, 2];
+        let handle = UniqueMmioPointer::from(&mut fake).into_handle();
+        assert_eq!(handle.size, 8);
+        // SAFETY: The handle was just created from a pointer to `fake`, and is only used once.
+        unsafe {
+            assert_eq!(
+                UniqueMmioPointer::<[u32; 4]>::from_handle(handle).unwrap_err(),
+                HandoffError::TooSmall {
+                    expected: 16,
+                    actual: 8
+                }
+            );
+            assert_eq!(
+                UniqueMmioPointer::<u32>::from_handle(RawMmioHandle {
+                    address: handle.address + 1,
+                    size: 4
+                })
+                .unwrap_err(),
+                HandoffError::InvalidAddress(handle.address + 1)
+            );
+            let mut regs = UniqueMmioPointer::<[u32; 2]>::from_handle(handle).unwrap();
+            assert_eq!(regs.get(1).unwrap().read_unsafe(), 2);
+        }
+    }
+}
@@ -0,0 +1,191 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Register blocks made up of a fixed header followed by a variable number of registers.
+//!
+//! Some devices end with an array of registers whose length is only known at runtime, such as one
+//! set of registers per queue where the number of queues is read from a capability register.
+//! [`HeaderSlice<H, [E]>`](HeaderSlice) describes such a device as a single unsized struct, so it
+//! doesn't need separate mappings for the header and the array. Use [`field!`](crate::field) to
+//! project to the header or to the tail, or [`split_header`](UniqueMmioPointer::split_header) to get
+//! both.
+//!
+//! # Example
+//!
+//! ```
+//! use core::ptr::NonNull;
+//! use safe_mmio::{
+//!     UniqueMmioPointer, field, field_shared,
+//!     fields::{ReadPure, ReadWrite},
+//!     header_slice::HeaderSlice,
+//! };
+//!
+//! #[repr(C)]
+//! struct ControllerHeader {
+//!     queue_count: ReadPure<u32>,
+//!     control: ReadWrite<u32>,
+//! }
+//!
+//! #[repr(C)]
+//! struct QueueRegisters {
+//!     base: ReadWrite<u32>,
+//!     doorbell: ReadWrite<u32>,
+//! }
+//!
+//! type Controller = HeaderSlice<ControllerHeader, [QueueRegisters]>;
+//!
+//! # let mut fake = HeaderSlice {
+//! #     header: ControllerHeader { queue_count: ReadPure(2), control: ReadWrite(0) },
+//! #     tail: [
+//! #         QueueRegisters { base: ReadWrite(0), doorbell: ReadWrite(0) },
+//! #         QueueRegisters { base: ReadWrite(0), doorbell: ReadWrite(0) },
+//! #     ],
+//! # };
+//! let base: NonNull<ControllerHeader>;
+//! # base = NonNull::from(&mut fake).cast();
+//! // SAFETY: `base` is the address of the controller's registers, which are mapped as device
+//! // memory, and nothing else accesses them.
+//! let queue_count = unsafe { UniqueMmioPointer::new(base) };
+//! let queue_count = field_shared!(queue_count, queue_count).read() as usize;
+//! // SAFETY: As above, and the controller has `queue_count` sets of queue registers.
+//! let mut controller: UniqueMmioPointer<Controller> =
+//!     unsafe { UniqueMmioPointer::new_with_len(base, queue_count) };
+//!
+//! assert_eq!(controller.len(), 2);
+//! let (mut header, mut queues) = controller.split_header();
+//! field!(header, control).write(1);
+//! let mut queue = queues.get(1).unwrap();
+//! field!(queue, doorbell).write(42);
+//! ```
+
+use crate::{SharedMmioPointer, UniqueMmioPointer, split_fields};
+use core::ptr::NonNull;
+
+/// A register block made up of a fixed header `H` followed by some number of registers in a slice
+/// `T`.
+///
+/// `T` is normally `[E]` for some element type `E`. A `HeaderSlice<H, [E; N]>` can be coerced to a
+/// `HeaderSlice<H, [E]>`, which is useful for fakes in tests.
+#[derive(Debug)]
+#[repr(C)]
+pub struct HeaderSlice<H, T: ?Sized> {
+    /// The fixed registers at the start of the block.
+    pub header: H,
+    /// The variable number of registers following the header.
+    pub tail: T,
+}
+
+impl<H, E> HeaderSlice<H, [E]> {
+    /// Returns a pointer to a `HeaderSlice` starting at `header`, with `len` elements in its tail.
+    pub const fn ptr_from_raw_parts(header: NonNull<H>, len: usize) -> NonNull<Self> {
+        let slice = NonNull::slice_from_raw_parts(header.cast::<E>(), len);
+        // SAFETY: The slice pointer is non-null, and casting it to a pointer to a struct whose last
+        // field is a slice of the same element type keeps the address and uses the slice length as
+        // the length of the last field.
+        unsafe { NonNull::new_unchecked(slice.as_ptr() as *mut Self) }
+    }
+}
+
+impl<H, E> UniqueMmioPointer<'_, HeaderSlice<H, [E]>> {
+    /// Creates a new `UniqueMmioPointer` to a register block starting at `header`, with `len`
+    /// elements after the header.
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as for [`UniqueMmioPointer::new`] apply to the whole block, including
+    /// all `len` elements of the tail.
+    pub const unsafe fn new_with_len(header: NonNull<H>, len: usize) -> Self {
+        // SAFETY: Our caller promised that the whole block is valid MMIO address space.
+        unsafe { Self::new(HeaderSlice::ptr_from_raw_parts(header, len)) }
+    }
+}
+
+impl<'a, H, E> UniqueMmioPointer<'a, HeaderSlice<H, [E]>> {
+    /// Splits this pointer into pointers to the header and to the tail.
+    pub fn split_header(self) -> (UniqueMmioPointer<'a, H>, UniqueMmioPointer<'a, [E]>) {
+        // SAFETY: The two fields are distinct.
+        unsafe { split_fields!(self, header, tail) }
+    }
+}
+
+impl<H, E> SharedMmioPointer<'_, HeaderSlice<H, [E]>> {
+    /// Returns the number of elements in the tail after the header.
+    pub const fn len(&self) -> usize {
+        (self.regs.as_ptr() as *mut [E]).len()
+    }
+
+    /// Returns whether the tail after the header is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the size in bytes of the whole register block, including the header, the tail and
+    /// any padding.
+    pub const fn byte_len(&self) -> usize {
+        let align = if align_of::<H>() > align_of::<E>() {
+            align_of::<H>()
+        } else {
+            align_of::<E>()
+        };
+        // The block is within a single allocation, so none of this can overflow.
+        let tail_offset = size_of::<H>().next_multiple_of(align_of::<E>());
+        (tail_offset + self.len() * size_of::<E>()).next_multiple_of(align)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        field, field_shared,
+        fields::{ReadPure, ReadPureWrite},
+    };
+
+    #[test]
+    fn header_and_tail() {
+        let mut fake = HeaderSlice {
+            header: ReadPure(3u16),
+            tail: [ReadPureWrite(0u64), ReadPureWrite(0), ReadPureWrite(0)],
+        };
+        {
+            let fake: &mut HeaderSlice<ReadPure<u16>, [ReadPureWrite<u64>]> = &mut fake;
+            let mut regs = UniqueMmioPointer::from(fake);
+            assert_eq!(regs.len(), 3);
+            assert!(!regs.is_empty());
+            assert_eq!(
+                regs.byte_len(),
+                size_of::<HeaderSlice<ReadPure<u16>, [ReadPureWrite<u64>; 3]>>()
+            );
+            assert_eq!(field_shared!(regs, header).read(), 3);
+            field!(regs, tail).get(2).unwrap().write(42);
+
+            let (header, mut tail) = regs.split_header();
+            assert_eq!(header.read(), 3);
+            assert_eq!(tail.len(), 3);
+            tail.get(0).unwrap().write(1);
+        }
+        assert_eq!(fake.tail[0].0, 1);
+        assert_eq!(fake.tail[2].0, 42);
+    }
+
+    #[test]
+    fn from_raw_parts() {
+        let mut fake = HeaderSlice {
+            header: ReadPure(2u32),
+            tail: [ReadPureWrite(5u32), ReadPureWrite(6)],
+        };
+        let header = NonNull::from(&mut fake).cast::<ReadPure<u32>>();
+        // SAFETY: `header` points to a `HeaderSlice` with two elements, which nothing else accesses
+        // while `regs` exists.
+        let regs: UniqueMmioPointer<HeaderSlice<ReadPure<u32>, [ReadPureWrite<u32>]>> =
+            unsafe { UniqueMmioPointer::new_with_len(header, 2) };
+        let (_, mut tail) = regs.split_header();
+        assert_eq!(tail.get(1).unwrap().read(), 6);
+
+        // SAFETY: As above, but only the first element is included.
+        let regs: UniqueMmioPointer<HeaderSlice<ReadPure<u32>, [ReadPureWrite<u32>]>> =
+            unsafe { UniqueMmioPointer::new_with_len(header, 1) };
+        assert_eq!(regs.len(), 1);
+    }
+}
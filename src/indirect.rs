@@ -0,0 +1,106 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Helper for devices which expose a register file through a pair of index and data registers.
+
+use crate::{UniqueMmioPointer, access::AtomicAccess};
+
+/// A register file accessed indirectly, by writing the index of a register to an index register
+/// and then reading or writing its value through a data register.
+///
+/// This owns both the index and data registers, so the index can't be changed by anything else
+/// between writing it and accessing the data register.
+///
+/// # Example
+///
+/// ```
+/// use safe_mmio::{UniqueMmioPointer, indirect::IndirectRegisterFile};
+///
+/// let index: UniqueMmioPointer<u8>;
+/// let data: UniqueMmioPointer<u8>;
+/// # let mut fake_index = 0;
+/// # let mut fake_data = 0;
+/// # index = UniqueMmioPointer::from(&mut fake_index);
+/// # data = UniqueMmioPointer::from(&mut fake_data);
+/// // SAFETY: The RTC allows any of its registers to be read or written through the data register.
+/// let mut rtc = unsafe { IndirectRegisterFile::new(index, data) };
+/// rtc.write_indexed(0x0b, 0x02);
+/// let status = rtc.read_indexed(0x0b);
+/// ```
+#[derive(Debug)]
+pub struct IndirectRegisterFile<'a, I, D> {
+    index: UniqueMmioPointer<'a, I>,
+    data: UniqueMmioPointer<'a, D>,
+}
+
+impl<'a, I: AtomicAccess, D: AtomicAccess> IndirectRegisterFile<'a, I, D> {
+    /// Creates a new `IndirectRegisterFile` from pointers to the index and data registers.
+    ///
+    /// # Safety
+    ///
+    /// It must be safe to write any index passed to [`read_indexed`](Self::read_indexed) or
+    /// [`write_indexed`](Self::write_indexed) to the index register, and then to read or write (as
+    /// appropriate) any value of type `D` from or to the data register.
+    pub const unsafe fn new(
+        index: UniqueMmioPointer<'a, I>,
+        data: UniqueMmioPointer<'a, D>,
+    ) -> Self {
+        Self { index, data }
+    }
+
+    /// Writes `index` to the index register, and then reads the data register.
+    pub fn read_indexed(&mut self, index: I) -> D {
+        // SAFETY: The caller of `new` promised that writing any index and then reading the data
+        // register is safe.
+        unsafe {
+            self.index.write_unsafe(index);
+            self.data.read_unsafe()
+        }
+    }
+
+    /// Writes `index` to the index register, and then writes `value` to the data register.
+    pub fn write_indexed(&mut self, index: I, value: D) {
+        // SAFETY: The caller of `new` promised that writing any index and then writing any value to
+        // the data register is safe.
+        unsafe {
+            self.index.write_unsafe(index);
+            self.data.write_unsafe(value);
+        }
+    }
+
+    /// Returns the original pointers to the index and data registers.
+    pub fn into_inner(self) -> (UniqueMmioPointer<'a, I>, UniqueMmioPointer<'a, D>) {
+        (self.index, self.data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_write_indexed() {
+        let mut index = 0u8;
+        let mut data = 0u16;
+
+        {
+            // SAFETY: These are just fakes, so accessing them has no side-effects.
+            let mut file = unsafe {
+                IndirectRegisterFile::new(
+                    UniqueMmioPointer::from(&mut index),
+                    UniqueMmioPointer::from(&mut data),
+                )
+            };
+            file.write_indexed(3, 0x1234);
+            assert_eq!(file.read_indexed(5), 0x1234);
+
+            let (mut index, _) = file.into_inner();
+            // SAFETY: This is just a fake.
+            assert_eq!(unsafe { index.read_unsafe() }, 5);
+        }
+
+        assert_eq!(index, 5);
+        assert_eq!(data, 0x1234);
+    }
+}
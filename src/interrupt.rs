@@ -0,0 +1,357 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Helpers for dispatching on the bits of interrupt status registers.
+
+use crate::{
+    SharedMmioPointer, UniqueMmioPointer,
+    access::{AtomicAccess, SideEffectReadable, Writable},
+    fields::{ReadOnly, ReadPure, ReadPureWrite, ReadWrite, WriteOnly},
+};
+use core::ops::{BitAnd, BitOr, Not};
+
+/// An integer type which can be used for a status register, where each bit represents a separate
+/// condition.
+pub trait StatusBits: AtomicAccess {
+    /// Calls `f` with the index of each bit which is set, starting from the least significant.
+    fn for_each_set_bit(self, f: impl FnMut(u32));
+}
+
+macro_rules! impl_status_bits {
+    ($($t:ty),*) => {
+        $(
+            impl StatusBits for $t {
+                fn for_each_set_bit(mut self, mut f: impl FnMut(u32)) {
+                    while self != 0 {
+                        f(self.trailing_zeros());
+                        self &= self - 1;
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_status_bits!(u8, u16, u32, usize);
+#[cfg(target_pointer_width = "64")]
+impl_status_bits!(u64);
+
+impl<T: StatusBits> UniqueMmioPointer<'_, ReadOnly<T>> {
+    /// Performs a single MMIO read of the register, and then calls `f` with the index of each bit
+    /// which is set, starting from the least significant.
+    pub fn for_each_set_bit(&mut self, f: impl FnMut(u32)) {
+        self.read().for_each_set_bit(f);
+    }
+}
+
+impl<T: StatusBits> SharedMmioPointer<'_, ReadPure<T>> {
+    /// Performs a single MMIO read of the register, and then calls `f` with the index of each bit
+    /// which is set, starting from the least significant.
+    pub fn for_each_set_bit(&self, f: impl FnMut(u32)) {
+        self.read().for_each_set_bit(f);
+    }
+}
+
+impl<T: StatusBits> UniqueMmioPointer<'_, ReadWrite<T>> {
+    /// Performs a single MMIO read of the register, writes the same value back to acknowledge all
+    /// the set bits of a write-1-to-clear register, and then calls `f` with the index of each bit
+    /// which was set, starting from the least significant.
+    pub fn for_each_set_bit_w1c(&mut self, f: impl FnMut(u32)) {
+        let value = self.read();
+        self.write(value);
+        value.for_each_set_bit(f);
+    }
+}
+
+impl<T: StatusBits> UniqueMmioPointer<'_, ReadPureWrite<T>> {
+    /// Performs a single MMIO read of the register, writes the same value back to acknowledge all
+    /// the set bits of a write-1-to-clear register, and then calls `f` with the index of each bit
+    /// which was set, starting from the least significant.
+    pub fn for_each_set_bit_w1c(&mut self, f: impl FnMut(u32)) {
+        let value = self.read();
+        self.write(value);
+        value.for_each_set_bit(f);
+    }
+}
+
+/// An interrupt status register paired with the write-1-to-clear register which acknowledges it.
+///
+/// [`take_pending`](Self::take_pending) reads the status and then acknowledges exactly the bits
+/// which were read, so that an interrupt which is raised in between isn't lost. Acknowledging
+/// before reading, or writing all ones, would silently drop it.
+///
+/// # Example
+///
+/// ```
+/// use safe_mmio::{
+///     UniqueMmioPointer,
+///     fields::{ReadOnly, WriteOnly},
+///     interrupt::IrqStatus,
+/// };
+///
+/// let status: UniqueMmioPointer<ReadOnly<u32>>;
+/// let clear: UniqueMmioPointer<WriteOnly<u32>>;
+/// # let mut fake_status = ReadOnly(0b101);
+/// # let mut fake_clear = WriteOnly(0);
+/// # status = UniqueMmioPointer::from(&mut fake_status);
+/// # clear = UniqueMmioPointer::from(&mut fake_clear);
+/// let mut irq = IrqStatus::new(status, clear);
+/// irq.for_each_pending(|bit| {
+///     // Handle the interrupt for `bit`.
+/// #   assert!(bit == 0 || bit == 2);
+/// });
+/// ```
+pub struct IrqStatus<'a, S, T> {
+    status: UniqueMmioPointer<'a, S>,
+    ack: UniqueMmioPointer<'a, WriteOnly<T>>,
+}
+
+impl<'a, S, T> IrqStatus<'a, S, T> {
+    /// Pairs the given status register with the given register to acknowledge it, which clears
+    /// each bit of the status register which is written as 1.
+    pub const fn new(
+        status: UniqueMmioPointer<'a, S>,
+        ack: UniqueMmioPointer<'a, WriteOnly<T>>,
+    ) -> Self {
+        Self { status, ack }
+    }
+
+    /// Returns the original pointers to the status and acknowledge registers.
+    pub fn into_parts(
+        self,
+    ) -> (
+        UniqueMmioPointer<'a, S>,
+        UniqueMmioPointer<'a, WriteOnly<T>>,
+    ) {
+        (self.status, self.ack)
+    }
+}
+
+impl<S, T: StatusBits> IrqStatus<'_, S, T>
+where
+    for<'b> UniqueMmioPointer<'b, S>: SideEffectReadable<Value = T>,
+{
+    /// Reads the status register, acknowledges the bits which were set, and returns them.
+    ///
+    /// The interrupts should be handled after this returns, so that if one is raised again while
+    /// it is being handled it stays pending rather than being acknowledged without being handled.
+    pub fn take_pending(&mut self) -> T {
+        let pending = self.status.reborrow().read();
+        self.ack.write(pending);
+        pending
+    }
+
+    /// Takes the pending interrupts as for [`take_pending`](Self::take_pending), and then calls `f`
+    /// with the index of each bit which was set, starting from the least significant.
+    pub fn for_each_pending(&mut self, f: impl FnMut(u32)) {
+        self.take_pending().for_each_set_bit(f);
+    }
+}
+
+/// The interrupt enable registers of a device, which are either a pair of write-1-to-set and
+/// write-1-to-clear registers, or a single register which is updated with a read-modify-write.
+///
+/// Either way, [`enable`](Self::enable) and [`disable`](Self::disable) only change the bits given
+/// in the mask, and [`with_disabled`](Self::with_disabled) restores them to their previous state
+/// afterwards.
+///
+/// # Example
+///
+/// ```
+/// use safe_mmio::{
+///     UniqueMmioPointer,
+///     fields::{ReadPureWrite, WriteOnly},
+///     interrupt::IrqMask,
+/// };
+///
+/// let set: UniqueMmioPointer<ReadPureWrite<u32>>;
+/// let clear: UniqueMmioPointer<WriteOnly<u32>>;
+/// # let mut fake_set = ReadPureWrite(0);
+/// # let mut fake_clear = WriteOnly(0);
+/// # set = UniqueMmioPointer::from(&mut fake_set);
+/// # clear = UniqueMmioPointer::from(&mut fake_clear);
+/// let mut mask = IrqMask::set_clear(set, clear);
+/// mask.enable(1 << 3);
+/// mask.with_disabled(1 << 3, || {
+///     // Interrupt 3 is disabled here.
+/// });
+/// ```
+pub struct IrqMask<'a, E, T> {
+    /// The write-1-to-set register, or the single read-modify-write register.
+    enable: UniqueMmioPointer<'a, E>,
+    /// The write-1-to-clear register, if there is one.
+    clear: Option<UniqueMmioPointer<'a, WriteOnly<T>>>,
+}
+
+impl<'a, E, T> IrqMask<'a, E, T> {
+    /// Uses the given write-1-to-set and write-1-to-clear registers to enable and disable
+    /// interrupts. Reading `set` must return the bits which are currently enabled.
+    pub const fn set_clear(
+        set: UniqueMmioPointer<'a, E>,
+        clear: UniqueMmioPointer<'a, WriteOnly<T>>,
+    ) -> Self {
+        Self {
+            enable: set,
+            clear: Some(clear),
+        }
+    }
+
+    /// Uses the given register, in which each set bit enables an interrupt, to enable and disable
+    /// interrupts with a read-modify-write.
+    pub const fn read_modify_write(enable: UniqueMmioPointer<'a, E>) -> Self {
+        Self {
+            enable,
+            clear: None,
+        }
+    }
+}
+
+impl<E, T> IrqMask<'_, E, T>
+where
+    T: AtomicAccess + BitAnd<Output = T> + BitOr<Output = T> + Not<Output = T>,
+    for<'b> UniqueMmioPointer<'b, E>: SideEffectReadable<Value = T> + Writable<Value = T>,
+{
+    /// Returns the bits of the interrupts which are currently enabled.
+    pub fn enabled(&mut self) -> T {
+        self.enable.reborrow().read()
+    }
+
+    /// Enables the interrupts whose bits are set in `mask`, leaving the others unchanged.
+    pub fn enable(&mut self, mask: T) {
+        if self.clear.is_some() {
+            self.enable.reborrow().write(mask);
+        } else {
+            let value = self.enabled();
+            self.enable.reborrow().write(value | mask);
+        }
+    }
+
+    /// Disables the interrupts whose bits are set in `mask`, leaving the others unchanged.
+    pub fn disable(&mut self, mask: T) {
+        if let Some(clear) = &mut self.clear {
+            clear.write(mask);
+        } else {
+            let value = self.enabled();
+            self.enable.reborrow().write(value & !mask);
+        }
+    }
+
+    /// Disables the interrupts whose bits are set in `mask`, calls `f`, and then re-enables those
+    /// of them which were enabled before.
+    pub fn with_disabled<R>(&mut self, mask: T, f: impl FnOnce() -> R) -> R {
+        let previous = self.enabled() & mask;
+        self.disable(mask);
+        let result = f();
+        self.enable(previous);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_bits() {
+        let mut bits = [0; 4];
+        let mut count = 0;
+        0x8000_0101u32.for_each_set_bit(|bit| {
+            bits[count] = bit;
+            count += 1;
+        });
+        assert_eq!(&bits[..count], &[0, 8, 31]);
+
+        0u8.for_each_set_bit(|_| panic!("No bits should be set"));
+    }
+
+    #[test]
+    fn read_only() {
+        let mut status = ReadOnly(0b1010u16);
+        let mut seen = 0;
+        UniqueMmioPointer::from(&mut status).for_each_set_bit(|bit| seen |= 1 << bit);
+        assert_eq!(seen, 0b1010);
+    }
+
+    #[test]
+    fn read_pure() {
+        let status = ReadPure(0b110u64);
+        let mut seen = 0;
+        SharedMmioPointer::from(&status).for_each_set_bit(|bit| seen |= 1 << bit);
+        assert_eq!(seen, 0b110);
+    }
+
+    #[test]
+    fn w1c() {
+        let mut status = ReadWrite(0b1001u32);
+        let mut seen = 0;
+        UniqueMmioPointer::from(&mut status).for_each_set_bit_w1c(|bit| seen |= 1 << bit);
+        assert_eq!(seen, 0b1001);
+        // A fake can't clear bits, so just check that the same value was written back.
+        assert_eq!(status.0, 0b1001);
+    }
+
+    #[test]
+    fn irq_status() {
+        let mut status = ReadOnly(0b1100u32);
+        let mut ack = WriteOnly(0);
+        let mut irq = IrqStatus::new(
+            UniqueMmioPointer::from(&mut status),
+            UniqueMmioPointer::from(&mut ack),
+        );
+        assert_eq!(irq.take_pending(), 0b1100);
+        let mut seen = 0;
+        irq.for_each_pending(|bit| seen |= 1 << bit);
+        assert_eq!(seen, 0b1100);
+        assert_eq!(ack.0, 0b1100);
+    }
+
+    #[test]
+    fn irq_status_read_pure() {
+        let mut status = ReadPure(0b1u8);
+        let mut ack = WriteOnly(0);
+        let mut irq = IrqStatus::new(
+            UniqueMmioPointer::from(&mut status),
+            UniqueMmioPointer::from(&mut ack),
+        );
+        assert_eq!(irq.take_pending(), 1);
+        assert_eq!(ack.0, 1);
+    }
+
+    #[test]
+    fn irq_mask_set_clear() {
+        let mut set = ReadPureWrite(0b0110u32);
+        let mut clear = WriteOnly(0);
+        let mut mask = IrqMask::set_clear(
+            UniqueMmioPointer::from(&mut set),
+            UniqueMmioPointer::from(&mut clear),
+        );
+        assert_eq!(mask.enabled(), 0b0110);
+        // A fake can't clear bits, so just check what was written to each register.
+        let seen = mask.with_disabled(0b0011, || 42);
+        assert_eq!(seen, 42);
+        assert_eq!(clear.0, 0b0011);
+        assert_eq!(set.0, 0b0010);
+    }
+
+    #[test]
+    fn irq_mask_read_modify_write() {
+        let mut enable = ReadWrite(0b0110u16);
+        let mut mask = IrqMask::<_, u16>::read_modify_write(UniqueMmioPointer::from(&mut enable));
+        mask.enable(0b1000);
+        assert_eq!(mask.enabled(), 0b1110);
+        mask.with_disabled(0b0011, || {});
+        assert_eq!(mask.enabled(), 0b1110);
+        mask.disable(0b0100);
+        assert_eq!(enable.0, 0b1010);
+    }
+
+    #[test]
+    fn irq_mask_with_disabled() {
+        let mut enable = ReadWrite(0b0110u16);
+        let mut mask = IrqMask::<_, u16>::read_modify_write(UniqueMmioPointer::from(&mut enable));
+        // Interrupts which were disabled beforehand stay disabled afterwards.
+        mask.with_disabled(0b0011, || {});
+        assert_eq!(enable.0, 0b0110);
+    }
+}
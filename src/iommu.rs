@@ -0,0 +1,117 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Pairing of physical addresses with the I/O virtual addresses at which devices see them.
+//!
+//! When a region is behind an IOMMU such as an Arm SMMU, the address which a device must use to
+//! access it (the IOVA) is different from the physical address which the CPU uses. Programming the
+//! CPU physical address into a DMA register is a common bug, which often goes unnoticed until the
+//! IOMMU is enabled.
+//!
+//! An [`IoMapped`] carries both the [`PhysicalInstance`] for a region and the [`Iova`] at which it
+//! is mapped in the IOMMU, and only the latter is a distinct type which DMA programming code can
+//! require.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_mmio::{
+//!     PhysicalInstance, UniqueMmioPointer, field,
+//!     fields::ReadWrite,
+//!     iommu::{IoMapped, Iova, MapperWithIommu},
+//! };
+//!
+//! #[repr(C)]
+//! struct DmaRegisters {
+//!     source: ReadWrite<u64>,
+//!     length: ReadWrite<u64>,
+//! }
+//!
+//! fn start_dma<T>(mut regs: UniqueMmioPointer<DmaRegisters>, buffer: &IoMapped<T>) {
+//!     field!(regs, source).write(buffer.iova().0);
+//!     field!(regs, length).write(size_of::<T>() as u64);
+//! }
+//!
+//! /// A fake IOMMU which maps everything at a fixed offset.
+//! struct OffsetIommu;
+//!
+//! impl MapperWithIommu for OffsetIommu {
+//!     type Error = ();
+//!
+//!     fn map<T>(&mut self, instance: PhysicalInstance<T>) -> Result<IoMapped<T>, ()> {
+//!         let iova = Iova(instance.pa_u64() + 0x8000_0000);
+//!         // SAFETY: We've mapped the region at `iova`, and it stays mapped until `unmap`.
+//!         Ok(unsafe { IoMapped::new(instance, iova) })
+//!     }
+//!
+//!     fn unmap<T>(&mut self, mapped: IoMapped<T>) -> PhysicalInstance<T> {
+//!         mapped.into_instance()
+//!     }
+//! }
+//!
+//! let buffer: PhysicalInstance<[u8; 4096]>;
+//! # buffer = unsafe { PhysicalInstance::new(0x4000_0000) };
+//! let regs: UniqueMmioPointer<DmaRegisters>;
+//! # let mut fake = DmaRegisters { source: ReadWrite(0), length: ReadWrite(0) };
+//! # regs = UniqueMmioPointer::from(&mut fake);
+//! let mut iommu = OffsetIommu;
+//! let buffer = iommu.map(buffer).unwrap();
+//! start_dma(regs, &buffer);
+//! # assert_eq!(fake.source.0, 0xc000_0000);
+//! ```
+
+use crate::{NonSecure, PhysicalInstance, World};
+
+/// An I/O virtual address, at which a device sees a region through an IOMMU.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Iova(pub u64);
+
+/// A region which is mapped in an IOMMU, along with the I/O virtual address at which it is mapped.
+#[derive(Debug, Eq, PartialEq)]
+pub struct IoMapped<T, W: World = NonSecure> {
+    instance: PhysicalInstance<T, W>,
+    iova: Iova,
+}
+
+impl<T, W: World> IoMapped<T, W> {
+    /// Pairs the given physical instance with the I/O virtual address at which it is mapped.
+    ///
+    /// # Safety
+    ///
+    /// The whole region of `instance` must be mapped in the IOMMU at `iova`, for the devices which
+    /// the IOVA will be given to, for as long as this `IoMapped` exists.
+    pub const unsafe fn new(instance: PhysicalInstance<T, W>, iova: Iova) -> Self {
+        Self { instance, iova }
+    }
+
+    /// Returns the physical instance, for the CPU's view of the region.
+    pub const fn instance(&self) -> &PhysicalInstance<T, W> {
+        &self.instance
+    }
+
+    /// Returns the I/O virtual address at which devices see the region.
+    pub const fn iova(&self) -> Iova {
+        self.iova
+    }
+
+    /// Returns the physical instance, discarding the IOVA.
+    ///
+    /// This should only be used by an IOMMU driver when unmapping the region.
+    pub fn into_instance(self) -> PhysicalInstance<T, W> {
+        self.instance
+    }
+}
+
+/// Something which can map regions in an IOMMU, such as an SMMU driver.
+pub trait MapperWithIommu<W: World = NonSecure> {
+    /// The error returned if a region can't be mapped.
+    type Error;
+
+    /// Maps the given region in the IOMMU, and returns it paired with the IOVA at which it is
+    /// mapped.
+    fn map<T>(&mut self, instance: PhysicalInstance<T, W>) -> Result<IoMapped<T, W>, Self::Error>;
+
+    /// Unmaps the given region from the IOMMU, and returns the physical instance for it.
+    fn unmap<T>(&mut self, mapped: IoMapped<T, W>) -> PhysicalInstance<T, W>;
+}
@@ -0,0 +1,22 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Register layouts for common devices, with the field wrapper for each register chosen according to
+//! the device's documentation.
+//!
+//! Reads which pop a FIFO or clear status bits are [`ReadOnly`](crate::fields::ReadOnly) or
+//! [`ReadWrite`](crate::fields::ReadWrite), reads with no side-effects are
+//! [`ReadPure`](crate::fields::ReadPure) or [`ReadPureWrite`](crate::fields::ReadPureWrite), and
+//! write-1-to-clear registers which can't usefully be read are
+//! [`WriteOnly`](crate::fields::WriteOnly). Reserved space is a plain
+//! integer array so it can't be accessed safely.
+//!
+//! Each layout is defined with [`register_map!`](macro@crate::register_map), so it can also be used
+//! with the [`monitor`](crate::monitor).
+
+pub mod generic_timer;
+pub mod gic;
+pub mod ns16550;
+pub mod pl011;
+pub mod sifive_uart;
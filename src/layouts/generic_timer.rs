@@ -0,0 +1,72 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! A memory-mapped Arm generic timer frame (CNTBaseN), as described in the Arm Architecture
+//! Reference Manual.
+//!
+//! The 64-bit registers can only be read or written safely with a single access on 64-bit targets.
+
+use crate::{
+    fields::{ReadPure, ReadPureWrite},
+    register_map,
+};
+
+register_map! {
+    /// The registers of a generic timer frame, CNTBaseN.
+    #[derive(Debug)]
+    #[repr(C, align(8))]
+    pub struct TimerFrameRegisters {
+        /// Physical count register, CNTPCT.
+        pub cntpct: ReadPure<u64>,
+        /// Virtual count register, CNTVCT.
+        pub cntvct: ReadPure<u64>,
+        /// Counter frequency register, CNTFRQ.
+        pub cntfrq: ReadPure<u32>,
+        /// Counter EL0 access control register, CNTEL0ACR.
+        pub cntel0acr: ReadPureWrite<u32>,
+        /// Virtual offset register, CNTVOFF.
+        pub cntvoff: ReadPure<u64>,
+        /// Physical timer compare value register, CNTP_CVAL.
+        pub cntp_cval: ReadPureWrite<u64>,
+        /// Physical timer value register, CNTP_TVAL.
+        pub cntp_tval: ReadPureWrite<u32>,
+        /// Physical timer control register, CNTP_CTL. See [`timer_control`].
+        pub cntp_ctl: ReadPureWrite<u32>,
+        /// Virtual timer compare value register, CNTV_CVAL.
+        pub cntv_cval: ReadPureWrite<u64>,
+        /// Virtual timer value register, CNTV_TVAL.
+        pub cntv_tval: ReadPureWrite<u32>,
+        /// Virtual timer control register, CNTV_CTL. See [`timer_control`].
+        pub cntv_ctl: ReadPureWrite<u32>,
+        reserved_40: [u32; 996],
+        /// Counter ID registers, CounterID0-11.
+        pub counter_id: [ReadPure<u32>; 12],
+    }
+}
+
+/// Bits of the timer control registers, CNTP_CTL and CNTV_CTL.
+pub mod timer_control {
+    /// Enables the timer.
+    pub const ENABLE: u32 = 1 << 0;
+    /// Masks the timer interrupt.
+    pub const IMASK: u32 = 1 << 1;
+    /// Read-only: the timer condition is met.
+    pub const ISTATUS: u32 = 1 << 2;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::mem::offset_of;
+
+    #[test]
+    fn layout() {
+        assert_eq!(offset_of!(TimerFrameRegisters, cntfrq), 0x10);
+        assert_eq!(offset_of!(TimerFrameRegisters, cntvoff), 0x18);
+        assert_eq!(offset_of!(TimerFrameRegisters, cntp_ctl), 0x2c);
+        assert_eq!(offset_of!(TimerFrameRegisters, cntv_ctl), 0x3c);
+        assert_eq!(offset_of!(TimerFrameRegisters, counter_id), 0xfd0);
+        assert_eq!(size_of::<TimerFrameRegisters>(), 0x1000);
+    }
+}
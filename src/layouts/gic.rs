@@ -0,0 +1,334 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! The Arm Generic Interrupt Controller, as described in the
+//! [GIC architecture specification](https://developer.arm.com/documentation/ihi0069/latest).
+//!
+//! Many GIC registers come in set/clear pairs, such as `isenabler` and `icenabler`: writing 1 to a
+//! bit sets or clears the corresponding state, writing 0 has no effect, and reading returns the
+//! current state without side-effects. These are all [`ReadPureWrite`]. Registers which acknowledge
+//! interrupts when read, such as `GICC_IAR`, are [`ReadOnly`].
+//!
+//! Registers for SGIs and PPIs are banked per CPU in the GICv2 distributor, and in GICv3 are in
+//! each CPU's redistributor instead. Extended SPI and PPI ranges are left as reserved space.
+
+use crate::{
+    fields::{ReadOnly, ReadPure, ReadPureWrite, WriteOnly},
+    register_map,
+};
+
+register_map! {
+    /// The registers of a GICv2 distributor, GICD.
+    #[derive(Debug)]
+    #[repr(C)]
+    pub struct Gicv2DistributorRegisters {
+        /// Distributor control register, GICD_CTLR.
+        pub ctlr: ReadPureWrite<u32>,
+        /// Interrupt controller type register, GICD_TYPER.
+        pub typer: ReadPure<u32>,
+        /// Distributor implementer identification register, GICD_IIDR.
+        pub iidr: ReadPure<u32>,
+        reserved_00c: [u32; 29],
+        /// Interrupt group registers, GICD_IGROUPRn.
+        pub igroupr: [ReadPureWrite<u32>; 32],
+        /// Interrupt set-enable registers, GICD_ISENABLERn.
+        pub isenabler: [ReadPureWrite<u32>; 32],
+        /// Interrupt clear-enable registers, GICD_ICENABLERn.
+        pub icenabler: [ReadPureWrite<u32>; 32],
+        /// Interrupt set-pending registers, GICD_ISPENDRn.
+        pub ispendr: [ReadPureWrite<u32>; 32],
+        /// Interrupt clear-pending registers, GICD_ICPENDRn.
+        pub icpendr: [ReadPureWrite<u32>; 32],
+        /// Interrupt set-active registers, GICD_ISACTIVERn.
+        pub isactiver: [ReadPureWrite<u32>; 32],
+        /// Interrupt clear-active registers, GICD_ICACTIVERn.
+        pub icactiver: [ReadPureWrite<u32>; 32],
+        /// Interrupt priority registers, GICD_IPRIORITYRn.
+        pub ipriorityr: [ReadPureWrite<u32>; 255],
+        reserved_7fc: u32,
+        /// Interrupt processor targets registers, GICD_ITARGETSRn. The first 8 are read-only.
+        pub itargetsr: [ReadPureWrite<u32>; 255],
+        reserved_bfc: u32,
+        /// Interrupt configuration registers, GICD_ICFGRn.
+        pub icfgr: [ReadPureWrite<u32>; 64],
+        reserved_d00: [u32; 64],
+        /// Non-secure access control registers, GICD_NSACRn.
+        pub nsacr: [ReadPureWrite<u32>; 64],
+        /// Software generated interrupt register, GICD_SGIR.
+        pub sgir: WriteOnly<u32>,
+        reserved_f04: [u32; 3],
+        /// SGI clear-pending registers, GICD_CPENDSGIRn.
+        pub cpendsgir: [ReadPureWrite<u32>; 4],
+        /// SGI set-pending registers, GICD_SPENDSGIRn.
+        pub spendsgir: [ReadPureWrite<u32>; 4],
+        reserved_f30: [u32; 40],
+        /// Identification registers.
+        pub id: [ReadPure<u32>; 12],
+    }
+}
+
+register_map! {
+    /// The registers of a GICv2 CPU interface, GICC.
+    #[derive(Debug)]
+    #[repr(C)]
+    pub struct Gicv2CpuInterfaceRegisters {
+        /// CPU interface control register, GICC_CTLR.
+        pub ctlr: ReadPureWrite<u32>,
+        /// Interrupt priority mask register, GICC_PMR.
+        pub pmr: ReadPureWrite<u32>,
+        /// Binary point register, GICC_BPR.
+        pub bpr: ReadPureWrite<u32>,
+        /// Interrupt acknowledge register, GICC_IAR. Reading acknowledges the interrupt.
+        pub iar: ReadOnly<u32>,
+        /// End of interrupt register, GICC_EOIR.
+        pub eoir: WriteOnly<u32>,
+        /// Running priority register, GICC_RPR.
+        pub rpr: ReadPure<u32>,
+        /// Highest priority pending interrupt register, GICC_HPPIR.
+        pub hppir: ReadPure<u32>,
+        /// Aliased binary point register, GICC_ABPR.
+        pub abpr: ReadPureWrite<u32>,
+        /// Aliased interrupt acknowledge register, GICC_AIAR. Reading acknowledges the interrupt.
+        pub aiar: ReadOnly<u32>,
+        /// Aliased end of interrupt register, GICC_AEOIR.
+        pub aeoir: WriteOnly<u32>,
+        /// Aliased highest priority pending interrupt register, GICC_AHPPIR.
+        pub ahppir: ReadPure<u32>,
+        reserved_02c: [u32; 41],
+        /// Active priorities registers, GICC_APRn.
+        pub apr: [ReadPureWrite<u32>; 4],
+        /// Non-secure active priorities registers, GICC_NSAPRn.
+        pub nsapr: [ReadPureWrite<u32>; 4],
+        reserved_0f0: [u32; 3],
+        /// CPU interface identification register, GICC_IIDR.
+        pub iidr: ReadPure<u32>,
+        reserved_100: [u32; 960],
+        /// Deactivate interrupt register, GICC_DIR.
+        pub dir: WriteOnly<u32>,
+    }
+}
+
+register_map! {
+    /// The registers of a GICv3 distributor, GICD.
+    #[derive(Debug)]
+    #[repr(C, align(8))]
+    pub struct Gicv3DistributorRegisters {
+        /// Distributor control register, GICD_CTLR.
+        pub ctlr: ReadPureWrite<u32>,
+        /// Interrupt controller type register, GICD_TYPER.
+        pub typer: ReadPure<u32>,
+        /// Distributor implementer identification register, GICD_IIDR.
+        pub iidr: ReadPure<u32>,
+        /// Interrupt controller type register 2, GICD_TYPER2.
+        pub typer2: ReadPure<u32>,
+        /// Error reporting status register, GICD_STATUSR.
+        pub statusr: ReadPureWrite<u32>,
+        reserved_014: [u32; 11],
+        /// Non-secure SPI set register, GICD_SETSPI_NSR.
+        pub setspi_nsr: WriteOnly<u32>,
+        reserved_044: u32,
+        /// Non-secure SPI clear register, GICD_CLRSPI_NSR.
+        pub clrspi_nsr: WriteOnly<u32>,
+        reserved_04c: u32,
+        /// Secure SPI set register, GICD_SETSPI_SR.
+        pub setspi_sr: WriteOnly<u32>,
+        reserved_054: u32,
+        /// Secure SPI clear register, GICD_CLRSPI_SR.
+        pub clrspi_sr: WriteOnly<u32>,
+        reserved_05c: [u32; 9],
+        /// Interrupt group registers, GICD_IGROUPRn.
+        pub igroupr: [ReadPureWrite<u32>; 32],
+        /// Interrupt set-enable registers, GICD_ISENABLERn.
+        pub isenabler: [ReadPureWrite<u32>; 32],
+        /// Interrupt clear-enable registers, GICD_ICENABLERn.
+        pub icenabler: [ReadPureWrite<u32>; 32],
+        /// Interrupt set-pending registers, GICD_ISPENDRn.
+        pub ispendr: [ReadPureWrite<u32>; 32],
+        /// Interrupt clear-pending registers, GICD_ICPENDRn.
+        pub icpendr: [ReadPureWrite<u32>; 32],
+        /// Interrupt set-active registers, GICD_ISACTIVERn.
+        pub isactiver: [ReadPureWrite<u32>; 32],
+        /// Interrupt clear-active registers, GICD_ICACTIVERn.
+        pub icactiver: [ReadPureWrite<u32>; 32],
+        /// Interrupt priority registers, GICD_IPRIORITYRn.
+        pub ipriorityr: [ReadPureWrite<u32>; 255],
+        reserved_7fc: u32,
+        /// Interrupt processor targets registers, GICD_ITARGETSRn, only used when affinity routing
+        /// is disabled.
+        pub itargetsr: [ReadPureWrite<u32>; 255],
+        reserved_bfc: u32,
+        /// Interrupt configuration registers, GICD_ICFGRn.
+        pub icfgr: [ReadPureWrite<u32>; 64],
+        /// Interrupt group modifier registers, GICD_IGRPMODRn.
+        pub igrpmodr: [ReadPureWrite<u32>; 32],
+        reserved_d80: [u32; 32],
+        /// Non-secure access control registers, GICD_NSACRn.
+        pub nsacr: [ReadPureWrite<u32>; 64],
+        /// Software generated interrupt register, GICD_SGIR, only used when affinity routing is
+        /// disabled.
+        pub sgir: WriteOnly<u32>,
+        reserved_f04: [u32; 3],
+        /// SGI clear-pending registers, GICD_CPENDSGIRn.
+        pub cpendsgir: [ReadPureWrite<u32>; 4],
+        /// SGI set-pending registers, GICD_SPENDSGIRn.
+        pub spendsgir: [ReadPureWrite<u32>; 4],
+        reserved_f30: [u32; 5236],
+        /// Interrupt routing registers for SPIs 32 to 1019, GICD_IROUTERn.
+        pub irouter: [ReadPureWrite<u64>; 988],
+        reserved_7fe0: [u32; 8188],
+        /// Identification registers.
+        pub id: [ReadPure<u32>; 12],
+    }
+}
+
+register_map! {
+    /// The registers of the RD_base frame of a GICv3 redistributor.
+    #[derive(Debug)]
+    #[repr(C, align(8))]
+    pub struct Gicv3RdFrameRegisters {
+        /// Redistributor control register, GICR_CTLR.
+        pub ctlr: ReadPureWrite<u32>,
+        /// Implementer identification register, GICR_IIDR.
+        pub iidr: ReadPure<u32>,
+        /// Redistributor type register, GICR_TYPER.
+        pub typer: ReadPure<u64>,
+        /// Error reporting status register, GICR_STATUSR.
+        pub statusr: ReadPureWrite<u32>,
+        /// Redistributor wake register, GICR_WAKER.
+        pub waker: ReadPureWrite<u32>,
+        /// Report maximum PARTID and PMG register, GICR_MPAMIDR.
+        pub mpamidr: ReadPure<u32>,
+        /// Set PARTID and PMG register, GICR_PARTIDR.
+        pub partidr: ReadPureWrite<u32>,
+        reserved_020: [u32; 8],
+        /// Set LPI pending register, GICR_SETLPIR.
+        pub setlpir: WriteOnly<u64>,
+        /// Clear LPI pending register, GICR_CLRLPIR.
+        pub clrlpir: WriteOnly<u64>,
+        reserved_050: [u32; 8],
+        /// Redistributor properties base address register, GICR_PROPBASER.
+        pub propbaser: ReadPureWrite<u64>,
+        /// Redistributor LPI pending table base address register, GICR_PENDBASER.
+        pub pendbaser: ReadPureWrite<u64>,
+        reserved_080: [u32; 8],
+        /// Redistributor invalidate LPI register, GICR_INVLPIR.
+        pub invlpir: WriteOnly<u64>,
+        reserved_0a8: [u32; 2],
+        /// Redistributor invalidate all register, GICR_INVALLR.
+        pub invallr: WriteOnly<u64>,
+        reserved_0b8: [u32; 2],
+        /// Redistributor synchronize register, GICR_SYNCR.
+        pub syncr: ReadPure<u32>,
+        reserved_0c4: [u32; 16323],
+        /// Identification registers.
+        pub id: [ReadPure<u32>; 12],
+    }
+}
+
+register_map! {
+    /// The registers of the SGI_base frame of a GICv3 redistributor, for the SGIs and PPIs of its
+    /// CPU.
+    #[derive(Debug)]
+    #[repr(C)]
+    pub struct Gicv3SgiFrameRegisters {
+        reserved_000: [u32; 32],
+        /// Interrupt group register 0, GICR_IGROUPR0.
+        pub igroupr0: ReadPureWrite<u32>,
+        reserved_084: [u32; 31],
+        /// Interrupt set-enable register 0, GICR_ISENABLER0.
+        pub isenabler0: ReadPureWrite<u32>,
+        reserved_104: [u32; 31],
+        /// Interrupt clear-enable register 0, GICR_ICENABLER0.
+        pub icenabler0: ReadPureWrite<u32>,
+        reserved_184: [u32; 31],
+        /// Interrupt set-pending register 0, GICR_ISPENDR0.
+        pub ispendr0: ReadPureWrite<u32>,
+        reserved_204: [u32; 31],
+        /// Interrupt clear-pending register 0, GICR_ICPENDR0.
+        pub icpendr0: ReadPureWrite<u32>,
+        reserved_284: [u32; 31],
+        /// Interrupt set-active register 0, GICR_ISACTIVER0.
+        pub isactiver0: ReadPureWrite<u32>,
+        reserved_304: [u32; 31],
+        /// Interrupt clear-active register 0, GICR_ICACTIVER0.
+        pub icactiver0: ReadPureWrite<u32>,
+        reserved_384: [u32; 31],
+        /// Interrupt priority registers, GICR_IPRIORITYRn.
+        pub ipriorityr: [ReadPureWrite<u32>; 8],
+        reserved_420: [u32; 504],
+        /// SGI configuration register, GICR_ICFGR0, and PPI configuration register, GICR_ICFGR1.
+        pub icfgr: [ReadPureWrite<u32>; 2],
+        reserved_c08: [u32; 62],
+        /// Interrupt group modifier register 0, GICR_IGRPMODR0.
+        pub igrpmodr0: ReadPureWrite<u32>,
+        reserved_d04: [u32; 63],
+        /// Non-secure access control register, GICR_NSACR.
+        pub nsacr: ReadPureWrite<u32>,
+        reserved_e04: [u32; 15487],
+    }
+}
+
+/// The registers of a GICv3 redistributor for a single CPU, without virtual LPI support.
+#[derive(Debug)]
+#[repr(C)]
+pub struct Gicv3RedistributorRegisters {
+    /// The RD_base frame, for overall control of the redistributor and LPIs.
+    pub rd: Gicv3RdFrameRegisters,
+    /// The SGI_base frame, for SGIs and PPIs.
+    pub sgi: Gicv3SgiFrameRegisters,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::mem::offset_of;
+
+    #[test]
+    fn gicv2_layout() {
+        assert_eq!(offset_of!(Gicv2DistributorRegisters, igroupr), 0x80);
+        assert_eq!(offset_of!(Gicv2DistributorRegisters, ipriorityr), 0x400);
+        assert_eq!(offset_of!(Gicv2DistributorRegisters, itargetsr), 0x800);
+        assert_eq!(offset_of!(Gicv2DistributorRegisters, icfgr), 0xc00);
+        assert_eq!(offset_of!(Gicv2DistributorRegisters, nsacr), 0xe00);
+        assert_eq!(offset_of!(Gicv2DistributorRegisters, sgir), 0xf00);
+        assert_eq!(offset_of!(Gicv2DistributorRegisters, cpendsgir), 0xf10);
+        assert_eq!(offset_of!(Gicv2DistributorRegisters, id), 0xfd0);
+        assert_eq!(size_of::<Gicv2DistributorRegisters>(), 0x1000);
+
+        assert_eq!(offset_of!(Gicv2CpuInterfaceRegisters, iar), 0xc);
+        assert_eq!(offset_of!(Gicv2CpuInterfaceRegisters, apr), 0xd0);
+        assert_eq!(offset_of!(Gicv2CpuInterfaceRegisters, iidr), 0xfc);
+        assert_eq!(offset_of!(Gicv2CpuInterfaceRegisters, dir), 0x1000);
+    }
+
+    #[test]
+    fn gicv3_layout() {
+        assert_eq!(offset_of!(Gicv3DistributorRegisters, setspi_nsr), 0x40);
+        assert_eq!(offset_of!(Gicv3DistributorRegisters, clrspi_sr), 0x58);
+        assert_eq!(offset_of!(Gicv3DistributorRegisters, igroupr), 0x80);
+        assert_eq!(offset_of!(Gicv3DistributorRegisters, igrpmodr), 0xd00);
+        assert_eq!(offset_of!(Gicv3DistributorRegisters, nsacr), 0xe00);
+        assert_eq!(offset_of!(Gicv3DistributorRegisters, irouter), 0x6100);
+        assert_eq!(offset_of!(Gicv3DistributorRegisters, id), 0xffd0);
+        assert_eq!(size_of::<Gicv3DistributorRegisters>(), 0x10000);
+
+        assert_eq!(offset_of!(Gicv3RdFrameRegisters, waker), 0x14);
+        assert_eq!(offset_of!(Gicv3RdFrameRegisters, setlpir), 0x40);
+        assert_eq!(offset_of!(Gicv3RdFrameRegisters, propbaser), 0x70);
+        assert_eq!(offset_of!(Gicv3RdFrameRegisters, invlpir), 0xa0);
+        assert_eq!(offset_of!(Gicv3RdFrameRegisters, invallr), 0xb0);
+        assert_eq!(offset_of!(Gicv3RdFrameRegisters, syncr), 0xc0);
+        assert_eq!(size_of::<Gicv3RdFrameRegisters>(), 0x10000);
+
+        assert_eq!(offset_of!(Gicv3SgiFrameRegisters, igroupr0), 0x80);
+        assert_eq!(offset_of!(Gicv3SgiFrameRegisters, icactiver0), 0x380);
+        assert_eq!(offset_of!(Gicv3SgiFrameRegisters, ipriorityr), 0x400);
+        assert_eq!(offset_of!(Gicv3SgiFrameRegisters, icfgr), 0xc00);
+        assert_eq!(offset_of!(Gicv3SgiFrameRegisters, igrpmodr0), 0xd00);
+        assert_eq!(offset_of!(Gicv3SgiFrameRegisters, nsacr), 0xe00);
+        assert_eq!(size_of::<Gicv3SgiFrameRegisters>(), 0x10000);
+
+        assert_eq!(offset_of!(Gicv3RedistributorRegisters, sgi), 0x10000);
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! The 16550 UART and compatible devices.
+//!
+//! Several registers share an offset and are selected by whether they are read or written, or by
+//! the divisor latch access bit (DLAB) in the line control register. The field names describe the
+//! registers available when DLAB is clear.
+//!
+//! [`Ns16550Registers`] is for devices with registers one byte apart, as on a PC. Many SoCs instead
+//! put each register in its own 32-bit word, for which [`Ns16550Registers32`] can be used.
+
+use crate::{
+    fields::{ReadOnly, ReadPureWrite, ReadWrite},
+    register_map,
+};
+
+register_map! {
+    /// The registers of a 16550 UART with registers one byte apart.
+    #[derive(Debug)]
+    #[repr(C)]
+    pub struct Ns16550Registers {
+        /// Receive buffer register (RBR) when read, which pops a character from the receive FIFO,
+        /// or transmit holding register (THR) when written. Divisor latch low byte when DLAB is set.
+        pub rbr_thr: ReadWrite<u8>,
+        /// Interrupt enable register (IER). Divisor latch high byte when DLAB is set.
+        pub ier: ReadPureWrite<u8>,
+        /// Interrupt identification register (IIR) when read, which may clear the transmitter empty
+        /// interrupt, or FIFO control register (FCR) when written.
+        pub iir_fcr: ReadWrite<u8>,
+        /// Line control register (LCR).
+        pub lcr: ReadPureWrite<u8>,
+        /// Modem control register (MCR).
+        pub mcr: ReadPureWrite<u8>,
+        /// Line status register (LSR). Reading clears the error bits. See [`line_status`].
+        pub lsr: ReadOnly<u8>,
+        /// Modem status register (MSR). Reading clears the delta bits.
+        pub msr: ReadOnly<u8>,
+        /// Scratch register (SCR).
+        pub scr: ReadPureWrite<u8>,
+    }
+}
+
+register_map! {
+    /// The registers of a 16550 UART with each register in its own 32-bit word, such as a
+    /// DesignWare APB UART or a devicetree node with `reg-shift = <2>`.
+    ///
+    /// Only the low 8 bits of each register are used.
+    #[derive(Debug)]
+    #[repr(C)]
+    pub struct Ns16550Registers32 {
+        /// Receive buffer register (RBR) when read, which pops a character from the receive FIFO,
+        /// or transmit holding register (THR) when written. Divisor latch low byte when DLAB is set.
+        pub rbr_thr: ReadWrite<u32>,
+        /// Interrupt enable register (IER). Divisor latch high byte when DLAB is set.
+        pub ier: ReadPureWrite<u32>,
+        /// Interrupt identification register (IIR) when read, which may clear the transmitter empty
+        /// interrupt, or FIFO control register (FCR) when written.
+        pub iir_fcr: ReadWrite<u32>,
+        /// Line control register (LCR).
+        pub lcr: ReadPureWrite<u32>,
+        /// Modem control register (MCR).
+        pub mcr: ReadPureWrite<u32>,
+        /// Line status register (LSR). Reading clears the error bits. See [`line_status`].
+        pub lsr: ReadOnly<u32>,
+        /// Modem status register (MSR). Reading clears the delta bits.
+        pub msr: ReadOnly<u32>,
+        /// Scratch register (SCR).
+        pub scr: ReadPureWrite<u32>,
+    }
+}
+
+/// Bits of the line status register (LSR).
+pub mod line_status {
+    /// Data ready: there is at least one character in the receive FIFO.
+    pub const DR: u8 = 1 << 0;
+    /// Overrun error.
+    pub const OE: u8 = 1 << 1;
+    /// Parity error.
+    pub const PE: u8 = 1 << 2;
+    /// Framing error.
+    pub const FE: u8 = 1 << 3;
+    /// Break interrupt.
+    pub const BI: u8 = 1 << 4;
+    /// Transmitter holding register empty: there is space in the transmit FIFO.
+    pub const THRE: u8 = 1 << 5;
+    /// Transmitter empty: the transmit FIFO and shift register are both empty.
+    pub const TEMT: u8 = 1 << 6;
+}
+
+/// Bits of the line control register (LCR).
+pub mod line_control {
+    /// 8 data bits.
+    pub const WORD_LENGTH_8: u8 = 0b11;
+    /// Divisor latch access bit.
+    pub const DLAB: u8 = 1 << 7;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::mem::offset_of;
+
+    #[test]
+    fn layout() {
+        assert_eq!(offset_of!(Ns16550Registers, lsr), 5);
+        assert_eq!(size_of::<Ns16550Registers>(), 8);
+        assert_eq!(offset_of!(Ns16550Registers32, lsr), 0x14);
+        assert_eq!(size_of::<Ns16550Registers32>(), 0x20);
+    }
+}
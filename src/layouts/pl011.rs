@@ -0,0 +1,102 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! The Arm PrimeCell UART (PL011), as described in the
+//! [PL011 Technical Reference Manual](https://developer.arm.com/documentation/ddi0183/latest).
+
+use crate::{
+    fields::{ReadPure, ReadPureWrite, ReadWrite, WriteOnly},
+    register_map,
+};
+
+register_map! {
+    /// The registers of a PL011 UART.
+    #[derive(Debug)]
+    #[repr(C)]
+    pub struct Pl011Registers {
+        /// Data register, UARTDR. Reading pops a character from the receive FIFO.
+        pub dr: ReadWrite<u32>,
+        /// Receive status register, UARTRSR, or error clear register, UARTECR when written.
+        pub rsr_ecr: ReadPureWrite<u32>,
+        reserved_08: [u32; 4],
+        /// Flag register, UARTFR. See [`flags`].
+        pub fr: ReadPure<u32>,
+        reserved_1c: u32,
+        /// IrDA low-power counter register, UARTILPR.
+        #[reset(0)]
+        #[writable(0xff)]
+        pub ilpr: ReadPureWrite<u32>,
+        /// Integer baud rate register, UARTIBRD.
+        #[reset(0)]
+        #[writable(0xffff)]
+        pub ibrd: ReadPureWrite<u32>,
+        /// Fractional baud rate register, UARTFBRD.
+        #[reset(0)]
+        #[writable(0x3f)]
+        pub fbrd: ReadPureWrite<u32>,
+        /// Line control register, UARTLCR_H.
+        #[reset(0)]
+        #[writable(0xff)]
+        pub lcr_h: ReadPureWrite<u32>,
+        /// Control register, UARTCR.
+        #[reset(0x0300)]
+        #[writable(0xff87)]
+        pub cr: ReadPureWrite<u32>,
+        /// Interrupt FIFO level select register, UARTIFLS.
+        #[reset(0x12)]
+        #[writable(0x3f)]
+        pub ifls: ReadPureWrite<u32>,
+        /// Interrupt mask set/clear register, UARTIMSC.
+        #[reset(0)]
+        #[writable(0x7ff)]
+        pub imsc: ReadPureWrite<u32>,
+        /// Raw interrupt status register, UARTRIS.
+        pub ris: ReadPure<u32>,
+        /// Masked interrupt status register, UARTMIS.
+        pub mis: ReadPure<u32>,
+        /// Interrupt clear register, UARTICR. Writing 1 to a bit clears the corresponding interrupt.
+        pub icr: WriteOnly<u32>,
+        /// DMA control register, UARTDMACR.
+        #[reset(0)]
+        #[writable(0x7)]
+        pub dmacr: ReadPureWrite<u32>,
+        reserved_4c: [u32; 997],
+        /// Peripheral identification registers, UARTPeriphID0-3.
+        pub periph_id: [ReadPure<u32>; 4],
+        /// PrimeCell identification registers, UARTPCellID0-3.
+        pub pcell_id: [ReadPure<u32>; 4],
+    }
+}
+
+/// Bits of the flag register, UARTFR.
+pub mod flags {
+    /// Clear to send.
+    pub const CTS: u32 = 1 << 0;
+    /// UART busy transmitting data.
+    pub const BUSY: u32 = 1 << 3;
+    /// Receive FIFO empty.
+    pub const RXFE: u32 = 1 << 4;
+    /// Transmit FIFO full.
+    pub const TXFF: u32 = 1 << 5;
+    /// Receive FIFO full.
+    pub const RXFF: u32 = 1 << 6;
+    /// Transmit FIFO empty.
+    pub const TXFE: u32 = 1 << 7;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::mem::offset_of;
+
+    #[test]
+    fn layout() {
+        assert_eq!(offset_of!(Pl011Registers, fr), 0x18);
+        assert_eq!(offset_of!(Pl011Registers, ilpr), 0x20);
+        assert_eq!(offset_of!(Pl011Registers, icr), 0x44);
+        assert_eq!(offset_of!(Pl011Registers, periph_id), 0xfe0);
+        assert_eq!(offset_of!(Pl011Registers, pcell_id), 0xff0);
+        assert_eq!(size_of::<Pl011Registers>(), 0x1000);
+    }
+}
@@ -0,0 +1,52 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! The SiFive UART, as found in the FU540 and FU740 SoCs and emulated by QEMU.
+
+use crate::{
+    fields::{ReadOnly, ReadPure, ReadPureWrite},
+    register_map,
+};
+
+register_map! {
+    /// The registers of a SiFive UART.
+    #[derive(Debug)]
+    #[repr(C)]
+    pub struct SifiveUartRegisters {
+        /// Transmit data register. Writing queues a character; reading returns
+        /// [`TXDATA_FULL`] if the transmit FIFO is full, without side-effects.
+        pub txdata: ReadPureWrite<u32>,
+        /// Receive data register. Reading pops a character from the receive FIFO, or returns
+        /// [`RXDATA_EMPTY`] if it is empty.
+        pub rxdata: ReadOnly<u32>,
+        /// Transmit control register.
+        pub txctrl: ReadPureWrite<u32>,
+        /// Receive control register.
+        pub rxctrl: ReadPureWrite<u32>,
+        /// Interrupt enable register.
+        pub ie: ReadPureWrite<u32>,
+        /// Interrupt pending register.
+        pub ip: ReadPure<u32>,
+        /// Baud rate divisor register.
+        pub div: ReadPureWrite<u32>,
+    }
+}
+
+/// Bit of the `txdata` register which is set when the transmit FIFO is full.
+pub const TXDATA_FULL: u32 = 1 << 31;
+
+/// Bit of the `rxdata` register which is set when the receive FIFO was empty.
+pub const RXDATA_EMPTY: u32 = 1 << 31;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::mem::offset_of;
+
+    #[test]
+    fn layout() {
+        assert_eq!(offset_of!(SifiveUartRegisters, ip), 0x14);
+        assert_eq!(size_of::<SifiveUartRegisters>(), 0x1c);
+    }
+}
@@ -9,25 +9,140 @@
 #![deny(unsafe_op_in_unsafe_fn)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
-#[cfg(all(target_arch = "aarch64", not(feature = "custom-mmio")))]
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(all(
+    target_arch = "aarch64",
+    not(miri),
+    not(feature = "custom-mmio"),
+    not(feature = "verification")
+))]
 mod aarch64_mmio;
+pub mod access;
+#[cfg(feature = "alias-check")]
+pub mod alias_check;
+pub mod attributes;
+pub mod banked;
+pub mod bitband;
+pub mod cached;
+pub mod capability;
+pub mod counter;
+pub mod critical;
 #[cfg(feature = "custom-mmio")]
 pub mod custom_mmio;
+#[cfg(feature = "debug-path")]
+mod debug_path;
+pub mod device_table;
+#[cfg(feature = "fault-injection")]
+pub mod fault;
+#[cfg(feature = "fdt")]
+pub mod fdt;
 pub mod fields;
+pub mod fifo;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
+pub mod guard;
+#[cfg(feature = "handoff")]
+pub mod handoff;
+pub mod header_slice;
+pub mod indirect;
+pub mod interrupt;
+pub mod iommu;
+#[cfg(feature = "layouts")]
+pub mod layouts;
+#[cfg(feature = "ls64")]
+pub mod ls64;
+pub mod masked;
+pub mod monitor;
+pub mod overlay;
+#[cfg(feature = "pci")]
+pub mod pci;
+pub mod percpu;
 mod physical;
-#[cfg(all(not(target_arch = "aarch64"), not(feature = "custom-mmio")))]
+pub mod platform;
+pub mod prelude;
+pub mod privileged;
+#[cfg(all(kani, feature = "verification"))]
+mod proofs;
+#[cfg(feature = "readback")]
+pub mod readback;
+pub mod register_map;
+#[cfg(feature = "registry")]
+pub mod registry;
+pub mod resettable;
+pub mod ring;
+pub mod shadow;
+#[cfg(feature = "simulated")]
+pub mod simulated;
+pub mod snapshot;
+#[cfg(feature = "stats")]
+pub mod stats;
+#[cfg(feature = "trace")]
+pub mod trace;
+pub mod typestate;
+pub mod unlock;
+pub mod value;
+#[cfg(feature = "verification")]
+mod verification_mmio;
+pub mod versioned;
+#[cfg(feature = "virtio")]
+pub mod virtio;
+#[cfg(any(
+    all(
+        any(not(target_arch = "aarch64"), miri),
+        not(feature = "custom-mmio"),
+        not(feature = "verification")
+    ),
+    all(
+        test,
+        target_arch = "aarch64",
+        not(miri),
+        not(feature = "custom-mmio"),
+        not(feature = "verification")
+    )
+))]
 mod volatile_mmio;
-
-use crate::fields::{ReadOnly, ReadPure, ReadPureWrite, ReadWrite, WriteOnly};
+#[cfg(feature = "volatile-register")]
+pub mod volatile_register;
+#[cfg(feature = "wc")]
+pub mod wc;
+
+#[cfg(all(feature = "custom-mmio", feature = "verification"))]
+compile_error!("The `custom-mmio` and `verification` features can't be enabled together.");
+
+use crate::access::AtomicAccess;
+#[cfg(feature = "debug-path")]
+use crate::debug_path::DebugPath;
+use crate::fields::{
+    Command, ReadConstant, ReadOnly, ReadPure, ReadPureCommand, ReadPureWrite, ReadWrite,
+    ReadWrite64HiLo, ReadWrite64LoHi, WriteOnly,
+};
+#[cfg(feature = "stats")]
+use crate::stats::AccessStats;
 use core::{
     array,
-    fmt::Debug,
+    fmt::{self, Debug, Display, Formatter},
+    iter::FusedIterator,
     marker::PhantomData,
     ops::{Deref, Range},
-    ptr::{self, NonNull, slice_from_raw_parts_mut},
+    ptr::{self, NonNull},
 };
-pub use physical::PhysicalInstance;
-use zerocopy::{FromBytes, Immutable, IntoBytes};
+pub use physical::{NonSecure, PhysicalInstance, Realm, Secure, World};
+#[cfg(feature = "derive")]
+pub use safe_mmio_derive::{MmioEnum, MmioInit, MmioValue, mmio_value};
+use zerocopy::{FromBytes, Immutable, IntoBytes, TryFromBytes};
+
+/// Returns the width in bytes of each MMIO access used to read or write a `T` which isn't a single
+/// access.
+///
+/// This is the alignment of `T` up to a maximum of 8 bytes, so that every access is naturally
+/// aligned and no wider than a register.
+#[allow(dead_code, reason = "Not used by every backend")]
+const fn access_width<T>() -> usize {
+    let align = align_of::<T>();
+    if align < 8 { align } else { 8 }
+}
 
 /// A unique owned pointer to the registers of some MMIO device.
 ///
@@ -36,16 +151,74 @@ use zerocopy::{FromBytes, Immutable, IntoBytes};
 ///
 /// A `UniqueMmioPointer` may be created from a mutable reference, but this should only be used for
 /// testing purposes, as references should never be constructed for real MMIO address space.
+///
+/// A `UniqueMmioPointer<T>` is `Send` if `T` is, and `Sync` if `T` is both `Send` and `Sync`. Use
+/// [`percpu::NotSendable`] for devices which must not be accessed from other CPUs.
 pub struct UniqueMmioPointer<'a, T: ?Sized>(SharedMmioPointer<'a, T>);
 
+/// The name of [`UniqueMmioPointer`] before version 0.2.0, kept so that drivers written against
+/// older releases can migrate incrementally.
+///
+/// As this is an alias rather than a separate type, values can be passed freely between code using
+/// either name.
+#[deprecated(note = "Renamed to `UniqueMmioPointer`")]
+pub type OwnedMmioPointer<'a, T> = UniqueMmioPointer<'a, T>;
+
+// SAFETY: A `UniqueMmioPointer` has exclusive access to the MMIO registers for its lifetime, like
+// `&mut T`. The caller of `UniqueMmioPointer::new` promises that the MMIO registers can be accessed
+// from any thread. Devices which must only be accessed from a particular CPU should be wrapped in
+// `percpu::NotSendable` to opt out of this.
+unsafe impl<T: ?Sized + Send> Send for UniqueMmioPointer<'_, T> {}
+
+// SAFETY: A `&UniqueMmioPointer` can only be used to get a `SharedMmioPointer`, so this has the same
+// requirements as sending a `SharedMmioPointer` to another thread.
+unsafe impl<T: ?Sized + Send + Sync> Sync for UniqueMmioPointer<'_, T> {}
+
+/// An error creating a typed pointer to a region of MMIO address space.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RegionError {
+    /// The region is smaller than the type being requested.
+    TooSmall {
+        /// The size in bytes of the type being requested.
+        expected: usize,
+        /// The size in bytes of the region.
+        actual: usize,
+    },
+    /// The region isn't aligned for the type being requested.
+    Misaligned {
+        /// The alignment in bytes required by the type being requested.
+        align: usize,
+    },
+}
+
+impl Display for RegionError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::TooSmall { expected, actual } => write!(
+                f,
+                "Region size {actual:#x} is smaller than requested size {expected:#x}"
+            ),
+            Self::Misaligned { align } => {
+                write!(f, "Region isn't aligned to {align:#x} bytes")
+            }
+        }
+    }
+}
+
+impl core::error::Error for RegionError {}
+
 // Implement Debug, Eq and PartialEq manually rather than deriving to avoid an unneccessary bound on
 // T.
 
 impl<T: ?Sized> Debug for UniqueMmioPointer<'_, T> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_tuple("UniqueMmioPointer")
-            .field(&self.0.regs)
-            .finish()
+        let mut tuple = f.debug_tuple("UniqueMmioPointer");
+        tuple.field(&self.0.regs);
+        #[cfg(feature = "debug-path")]
+        if !self.0.path.is_empty() {
+            tuple.field(&self.0.path);
+        }
+        tuple.finish()
     }
 }
 
@@ -57,6 +230,88 @@ impl<T: ?Sized> PartialEq for UniqueMmioPointer<'_, T> {
 
 impl<T: ?Sized> Eq for UniqueMmioPointer<'_, T> {}
 
+impl<T> UniqueMmioPointer<'_, T> {
+    /// Creates a new `UniqueMmioPointer` from a virtual address, using exposed provenance.
+    ///
+    /// This should be used rather than casting an integer to a pointer, so that the crate can be
+    /// used under strict provenance rules. See [`core::ptr::with_exposed_provenance_mut`] for
+    /// details.
+    ///
+    /// # Safety
+    ///
+    /// `addr` must be the address of some MMIO address space of type `T`, which satisfies all the
+    /// requirements of [`UniqueMmioPointer::new`]. If the page tables mapping it were created by
+    /// Rust code then the provenance of the mapping must have been exposed, e.g. with
+    /// [`pointer::expose_provenance`](https://doc.rust-lang.org/core/primitive.pointer.html#method.expose_provenance).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `addr` is 0. See [`try_from_exposed_addr`](Self::try_from_exposed_addr) for a
+    /// non-panicking version.
+    pub unsafe fn from_exposed_addr(addr: usize) -> Self {
+        // SAFETY: The caller promised that `addr` satisfies the requirements of `new`.
+        unsafe { Self::try_from_exposed_addr(addr) }.expect("MMIO address must not be 0")
+    }
+
+    /// Creates a new `UniqueMmioPointer` from a virtual address, using exposed provenance, or
+    /// returns `None` if `addr` is 0.
+    ///
+    /// # Safety
+    ///
+    /// If `addr` is not 0 then it must satisfy the requirements of
+    /// [`from_exposed_addr`](Self::from_exposed_addr).
+    pub unsafe fn try_from_exposed_addr(addr: usize) -> Option<Self> {
+        let regs = NonNull::new(ptr::with_exposed_provenance_mut(addr))?;
+        // SAFETY: The caller promised that `addr` satisfies the requirements of `new`.
+        Some(unsafe { Self::new(regs) })
+    }
+
+    /// Creates a new `UniqueMmioPointer` to the start of a region of `len` bytes, such as one whose
+    /// size came from a devicetree `reg` property or a PCI BAR, after checking that the region is
+    /// large enough and properly aligned for `T`.
+    ///
+    /// This catches truncated or misaligned mappings before any registers are accessed, rather than
+    /// accessing past the end of the mapping.
+    ///
+    /// # Safety
+    ///
+    /// If the checks pass, `regs` must satisfy all the requirements of [`UniqueMmioPointer::new`]
+    /// for a `T`.
+    pub unsafe fn try_new_with_len(regs: NonNull<u8>, len: usize) -> Result<Self, RegionError> {
+        if len < size_of::<T>() {
+            return Err(RegionError::TooSmall {
+                expected: size_of::<T>(),
+                actual: len,
+            });
+        }
+        if !regs.cast::<T>().is_aligned() {
+            return Err(RegionError::Misaligned {
+                align: align_of::<T>(),
+            });
+        }
+        // SAFETY: The caller promised that `regs` satisfies the requirements of `new`, now that we
+        // have checked the size and alignment.
+        Ok(unsafe { Self::new(regs.cast()) })
+    }
+
+    /// Returns a `UniqueMmioPointer` to a `U` at the given byte offset within these registers, or
+    /// `None` if it would extend past the end of `T` or wouldn't be properly aligned.
+    ///
+    /// This is useful for structures whose location is read from the device, such as capability
+    /// lists, so that they can be accessed without going through a raw pointer.
+    ///
+    /// # Safety
+    ///
+    /// The MMIO address space at `byte_offset` must be valid registers of type `U`. If `U` contains
+    /// any fields wrapped in [`ReadOnly`], [`WriteOnly`] or [`ReadWrite`] then they must indeed be
+    /// safe to perform MMIO reads or writes on.
+    pub unsafe fn offset_as<U>(&mut self, byte_offset: usize) -> Option<UniqueMmioPointer<'_, U>> {
+        // SAFETY: The registers are `size_of::<T>()` bytes long, and our caller promised that there
+        // are valid registers of type `U` at `byte_offset`.
+        unsafe { self.offset_within(size_of::<T>(), byte_offset) }
+    }
+}
+
 impl<T: ?Sized> UniqueMmioPointer<'_, T> {
     /// Creates a new `UniqueMmioPointer` from a non-null raw pointer.
     ///
@@ -73,6 +328,10 @@ impl<T: ?Sized> UniqueMmioPointer<'_, T> {
         Self(SharedMmioPointer {
             regs,
             phantom: PhantomData,
+            #[cfg(feature = "stats")]
+            stats: None,
+            #[cfg(feature = "debug-path")]
+            path: DebugPath::EMPTY,
         })
     }
 
@@ -85,10 +344,8 @@ impl<T: ?Sized> UniqueMmioPointer<'_, T> {
     /// `regs` must be a properly aligned and valid pointer to some MMIO address space of type T,
     /// within the allocation that `self` points to.
     pub const unsafe fn child<U: ?Sized>(&mut self, regs: NonNull<U>) -> UniqueMmioPointer<'_, U> {
-        UniqueMmioPointer(SharedMmioPointer {
-            regs,
-            phantom: PhantomData,
-        })
+        // SAFETY: Our caller promised that `regs` is within our allocation.
+        UniqueMmioPointer(unsafe { self.0.child(regs) })
     }
 
     /// Returns a raw mut pointer to the MMIO registers.
@@ -108,9 +365,69 @@ impl<T: ?Sized> UniqueMmioPointer<'_, T> {
         // exactly our allocation.
         unsafe { self.child(ptr) }
     }
+
+    /// Returns a `UniqueMmioPointer` to a `U` at the given byte offset, or `None` if it would
+    /// extend past `region_len` bytes or wouldn't be properly aligned.
+    ///
+    /// # Safety
+    ///
+    /// `region_len` must be no more than the size in bytes of the registers which `self` points
+    /// to, and the MMIO address space at `byte_offset` must be valid registers of type `U`.
+    unsafe fn offset_within<U>(
+        &mut self,
+        region_len: usize,
+        byte_offset: usize,
+    ) -> Option<UniqueMmioPointer<'_, U>> {
+        if byte_offset.checked_add(size_of::<U>())? > region_len {
+            return None;
+        }
+        // SAFETY: We checked that `byte_offset` is within the region, and our caller promised that
+        // the region is within our allocation.
+        let regs = unsafe { self.ptr_nonnull().cast::<u8>().add(byte_offset) }.cast::<U>();
+        if !regs.is_aligned() {
+            return None;
+        }
+        // SAFETY: `regs` is properly aligned and within our allocation, and our caller promised
+        // that it points to valid registers of type `U`.
+        Some(unsafe { self.child(regs) })
+    }
 }
 
 impl<'a, T: ?Sized> UniqueMmioPointer<'a, T> {
+    /// Returns this pointer, tagged with the given device name such as `"pl011@9000000"`.
+    ///
+    /// With the `debug-path` feature, the name is included in the `Debug` output of this pointer
+    /// and of all pointers projected from it, to tell apart multiple instances of the same
+    /// peripheral. Without the feature this does nothing.
+    pub const fn with_name(self, name: &'static str) -> Self {
+        Self(self.0.with_name(name))
+    }
+
+    /// Returns the device name which this pointer, or the pointer it was projected from, was
+    /// tagged with by [`with_name`](Self::with_name).
+    #[cfg(feature = "debug-path")]
+    pub const fn name(&self) -> Option<&'static str> {
+        self.0.name()
+    }
+
+    /// Returns this pointer, changed to count its accesses in the given `AccessStats`.
+    ///
+    /// Pointers derived from the returned pointer, such as to its fields or elements, count their
+    /// accesses in the same `AccessStats`.
+    #[cfg(feature = "stats")]
+    pub const fn with_stats(self, stats: &'a AccessStats) -> Self {
+        Self(self.0.with_stats(stats))
+    }
+
+    /// Creates a new `UniqueMmioPointer` from a mutable reference.
+    ///
+    /// This is equivalent to the `From<&mut T>` implementation, but can be used in const contexts.
+    /// It should only be used for testing purposes, as references should never be constructed for
+    /// real MMIO address space.
+    pub const fn from_mut(r: &'a mut T) -> Self {
+        Self(SharedMmioPointer::from_ref(r))
+    }
+
     /// Creates a new `UniqueMmioPointer` with the same lifetime as this one, but not tied to the
     /// lifetime this one is borrowed for.
     ///
@@ -126,15 +443,30 @@ impl<'a, T: ?Sized> UniqueMmioPointer<'a, T> {
         &mut self,
         regs: NonNull<U>,
     ) -> UniqueMmioPointer<'a, U> {
-        UniqueMmioPointer(SharedMmioPointer {
-            regs,
-            phantom: PhantomData,
-        })
+        // SAFETY: Our caller promised that `regs` is within our allocation.
+        UniqueMmioPointer(unsafe { self.0.child(regs) })
+    }
+
+    /// Records that this pointer is to the given field of the pointer given by the expression
+    /// `parent`, for its `Debug` output with the `debug-path` feature.
+    ///
+    /// This is used internally by the [`field!`] and [`split_fields!`] macros and shouldn't be
+    /// called directly.
+    #[doc(hidden)]
+    pub const fn in_field(self, parent: &'static str, field: &'static str) -> Self {
+        Self(self.0.in_field(parent, field))
+    }
+
+    /// Records that this pointer is to the given element of its parent, for its `Debug` output
+    /// with the `debug-path` feature.
+    const fn in_index(self, index: usize) -> Self {
+        Self(self.0.in_index(index))
     }
 }
 
-impl<T: FromBytes + IntoBytes> UniqueMmioPointer<'_, ReadWrite<T>> {
+impl<T: AtomicAccess> UniqueMmioPointer<'_, ReadWrite<T>> {
     /// Performs an MMIO read of the entire `T`.
+    #[inline(always)]
     pub fn read(&mut self) -> T {
         // SAFETY: self.regs is always a valid and unique pointer to MMIO address space, and `T`
         // being wrapped in `ReadWrite` implies that it is safe to read.
@@ -142,8 +474,9 @@ impl<T: FromBytes + IntoBytes> UniqueMmioPointer<'_, ReadWrite<T>> {
     }
 }
 
-impl<T: Immutable + IntoBytes> UniqueMmioPointer<'_, ReadWrite<T>> {
+impl<T: AtomicAccess> UniqueMmioPointer<'_, ReadWrite<T>> {
     /// Performs an MMIO write of the entire `T`.
+    #[inline(always)]
     pub fn write(&mut self, value: T) {
         // SAFETY: self.regs is always a valid and unique pointer to MMIO address space, and `T`
         // being wrapped in `ReadWrite` implies that it is safe to write.
@@ -153,7 +486,7 @@ impl<T: Immutable + IntoBytes> UniqueMmioPointer<'_, ReadWrite<T>> {
     }
 }
 
-impl<T: FromBytes + Immutable + IntoBytes> UniqueMmioPointer<'_, ReadWrite<T>> {
+impl<T: AtomicAccess> UniqueMmioPointer<'_, ReadWrite<T>> {
     /// Performs an MMIO read of the entire `T`, applies the given function to it, and then performs
     /// an MMIO write of the resulting value.
     ///
@@ -174,18 +507,41 @@ impl<T: FromBytes + Immutable + IntoBytes> UniqueMmioPointer<'_, ReadWrite<T>> {
     }
 }
 
-impl<T: Immutable + IntoBytes> UniqueMmioPointer<'_, ReadPureWrite<T>> {
+impl<T: AtomicAccess> UniqueMmioPointer<'_, ReadPureWrite<T>> {
     /// Performs an MMIO write of the entire `T`.
+    ///
+    /// With the `readback` feature enabled and a mismatch hook set, this then reads the register
+    /// back and calls the hook if any bit differs from the value written.
+    #[inline(always)]
     pub fn write(&mut self, value: T) {
         // SAFETY: self.regs is always a valid and unique pointer to MMIO address space, and `T`
         // being wrapped in `ReadPureWrite` implies that it is safe to write.
         unsafe {
             self.write_unsafe(ReadPureWrite(value));
         }
+        #[cfg(feature = "readback")]
+        readback::verify(self, value, None);
+    }
+
+    /// Performs an MMIO write of the entire `T`.
+    ///
+    /// With the `readback` feature enabled and a mismatch hook set, this then reads the register
+    /// back and calls the hook if any of the bits set in `writable_mask` differ from the value
+    /// written. Without the feature this is the same as [`write`](Self::write).
+    #[inline(always)]
+    #[allow(unused_variables)]
+    pub fn write_checked(&mut self, value: T, writable_mask: T) {
+        // SAFETY: self.regs is always a valid and unique pointer to MMIO address space, and `T`
+        // being wrapped in `ReadPureWrite` implies that it is safe to write.
+        unsafe {
+            self.write_unsafe(ReadPureWrite(value));
+        }
+        #[cfg(feature = "readback")]
+        readback::verify(self, value, Some(writable_mask));
     }
 }
 
-impl<T: FromBytes + Immutable + IntoBytes> UniqueMmioPointer<'_, ReadPureWrite<T>> {
+impl<T: AtomicAccess> UniqueMmioPointer<'_, ReadPureWrite<T>> {
     /// Performs an MMIO read of the entire `T`, applies the given function to it, and then performs
     /// an MMIO write of the resulting value.
     ///
@@ -206,18 +562,161 @@ impl<T: FromBytes + Immutable + IntoBytes> UniqueMmioPointer<'_, ReadPureWrite<T
     }
 }
 
-impl<T: FromBytes + IntoBytes> UniqueMmioPointer<'_, ReadOnly<T>> {
+impl<T: AtomicAccess> UniqueMmioPointer<'_, ReadOnly<T>> {
     /// Performs an MMIO read of the entire `T`.
+    #[inline(always)]
     pub fn read(&mut self) -> T {
+        #[cfg(feature = "trace")]
+        trace::record_direction(self.addr(), size_of::<T>(), trace::AccessKind::Read);
         // SAFETY: self.regs is always a valid and unique pointer to MMIO address space, and `T`
         // being wrapped in `ReadOnly` implies that it is safe to read.
         unsafe { self.read_unsafe().0 }
     }
 }
 
-impl<T: Immutable + IntoBytes> UniqueMmioPointer<'_, WriteOnly<T>> {
+impl UniqueMmioPointer<'_, ReadWrite64LoHi> {
+    /// Performs an MMIO read of the low half and then the high half of the register.
+    pub fn read(&mut self) -> u64 {
+        let [mut low, mut high] = split_halves(self);
+        // SAFETY: The field being wrapped in `ReadWrite64LoHi` implies that it is safe to read each
+        // half, low half first.
+        unsafe {
+            let low = low.read_unsafe();
+            let high = high.read_unsafe();
+            u64::from(high) << 32 | u64::from(low)
+        }
+    }
+
+    /// Performs an MMIO write of the low half and then the high half of the register.
+    pub fn write(&mut self, value: u64) {
+        let [mut low, mut high] = split_halves(self);
+        // SAFETY: The field being wrapped in `ReadWrite64LoHi` implies that it is safe to write
+        // each half, low half first.
+        unsafe {
+            low.write_unsafe(value as u32);
+            high.write_unsafe((value >> 32) as u32);
+        }
+    }
+}
+
+impl UniqueMmioPointer<'_, ReadWrite64HiLo> {
+    /// Performs an MMIO read of the high half and then the low half of the register.
+    pub fn read(&mut self) -> u64 {
+        let [mut low, mut high] = split_halves(self);
+        // SAFETY: The field being wrapped in `ReadWrite64HiLo` implies that it is safe to read each
+        // half, high half first.
+        unsafe {
+            let high = high.read_unsafe();
+            let low = low.read_unsafe();
+            u64::from(high) << 32 | u64::from(low)
+        }
+    }
+
+    /// Performs an MMIO write of the high half and then the low half of the register.
+    pub fn write(&mut self, value: u64) {
+        let [mut low, mut high] = split_halves(self);
+        // SAFETY: The field being wrapped in `ReadWrite64HiLo` implies that it is safe to write
+        // each half, high half first.
+        unsafe {
+            high.write_unsafe((value >> 32) as u32);
+            low.write_unsafe(value as u32);
+        }
+    }
+}
+
+/// Returns whether `chosen` contains only indices less than `len`, with no duplicates.
+fn valid_choice(chosen: &[usize], len: usize) -> bool {
+    chosen
+        .iter()
+        .enumerate()
+        .all(|(i, &index)| index < len && chosen.iter().take(i).all(|&previous| previous != index))
+}
+
+/// Returns pointers to the low and high 32-bit halves of the given 64-bit little-endian register.
+fn split_halves<'a, T>(regs: &'a mut UniqueMmioPointer<'_, T>) -> [UniqueMmioPointer<'a, u32>; 2] {
+    const { assert!(size_of::<T>() == 8) };
+    let halves = regs.ptr_nonnull().cast::<[u32; 2]>();
+    // SAFETY: `T` is 8 bytes long, so its two halves are exactly the same allocation.
+    unsafe { regs.child(halves) }.split()
+}
+
+/// Asserts at compile time that `C` is a single naturally aligned 1, 2, 4 or 8 byte value, so that it
+/// can be used as a command.
+const fn assert_command<C>() {
+    assert!(
+        matches!(size_of::<C>(), 1 | 2 | 4 | 8) && align_of::<C>() == size_of::<C>(),
+        "Commands must be a single naturally aligned 1, 2, 4 or 8 byte value"
+    );
+}
+
+impl<C: Immutable + IntoBytes> UniqueMmioPointer<'_, Command<C>> {
+    /// Performs an MMIO write of the given command.
+    #[inline(always)]
+    pub fn issue(&mut self, command: C) {
+        const { assert_command::<C>() };
+        // SAFETY: self.regs is always a valid and unique pointer to MMIO address space, and `C`
+        // being wrapped in `Command` implies that it is safe to write any value of it.
+        unsafe {
+            self.write_unsafe(Command(command));
+        }
+    }
+}
+
+impl<C: Immutable + IntoBytes> UniqueMmioPointer<'_, ReadPureCommand<C>> {
+    /// Performs an MMIO write of the given command.
+    #[inline(always)]
+    pub fn issue(&mut self, command: C) {
+        const { assert_command::<C>() };
+        // SAFETY: self.regs is always a valid and unique pointer to MMIO address space, and `C`
+        // being wrapped in `ReadPureCommand` implies that it is safe to write any value of it.
+        unsafe {
+            self.write_unsafe(ReadPureCommand(command));
+        }
+    }
+}
+
+impl<C: TryFromBytes> SharedMmioPointer<'_, ReadPureCommand<C>> {
+    /// Performs an MMIO read of the register, and returns the command in it, or `None` if it
+    /// doesn't contain a valid value of `C`.
+    #[inline(always)]
+    pub fn read(&self) -> Option<C> {
+        const { assert_command::<C>() };
+        // SAFETY: self.regs is always a valid pointer to MMIO address space, and the field being
+        // wrapped in `ReadPureCommand` implies that it is safe to read from a shared reference
+        // because doing so has no side-effects. `C` is the same size as the integer type used in
+        // each case, so the child pointer is exactly the same allocation.
+        unsafe {
+            match size_of::<C>() {
+                1 => read_command::<C, u8>(self),
+                2 => read_command::<C, u16>(self),
+                4 => read_command::<C, u32>(self),
+                _ => read_command::<C, u64>(self),
+            }
+        }
+    }
+}
+
+/// Reads the given command register as an `I` and converts it to a `C`, if it is a valid value.
+///
+/// # Safety
+///
+/// `I` must be the same size as `C`, and it must be safe to read the register.
+#[inline(always)]
+unsafe fn read_command<C: TryFromBytes, I: FromBytes + Immutable + IntoBytes>(
+    regs: &SharedMmioPointer<ReadPureCommand<C>>,
+) -> Option<C> {
+    // SAFETY: Our caller promised that `I` is the same size as `C`, so the child pointer is exactly
+    // the same allocation, and that it is safe to read.
+    let value = unsafe { regs.child(regs.regs.cast::<I>()).read_unsafe() };
+    C::try_read_from_bytes(value.as_bytes()).ok()
+}
+
+impl<T: AtomicAccess> UniqueMmioPointer<'_, WriteOnly<T>> {
     /// Performs an MMIO write of the entire `T`.
+    #[inline(always)]
     pub fn write(&mut self, value: T) {
+        #[cfg(feature = "trace")]
+        trace::record_direction(self.addr(), size_of::<T>(), trace::AccessKind::Write);
         // SAFETY: self.regs is always a valid and unique pointer to MMIO address space, and `T`
         // being wrapped in `WriteOnly` implies that it is safe to write.
         unsafe {
@@ -226,6 +725,113 @@ impl<T: Immutable + IntoBytes> UniqueMmioPointer<'_, WriteOnly<T>> {
     }
 }
 
+impl<T: AtomicAccess, const LEN: usize> UniqueMmioPointer<'_, [ReadOnly<T>; LEN]> {
+    /// Performs an MMIO read of each element of the array in index order, and returns the values.
+    pub fn read_array(&mut self) -> [T; LEN] {
+        array::from_fn(|i| {
+            // SAFETY: `i` is always less than `LEN`.
+            unsafe { self.get_unchecked(i) }.read()
+        })
+    }
+}
+
+impl<T: AtomicAccess, const LEN: usize> UniqueMmioPointer<'_, [ReadWrite<T>; LEN]> {
+    /// Performs an MMIO read of each element of the array in index order, and returns the values.
+    pub fn read_array(&mut self) -> [T; LEN] {
+        array::from_fn(|i| {
+            // SAFETY: `i` is always less than `LEN`.
+            unsafe { self.get_unchecked(i) }.read()
+        })
+    }
+}
+
+impl<T: AtomicAccess, const LEN: usize> UniqueMmioPointer<'_, [WriteOnly<T>; LEN]> {
+    /// Performs an MMIO write of each element of the array in index order.
+    pub fn write_array(&mut self, values: [T; LEN]) {
+        for (i, value) in values.into_iter().enumerate() {
+            // SAFETY: `i` is always less than `LEN`.
+            unsafe { self.get_unchecked(i) }.write(value);
+        }
+    }
+}
+
+impl<T: AtomicAccess> UniqueMmioPointer<'_, [WriteOnly<T>]> {
+    /// Performs an MMIO write of each element of the slice in index order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` has a different length to this slice.
+    pub fn write_slice(&mut self, values: &[T]) {
+        assert_eq!(
+            values.len(),
+            self.len(),
+            "source slice length does not match destination slice length"
+        );
+        for (i, value) in values.iter().enumerate() {
+            // SAFETY: We checked that `values` has the same length as `self`, so `i` is in bounds.
+            unsafe { self.get_unchecked(i) }.write(*value);
+        }
+    }
+}
+
+impl<T: AtomicAccess, const LEN: usize> UniqueMmioPointer<'_, [ReadWrite<T>; LEN]> {
+    /// Performs an MMIO write of each element of the array in index order.
+    pub fn write_array(&mut self, values: [T; LEN]) {
+        for (i, value) in values.into_iter().enumerate() {
+            // SAFETY: `i` is always less than `LEN`.
+            unsafe { self.get_unchecked(i) }.write(value);
+        }
+    }
+}
+
+impl<T: AtomicAccess> UniqueMmioPointer<'_, [ReadWrite<T>]> {
+    /// Performs an MMIO write of each element of the slice in index order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` has a different length to this slice.
+    pub fn write_slice(&mut self, values: &[T]) {
+        assert_eq!(
+            values.len(),
+            self.len(),
+            "source slice length does not match destination slice length"
+        );
+        for (i, value) in values.iter().enumerate() {
+            // SAFETY: We checked that `values` has the same length as `self`, so `i` is in bounds.
+            unsafe { self.get_unchecked(i) }.write(*value);
+        }
+    }
+}
+
+impl<T: AtomicAccess, const LEN: usize> UniqueMmioPointer<'_, [ReadPureWrite<T>; LEN]> {
+    /// Performs an MMIO write of each element of the array in index order.
+    pub fn write_array(&mut self, values: [T; LEN]) {
+        for (i, value) in values.into_iter().enumerate() {
+            // SAFETY: `i` is always less than `LEN`.
+            unsafe { self.get_unchecked(i) }.write(value);
+        }
+    }
+}
+
+impl<T: AtomicAccess> UniqueMmioPointer<'_, [ReadPureWrite<T>]> {
+    /// Performs an MMIO write of each element of the slice in index order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` has a different length to this slice.
+    pub fn write_slice(&mut self, values: &[T]) {
+        assert_eq!(
+            values.len(),
+            self.len(),
+            "source slice length does not match destination slice length"
+        );
+        for (i, value) in values.iter().enumerate() {
+            // SAFETY: We checked that `values` has the same length as `self`, so `i` is in bounds.
+            unsafe { self.get_unchecked(i) }.write(*value);
+        }
+    }
+}
+
 impl<'a, T> UniqueMmioPointer<'a, [T]> {
     /// Returns a `UniqueMmioPointer` to an element of this slice, or `None` if the index is out of
     /// bounds.
@@ -245,12 +851,70 @@ impl<'a, T> UniqueMmioPointer<'a, [T]> {
         if index >= self.0.len() {
             return None;
         }
+        // SAFETY: We just checked that `index` is in bounds.
+        Some(unsafe { self.get_unchecked(index) })
+    }
+
+    /// Returns a `UniqueMmioPointer` to a `U` at the given byte offset within this slice, or
+    /// `None` if it would extend past the end of the slice or wouldn't be properly aligned.
+    ///
+    /// This is useful for structures whose location is read from the device, such as capability
+    /// lists, so that they can be accessed without going through a raw pointer.
+    ///
+    /// # Safety
+    ///
+    /// The MMIO address space at `byte_offset` must be valid registers of type `U`. If `U` contains
+    /// any fields wrapped in [`ReadOnly`], [`WriteOnly`] or [`ReadWrite`] then they must indeed be
+    /// safe to perform MMIO reads or writes on.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_mmio::{UniqueMmioPointer, fields::ReadPure};
+    ///
+    /// # use zerocopy::IntoBytes;
+    /// let mut config: UniqueMmioPointer<[u8]>;
+    /// # let mut fake = [0u32; 64];
+    /// # fake[13] = 0x40;
+    /// # config = UniqueMmioPointer::from(fake.as_mut_bytes());
+    /// // SAFETY: The capabilities pointer of a PCI configuration header is a pure read.
+    /// let pointer = unsafe { config.offset_as::<ReadPure<u8>>(0x34) }.unwrap().read();
+    /// // SAFETY: The first capability header is at the offset in the capabilities pointer.
+    /// let capability = unsafe { config.offset_as::<ReadPure<u16>>(pointer.into()) }.unwrap();
+    /// ```
+    pub unsafe fn offset_as<U>(&mut self, byte_offset: usize) -> Option<UniqueMmioPointer<'_, U>> {
+        let region_len = self.0.len() * size_of::<T>();
+        // SAFETY: The slice is `region_len` bytes long, and our caller promised that there are
+        // valid registers of type `U` at `byte_offset`.
+        unsafe { self.offset_within(region_len, byte_offset) }
+    }
+
+    /// Returns a `UniqueMmioPointer` to an element of this slice, without doing bounds checking.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be less than the length of the slice.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_mmio::{UniqueMmioPointer, fields::ReadWrite};
+    ///
+    /// let mut slice: UniqueMmioPointer<[ReadWrite<u32>]>;
+    /// # let mut fake = [ReadWrite(1), ReadWrite(2), ReadWrite(3)];
+    /// # slice = UniqueMmioPointer::from(fake.as_mut_slice());
+    /// // SAFETY: The slice has 3 elements, so index 1 is in bounds.
+    /// let mut element = unsafe { slice.get_unchecked(1) };
+    /// element.write(42);
+    /// ```
+    pub const unsafe fn get_unchecked(&mut self, index: usize) -> UniqueMmioPointer<'_, T> {
         // SAFETY: self.ptr_mut() is guaranteed to return a pointer that is valid for MMIO and
-        // unique, as promised by the caller of `UniqueMmioPointer::new`.
-        let regs = NonNull::new(unsafe { &raw mut (*self.ptr_mut())[index] }).unwrap();
+        // unique, as promised by the caller of `UniqueMmioPointer::new`. The caller promised that
+        // `index` is in bounds.
+        let regs = unsafe { NonNull::new_unchecked(self.ptr_mut().cast::<T>().add(index)) };
         // SAFETY: We created regs from the raw slice in self.regs, so it must also be valid, unique
         // and within the allocation of self.regs.
-        Some(unsafe { self.child(regs) })
+        unsafe { self.child(regs) }.in_index(index)
     }
 
     /// Returns a `UniqueMmioPointer` to a range of elements of this slice, or `None` if the range
@@ -276,18 +940,12 @@ impl<'a, T> UniqueMmioPointer<'a, [T]> {
             return None;
         }
 
-        let regs_start = if !range.is_empty() {
-            // SAFETY: self.ptr_mut() is guaranteed to return a pointer that is valid for MMIO and
-            // unique, as promised by the caller of `UniqueMmioPointer::new`. range.start is within the
-            // boundaries of the slice.
-            unsafe { &raw mut (*self.ptr_mut())[range.start] }
-        } else {
-            // Based on the documentation of core::slice::from_raw_parts_mut, NonNull::dangling()
-            // should be used for creating zero-length slices.
-            NonNull::dangling().as_ptr()
-        };
+        // SAFETY: range.start is no greater than the length, so this is within the region or one past
+        // its end. An empty range still points into the original region, rather than being
+        // dangling, so that its address is meaningful.
+        let regs_start = unsafe { self.ptr_nonnull().cast::<T>().add(range.start) };
 
-        let regs = NonNull::new(slice_from_raw_parts_mut(regs_start, range.len())).unwrap();
+        let regs = NonNull::slice_from_raw_parts(regs_start, range.len());
 
         // SAFETY: We created regs from the valid start address of regs_start and `range` is within
         // the boundaries of self.regs, so it must also be valid, unique and within the allocation
@@ -302,6 +960,70 @@ impl<'a, T> UniqueMmioPointer<'a, [T]> {
         }
     }
 
+    /// Returns an iterator over `chunk_size` elements of the slice at a time, starting at the
+    /// beginning of the slice.
+    ///
+    /// The chunks are slice pointers and do not overlap. If `chunk_size` does not divide the length
+    /// of the slice, then the last up to `chunk_size - 1` elements will be omitted and can be
+    /// retrieved from the [`into_remainder`](UniqueMmioPointerChunksExact::into_remainder) method
+    /// of the iterator.
+    ///
+    /// Panics if `chunk_size` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_mmio::{UniqueMmioPointer, fields::ReadWrite};
+    ///
+    /// let mut slice: UniqueMmioPointer<[ReadWrite<u8>]>;
+    /// # let mut fake = [const { ReadWrite(0) }; 10];
+    /// # slice = UniqueMmioPointer::from(fake.as_mut_slice());
+    /// let mut chunks = slice.chunks_exact(4);
+    /// for mut frame in &mut chunks {
+    ///     assert_eq!(frame.len(), 4);
+    ///     frame.get(0).unwrap().write(42);
+    /// }
+    /// assert_eq!(chunks.into_remainder().len(), 2);
+    /// ```
+    pub fn chunks_exact(&mut self, chunk_size: usize) -> UniqueMmioPointerChunksExact<'_, T> {
+        UniqueMmioPointerChunksExact::new(self.reborrow(), chunk_size)
+    }
+
+    /// Returns a `UniqueMmioPointer` to the first element of this slice, or `None` if it is empty.
+    pub const fn first(&mut self) -> Option<UniqueMmioPointer<'_, T>> {
+        self.get(0)
+    }
+
+    /// Returns a `UniqueMmioPointer` to the last element of this slice, or `None` if it is empty.
+    pub const fn last(&mut self) -> Option<UniqueMmioPointer<'_, T>> {
+        match self.0.len().checked_sub(1) {
+            Some(index) => self.get(index),
+            None => None,
+        }
+    }
+
+    /// Splits this slice pointer into a pointer to its first element and a slice pointer to the
+    /// rest, or returns `None` if it is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_mmio::{UniqueMmioPointer, fields::ReadWrite};
+    ///
+    /// let mut slice: UniqueMmioPointer<[ReadWrite<u32>]>;
+    /// # let mut fake = [ReadWrite(1), ReadWrite(2), ReadWrite(3)];
+    /// # slice = UniqueMmioPointer::from(fake.as_mut_slice());
+    /// let (mut head, mut rest) = slice.split_first().unwrap();
+    /// head.write(42);
+    /// assert_eq!(rest.len(), 2);
+    /// assert_eq!(rest.get(0).unwrap().read(), 2);
+    /// ```
+    pub fn split_first(self) -> Option<(UniqueMmioPointer<'a, T>, UniqueMmioPointer<'a, [T]>)> {
+        let mut iter = self.into_iter();
+        let head = iter.next()?;
+        Some((head, iter.tail))
+    }
+
     /// Returns a `UniqueMmioPointer` to an element of this slice, or `None` if the index is out of
     /// bounds.
     ///
@@ -326,12 +1048,43 @@ impl<'a, T> UniqueMmioPointer<'a, [T]> {
             return None;
         }
         // SAFETY: self.ptr_mut() is guaranteed to return a pointer that is valid for MMIO and
-        // unique, as promised by the caller of `UniqueMmioPointer::new`.
-        let regs = NonNull::new(unsafe { &raw mut (*self.ptr_mut())[index] }).unwrap();
+        // unique, as promised by the caller of `UniqueMmioPointer::new`. We just checked that `index`
+        // is in bounds.
+        let regs = unsafe { NonNull::new_unchecked(self.ptr_mut().cast::<T>().add(index)) };
         // SAFETY: We created regs from the raw slice in self.regs, so it must also be valid, unique
         // and within the allocation of self.regs. `self` is dropped immediately after this and we
         // don't split out any other children.
-        Some(unsafe { self.split_child(regs) })
+        Some(unsafe { self.split_child(regs) }.in_index(index))
+    }
+    /// Splits a `UniqueMmioPointer` to a slice into an array of `UniqueMmioPointer`s, taking only
+    /// the `chosen` indices.
+    ///
+    /// Panics if `chosen` contains the same index more than once, or any index out of bounds. See
+    /// [`try_split_some`](Self::try_split_some) for a non-panicking version.
+    pub fn split_some<const N: usize>(self, chosen: [usize; N]) -> [UniqueMmioPointer<'a, T>; N] {
+        self.try_split_some(chosen)
+            .expect("chosen array must not contain duplicates or indices out of bounds")
+    }
+
+    /// Splits a `UniqueMmioPointer` to a slice into an array of `UniqueMmioPointer`s, taking only
+    /// the `chosen` indices, or returns `None` if `chosen` contains the same index more than once
+    /// or any index out of bounds.
+    pub fn try_split_some<const N: usize>(
+        mut self,
+        chosen: [usize; N],
+    ) -> Option<[UniqueMmioPointer<'a, T>; N]> {
+        if !valid_choice(&chosen, self.len()) {
+            return None;
+        }
+        Some(chosen.map(|chosen_index| {
+            // SAFETY: self.regs is always unique and valid for MMIO access. We checked that
+            // `chosen` doesn't contain duplicates or indices out of bounds, so the pointers we
+            // split it into are within it and don't overlap, so the same applies to each of them.
+            unsafe {
+                let regs = NonNull::new_unchecked(self.ptr_mut().cast::<T>().add(chosen_index));
+                self.split_child(regs).in_index(chosen_index)
+            }
+        }))
     }
 }
 
@@ -339,42 +1092,49 @@ impl<'a, T, const LEN: usize> UniqueMmioPointer<'a, [T; LEN]> {
     /// Splits a `UniqueMmioPointer` to an array into an array of `UniqueMmioPointer`s.
     pub fn split(mut self) -> [UniqueMmioPointer<'a, T>; LEN] {
         array::from_fn(|i| {
-            UniqueMmioPointer(SharedMmioPointer {
-                // SAFETY: self.regs is always unique and valid for MMIO access. We make sure the
-                // pointers we split it into don't overlap, so the same applies to each of them.
-                regs: NonNull::new(unsafe { &raw mut (*self.ptr_mut())[i] }).unwrap(),
-                phantom: PhantomData,
-            })
+            // SAFETY: self.regs is always unique and valid for MMIO access. We make sure the
+            // pointers we split it into don't overlap, so the same applies to each of them.
+            unsafe {
+                let regs = NonNull::new_unchecked(self.ptr_mut().cast::<T>().add(i));
+                self.split_child(regs).in_index(i)
+            }
         })
     }
 
     /// Splits a `UniqueMmioPointer` to an array into an array of `UniqueMmioPointer`s, taking only
     /// the `chosen` indices.
     ///
-    /// Panics if `chosen` contains the same index more than once, or any index out of bounds.
-    pub fn split_some<const N: usize>(
+    /// Panics if `chosen` contains the same index more than once, or any index out of bounds. See
+    /// [`try_split_some`](Self::try_split_some) for a non-panicking version.
+    pub fn split_some<const N: usize>(self, chosen: [usize; N]) -> [UniqueMmioPointer<'a, T>; N] {
+        self.try_split_some(chosen)
+            .expect("chosen array must not contain duplicates or indices out of bounds")
+    }
+
+    /// Splits a `UniqueMmioPointer` to an array into an array of `UniqueMmioPointer`s, taking only
+    /// the `chosen` indices, or returns `None` if `chosen` contains the same index more than once
+    /// or any index out of bounds.
+    pub fn try_split_some<const N: usize>(
         mut self,
         chosen: [usize; N],
-    ) -> [UniqueMmioPointer<'a, T>; N] {
-        for (i, a) in chosen.iter().enumerate() {
-            for (j, b) in chosen.iter().enumerate() {
-                assert!(i == j || a != b, "chosen array must not contain duplicates");
-            }
+    ) -> Option<[UniqueMmioPointer<'a, T>; N]> {
+        if !valid_choice(&chosen, LEN) {
+            return None;
         }
-        chosen.map(|chosen_index| {
-            UniqueMmioPointer(SharedMmioPointer {
-                // SAFETY: self.regs is always unique and valid for MMIO access. We checked that
-                // `chosen` doesn't contain duplicates so the pointers we split it into don't
-                // overlap, so the same applies to each of them.
-                regs: NonNull::new(unsafe { &raw mut (*self.ptr_mut())[chosen_index] }).unwrap(),
-                phantom: PhantomData,
-            })
-        })
+        Some(chosen.map(|chosen_index| {
+            // SAFETY: self.regs is always unique and valid for MMIO access. We checked that
+            // `chosen` doesn't contain duplicates or indices out of bounds, so the pointers we
+            // split it into are within it and don't overlap, so the same applies to each of them.
+            unsafe {
+                let regs = NonNull::new_unchecked(self.ptr_mut().cast::<T>().add(chosen_index));
+                self.split_child(regs).in_index(chosen_index)
+            }
+        }))
     }
 
     /// Converts this array pointer to an equivalent slice pointer.
     pub const fn as_mut_slice(&mut self) -> UniqueMmioPointer<'_, [T]> {
-        let regs = NonNull::new(self.ptr_mut()).unwrap();
+        let regs: NonNull<[T]> = self.ptr_nonnull();
         // SAFETY: We created regs from the raw array in self.regs, so it must also be valid, unique
         // and within the allocation of self.regs.
         unsafe { self.child(regs) }
@@ -399,12 +1159,23 @@ impl<'a, T, const LEN: usize> UniqueMmioPointer<'a, [T; LEN]> {
         if index >= LEN {
             return None;
         }
+        // SAFETY: We just checked that `index` is in bounds.
+        Some(unsafe { self.get_unchecked(index) })
+    }
+
+    /// Returns a `UniqueMmioPointer` to an element of this array, without doing bounds checking.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be less than `LEN`.
+    pub const unsafe fn get_unchecked(&mut self, index: usize) -> UniqueMmioPointer<'_, T> {
         // SAFETY: self.ptr_mut() is guaranteed to return a pointer that is valid for MMIO and
-        // unique, as promised by the caller of `UniqueMmioPointer::new`.
-        let regs = NonNull::new(unsafe { &raw mut (*self.ptr_mut())[index] }).unwrap();
+        // unique, as promised by the caller of `UniqueMmioPointer::new`. The caller promised that
+        // `index` is in bounds.
+        let regs = unsafe { NonNull::new_unchecked(self.ptr_mut().cast::<T>().add(index)) };
         // SAFETY: We created regs from the raw array in self.regs, so it must also be valid, unique
         // and within the allocation of self.regs.
-        Some(unsafe { self.child(regs) })
+        unsafe { self.child(regs) }.in_index(index)
     }
 
     /// Returns a `UniqueMmioPointer` to a range of elements of this array, or `None` if the range
@@ -430,18 +1201,12 @@ impl<'a, T, const LEN: usize> UniqueMmioPointer<'a, [T; LEN]> {
             return None;
         }
 
-        let regs_start = if !range.is_empty() {
-            // SAFETY: self.ptr_mut() is guaranteed to return a pointer that is valid for MMIO and
-            // unique, as promised by the caller of `UniqueMmioPointer::new`. range.start is within the
-            // boundaries of the array.
-            unsafe { &raw mut (*self.ptr_mut())[range.start] }
-        } else {
-            // Based on the documentation of core::slice::from_raw_parts_mut, NonNull::dangling()
-            // should be used for creating zero-length slices.
-            NonNull::dangling().as_ptr()
-        };
+        // SAFETY: range.start is no greater than the length, so this is within the region or one past
+        // its end. An empty range still points into the original region, rather than being
+        // dangling, so that its address is meaningful.
+        let regs_start = unsafe { self.ptr_nonnull().cast::<T>().add(range.start) };
 
-        let regs = NonNull::new(slice_from_raw_parts_mut(regs_start, range.len())).unwrap();
+        let regs = NonNull::slice_from_raw_parts(regs_start, range.len());
 
         // SAFETY: We created regs from the valid start address of regs_start and `range` is within
         // the boundaries of self.regs, so it must also be valid, unique and within the allocation
@@ -456,6 +1221,25 @@ impl<'a, T, const LEN: usize> UniqueMmioPointer<'a, [T; LEN]> {
         }
     }
 
+    /// Returns a `UniqueMmioPointer` to the first element of this array, or `None` if it is empty.
+    pub const fn first(&mut self) -> Option<UniqueMmioPointer<'_, T>> {
+        self.get(0)
+    }
+
+    /// Returns a `UniqueMmioPointer` to the last element of this array, or `None` if it is empty.
+    pub const fn last(&mut self) -> Option<UniqueMmioPointer<'_, T>> {
+        match LEN.checked_sub(1) {
+            Some(index) => self.get(index),
+            None => None,
+        }
+    }
+
+    /// Splits this array pointer into a pointer to its first element and a slice pointer to the
+    /// rest, or returns `None` if it is empty.
+    pub fn split_first(self) -> Option<(UniqueMmioPointer<'a, T>, UniqueMmioPointer<'a, [T]>)> {
+        UniqueMmioPointer::<[T]>::from(self).split_first()
+    }
+
     /// Returns a `UniqueMmioPointer` to an element of this array, or `None` if the index is out of
     /// bounds.
     ///
@@ -480,62 +1264,111 @@ impl<'a, T, const LEN: usize> UniqueMmioPointer<'a, [T; LEN]> {
             return None;
         }
         // SAFETY: self.ptr_mut() is guaranteed to return a pointer that is valid for MMIO and
-        // unique, as promised by the caller of `UniqueMmioPointer::new`.
-        let regs = NonNull::new(unsafe { &raw mut (*self.ptr_mut())[index] }).unwrap();
+        // unique, as promised by the caller of `UniqueMmioPointer::new`. We just checked that `index`
+        // is in bounds.
+        let regs = unsafe { NonNull::new_unchecked(self.ptr_mut().cast::<T>().add(index)) };
         // SAFETY: We created regs from the raw array in self.regs, so it must also be valid, unique
         // and within the allocation of self.regs. `self` is dropped immediately after this and we
         // don't split out any other children.
-        Some(unsafe { self.split_child(regs) })
+        Some(unsafe { self.split_child(regs) }.in_index(index))
+    }
+}
+
+impl<T, const COLUMNS: usize, const ROWS: usize> UniqueMmioPointer<'_, [[T; COLUMNS]; ROWS]> {
+    /// Returns a `UniqueMmioPointer` to the element at the given row and column of this 2-D
+    /// array, or `None` if either index is out of bounds.
+    ///
+    /// This is equivalent to `get(row)?.get(column)`, but computes the address of the element
+    /// directly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use safe_mmio::{UniqueMmioPointer, fields::ReadWrite};
+    ///
+    /// // Four queues for each of two ports.
+    /// let mut queues: UniqueMmioPointer<[[ReadWrite<u32>; 4]; 2]>;
+    /// # let mut fake = [const { [const { ReadWrite(0) }; 4] }; 2];
+    /// # queues = UniqueMmioPointer::from(&mut fake);
+    /// queues.get2(1, 3).unwrap().write(42);
+    /// assert!(queues.get2(2, 0).is_none());
+    /// ```
+    pub const fn get2(&mut self, row: usize, column: usize) -> Option<UniqueMmioPointer<'_, T>> {
+        if row >= ROWS || column >= COLUMNS {
+            return None;
+        }
+        // SAFETY: self.ptr_mut() is guaranteed to return a pointer that is valid for MMIO and
+        // unique, as promised by the caller of `UniqueMmioPointer::new`. We just checked that `row`
+        // and `column` are in bounds, so the element is within the array.
+        let regs = unsafe {
+            NonNull::new_unchecked(self.ptr_mut().cast::<T>().add(row * COLUMNS + column))
+        };
+        // SAFETY: We created regs from the raw array in self.regs, so it must also be valid, unique
+        // and within the allocation of self.regs.
+        Some(unsafe { self.child(regs) }.in_index(row).in_index(column))
+    }
+}
+
+impl<T, const COLUMNS: usize> UniqueMmioPointer<'_, [[T; COLUMNS]]> {
+    /// Returns a `UniqueMmioPointer` to the element at the given row and column of this 2-D
+    /// slice, or `None` if either index is out of bounds.
+    ///
+    /// This is equivalent to `get(row)?.get(column)`, but computes the address of the element
+    /// directly.
+    pub const fn get2(&mut self, row: usize, column: usize) -> Option<UniqueMmioPointer<'_, T>> {
+        if row >= self.0.len() || column >= COLUMNS {
+            return None;
+        }
+        // SAFETY: self.ptr_mut() is guaranteed to return a pointer that is valid for MMIO and
+        // unique, as promised by the caller of `UniqueMmioPointer::new`. We just checked that `row`
+        // and `column` are in bounds, so the element is within the slice.
+        let regs = unsafe {
+            NonNull::new_unchecked(self.ptr_mut().cast::<T>().add(row * COLUMNS + column))
+        };
+        // SAFETY: We created regs from the raw slice in self.regs, so it must also be valid, unique
+        // and within the allocation of self.regs.
+        Some(unsafe { self.child(regs) }.in_index(row).in_index(column))
     }
 }
 
 impl<'a, T, const LEN: usize> From<UniqueMmioPointer<'a, [T; LEN]>> for UniqueMmioPointer<'a, [T]> {
     fn from(mut value: UniqueMmioPointer<'a, [T; LEN]>) -> Self {
-        let regs = NonNull::new(value.ptr_mut()).unwrap();
-        // SAFETY: regs comes from a UniqueMmioPointer so already satisfies all the safety
-        // requirements.
-        unsafe { UniqueMmioPointer::new(regs) }
+        let regs: NonNull<[T]> = value.ptr_nonnull();
+        // SAFETY: regs is the whole of the allocation that value points to, and value is dropped
+        // immediately afterwards. Using `split_child` keeps the stats and debug path of `value`.
+        unsafe { value.split_child(regs) }
     }
 }
 
 impl<'a, T> From<UniqueMmioPointer<'a, T>> for UniqueMmioPointer<'a, [T; 1]> {
     fn from(mut value: UniqueMmioPointer<'a, T>) -> Self {
-        let regs = NonNull::new(value.ptr_mut()).unwrap().cast();
-        // SAFETY: regs comes from a UniqueMmioPointer so already satisfies all the safety
-        // requirements.
-        unsafe { UniqueMmioPointer::new(regs) }
+        let regs = value.ptr_nonnull().cast();
+        // SAFETY: regs is the whole of the allocation that value points to, and value is dropped
+        // immediately afterwards. Using `split_child` keeps the stats and debug path of `value`.
+        unsafe { value.split_child(regs) }
     }
 }
 
 impl<'a, T> From<UniqueMmioPointer<'a, T>> for UniqueMmioPointer<'a, [T]> {
     fn from(mut value: UniqueMmioPointer<'a, T>) -> Self {
-        let array: *mut [T; 1] = value.ptr_mut().cast();
-        let regs = NonNull::new(array).unwrap();
-        // SAFETY: regs comes from a UniqueMmioPointer so already satisfies all the safety
-        // requirements.
-        unsafe { UniqueMmioPointer::new(regs) }
+        let regs: NonNull<[T]> = value.ptr_nonnull().cast::<[T; 1]>();
+        // SAFETY: regs is the whole of the allocation that value points to, and value is dropped
+        // immediately afterwards. Using `split_child` keeps the stats and debug path of `value`.
+        unsafe { value.split_child(regs) }
     }
 }
 
 impl<'a, T, const LEN: usize> From<UniqueMmioPointer<'a, [T; LEN]>>
     for [UniqueMmioPointer<'a, T>; LEN]
 {
-    fn from(mut value: UniqueMmioPointer<'a, [T; LEN]>) -> Self {
-        array::from_fn(|i| {
-            let item_pointer = value.get(i).unwrap().ptr_mut();
-            // SAFETY: `split_child` is called only once on each item and the original
-            // `UniqueMmioPointer` is consumed by this function.
-            unsafe { value.split_child(core::ptr::NonNull::new(item_pointer).unwrap()) }
-        })
+    fn from(value: UniqueMmioPointer<'a, [T; LEN]>) -> Self {
+        value.split()
     }
 }
 
 impl<'a, T: ?Sized> From<&'a mut T> for UniqueMmioPointer<'a, T> {
     fn from(r: &'a mut T) -> Self {
-        Self(SharedMmioPointer {
-            regs: r.into(),
-            phantom: PhantomData,
-        })
+        Self::from_mut(r)
     }
 }
 
@@ -557,6 +1390,26 @@ impl<'a, T> IntoIterator for UniqueMmioPointer<'a, [T]> {
     }
 }
 
+/// Consumes the array pointer, yielding pointers to its elements which keep the full lifetime `'a`.
+///
+/// This is useful to hand out each channel of a multi-channel device to a separate driver.
+///
+/// # Example
+///
+/// ```
+/// use safe_mmio::{UniqueMmioPointer, fields::ReadWrite};
+///
+/// struct Channel<'a> {
+///     regs: UniqueMmioPointer<'a, ReadWrite<u32>>,
+/// }
+///
+/// fn channels<'a>(regs: UniqueMmioPointer<'a, [ReadWrite<u32>; 4]>) -> Vec<Channel<'a>> {
+///     regs.into_iter().map(|regs| Channel { regs }).collect()
+/// }
+/// # let mut fake = [const { ReadWrite(0) }; 4];
+/// # let mut channels = channels(UniqueMmioPointer::from(&mut fake));
+/// # channels[3].regs.write(42);
+/// ```
 impl<'a, T, const LEN: usize> IntoIterator for UniqueMmioPointer<'a, [T; LEN]> {
     type Item = UniqueMmioPointer<'a, T>;
 
@@ -581,21 +1434,18 @@ impl<'a, T> Iterator for UniqueMmioPointerIterator<'a, T> {
 
     fn next(&mut self) -> Option<Self::Item> {
         if !self.tail.is_empty() {
-            // SAFETY: self.ptr_mut() is guaranteed to return a pointer that is valid for MMIO and
-            // unique, as promised by the caller of `UniqueMmioPointer::new` and the slice is
-            // not empty.
-            let regs_head = NonNull::new(unsafe { &raw mut (*self.tail.ptr_mut())[0] }).unwrap();
+            // The slice is not empty, so this is a valid pointer to its first element.
+            let regs_head = self.tail.ptr_nonnull().cast::<T>();
 
             // SAFETY: regs_head is created from self.tail so it is valid and within the range of
             // the original pointer. There no other further split_child calls to the same child and
             // self.tail is moved by one in the following lines.
             let head = unsafe { self.tail.split_child(regs_head) };
 
-            let regs_tail = NonNull::new(slice_from_raw_parts_mut(
-                regs_head.as_ptr().wrapping_add(1),
-                self.tail.len() - 1,
-            ))
-            .unwrap();
+            // SAFETY: The slice is not empty, so one element past its start is still within it or
+            // one past its end.
+            let regs_tail_start = unsafe { regs_head.add(1) };
+            let regs_tail = NonNull::slice_from_raw_parts(regs_tail_start, self.tail.len() - 1);
 
             // SAFETY: regs is created from self.tail so it is valid and within the range of the
             // original pointer. The new pointer overwrites the original so it cannot be used
@@ -613,12 +1463,98 @@ impl<'a, T> Iterator for UniqueMmioPointerIterator<'a, T> {
     }
 }
 
+impl<T> ExactSizeIterator for UniqueMmioPointerIterator<'_, T> {}
+
+impl<T> FusedIterator for UniqueMmioPointerIterator<'_, T> {}
+
+/// Iterator over a `UniqueMmioPointer` slice, yielding non-overlapping slice pointers of a fixed
+/// size.
+///
+/// This is created by [`UniqueMmioPointer::chunks_exact`].
+#[derive(Debug)]
+pub struct UniqueMmioPointerChunksExact<'a, T> {
+    tail: UniqueMmioPointer<'a, [T]>,
+    remainder: UniqueMmioPointer<'a, [T]>,
+    chunk_size: usize,
+}
+
+impl<'a, T> UniqueMmioPointerChunksExact<'a, T> {
+    fn new(mut slice: UniqueMmioPointer<'a, [T]>, chunk_size: usize) -> Self {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        let len = slice.len();
+        let exact_len = len - len % chunk_size;
+        let start = slice.ptr_nonnull().cast::<T>();
+        let regs_exact = NonNull::slice_from_raw_parts(start, exact_len);
+        // SAFETY: `exact_len` is no greater than the length of the slice, so this is still within it
+        // or one past its end.
+        let remainder_start = unsafe { start.add(exact_len) };
+        let regs_remainder = NonNull::slice_from_raw_parts(remainder_start, len - exact_len);
+        // SAFETY: Both pointers are created from `slice` and don't overlap, and `slice` is dropped
+        // immediately afterwards.
+        unsafe {
+            Self {
+                tail: slice.split_child(regs_exact),
+                remainder: slice.split_child(regs_remainder),
+                chunk_size,
+            }
+        }
+    }
+
+    /// Returns the remaining elements which don't make up a full chunk.
+    pub fn into_remainder(self) -> UniqueMmioPointer<'a, [T]> {
+        self.remainder
+    }
+}
+
+impl<'a, T> Iterator for UniqueMmioPointerChunksExact<'a, T> {
+    type Item = UniqueMmioPointer<'a, [T]>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.tail.len() >= self.chunk_size {
+            let start = self.tail.ptr_nonnull().cast::<T>();
+            let regs_head = NonNull::slice_from_raw_parts(start, self.chunk_size);
+
+            // SAFETY: regs_head is created from self.tail so it is valid and within the range of
+            // the original pointer. There are no further split_child calls to the same child and
+            // self.tail is moved by chunk_size in the following lines.
+            let head = unsafe { self.tail.split_child(regs_head) };
+
+            // SAFETY: The slice has at least `chunk_size` elements, so this is still within it or
+            // one past its end.
+            let regs_tail_start = unsafe { start.add(self.chunk_size) };
+            let regs_tail =
+                NonNull::slice_from_raw_parts(regs_tail_start, self.tail.len() - self.chunk_size);
+
+            // SAFETY: regs_tail is created from self.tail so it is valid and within the range of
+            // the original pointer. The new pointer overwrites the original so it cannot be used
+            // afterwards, and there are no further calls to split_child().
+            self.tail = unsafe { self.tail.split_child(regs_tail) };
+
+            Some(head)
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.tail.len() / self.chunk_size;
+        (len, Some(len))
+    }
+}
+
 /// A shared pointer to the registers of some MMIO device.
 ///
 /// It is guaranteed to be valid but unlike [`UniqueMmioPointer`] may not be unique.
+///
+/// A `SharedMmioPointer<T>` is `Send` and `Sync` if `T` is both `Send` and `Sync`.
 pub struct SharedMmioPointer<'a, T: ?Sized> {
     regs: NonNull<T>,
     phantom: PhantomData<&'a T>,
+    #[cfg(feature = "stats")]
+    stats: Option<&'a AccessStats>,
+    /// The path of fields and elements by which this pointer was projected, for `Debug` output.
+    #[cfg(feature = "debug-path")]
+    path: DebugPath,
 }
 
 // Implement Debug, Eq and PartialEq manually rather than deriving to avoid an unneccessary bound on
@@ -626,9 +1562,13 @@ pub struct SharedMmioPointer<'a, T: ?Sized> {
 
 impl<T: ?Sized> Debug for SharedMmioPointer<'_, T> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_tuple("SharedMmioPointer")
-            .field(&self.regs)
-            .finish()
+        let mut tuple = f.debug_tuple("SharedMmioPointer");
+        tuple.field(&self.regs);
+        #[cfg(feature = "debug-path")]
+        if !self.path.is_empty() {
+            tuple.field(&self.path);
+        }
+        tuple.finish()
     }
 }
 
@@ -649,6 +1589,23 @@ impl<T: ?Sized> Clone for SharedMmioPointer<'_, T> {
 impl<T: ?Sized> Copy for SharedMmioPointer<'_, T> {}
 
 impl<'a, T: ?Sized> SharedMmioPointer<'a, T> {
+    /// Creates a new `SharedMmioPointer` from a reference.
+    ///
+    /// This is equivalent to the `From<&T>` implementation, but can be used in const contexts. It
+    /// should only be used for testing purposes, as references should never be constructed for real
+    /// MMIO address space.
+    pub const fn from_ref(r: &'a T) -> Self {
+        Self {
+            // SAFETY: A reference is never null.
+            regs: unsafe { NonNull::new_unchecked(ptr::from_ref(r).cast_mut()) },
+            phantom: PhantomData,
+            #[cfg(feature = "stats")]
+            stats: None,
+            #[cfg(feature = "debug-path")]
+            path: DebugPath::EMPTY,
+        }
+    }
+
     /// Creates a new `SharedMmioPointer` with the same lifetime as this one.
     ///
     /// This is used internally by the [`field_shared!`] macro and shouldn't be called directly.
@@ -661,13 +1618,149 @@ impl<'a, T: ?Sized> SharedMmioPointer<'a, T> {
         SharedMmioPointer {
             regs,
             phantom: PhantomData,
+            #[cfg(feature = "stats")]
+            stats: self.stats,
+            #[cfg(feature = "debug-path")]
+            path: self.path,
         }
     }
 
+    /// Records that this pointer is to the given field of the pointer given by the expression
+    /// `parent`, for its `Debug` output with the `debug-path` feature.
+    ///
+    /// This is used internally by the [`field_shared!`] macro and shouldn't be called directly.
+    #[doc(hidden)]
+    #[cfg_attr(not(feature = "debug-path"), allow(unused_variables))]
+    pub const fn in_field(self, parent: &'static str, field: &'static str) -> Self {
+        #[cfg(feature = "debug-path")]
+        return Self {
+            path: self.path.field(parent, field),
+            ..self
+        };
+        #[cfg(not(feature = "debug-path"))]
+        self
+    }
+
+    /// Records that this pointer is to the given element of its parent, for its `Debug` output
+    /// with the `debug-path` feature.
+    #[cfg_attr(not(feature = "debug-path"), allow(unused_variables))]
+    const fn in_index(self, index: usize) -> Self {
+        #[cfg(feature = "debug-path")]
+        return Self {
+            path: self.path.index(index),
+            ..self
+        };
+        #[cfg(not(feature = "debug-path"))]
+        self
+    }
+
     /// Returns a raw const pointer to the MMIO registers.
     pub const fn ptr(&self) -> *const T {
         self.regs.as_ptr()
     }
+
+    /// Returns the virtual address of the MMIO registers, without exposing its provenance.
+    pub fn addr(&self) -> usize {
+        self.regs.addr().get()
+    }
+
+    /// Returns the virtual address of the MMIO registers, and exposes its provenance so that a
+    /// pointer can later be created from it with [`UniqueMmioPointer::from_exposed_addr`].
+    pub fn expose_provenance(&self) -> usize {
+        self.regs.as_ptr().expose_provenance()
+    }
+
+    /// Returns a copy of this pointer, tagged with the given device name such as `"pl011@9000000"`.
+    ///
+    /// With the `debug-path` feature, the name is included in the `Debug` output of this pointer
+    /// and of all pointers projected from it, to tell apart multiple instances of the same
+    /// peripheral. Without the feature this does nothing.
+    #[cfg_attr(not(feature = "debug-path"), allow(unused_variables))]
+    pub const fn with_name(self, name: &'static str) -> Self {
+        #[cfg(feature = "debug-path")]
+        return Self {
+            path: DebugPath::named(name),
+            ..self
+        };
+        #[cfg(not(feature = "debug-path"))]
+        self
+    }
+
+    /// Returns the device name which this pointer, or the pointer it was projected from, was
+    /// tagged with by [`with_name`](Self::with_name).
+    #[cfg(feature = "debug-path")]
+    pub const fn name(&self) -> Option<&'static str> {
+        self.path.name()
+    }
+
+    /// Returns a copy of this pointer which counts its accesses in the given `AccessStats`.
+    ///
+    /// Pointers derived from the returned pointer, such as to its fields or elements, count their
+    /// accesses in the same `AccessStats`.
+    #[cfg(feature = "stats")]
+    pub const fn with_stats(self, stats: &'a AccessStats) -> Self {
+        Self {
+            stats: Some(stats),
+            ..self
+        }
+    }
+
+    /// Returns the `AccessStats` in which accesses through this pointer are counted, if any.
+    #[cfg(feature = "stats")]
+    pub const fn stats(&self) -> Option<&'a AccessStats> {
+        self.stats
+    }
+
+    /// Records an MMIO read of the given value in the access statistics and trace, if enabled.
+    #[inline(always)]
+    #[allow(unused_variables)]
+    fn record_read(&self, value: &[u8]) {
+        #[cfg(feature = "stats")]
+        if let Some(stats) = self.stats {
+            stats.record_read();
+        }
+        #[cfg(feature = "trace")]
+        trace::record(
+            self.regs.addr().get(),
+            value,
+            trace::AccessKind::Read,
+            self.debug_path(),
+        );
+    }
+
+    /// Records an MMIO write of the given value in the access statistics and trace, if enabled.
+    #[inline(always)]
+    fn record_write(&self, value: &[u8]) {
+        self.record_write_at(0, value);
+    }
+
+    /// Records an MMIO write of the given value at the given byte offset from this pointer in the
+    /// access statistics and trace, if enabled.
+    #[inline(always)]
+    #[allow(unused_variables)]
+    fn record_write_at(&self, offset: usize, value: &[u8]) {
+        #[cfg(feature = "stats")]
+        if let Some(stats) = self.stats {
+            stats.record_write();
+        }
+        #[cfg(feature = "trace")]
+        trace::record(
+            self.regs.addr().get() + offset,
+            value,
+            trace::AccessKind::Write,
+            self.debug_path(),
+        );
+    }
+
+    /// Returns the device name and path of this pointer for tracing, if known.
+    #[cfg(feature = "trace")]
+    fn debug_path(&self) -> Option<&dyn core::fmt::Display> {
+        #[cfg(feature = "debug-path")]
+        if !self.path.is_empty() {
+            return Some(&self.path);
+        }
+        None
+    }
 }
 
 // SAFETY: A `SharedMmioPointer` always originates either from a reference or from a
@@ -675,12 +1768,13 @@ impl<'a, T: ?Sized> SharedMmioPointer<'a, T> {
 // be accessed from any thread.
 unsafe impl<T: ?Sized + Send + Sync> Send for SharedMmioPointer<'_, T> {}
 
+// SAFETY: A `&SharedMmioPointer` can only be used to make a copy of the `SharedMmioPointer`, which
+// has the same requirements as sending it to another thread.
+unsafe impl<T: ?Sized + Send + Sync> Sync for SharedMmioPointer<'_, T> {}
+
 impl<'a, T: ?Sized> From<&'a T> for SharedMmioPointer<'a, T> {
     fn from(r: &'a T) -> Self {
-        Self {
-            regs: r.into(),
-            phantom: PhantomData,
-        }
+        Self::from_ref(r)
     }
 }
 
@@ -690,47 +1784,181 @@ impl<'a, T: ?Sized> From<UniqueMmioPointer<'a, T>> for SharedMmioPointer<'a, T>
     }
 }
 
-impl<T: FromBytes + IntoBytes> SharedMmioPointer<'_, ReadPure<T>> {
+#[cfg(not(all(
+    target_arch = "aarch64",
+    not(miri),
+    not(feature = "custom-mmio"),
+    not(feature = "verification")
+)))]
+impl<T: FromBytes + IntoBytes> SharedMmioPointer<'_, T> {
+    /// Performs an MMIO read which the compiler may treat as an ordinary memory read. Only the
+    /// aarch64 backend takes advantage of this, so elsewhere it is the same as `read_unsafe`.
+    ///
+    /// # Safety
+    ///
+    /// This field must be safe to perform an MMIO read from, doing so must not cause any
+    /// side-effects, and its value must not change other than by writes from this program.
+    #[inline(always)]
+    pub(crate) unsafe fn read_constant_unsafe(&self) -> T {
+        // SAFETY: The caller's guarantees are a superset of those needed by `read_unsafe`.
+        unsafe { self.read_unsafe() }
+    }
+}
+
+impl<T: FromBytes + IntoBytes> SharedMmioPointer<'_, T> {
+    /// Performs an MMIO read of a field with no side-effects, which the compiler may merge with
+    /// other reads or remove only if the user opts in with `cfg(safe_mmio_pure_reads)`.
+    ///
+    /// # Safety
+    ///
+    /// This field must be safe to perform an MMIO read from, and doing so must not cause any
+    /// side-effects.
+    #[inline(always)]
+    unsafe fn read_pure_unsafe(&self) -> T {
+        // SAFETY: The caller guarantees that the read is safe and has no side-effects. By setting
+        // `cfg(safe_mmio_pure_reads)` the user promises that the values of such fields don't change
+        // other than by the program's own writes.
+        unsafe {
+            if cfg!(safe_mmio_pure_reads) {
+                self.read_constant_unsafe()
+            } else {
+                self.read_unsafe()
+            }
+        }
+    }
+}
+
+impl<T: AtomicAccess> SharedMmioPointer<'_, ReadPure<T>> {
     /// Performs an MMIO read of the entire `T`.
+    ///
+    /// With `cfg(safe_mmio_pure_reads)` on aarch64, the compiler may merge this with other reads of
+    /// the same register or remove it if the value is unused.
+    #[inline(always)]
     pub fn read(&self) -> T {
+        #[cfg(feature = "trace")]
+        trace::record_direction(self.addr(), size_of::<T>(), trace::AccessKind::Read);
         // SAFETY: self.regs is always a valid and unique pointer to MMIO address space, and `T`
         // being wrapped in `ReadPure` implies that it is safe to read from a shared reference
         // because doing so has no side-effects.
-        unsafe { self.read_unsafe().0 }
+        unsafe { self.read_pure_unsafe().0 }
+    }
+
+    /// Performs an MMIO read of the register and discards the value, to make sure that all previous
+    /// writes to the same device have reached it.
+    ///
+    /// On PCI and many SoC interconnects writes may be posted, so aren't guaranteed to have reached
+    /// the device until a subsequent read from the same device has completed.
+    pub fn flush_posted_writes(&self) {
+        // SAFETY: self.regs is always a valid pointer to MMIO address space, and `T` being wrapped
+        // in `ReadPure` implies that it is safe to read from a shared reference. This uses
+        // `read_unsafe` rather than `read` so that the read can't be removed.
+        unsafe { self.read_unsafe() };
+    }
+}
+
+impl<T: AtomicAccess> SharedMmioPointer<'_, ReadConstant<T>> {
+    /// Performs an MMIO read of the entire `T`.
+    ///
+    /// On aarch64 the compiler may merge this with other reads of the same register or remove it if
+    /// the value is unused.
+    #[inline(always)]
+    pub fn read(&self) -> T {
+        #[cfg(feature = "trace")]
+        trace::record_direction(self.addr(), size_of::<T>(), trace::AccessKind::Read);
+        // SAFETY: self.regs is always a valid pointer to MMIO address space, and `T` being wrapped
+        // in `ReadConstant` implies that it is safe to read from a shared reference because doing
+        // so has no side-effects, and that its value doesn't change.
+        unsafe { self.read_constant_unsafe().0 }
     }
 }
 
-impl<T: FromBytes + IntoBytes> SharedMmioPointer<'_, ReadPureWrite<T>> {
+impl<T: AtomicAccess> SharedMmioPointer<'_, ReadPureWrite<T>> {
     /// Performs an MMIO read of the entire `T`.
+    ///
+    /// With `cfg(safe_mmio_pure_reads)` on aarch64, the compiler may merge this with other reads of
+    /// the same register or remove it if the value is unused.
+    #[inline(always)]
     pub fn read(&self) -> T {
         // SAFETY: self.regs is always a valid pointer to MMIO address space, and `T`
         // being wrapped in `ReadPureWrite` implies that it is safe to read from a shared reference
         // because doing so has no side-effects.
-        unsafe { self.read_unsafe().0 }
+        unsafe { self.read_pure_unsafe().0 }
+    }
+
+    /// Performs an MMIO read of the register and discards the value, to make sure that all previous
+    /// writes to the same device have reached it.
+    ///
+    /// On PCI and many SoC interconnects writes may be posted, so aren't guaranteed to have reached
+    /// the device until a subsequent read from the same device has completed.
+    pub fn flush_posted_writes(&self) {
+        // SAFETY: self.regs is always a valid pointer to MMIO address space, and `T` being wrapped
+        // in `ReadPureWrite` implies that it is safe to read from a shared reference. This uses
+        // `read_unsafe` rather than `read` so that the read can't be removed.
+        unsafe { self.read_unsafe() };
+    }
+}
+
+impl<T: AtomicAccess, const LEN: usize> SharedMmioPointer<'_, [ReadPure<T>; LEN]> {
+    /// Performs an MMIO read of each element of the array in index order, and returns the values.
+    pub fn read_array(&self) -> [T; LEN] {
+        array::from_fn(|i| {
+            // SAFETY: `i` is always less than `LEN`.
+            unsafe { self.get_unchecked(i) }.read()
+        })
+    }
+}
+
+impl<T: AtomicAccess, const LEN: usize> SharedMmioPointer<'_, [ReadConstant<T>; LEN]> {
+    /// Performs an MMIO read of each element of the array in index order, and returns the values.
+    pub fn read_array(&self) -> [T; LEN] {
+        array::from_fn(|i| {
+            // SAFETY: `i` is always less than `LEN`.
+            unsafe { self.get_unchecked(i) }.read()
+        })
+    }
+}
+
+impl<T: AtomicAccess, const LEN: usize> SharedMmioPointer<'_, [ReadPureWrite<T>; LEN]> {
+    /// Performs an MMIO read of each element of the array in index order, and returns the values.
+    pub fn read_array(&self) -> [T; LEN] {
+        array::from_fn(|i| {
+            // SAFETY: `i` is always less than `LEN`.
+            unsafe { self.get_unchecked(i) }.read()
+        })
+    }
+}
+
+impl<T> SharedMmioPointer<'_, T> {
+    /// Returns the size in bytes of the MMIO registers.
+    ///
+    /// Together with [`addr`](Self::addr) this gives the region which the pointer covers, for
+    /// logging or for computing offsets within a larger window.
+    pub const fn byte_len(&self) -> usize {
+        size_of::<T>()
     }
 }
 
 impl<'a, T> SharedMmioPointer<'a, [T]> {
-    /// Splits a `UniqueMmioPointer` to a slice into an array of `UniqueMmioPointer`s, taking only
+    /// Splits a `SharedMmioPointer` to a slice into an array of `SharedMmioPointer`s, taking only
     /// the `chosen` indices.
     ///
-    /// Panics if `chosen` contains the same index more than once, or any index out of bounds.
-    pub fn split_some<const N: usize>(self, chosen: [usize; N]) -> [UniqueMmioPointer<'a, T>; N] {
-        for (i, a) in chosen.iter().enumerate() {
-            for (j, b) in chosen.iter().enumerate() {
-                assert!(i == j || a != b, "chosen array must not contain duplicates");
-            }
+    /// Panics if `chosen` contains any index out of bounds. See
+    /// [`try_split_some`](Self::try_split_some) for a non-panicking version.
+    pub fn split_some<const N: usize>(self, chosen: [usize; N]) -> [SharedMmioPointer<'a, T>; N] {
+        self.try_split_some(chosen).expect("index out of bounds")
+    }
+
+    /// Splits a `SharedMmioPointer` to a slice into an array of `SharedMmioPointer`s, taking only
+    /// the `chosen` indices, or returns `None` if `chosen` contains any index out of bounds.
+    pub fn try_split_some<const N: usize>(
+        self,
+        chosen: [usize; N],
+    ) -> Option<[SharedMmioPointer<'a, T>; N]> {
+        if chosen.iter().any(|&index| index >= self.len()) {
+            return None;
         }
-        chosen.map(|chosen_index| {
-            UniqueMmioPointer(SharedMmioPointer {
-                // SAFETY: self.regs is always unique and valid for MMIO access. We checked that
-                // `chosen` doesn't contain duplicates so the pointers we split it into don't
-                // overlap, so the same applies to each of them.
-                regs: NonNull::new(unsafe { &raw mut (*self.regs.as_ptr())[chosen_index] })
-                    .unwrap(),
-                phantom: PhantomData,
-            })
-        })
+        // SAFETY: We just checked that every index is in bounds.
+        Some(chosen.map(|index| unsafe { self.get_unchecked(index) }))
     }
 
     /// Returns a `SharedMmioPointer` to an element of this slice, or `None` if the index is out of
@@ -739,32 +1967,37 @@ impl<'a, T> SharedMmioPointer<'a, [T]> {
         if index >= self.len() {
             return None;
         }
-        // SAFETY: self.regs is always unique and valid for MMIO access.
-        let regs = NonNull::new(unsafe { &raw mut (*self.regs.as_ptr())[index] }).unwrap();
-        // SAFETY: We created regs from the raw slice in self.regs, so it must also be valid, unique
-        // and within the allocation of self.regs.
-        Some(unsafe { self.child(regs) })
+        // SAFETY: We just checked that `index` is in bounds.
+        Some(unsafe { self.get_unchecked(index) })
+    }
+
+    /// Returns a `SharedMmioPointer` to an element of this slice, without doing bounds checking.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be less than the length of the slice.
+    pub const unsafe fn get_unchecked(&self, index: usize) -> SharedMmioPointer<'a, T> {
+        // SAFETY: self.regs is always valid for MMIO access. The caller promised that `index` is
+        // in bounds.
+        let regs = unsafe { NonNull::new_unchecked(self.regs.as_ptr().cast::<T>().add(index)) };
+        // SAFETY: We created regs from the raw slice in self.regs, so it must also be valid and
+        // within the allocation of self.regs.
+        unsafe { self.child(regs) }.in_index(index)
     }
 
     /// Returns a `SharedMmioPointer` to a range of elements of this slice, or `None` if the range
     /// is out of bounds.
-    pub fn get_range(&self, range: Range<usize>) -> Option<SharedMmioPointer<'_, [T]>> {
+    pub fn get_range(&self, range: Range<usize>) -> Option<SharedMmioPointer<'a, [T]>> {
         if range.start > range.end || range.end > self.len() {
             return None;
         }
 
-        let regs_start = if !range.is_empty() {
-            // SAFETY: self.ptr_mut() is guaranteed to return a pointer that is valid for MMIO and
-            // unique, as promised by the caller of `UniqueMmioPointer::new`. range.start is within the
-            // boundaries of the slice.
-            unsafe { &raw mut (*self.regs.as_ptr())[range.start] }
-        } else {
-            // Based on the documentation of core::slice::from_raw_parts_mut, NonNull::dangling()
-            // should be used for creating zero-length slices.
-            NonNull::dangling().as_ptr()
-        };
+        // SAFETY: range.start is no greater than the length, so this is within the region or one past
+        // its end. An empty range still points into the original region, rather than being
+        // dangling, so that its address is meaningful.
+        let regs_start = unsafe { self.regs.cast::<T>().add(range.start) };
 
-        let regs = NonNull::new(slice_from_raw_parts_mut(regs_start, range.len())).unwrap();
+        let regs = NonNull::slice_from_raw_parts(regs_start, range.len());
 
         // SAFETY: We created regs from the valid start address of regs_start and `range` is within
         // the boundaries of self.regs, so it must also be valid, unique and within the allocation
@@ -773,10 +2006,31 @@ impl<'a, T> SharedMmioPointer<'a, [T]> {
     }
 
     /// Returns a new iterator of the items of the slice.
-    pub fn iter(&self) -> SharedMmioPointerIterator<'_, T> {
+    pub fn iter(&self) -> SharedMmioPointerIterator<'a, T> {
         SharedMmioPointerIterator { tail: *self }
     }
 
+    /// Returns a `SharedMmioPointer` to the first element of this slice, or `None` if it is empty.
+    pub const fn first(&self) -> Option<SharedMmioPointer<'a, T>> {
+        self.get(0)
+    }
+
+    /// Returns a `SharedMmioPointer` to the last element of this slice, or `None` if it is empty.
+    pub const fn last(&self) -> Option<SharedMmioPointer<'a, T>> {
+        match self.len().checked_sub(1) {
+            Some(index) => self.get(index),
+            None => None,
+        }
+    }
+
+    /// Splits this slice pointer into a pointer to its first element and a slice pointer to the
+    /// rest, or returns `None` if it is empty.
+    pub fn split_first(self) -> Option<(SharedMmioPointer<'a, T>, SharedMmioPointer<'a, [T]>)> {
+        let mut iter = self.into_iter();
+        let head = iter.next()?;
+        Some((head, iter.tail))
+    }
+
     /// Returns the length of the slice.
     pub const fn len(&self) -> usize {
         self.regs.len()
@@ -786,22 +2040,36 @@ impl<'a, T> SharedMmioPointer<'a, [T]> {
     pub const fn is_empty(&self) -> bool {
         self.regs.is_empty()
     }
+
+    /// Returns the size in bytes of the whole slice.
+    pub const fn byte_len(&self) -> usize {
+        // The slice is within a single allocation, so this can't overflow.
+        self.regs.len() * size_of::<T>()
+    }
 }
 
 impl<'a, T, const LEN: usize> SharedMmioPointer<'a, [T; LEN]> {
+    /// Returns the length of the array.
+    pub const fn len(&self) -> usize {
+        LEN
+    }
+
+    /// Returns whether the array is empty.
+    pub const fn is_empty(&self) -> bool {
+        LEN == 0
+    }
+
     /// Splits a `SharedMmioPointer` to an array into an array of `SharedMmioPointer`s.
     pub fn split(self) -> [SharedMmioPointer<'a, T>; LEN] {
-        array::from_fn(|i| SharedMmioPointer {
-            // SAFETY: self.regs is always unique and valid for MMIO access. We make sure the
-            // pointers we split it into don't overlap, so the same applies to each of them.
-            regs: NonNull::new(unsafe { &raw mut (*self.regs.as_ptr())[i] }).unwrap(),
-            phantom: PhantomData,
+        array::from_fn(|i| {
+            // SAFETY: `i` is always less than `LEN`.
+            unsafe { self.get_unchecked(i) }
         })
     }
 
     /// Converts this array pointer to an equivalent slice pointer.
     pub const fn as_slice(&self) -> SharedMmioPointer<'a, [T]> {
-        let regs = NonNull::new(self.regs.as_ptr()).unwrap();
+        let regs: NonNull<[T]> = self.regs;
         // SAFETY: We created regs from the raw array in self.regs, so it must also be valid, unique
         // and within the allocation of self.regs.
         unsafe { self.child(regs) }
@@ -813,31 +2081,37 @@ impl<'a, T, const LEN: usize> SharedMmioPointer<'a, [T; LEN]> {
         if index >= LEN {
             return None;
         }
-        // SAFETY: self.regs is always unique and valid for MMIO access.
-        let regs = NonNull::new(unsafe { &raw mut (*self.regs.as_ptr())[index] }).unwrap();
-        // SAFETY: We created regs from the raw array in self.regs, so it must also be valid, unique
-        // and within the allocation of self.regs.
-        Some(unsafe { self.child(regs) })
+        // SAFETY: We just checked that `index` is in bounds.
+        Some(unsafe { self.get_unchecked(index) })
+    }
+
+    /// Returns a `SharedMmioPointer` to an element of this array, without doing bounds checking.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be less than `LEN`.
+    pub const unsafe fn get_unchecked(&self, index: usize) -> SharedMmioPointer<'a, T> {
+        // SAFETY: self.regs is always valid for MMIO access. The caller promised that `index` is
+        // in bounds.
+        let regs = unsafe { NonNull::new_unchecked(self.regs.as_ptr().cast::<T>().add(index)) };
+        // SAFETY: We created regs from the raw array in self.regs, so it must also be valid and
+        // within the allocation of self.regs.
+        unsafe { self.child(regs) }.in_index(index)
     }
 
     /// Returns a `SharedMmioPointer` to a range of elements of this array, or `None` if the range
     /// is out of bounds.
-    pub fn get_range(&self, range: Range<usize>) -> Option<SharedMmioPointer<'_, [T]>> {
+    pub fn get_range(&self, range: Range<usize>) -> Option<SharedMmioPointer<'a, [T]>> {
         if range.start > range.end || range.end > LEN {
             return None;
         }
 
-        let regs_start = if !range.is_empty() {
-            // SAFETY: self.regs is always unique and valid for MMIO access. range.start is within the
-            // boundaries of the slice.
-            unsafe { &raw mut (*self.regs.as_ptr())[range.start] }
-        } else {
-            // Based on the documentation of core::slice::from_raw_parts_mut, NonNull::dangling()
-            // should be used for creating zero-length slices.
-            NonNull::dangling().as_ptr()
-        };
+        // SAFETY: range.start is no greater than the length, so this is within the region or one past
+        // its end. An empty range still points into the original region, rather than being
+        // dangling, so that its address is meaningful.
+        let regs_start = unsafe { self.regs.cast::<T>().add(range.start) };
 
-        let regs = NonNull::new(slice_from_raw_parts_mut(regs_start, range.len())).unwrap();
+        let regs = NonNull::slice_from_raw_parts(regs_start, range.len());
 
         // SAFETY: We created regs from the valid start address of regs_start and `range` is within
         // the boundaries of self.regs, so it must also be valid, unique and within the allocation
@@ -845,42 +2119,96 @@ impl<'a, T, const LEN: usize> SharedMmioPointer<'a, [T; LEN]> {
         Some(unsafe { self.child(regs) })
     }
 
-    /// Returns a new iterator of the items of the array.
-    pub fn iter(&self) -> SharedMmioPointerIterator<'_, T> {
-        SharedMmioPointerIterator {
-            tail: self.as_slice(),
+    /// Returns a new iterator of the items of the array.
+    pub fn iter(&self) -> SharedMmioPointerIterator<'a, T> {
+        SharedMmioPointerIterator {
+            tail: self.as_slice(),
+        }
+    }
+
+    /// Returns a `SharedMmioPointer` to the first element of this array, or `None` if it is empty.
+    pub const fn first(&self) -> Option<SharedMmioPointer<'a, T>> {
+        self.get(0)
+    }
+
+    /// Returns a `SharedMmioPointer` to the last element of this array, or `None` if it is empty.
+    pub const fn last(&self) -> Option<SharedMmioPointer<'a, T>> {
+        match LEN.checked_sub(1) {
+            Some(index) => self.get(index),
+            None => None,
+        }
+    }
+
+    /// Splits this array pointer into a pointer to its first element and a slice pointer to the
+    /// rest, or returns `None` if it is empty.
+    pub fn split_first(self) -> Option<(SharedMmioPointer<'a, T>, SharedMmioPointer<'a, [T]>)> {
+        self.as_slice().split_first()
+    }
+}
+
+impl<'a, T, const COLUMNS: usize, const ROWS: usize> SharedMmioPointer<'a, [[T; COLUMNS]; ROWS]> {
+    /// Returns a `SharedMmioPointer` to the element at the given row and column of this 2-D
+    /// array, or `None` if either index is out of bounds.
+    ///
+    /// This is equivalent to `get(row)?.get(column)`, but computes the address of the element
+    /// directly.
+    pub const fn get2(&self, row: usize, column: usize) -> Option<SharedMmioPointer<'a, T>> {
+        if row >= ROWS || column >= COLUMNS {
+            return None;
+        }
+        // SAFETY: self.regs is always valid for MMIO access. We just checked that `row` and
+        // `column` are in bounds, so the element is within the array.
+        let regs = unsafe {
+            NonNull::new_unchecked(self.regs.as_ptr().cast::<T>().add(row * COLUMNS + column))
+        };
+        // SAFETY: We created regs from the raw array in self.regs, so it must also be valid and
+        // within the allocation of self.regs.
+        Some(unsafe { self.child(regs) }.in_index(row).in_index(column))
+    }
+}
+
+impl<'a, T, const COLUMNS: usize> SharedMmioPointer<'a, [[T; COLUMNS]]> {
+    /// Returns a `SharedMmioPointer` to the element at the given row and column of this 2-D
+    /// slice, or `None` if either index is out of bounds.
+    ///
+    /// This is equivalent to `get(row)?.get(column)`, but computes the address of the element
+    /// directly.
+    pub const fn get2(&self, row: usize, column: usize) -> Option<SharedMmioPointer<'a, T>> {
+        if row >= self.len() || column >= COLUMNS {
+            return None;
         }
+        // SAFETY: self.regs is always valid for MMIO access. We just checked that `row` and
+        // `column` are in bounds, so the element is within the slice.
+        let regs = unsafe {
+            NonNull::new_unchecked(self.regs.as_ptr().cast::<T>().add(row * COLUMNS + column))
+        };
+        // SAFETY: We created regs from the raw slice in self.regs, so it must also be valid and
+        // within the allocation of self.regs.
+        Some(unsafe { self.child(regs) }.in_index(row).in_index(column))
     }
 }
 
 impl<'a, T, const LEN: usize> From<SharedMmioPointer<'a, [T; LEN]>> for SharedMmioPointer<'a, [T]> {
     fn from(value: SharedMmioPointer<'a, [T; LEN]>) -> Self {
-        let regs = NonNull::new(value.regs.as_ptr()).unwrap();
-        SharedMmioPointer {
-            regs,
-            phantom: PhantomData,
-        }
+        let regs: NonNull<[T]> = value.regs;
+        // SAFETY: regs points to the same MMIO region as value, so it must also be valid.
+        unsafe { value.child(regs) }
     }
 }
 
 impl<'a, T> From<SharedMmioPointer<'a, T>> for SharedMmioPointer<'a, [T; 1]> {
     fn from(value: SharedMmioPointer<'a, T>) -> Self {
-        let regs = NonNull::new(value.regs.as_ptr()).unwrap().cast();
-        SharedMmioPointer {
-            regs,
-            phantom: PhantomData,
-        }
+        let regs = value.regs.cast();
+        // SAFETY: regs points to the same MMIO region as value, so it must also be valid.
+        unsafe { value.child(regs) }
     }
 }
 
 impl<'a, T> From<SharedMmioPointer<'a, T>> for SharedMmioPointer<'a, [T]> {
     fn from(value: SharedMmioPointer<'a, T>) -> Self {
-        let array: *mut [T; 1] = value.regs.as_ptr().cast();
-        let regs = NonNull::new(array).unwrap();
-        SharedMmioPointer {
-            regs,
-            phantom: PhantomData,
-        }
+        let regs: NonNull<[T]> = value.regs.cast::<[T; 1]>();
+        // SAFETY: regs points to the same MMIO region as value, so it must also be valid.
+        unsafe { value.child(regs) }
     }
 }
 
@@ -918,21 +2246,17 @@ impl<'a, T> Iterator for SharedMmioPointerIterator<'a, T> {
 
     fn next(&mut self) -> Option<Self::Item> {
         if !self.tail.is_empty() {
-            // SAFETY: self.ptr_mut() is guaranteed to return a pointer that is valid for MMIO and
-            // unique, as promised by the caller of `UniqueMmioPointer::new` and the slice is
-            // not empty.
-            let regs_head =
-                NonNull::new(unsafe { &raw mut (*self.tail.regs.as_ptr())[0] }).unwrap();
+            // The slice is not empty, so this is a valid pointer to its first element.
+            let regs_head = self.tail.regs.cast::<T>();
 
             // SAFETY: regs_head is created from self.tail so it is valid and within the range of
             // the original pointer.
             let head = unsafe { self.tail.child(regs_head) };
 
-            let regs_tail = NonNull::new(slice_from_raw_parts_mut(
-                regs_head.as_ptr().wrapping_add(1),
-                self.tail.len() - 1,
-            ))
-            .unwrap();
+            // SAFETY: The slice is not empty, so one element past its start is still within it or
+            // one past its end.
+            let regs_tail_start = unsafe { regs_head.add(1) };
+            let regs_tail = NonNull::slice_from_raw_parts(regs_tail_start, self.tail.len() - 1);
 
             // SAFETY: We created regs from the raw array in self.regs, so it must also be valid,
             // unique and within the allocation of self.regs.
@@ -949,21 +2273,63 @@ impl<'a, T> Iterator for SharedMmioPointerIterator<'a, T> {
     }
 }
 
+impl<T> ExactSizeIterator for SharedMmioPointerIterator<'_, T> {}
+
+impl<T> FusedIterator for SharedMmioPointerIterator<'_, T> {}
+
 /// Gets a `UniqueMmioPointer` to a field of a type wrapped in a `UniqueMmioPointer`.
+///
+/// The type of the field may also be given, as `field!(regs, head: ReadWrite<u32>)`. This is checked
+/// at compile time, and documents the register's type in generic driver code where the field's type
+/// depends on a type parameter.
+///
+/// ```
+/// use safe_mmio::{UniqueMmioPointer, field, fields::ReadWrite};
+///
+/// trait QueueConfig {
+///     type Entry;
+/// }
+///
+/// #[repr(C)]
+/// struct Queue<C: QueueConfig> {
+///     head: ReadWrite<u32>,
+///     entry: C::Entry,
+/// }
+///
+/// fn push<C: QueueConfig<Entry = ReadWrite<u64>>>(
+///     queue: &mut UniqueMmioPointer<Queue<C>>,
+///     value: u64,
+/// ) {
+///     field!(*queue, entry: ReadWrite<u64>).write(value);
+///     field!(*queue, head: ReadWrite<u32>).modify(|head| head + 1);
+/// }
+/// # struct Config;
+/// # impl QueueConfig for Config {
+/// #     type Entry = ReadWrite<u64>;
+/// # }
+/// # let mut fake = Queue::<Config> { head: ReadWrite(0), entry: ReadWrite(0) };
+/// # push(&mut UniqueMmioPointer::from(&mut fake), 42);
+/// # assert_eq!(fake.head.0, 1);
+/// # assert_eq!(fake.entry.0, 42);
+/// ```
 #[macro_export]
 macro_rules! field {
+    ($mmio_pointer:expr, $field:ident: $type:ty) => {{
+        let child: $crate::UniqueMmioPointer<$type> = $crate::field!($mmio_pointer, $field);
+        child
+    }};
     ($mmio_pointer:expr, $field:ident) => {{
         _ = &mut $mmio_pointer;
 
         // SAFETY: ptr_mut is guaranteed to return a valid pointer for MMIO, so the pointer to the
-        // field must also be valid. UniqueMmioPointer::child gives it the same lifetime as the
-        // original pointer.
+        // field must also be valid, and in particular non-null. UniqueMmioPointer::child gives it
+        // the same lifetime as the original pointer.
         unsafe {
-            let child_pointer = core::ptr::NonNull::new(
+            let child_pointer = core::ptr::NonNull::new_unchecked(
                 &raw mut (*$crate::UniqueMmioPointer::ptr_mut(&mut $mmio_pointer)).$field,
-            )
-            .unwrap();
+            );
             $crate::UniqueMmioPointer::child(&mut $mmio_pointer, child_pointer)
+                .in_field(stringify!($mmio_pointer), stringify!($field))
         }
     }};
 }
@@ -982,12 +2348,79 @@ macro_rules! split_fields {
         let ret = (
             $(
                 // SAFETY: ptr_mut is guaranteed to return a valid pointer for MMIO, so the pointer
-                // to the field must also be valid. MmioPointer::child gives it the same lifetime as
-                // the original pointer, and the caller of `split_fields!` promised not to pass the
-                // same field more than once.
+                // to the field must also be valid, and in particular non-null. MmioPointer::child
+                // gives it the same lifetime as the original pointer, and the caller of
+                // `split_fields!` promised not to pass the same field more than once.
                 {
-                    let child_pointer = core::ptr::NonNull::new(&raw mut (*pointer).$field).unwrap();
-                    mmio_pointer.split_child(child_pointer)
+                    let child_pointer = core::ptr::NonNull::new_unchecked(&raw mut (*pointer).$field);
+                    mmio_pointer
+                        .split_child(child_pointer)
+                        .in_field(stringify!($mmio_pointer), stringify!($field))
+                }
+            ),+
+        );
+        ret
+    }};
+}
+
+/// Gets `UniqueMmioPointer`s to several fields of a type wrapped in a `UniqueMmioPointer`, checking
+/// at compile time that no field is passed more than once.
+///
+/// Unlike [`split_fields!`] this is safe to use, so it can be used in crates which forbid unsafe
+/// code.
+///
+/// # Example
+///
+/// ```
+/// use safe_mmio::{UniqueMmioPointer, fields::{ReadPure, ReadWrite}, split_fields_checked};
+///
+/// #[repr(C)]
+/// struct Registers {
+///     data: ReadWrite<u32>,
+///     status: ReadPure<u32>,
+/// }
+///
+/// # let mut fake = Registers { data: ReadWrite(0), status: ReadPure(0) };
+/// let regs: UniqueMmioPointer<Registers> = UniqueMmioPointer::from(&mut fake);
+/// let (mut data, status) = split_fields_checked!(regs, data, status);
+/// data.write(status.read());
+/// ```
+///
+/// Passing the same field twice fails to compile:
+///
+/// ```compile_fail,E0499
+/// # use safe_mmio::{UniqueMmioPointer, fields::ReadWrite, split_fields_checked};
+/// # #[repr(C)]
+/// # struct Registers {
+/// #     data: ReadWrite<u32>,
+/// # }
+/// # let mut fake = Registers { data: ReadWrite(0) };
+/// let regs: UniqueMmioPointer<Registers> = UniqueMmioPointer::from(&mut fake);
+/// let (a, b) = split_fields_checked!(regs, data, data);
+/// ```
+#[macro_export]
+macro_rules! split_fields_checked {
+    ($mmio_pointer:expr, $( $field:ident ),+) => {{
+        // Make sure $mmio_pointer is the right type, and take ownership of it.
+        let mut mmio_pointer: $crate::UniqueMmioPointer<_> = $mmio_pointer;
+        // Borrowing all the fields mutably at once fails to compile if any of them is repeated. The
+        // closure is never called.
+        $crate::check_distinct_fields(&mmio_pointer, |regs| {
+            _ = ($(&mut regs.$field,)+);
+        });
+        let pointer = mmio_pointer.ptr_mut();
+        #[allow(unused_unsafe, reason = "May be nested")]
+        let ret = (
+            $(
+                // SAFETY: ptr_mut is guaranteed to return a valid pointer for MMIO, so the pointer
+                // to the field must also be valid, and in particular non-null. MmioPointer::child
+                // gives it the same lifetime as the original pointer, and we checked above that
+                // the same field isn't passed more than once.
+                unsafe {
+                    let child_pointer = core::ptr::NonNull::new_unchecked(&raw mut (*pointer).$field);
+                    mmio_pointer
+                        .split_child(child_pointer)
+                        .in_field(stringify!($mmio_pointer), stringify!($field))
                 }
             ),+
         );
@@ -995,22 +2428,33 @@ macro_rules! split_fields {
     }};
 }
 
+/// Used by [`split_fields_checked!`] to check that it isn't passed the same field more than once.
+#[doc(hidden)]
+pub fn check_distinct_fields<T: ?Sized>(_: &UniqueMmioPointer<T>, _: impl FnOnce(&mut T)) {}
+
 /// Gets a `SharedMmioPointer` to a field of a type wrapped in a `SharedMmioPointer`.
+///
+/// As with [`field!`], the type of the field may also be given, as
+/// `field_shared!(regs, status: ReadPure<u32>)`.
 #[macro_export]
 macro_rules! field_shared {
+    ($mmio_pointer:expr, $field:ident: $type:ty) => {{
+        let child: $crate::SharedMmioPointer<$type> = $crate::field_shared!($mmio_pointer, $field);
+        child
+    }};
     ($mmio_pointer:expr, $field:ident) => {{
         _ = &$mmio_pointer;
 
         // SAFETY: ptr_mut is guaranteed to return a valid pointer for MMIO, so the pointer to the
-        // field must also be valid. MmioPointer::child gives it the same lifetime as the original
-        // pointer.
+        // field must also be valid, and in particular non-null. MmioPointer::child gives it the
+        // same lifetime as the original pointer.
         #[allow(unused_unsafe, reason = "May be nested")]
         unsafe {
-            let child_pointer = core::ptr::NonNull::new(
+            let child_pointer = core::ptr::NonNull::new_unchecked(
                 (&raw const (*$crate::SharedMmioPointer::ptr(&$mmio_pointer)).$field).cast_mut(),
-            )
-            .unwrap();
+            );
             $crate::SharedMmioPointer::child(&$mmio_pointer, child_pointer)
+                .in_field(stringify!($mmio_pointer), stringify!($field))
         }
     }};
 }
@@ -1019,6 +2463,20 @@ macro_rules! field_shared {
 mod tests {
     use super::*;
 
+    #[test]
+    #[allow(deprecated)]
+    fn owned_alias() {
+        fn write(regs: &mut OwnedMmioPointer<ReadWrite<u32>>) {
+            regs.write(42);
+        }
+
+        let mut fake = [ReadWrite(0)];
+        let mut regs = UniqueMmioPointer::from(&mut fake);
+        write(&mut regs.get(0).unwrap());
+        let mut owned: OwnedMmioPointer<[ReadWrite<u32>]> = regs.into();
+        assert_eq!(owned.get(0).unwrap().read(), 42);
+    }
+
     #[test]
     fn fields() {
         #[repr(C)]
@@ -1072,6 +2530,28 @@ mod tests {
         assert_eq!(shared_b.read(), 2);
     }
 
+    #[test]
+    fn typed_fields() {
+        #[repr(C)]
+        struct Foo<T> {
+            a: ReadPureWrite<u32>,
+            b: T,
+        }
+
+        fn increment<T>(foo: &mut UniqueMmioPointer<Foo<T>>) -> u32 {
+            field!(*foo, a: ReadPureWrite<u32>).modify(|a| a + 1);
+            field_shared!(*foo, a: ReadPureWrite<u32>).read()
+        }
+
+        let mut foo = Foo {
+            a: ReadPureWrite(1),
+            b: ReadPure(2u8),
+        };
+        let mut unique = UniqueMmioPointer::from(&mut foo);
+        assert_eq!(increment(&mut unique), 2);
+        assert_eq!(field_shared!(unique, b: ReadPure<u8>).read(), 2);
+    }
+
     #[test]
     fn shared_from_unique() {
         #[repr(C)]
@@ -1093,6 +2573,286 @@ mod tests {
         assert_eq!(shared_b.read(), 2);
     }
 
+    #[test]
+    fn flush_posted_writes() {
+        #[repr(C)]
+        struct Foo {
+            control: WriteOnly<u32>,
+            status: ReadPure<u32>,
+            id: ReadPureWrite<u32>,
+        }
+
+        let mut foo = Foo {
+            control: WriteOnly(0),
+            status: ReadPure(1),
+            id: ReadPureWrite(2),
+        };
+        let mut unique: UniqueMmioPointer<Foo> = UniqueMmioPointer::from(&mut foo);
+        field!(unique, control).write(42);
+        field_shared!(unique, status).flush_posted_writes();
+        field!(unique, control).write(43);
+        field!(unique, id).flush_posted_writes();
+        assert_eq!(foo.control.0, 43);
+    }
+
+    #[test]
+    fn split_some_slice() {
+        let mut foo = [ReadWrite(1), ReadWrite(2), ReadWrite(3)];
+        let slice: UniqueMmioPointer<[ReadWrite<u32>]> = UniqueMmioPointer::from(&mut foo[..]);
+        let [mut a, mut b] = slice.split_some([2, 0]);
+        assert_eq!(a.read(), 3);
+        assert_eq!(b.read(), 1);
+    }
+
+    #[test]
+    fn try_split_some() {
+        let mut foo = [ReadWrite(1), ReadWrite(2), ReadWrite(3)];
+        let mut array = UniqueMmioPointer::from(&mut foo);
+        assert!(array.reborrow().try_split_some([0, 3]).is_none());
+        assert!(array.reborrow().try_split_some([1, 0, 1]).is_none());
+        assert!(array.as_mut_slice().try_split_some([2, 2]).is_none());
+        let [mut a, mut b] = array.try_split_some([2, 0]).unwrap();
+        assert_eq!(a.read(), 3);
+        assert_eq!(b.read(), 1);
+
+        let shared = SharedMmioPointer::from(&foo[..]);
+        assert!(shared.try_split_some([1, 3]).is_none());
+        let [a, b] = shared.try_split_some([1, 1]).unwrap();
+        assert_eq!(a.ptr(), b.ptr());
+    }
+
+    #[test]
+    fn addr_and_byte_len() {
+        let mut fake = [ReadWrite(0u32), ReadWrite(0), ReadWrite(0)];
+        let mut window = UniqueMmioPointer::from(&mut fake);
+        let window_addr = window.addr();
+        assert_eq!(window.byte_len(), 12);
+        assert_eq!(window.as_mut_slice().byte_len(), 12);
+        let element = window.get(2).unwrap();
+        assert_eq!(element.addr() - window_addr, 8);
+        assert_eq!(element.byte_len(), 4);
+        assert_eq!(SharedMmioPointer::from(&fake[1..]).byte_len(), 8);
+    }
+
+    #[test]
+    fn try_new_with_len() {
+        let mut fake = [ReadWrite(0u32), ReadWrite(0)];
+        let bytes = NonNull::from(&mut fake).cast::<u8>();
+        // SAFETY: `fake` is valid for two `u32`s, and the checks reject anything larger.
+        unsafe {
+            assert_eq!(
+                UniqueMmioPointer::<[ReadWrite<u32>; 2]>::try_new_with_len(bytes, 7),
+                Err(RegionError::TooSmall {
+                    expected: 8,
+                    actual: 7
+                })
+            );
+            assert_eq!(
+                UniqueMmioPointer::<ReadWrite<u32>>::try_new_with_len(bytes.add(2), 4),
+                Err(RegionError::Misaligned { align: 4 })
+            );
+            let mut regs =
+                UniqueMmioPointer::<[ReadWrite<u32>; 2]>::try_new_with_len(bytes, 8).unwrap();
+            regs.get(1).unwrap().write(42);
+            let mut regs =
+                UniqueMmioPointer::<ReadWrite<u32>>::try_new_with_len(bytes, 4096).unwrap();
+            regs.write(1);
+        }
+        assert_eq!(fake[0].0, 1);
+        assert_eq!(fake[1].0, 42);
+    }
+
+    #[test]
+    fn try_from_exposed_addr() {
+        // SAFETY: A null address is always rejected.
+        assert!(unsafe { UniqueMmioPointer::<ReadWrite<u32>>::try_from_exposed_addr(0) }.is_none());
+    }
+
+    #[test]
+    fn shared_copy() {
+        let foo = [ReadPure(1), ReadPure(2), ReadPure(3)];
+        let shared: SharedMmioPointer<[ReadPure<u32>]> = SharedMmioPointer::from(&foo[..]);
+
+        let [a, b, c] = shared.split_some([1, 1, 0]);
+        assert_eq!(a.read(), 2);
+        assert_eq!(b.read(), 2);
+        assert_eq!(c.read(), 1);
+
+        // Pointers derived from a shared pointer live as long as the original, not the copy they
+        // were derived from.
+        let range = { shared }.get_range(1..3).unwrap();
+        let mut iter = { shared }.iter();
+        let read_first = move || shared.first().unwrap().read();
+        assert_eq!(range.get(0).unwrap().read(), 2);
+        assert_eq!(iter.next().unwrap().read(), 1);
+        assert_eq!(read_first(), 1);
+    }
+
+    #[test]
+    fn send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+
+        assert_send_sync::<UniqueMmioPointer<ReadWrite<u32>>>();
+        assert_send_sync::<SharedMmioPointer<ReadPure<u32>>>();
+        assert_send_sync::<UniqueMmioPointer<[ReadWrite<u32>]>>();
+    }
+
+    #[test]
+    fn read_array() {
+        let mut read_only = [ReadOnly(1), ReadOnly(2), ReadOnly(3)];
+        let mut read_write = [ReadWrite(4), ReadWrite(5)];
+        let pure = [ReadPure(6u8), ReadPure(7)];
+        let pure_write = [ReadPureWrite(8u64)];
+        let constant = [ReadConstant(9u16), ReadConstant(10)];
+
+        assert_eq!(
+            UniqueMmioPointer::from(&mut read_only).read_array(),
+            [1, 2, 3]
+        );
+        assert_eq!(
+            UniqueMmioPointer::from(&mut read_write).read_array(),
+            [4, 5]
+        );
+        assert_eq!(SharedMmioPointer::from(&pure).read_array(), [6, 7]);
+        assert_eq!(SharedMmioPointer::from(&pure_write).read_array(), [8]);
+        assert_eq!(SharedMmioPointer::from(&constant).read_array(), [9, 10]);
+    }
+
+    #[test]
+    fn read_constant() {
+        let mut fake = ReadConstant(0x1234_5678u32);
+        let regs = UniqueMmioPointer::from(&mut fake);
+        assert_eq!(regs.read(), 0x1234_5678);
+        assert_eq!(access::PureReadable::read(&regs), 0x1234_5678);
+        assert_eq!(SharedMmioPointer::from(regs).read(), 0x1234_5678);
+    }
+
+    #[test]
+    fn write_array() {
+        let mut write_only = [WriteOnly(0), WriteOnly(0)];
+        let mut read_write = [const { ReadWrite(0u8) }; 3];
+        let mut pure_write = [ReadPureWrite(0u64)];
+
+        UniqueMmioPointer::from(&mut write_only).write_array([1, 2]);
+        UniqueMmioPointer::from(&mut read_write).write_array([3, 4, 5]);
+        UniqueMmioPointer::from(&mut pure_write).write_array([6]);
+        assert_eq!(write_only.map(|register| register.0), [1, 2]);
+        assert_eq!(read_write.map(|register| register.0), [3, 4, 5]);
+        assert_eq!(pure_write[0].0, 6);
+    }
+
+    #[test]
+    fn write_slice() {
+        let mut registers = [const { ReadWrite(0u32) }; 3];
+        UniqueMmioPointer::from(&mut registers[..]).write_slice(&[7, 8, 9]);
+        assert_eq!(registers.map(|register| register.0), [7, 8, 9]);
+    }
+
+    #[test]
+    #[should_panic(expected = "source slice length does not match destination slice length")]
+    fn write_slice_wrong_length() {
+        let mut registers = [WriteOnly(0u32), WriteOnly(0)];
+        UniqueMmioPointer::from(&mut registers[..]).write_slice(&[1]);
+    }
+
+    #[test]
+    fn const_projection() {
+        #[repr(C)]
+        struct Foo {
+            a: ReadWrite<u32>,
+            b: ReadPure<u32>,
+        }
+
+        const fn project_a<'a>(
+            foo: UniqueMmioPointer<'a, Foo>,
+        ) -> UniqueMmioPointer<'a, ReadWrite<u32>> {
+            // SAFETY: Only one field is split.
+            unsafe { split_fields!(foo, a) }
+        }
+
+        const fn project_b<'a>(
+            foo: SharedMmioPointer<'a, Foo>,
+        ) -> SharedMmioPointer<'a, ReadPure<u32>> {
+            field_shared!(foo, b)
+        }
+
+        const fn borrow_a<'a>(
+            foo: &'a mut UniqueMmioPointer<Foo>,
+        ) -> UniqueMmioPointer<'a, ReadWrite<u32>> {
+            field!(*foo, a)
+        }
+
+        const B_OFFSET: usize = core::mem::offset_of!(Foo, b);
+        assert_eq!(B_OFFSET, 4);
+
+        let mut foo = Foo {
+            a: ReadWrite(1),
+            b: ReadPure(2),
+        };
+        assert_eq!(project_b(SharedMmioPointer::from_ref(&foo)).read(), 2);
+        let mut unique = UniqueMmioPointer::from_mut(&mut foo);
+        borrow_a(&mut unique).write(41);
+        project_a(unique).write(42);
+        assert_eq!(foo.a.0, 42);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "Uses exposed provenance")]
+    fn exposed_addr() {
+        let mut foo = ReadWrite(1u32);
+        let addr = UniqueMmioPointer::from(&mut foo).expose_provenance();
+        // SAFETY: `addr` is the exposed address of `foo`, which isn't accessed any other way while
+        // `unique` exists.
+        let mut unique = unsafe { UniqueMmioPointer::<ReadWrite<u32>>::from_exposed_addr(addr) };
+        assert_eq!(unique.addr(), addr);
+        unique.write(42);
+        assert_eq!(foo.0, 42);
+    }
+
+    #[test]
+    fn split_64_bit() {
+        let mut low_first = ReadWrite64LoHi(0x1234_5678_9abc_def0);
+        let mut high_first = ReadWrite64HiLo(0x0fed_cba9_8765_4321);
+        {
+            let mut low_first = UniqueMmioPointer::from(&mut low_first);
+            assert_eq!(low_first.read(), 0x1234_5678_9abc_def0);
+            low_first.write(0x1111_2222_3333_4444);
+            let mut high_first = UniqueMmioPointer::from(&mut high_first);
+            assert_eq!(high_first.read(), 0x0fed_cba9_8765_4321);
+            high_first.write(0x5555_6666_7777_8888);
+        }
+        assert_eq!(low_first.0, 0x1111_2222_3333_4444);
+        assert_eq!(high_first.0, 0x5555_6666_7777_8888);
+    }
+
+    #[test]
+    fn commands() {
+        #[derive(Clone, Copy, Debug, Eq, Immutable, IntoBytes, PartialEq, TryFromBytes)]
+        #[repr(u32)]
+        enum Doorbell {
+            Start = 1,
+            Stop = 2,
+        }
+
+        let mut command = Command(Doorbell::Stop);
+        UniqueMmioPointer::from(&mut command).issue(Doorbell::Start);
+        assert_eq!(command.0, Doorbell::Start);
+
+        let mut command = ReadPureCommand(Doorbell::Stop);
+        {
+            let mut command = UniqueMmioPointer::from(&mut command);
+            assert_eq!(command.read(), Some(Doorbell::Stop));
+            command.issue(Doorbell::Start);
+            assert_eq!(command.read(), Some(Doorbell::Start));
+        }
+        // Simulate the device putting an invalid value in the register.
+        let raw = ReadPure(3u32);
+        let raw = SharedMmioPointer::from(&raw);
+        // SAFETY: `ReadPureCommand<Doorbell>` is the same size as `ReadPure<u32>`.
+        let command = unsafe { raw.child(raw.regs.cast::<ReadPureCommand<Doorbell>>()) };
+        assert_eq!(command.read(), None);
+    }
+
     #[test]
     fn restricted_fields() {
         #[repr(C)]
@@ -1193,6 +2953,34 @@ mod tests {
         assert_eq!(second.read(), 2);
     }
 
+    #[test]
+    fn get_unchecked() {
+        let mut regs = [ReadWrite(1), ReadWrite(2), ReadWrite(3)];
+        let mut owned = UniqueMmioPointer::from(&mut regs);
+
+        // SAFETY: All indices are less than the length of the array.
+        unsafe {
+            assert_eq!(owned.get_unchecked(0).read(), 1);
+            owned.get_unchecked(2).write(42);
+            assert_eq!(owned.as_mut_slice().get_unchecked(1).read(), 2);
+            assert_eq!(owned.as_mut_slice().get_unchecked(2).read(), 42);
+            assert_eq!(owned.get_unchecked(1).ptr(), owned.get(1).unwrap().ptr());
+        }
+    }
+
+    #[test]
+    fn shared_get_unchecked() {
+        let regs = [ReadPure(1), ReadPure(2), ReadPure(3)];
+        let shared = SharedMmioPointer::from(&regs);
+
+        // SAFETY: All indices are less than the length of the array.
+        unsafe {
+            assert_eq!(shared.get_unchecked(0).read(), 1);
+            assert_eq!(shared.as_slice().get_unchecked(2).read(), 3);
+            assert_eq!(shared.get_unchecked(1), shared.get(1).unwrap());
+        }
+    }
+
     #[test]
     fn array_field() {
         #[repr(C)]
@@ -1306,6 +3094,32 @@ mod tests {
         assert_eq!(foo.subregs.field.0, 42);
     }
 
+    #[test]
+    fn offset_as() {
+        let mut fake = [1u16, 2, 3, 4];
+        let mut regs = UniqueMmioPointer::from(&mut fake);
+        // SAFETY: These are just fakes.
+        unsafe {
+            assert_eq!(regs.offset_as::<u16>(6).unwrap().read_unsafe(), 4);
+            assert_eq!(regs.offset_as::<u16>(8), None);
+            assert_eq!(regs.offset_as::<u16>(3), None);
+            assert_eq!(regs.offset_as::<[u16; 4]>(usize::MAX), None);
+        }
+
+        let mut regs = UniqueMmioPointer::from(fake.as_mut_slice());
+        // SAFETY: These are just fakes.
+        unsafe {
+            assert_eq!(regs.offset_as::<[u16; 2]>(4).unwrap().read_unsafe(), [3, 4]);
+            assert_eq!(regs.offset_as::<[u16; 2]>(6), None);
+        }
+        let mut regs = regs.get_range(0..1).unwrap();
+        // SAFETY: These are just fakes.
+        unsafe {
+            assert_eq!(regs.offset_as::<u16>(0).unwrap().read_unsafe(), 1);
+            assert_eq!(regs.offset_as::<u16>(2), None);
+        }
+    }
+
     #[test]
     fn get_range_slice() {
         let mut regs = [ReadWrite(1), ReadWrite(2), ReadWrite(3)];
@@ -1497,6 +3311,244 @@ mod tests {
         assert_eq!(regs[2].0, 6);
     }
 
+    #[test]
+    fn first_last() {
+        let mut regs = [ReadWrite(1), ReadWrite(2), ReadWrite(3)];
+        let mut ptr = UniqueMmioPointer::from(&mut regs);
+
+        assert_eq!(ptr.first().unwrap().read(), 1);
+        assert_eq!(ptr.last().unwrap().read(), 3);
+        assert_eq!(ptr.as_mut_slice().first().unwrap().read(), 1);
+        assert_eq!(ptr.as_mut_slice().last().unwrap().read(), 3);
+
+        let mut empty = ptr.get_range(1..1).unwrap();
+        assert_eq!(empty.first(), None);
+        assert_eq!(empty.last(), None);
+
+        let mut empty_array: [ReadWrite<u32>; 0] = [];
+        let mut empty_array = UniqueMmioPointer::from(&mut empty_array);
+        assert_eq!(empty_array.first(), None);
+        assert_eq!(empty_array.last(), None);
+    }
+
+    #[test]
+    fn shared_first_last() {
+        let regs = [ReadPure(1), ReadPure(2), ReadPure(3)];
+        let ptr = SharedMmioPointer::from(&regs);
+
+        assert_eq!(ptr.first().unwrap().read(), 1);
+        assert_eq!(ptr.last().unwrap().read(), 3);
+        assert_eq!(ptr.as_slice().first().unwrap().read(), 1);
+        assert_eq!(ptr.as_slice().last().unwrap().read(), 3);
+        assert_eq!(ptr.get_range(0..0).unwrap().first(), None);
+        assert_eq!(ptr.get_range(3..3).unwrap().last(), None);
+    }
+
+    #[test]
+    fn split_first() {
+        let mut regs = [ReadWrite(1), ReadWrite(2), ReadWrite(3)];
+
+        {
+            let ptr = UniqueMmioPointer::from(&mut regs);
+            let (mut head, rest) = ptr.split_first().unwrap();
+            head.write(4);
+            assert_eq!(rest.len(), 2);
+
+            let (mut head, rest) = rest.split_first().unwrap();
+            head.write(5);
+            let (mut head, rest) = rest.split_first().unwrap();
+            head.write(6);
+            assert!(rest.is_empty());
+            assert!(rest.split_first().is_none());
+        }
+
+        assert_eq!(regs[0].0, 4);
+        assert_eq!(regs[1].0, 5);
+        assert_eq!(regs[2].0, 6);
+    }
+
+    #[test]
+    fn shared_split_first() {
+        let regs = [ReadPure(1), ReadPure(2), ReadPure(3)];
+        let ptr = SharedMmioPointer::from(&regs);
+
+        let (head, rest) = ptr.split_first().unwrap();
+        assert_eq!(head.read(), 1);
+        assert_eq!(rest.len(), 2);
+        assert_eq!(rest.get(0).unwrap().read(), 2);
+        assert!(ptr.get_range(3..3).unwrap().split_first().is_none());
+    }
+
+    #[test]
+    fn chunks_exact() {
+        let mut regs = [const { ReadWrite(0u8) }; 7];
+
+        {
+            let mut ptr = UniqueMmioPointer::from(&mut regs);
+            let mut slice = ptr.as_mut_slice();
+            let mut chunks = slice.chunks_exact(3);
+            assert_eq!(chunks.size_hint(), (2, Some(2)));
+
+            for (i, mut chunk) in (&mut chunks).enumerate() {
+                assert_eq!(chunk.len(), 3);
+                for mut element in chunk.iter() {
+                    element.write(i as u8 + 1);
+                }
+            }
+
+            let mut remainder = chunks.into_remainder();
+            assert_eq!(remainder.len(), 1);
+            remainder.get(0).unwrap().write(42);
+
+            assert_eq!(slice.chunks_exact(7).count(), 1);
+            assert_eq!(slice.chunks_exact(8).count(), 0);
+            assert_eq!(slice.chunks_exact(8).into_remainder().len(), 7);
+        }
+
+        assert_eq!(regs.map(|r| r.0), [1, 1, 1, 2, 2, 2, 42]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn chunks_exact_zero() {
+        let mut regs = [ReadWrite(0u8)];
+        let mut ptr = UniqueMmioPointer::from(regs.as_mut_slice());
+        ptr.chunks_exact(0);
+    }
+
+    #[test]
+    fn empty_array() {
+        let mut regs: [ReadWrite<u32>; 0] = [];
+        let mut ptr = UniqueMmioPointer::from(&mut regs);
+        assert_eq!(ptr.len(), 0);
+        assert!(ptr.is_empty());
+        assert!(ptr.get(0).is_none());
+        assert!(ptr.first().is_none());
+        assert!(ptr.last().is_none());
+        assert_eq!(ptr.iter().count(), 0);
+        let range = ptr.get_range(0..0).unwrap();
+        assert!(range.is_empty());
+        assert_eq!(range.ptr().cast(), ptr.ptr());
+        assert!(ptr.get_range(0..1).is_none());
+        assert_eq!(ptr.as_mut_slice().chunks_exact(2).count(), 0);
+        assert!(ptr.as_mut_slice().split_first().is_none());
+        let [] = ptr.reborrow().split_some([]);
+        let [] = ptr.reborrow().split();
+        assert!(ptr.take(0).is_none());
+
+        let shared = SharedMmioPointer::from(&regs);
+        assert!(shared.is_empty());
+        assert!(shared.get(0).is_none());
+        assert!(shared.last().is_none());
+        assert_eq!(shared.iter().count(), 0);
+        let [] = shared.split();
+        let [] = shared.as_slice().split_some([]);
+    }
+
+    #[test]
+    fn empty_slice() {
+        let mut regs = [ReadWrite(1u32), ReadWrite(2)];
+        let mut ptr = UniqueMmioPointer::from(regs.as_mut_slice());
+        let end = ptr.ptr().cast::<ReadWrite<u32>>().wrapping_add(2);
+        let mut empty = ptr.get_range(2..2).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(empty.ptr().cast(), end);
+        assert!(empty.get(0).is_none());
+        assert!(empty.first().is_none());
+        assert!(empty.get_range(0..0).is_some());
+        assert_eq!(empty.iter().count(), 0);
+        assert_eq!(empty.chunks_exact(1).into_remainder().len(), 0);
+        assert!(empty.split_first().is_none());
+
+        let (_, rest) = ptr.reborrow().split_first().unwrap();
+        let (_, rest) = rest.split_first().unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(rest.ptr().cast(), end);
+    }
+
+    #[test]
+    fn zero_sized_elements() {
+        let mut regs: [[ReadWrite<u32>; 0]; 3] = [[], [], []];
+        let mut ptr = UniqueMmioPointer::from(&mut regs);
+        let start = ptr.ptr().cast::<[ReadWrite<u32>; 0]>();
+        assert_eq!(ptr.len(), 3);
+        assert_eq!(ptr.get(2).unwrap().ptr(), start);
+        assert!(ptr.get(3).is_none());
+        assert_eq!(ptr.get_range(1..3).unwrap().len(), 2);
+        assert_eq!(ptr.iter().count(), 3);
+        let mut slice = ptr.as_mut_slice();
+        let mut chunks = slice.chunks_exact(2);
+        assert_eq!(chunks.next().unwrap().len(), 2);
+        assert!(chunks.next().is_none());
+        assert_eq!(chunks.into_remainder().len(), 1);
+        let [a, b] = ptr.reborrow().split_some([0, 2]);
+        assert_eq!(a.ptr(), b.ptr());
+        let split = ptr.split();
+        assert!(split.iter().all(|element| element.ptr() == start));
+
+        let mut device = ();
+        let mut ptr = UniqueMmioPointer::from(&mut device);
+        assert_eq!(ptr.reborrow().ptr(), ptr.ptr());
+        let slice = UniqueMmioPointer::<[()]>::from(ptr);
+        assert_eq!(slice.len(), 1);
+        assert_eq!(slice.into_iter().count(), 1);
+    }
+
+    #[test]
+    fn into_iter_array() {
+        struct Channel<'a> {
+            regs: UniqueMmioPointer<'a, ReadWrite<u8>>,
+        }
+
+        let mut regs = [ReadWrite(0), ReadWrite(1), ReadWrite(2)];
+        let (mut first, mut rest) = {
+            let mut iter = UniqueMmioPointer::from(&mut regs).into_iter();
+            assert_eq!(iter.len(), 3);
+            let first = Channel {
+                regs: iter.next().unwrap(),
+            };
+            let rest: [Channel; 2] = core::array::from_fn(|_| Channel {
+                regs: iter.next().unwrap(),
+            });
+            assert_eq!(iter.len(), 0);
+            assert!(iter.next().is_none());
+            (first, rest)
+        };
+        // The channels outlive the iterator.
+        first.regs.write(10);
+        rest[1].regs.write(12);
+        assert_eq!(rest[0].regs.read(), 1);
+        assert_eq!(regs[0].0, 10);
+        assert_eq!(regs[2].0, 12);
+    }
+
+    #[test]
+    fn get2() {
+        let mut regs = [
+            [ReadWrite(0u16), ReadWrite(1), ReadWrite(2)],
+            [ReadWrite(3), ReadWrite(4), ReadWrite(5)],
+        ];
+        let mut matrix = UniqueMmioPointer::from(&mut regs);
+        assert_eq!(matrix.get2(0, 2).unwrap().read(), 2);
+        assert_eq!(matrix.get2(1, 0).unwrap().read(), 3);
+        assert_eq!(
+            matrix.get2(1, 1).unwrap().ptr(),
+            matrix.get(1).unwrap().get(1).unwrap().ptr()
+        );
+        assert!(matrix.get2(2, 0).is_none());
+        assert!(matrix.get2(0, 3).is_none());
+        let mut slice = matrix.as_mut_slice();
+        slice.get2(1, 2).unwrap().write(42);
+        assert!(slice.get2(2, 2).is_none());
+
+        let regs = [[ReadPure(0u16), ReadPure(1)], [ReadPure(2), ReadPure(3)]];
+        let matrix = SharedMmioPointer::from(&regs);
+        assert_eq!(matrix.get2(1, 0).unwrap().read(), 2);
+        assert_eq!(matrix.as_slice().get2(1, 1).unwrap().read(), 3);
+        assert!(matrix.get2(0, 2).is_none());
+        assert!(matrix.as_slice().get2(2, 0).is_none());
+    }
+
     #[test]
     fn shared_iterator_slice() {
         let regs = [ReadPureWrite(1), ReadPureWrite(2), ReadPureWrite(3)];
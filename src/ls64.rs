@@ -0,0 +1,319 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Single-copy atomic 64-byte accesses, enabled by the `ls64` feature.
+//!
+//! Some devices, such as accelerator doorbells and work queues, require a 64-byte command to be
+//! written as a single bus transaction, so that it can't be torn or interleaved with writes from
+//! another CPU. On CPUs implementing the Armv8.7 `FEAT_LS64` extension the `ld64b` and `st64b`
+//! instructions do this, and [`read_64b`](UniqueMmioPointer::read_64b) and
+//! [`write_64b`](UniqueMmioPointer::write_64b) use them for 64-byte aligned `[u64; 8]` fields.
+//!
+//! Whether the CPU supports `FEAT_LS64` is checked at runtime by reading `ID_AA64ISAR1_EL1`, which
+//! must be accessible at the current exception level. This is the case at EL1 and above, and at EL0
+//! on Linux, which emulates reads of the ID registers. On other architectures, or when using a
+//! custom MMIO backend, Miri or the verification backend, the accesses always fail with
+//! [`Ls64Error::Unsupported`], as they can't be emulated with narrower accesses.
+//!
+//! The memory type of the mapping must also support 64-byte accesses, which is generally only the
+//! case for Device memory, and the device must accept them. Otherwise the access may cause a data
+//! abort.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_mmio::{UniqueMmioPointer, fields::WriteOnly, ls64::Ls64Error};
+//!
+//! #[repr(C, align(64))]
+//! struct Doorbell {
+//!     command: WriteOnly<[u64; 8]>,
+//! }
+//!
+//! # let mut fake = Doorbell { command: WriteOnly([0; 8]) };
+//! let mut doorbell: UniqueMmioPointer<Doorbell> = UniqueMmioPointer::from(&mut fake);
+//! let mut command = safe_mmio::field!(doorbell, command);
+//! match command.write_64b([1, 2, 3, 4, 5, 6, 7, 8]) {
+//!     Ok(()) => {}
+//!     Err(Ls64Error::Unsupported) => {
+//!         // Fall back to some other way of submitting the command.
+//!     }
+//!     Err(e) => panic!("{e}"),
+//! }
+//! ```
+
+use crate::{
+    UniqueMmioPointer,
+    fields::{ReadOnly, ReadPure, ReadPureWrite, ReadWrite, WriteOnly},
+};
+use core::fmt::{self, Display, Formatter};
+use zerocopy::IntoBytes;
+
+/// The size and required alignment in bytes of a 64-byte access.
+const SIZE: usize = 64;
+
+/// An error performing a 64-byte access.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Ls64Error {
+    /// The field isn't aligned to 64 bytes.
+    Misaligned,
+    /// The CPU doesn't implement `FEAT_LS64`, or the MMIO backend doesn't support it.
+    Unsupported,
+}
+
+impl Display for Ls64Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Misaligned => write!(f, "64-byte access to a field not aligned to 64 bytes"),
+            Self::Unsupported => write!(f, "64-byte accesses aren't supported by this CPU"),
+        }
+    }
+}
+
+impl core::error::Error for Ls64Error {}
+
+/// Returns whether the CPU supports 64-byte accesses with `ld64b` and `st64b`.
+///
+/// The result is cached after the first call.
+pub fn is_supported() -> bool {
+    arch::is_supported()
+}
+
+macro_rules! impl_read_64b {
+    ($wrapper:ident) => {
+        impl UniqueMmioPointer<'_, $wrapper<[u64; 8]>> {
+            /// Reads all 64 bytes of the field with a single-copy atomic `ld64b` access.
+            ///
+            /// Returns an error without accessing the field if it isn't aligned to 64 bytes or the
+            /// CPU doesn't support `FEAT_LS64`.
+            pub fn read_64b(&mut self) -> Result<[u64; 8], Ls64Error> {
+                check_aligned(self.addr())?;
+                // SAFETY: `self` is a valid and unique pointer to MMIO address space which is safe
+                // to read from, as `$wrapper` allows reads, and it is aligned to 64 bytes.
+                let value = unsafe { arch::read_64b(self.ptr().cast())? };
+                self.record_read(value.as_bytes());
+                Ok(value)
+            }
+        }
+    };
+}
+
+macro_rules! impl_write_64b {
+    ($wrapper:ident) => {
+        impl UniqueMmioPointer<'_, $wrapper<[u64; 8]>> {
+            /// Writes all 64 bytes of the field with a single-copy atomic `st64b` access.
+            ///
+            /// Returns an error without accessing the field if it isn't aligned to 64 bytes or the
+            /// CPU doesn't support `FEAT_LS64`.
+            pub fn write_64b(&mut self, value: [u64; 8]) -> Result<(), Ls64Error> {
+                check_aligned(self.addr())?;
+                // SAFETY: `self` is a valid and unique pointer to MMIO address space which is safe
+                // to write any value to, as `$wrapper` allows writes, and it is aligned to 64 bytes.
+                unsafe { arch::write_64b(self.ptr_mut().cast(), value)? };
+                self.record_write(value.as_bytes());
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_read_64b!(ReadOnly);
+impl_read_64b!(ReadPure);
+impl_read_64b!(ReadPureWrite);
+impl_read_64b!(ReadWrite);
+impl_write_64b!(WriteOnly);
+impl_write_64b!(ReadPureWrite);
+impl_write_64b!(ReadWrite);
+
+/// Returns an error if `address` isn't aligned for a 64-byte access.
+fn check_aligned(address: usize) -> Result<(), Ls64Error> {
+    if address % SIZE == 0 {
+        Ok(())
+    } else {
+        Err(Ls64Error::Misaligned)
+    }
+}
+
+#[cfg(all(
+    target_arch = "aarch64",
+    not(miri),
+    not(feature = "custom-mmio"),
+    not(feature = "verification")
+))]
+mod arch {
+    use super::Ls64Error;
+    use core::{
+        arch::asm,
+        sync::atomic::{AtomicU8, Ordering},
+    };
+
+    const UNKNOWN: u8 = 0;
+    const UNSUPPORTED: u8 = 1;
+    const SUPPORTED: u8 = 2;
+
+    /// Whether the CPU supports `FEAT_LS64`, once it has been checked.
+    static SUPPORT: AtomicU8 = AtomicU8::new(UNKNOWN);
+
+    pub fn is_supported() -> bool {
+        match SUPPORT.load(Ordering::Relaxed) {
+            UNKNOWN => {
+                let isar1: u64;
+                // SAFETY: Reading an ID register has no side-effects. The module documentation
+                // describes when it is accessible.
+                unsafe {
+                    asm!(
+                        "mrs {isar1}, id_aa64isar1_el1",
+                        isar1 = out(reg) isar1,
+                        options(nomem, nostack, preserves_flags),
+                    );
+                }
+                // Any non-zero value of the LS64 field includes `ld64b` and `st64b`.
+                let supported = (isar1 >> 60) & 0xf != 0;
+                SUPPORT.store(
+                    if supported { SUPPORTED } else { UNSUPPORTED },
+                    Ordering::Relaxed,
+                );
+                supported
+            }
+            state => state == SUPPORTED,
+        }
+    }
+
+    /// Reads 64 bytes from `ptr` with `ld64b`, if the CPU supports it.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be aligned to 64 bytes and valid for an MMIO read of 64 bytes.
+    #[inline(always)]
+    pub unsafe fn read_64b(ptr: *const [u64; 8]) -> Result<[u64; 8], Ls64Error> {
+        if !is_supported() {
+            return Err(Ls64Error::Unsupported);
+        }
+        let value: [u64; 8];
+        // SAFETY: Our caller guarantees that `ptr` is aligned and valid, and we checked that the
+        // CPU supports `ld64b`. It loads into eight consecutive registers starting from an
+        // even-numbered one, so they are specified explicitly.
+        unsafe {
+            let (x0, x1, x2, x3, x4, x5, x6, x7);
+            asm!(
+                ".arch_extension ls64",
+                "ld64b x0, [{ptr}]",
+                ptr = in(reg) ptr,
+                out("x0") x0,
+                out("x1") x1,
+                out("x2") x2,
+                out("x3") x3,
+                out("x4") x4,
+                out("x5") x5,
+                out("x6") x6,
+                out("x7") x7,
+                options(nostack, preserves_flags),
+            );
+            value = [x0, x1, x2, x3, x4, x5, x6, x7];
+        }
+        Ok(value)
+    }
+
+    /// Writes `value` to `ptr` with `st64b`, if the CPU supports it.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be aligned to 64 bytes and valid for an MMIO write of 64 bytes.
+    #[inline(always)]
+    pub unsafe fn write_64b(ptr: *mut [u64; 8], value: [u64; 8]) -> Result<(), Ls64Error> {
+        if !is_supported() {
+            return Err(Ls64Error::Unsupported);
+        }
+        // SAFETY: Our caller guarantees that `ptr` is aligned and valid, and we checked that the
+        // CPU supports `st64b`. It stores from eight consecutive registers starting from an
+        // even-numbered one, so they are specified explicitly.
+        unsafe {
+            asm!(
+                ".arch_extension ls64",
+                "st64b x0, [{ptr}]",
+                ptr = in(reg) ptr,
+                in("x0") value[0],
+                in("x1") value[1],
+                in("x2") value[2],
+                in("x3") value[3],
+                in("x4") value[4],
+                in("x5") value[5],
+                in("x6") value[6],
+                in("x7") value[7],
+                options(nostack, preserves_flags),
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(all(
+    target_arch = "aarch64",
+    not(miri),
+    not(feature = "custom-mmio"),
+    not(feature = "verification")
+)))]
+mod arch {
+    use super::Ls64Error;
+
+    pub fn is_supported() -> bool {
+        false
+    }
+
+    /// Always fails, as 64-byte accesses are only supported by the aarch64 backend.
+    ///
+    /// # Safety
+    ///
+    /// Always safe, but unsafe to match the aarch64 version.
+    pub unsafe fn read_64b(_ptr: *const [u64; 8]) -> Result<[u64; 8], Ls64Error> {
+        Err(Ls64Error::Unsupported)
+    }
+
+    /// Always fails, as 64-byte accesses are only supported by the aarch64 backend.
+    ///
+    /// # Safety
+    ///
+    /// Always safe, but unsafe to match the aarch64 version.
+    pub unsafe fn write_64b(_ptr: *mut [u64; 8], _value: [u64; 8]) -> Result<(), Ls64Error> {
+        Err(Ls64Error::Unsupported)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[repr(C, align(64))]
+    struct Aligned<T>(T);
+
+    #[test]
+    fn misaligned() {
+        let mut fake = Aligned([const { ReadWrite(0u64) }; 16]);
+        let mut regs = UniqueMmioPointer::from(&mut fake.0);
+        // SAFETY: The 64 bytes starting at the second element are within the array.
+        let field_ptr = unsafe {
+            regs.ptr_nonnull()
+                .cast::<ReadWrite<u64>>()
+                .add(1)
+                .cast::<ReadWrite<[u64; 8]>>()
+        };
+        // SAFETY: `field_ptr` is within `regs`, and properly aligned for `[u64; 8]`.
+        let mut field = unsafe { regs.child(field_ptr) };
+        assert_eq!(field.read_64b(), Err(Ls64Error::Misaligned));
+        assert_eq!(field.write_64b([1; 8]), Err(Ls64Error::Misaligned));
+    }
+
+    /// Ordinary memory doesn't support 64-byte accesses, so this only checks the error when the
+    /// CPU or backend doesn't support them.
+    #[test]
+    fn unsupported() {
+        if is_supported() {
+            return;
+        }
+        let mut fake = Aligned(ReadWrite([0u64; 8]));
+        let mut regs = UniqueMmioPointer::from(&mut fake.0);
+        assert_eq!(regs.read_64b(), Err(Ls64Error::Unsupported));
+        assert_eq!(regs.write_64b([1; 8]), Err(Ls64Error::Unsupported));
+        assert_eq!(fake.0.0, [0; 8]);
+    }
+}
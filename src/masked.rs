@@ -0,0 +1,248 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Helpers for [`Masked`] registers, which have reserved bits which must not be changed.
+//!
+//! Many registers have reserved bits which must be written with their current value, or with
+//! zero, whatever value the driver computed. Wrapping such a register in `Masked<W, MASK, R>`
+//! makes `write` and `modify` only take the bits of the new value in `MASK`, and fill in the
+//! reserved bits according to the policy `R`: [`PreserveReserved`] by reading the register first,
+//! or [`ZeroReserved`] with zeroes. Bits of `MASK` beyond the width of the register are ignored.
+//!
+//! With the `readback` feature, writes to a `Masked<ReadPureWrite<T>, MASK, R>` register only
+//! check the bits in `MASK`. A [`register_map!`](macro@crate::register_map) table records the mask
+//! and policy of `Masked` fields, so accesses by offset apply them too.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_mmio::{
+//!     UniqueMmioPointer, field,
+//!     fields::{Masked, ReadPureWrite, ReadWrite, ZeroReserved},
+//! };
+//!
+//! #[repr(C)]
+//! pub struct TimerRegisters {
+//!     /// Bits 4 to 6 are reserved, and must be preserved.
+//!     control: Masked<ReadPureWrite<u32>, 0xff8f>,
+//!     /// Only the low 6 bits are defined, and the rest must be written as zero.
+//!     levels: Masked<ReadWrite<u32>, 0x3f, ZeroReserved>,
+//! }
+//!
+//! let mut regs: UniqueMmioPointer<TimerRegisters>;
+//! # let mut fake = TimerRegisters {
+//! #     control: Masked(ReadPureWrite(0x30), Default::default()),
+//! #     levels: Masked(ReadWrite(0), Default::default()),
+//! # };
+//! # regs = UniqueMmioPointer::from(&mut fake);
+//! field!(regs, control).modify(|control| control | 0xffff);
+//! assert_eq!(field!(regs, control).read(), 0xffbf);
+//! field!(regs, levels).write(0xff);
+//! assert_eq!(field!(regs, levels).read(), 0x3f);
+//! ```
+
+use crate::{
+    SharedMmioPointer, UniqueMmioPointer,
+    access::AtomicAccess,
+    fields::{Masked, PreserveReserved, ReadPureWrite, ReadWrite, WriteOnly, ZeroReserved},
+    register_map::ReservedBits,
+};
+
+/// The policy of a [`Masked`] register for what to write to its reserved bits.
+///
+/// This trait is sealed, so can't be implemented outside this crate.
+pub trait ReservedPolicy: sealed::Sealed {
+    /// What to write to the reserved bits.
+    const RESERVED: ReservedBits;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+impl sealed::Sealed for PreserveReserved {}
+impl sealed::Sealed for ZeroReserved {}
+
+impl ReservedPolicy for PreserveReserved {
+    const RESERVED: ReservedBits = ReservedBits::Preserve;
+}
+
+impl ReservedPolicy for ZeroReserved {
+    const RESERVED: ReservedBits = ReservedBits::Zero;
+}
+
+/// Returns `value` with the bits outside `mask` replaced by those of `reserved`.
+fn merge<T: AtomicAccess>(mut value: T, reserved: T, mask: u64) -> T {
+    let mask = mask.to_ne_bytes();
+    // The register corresponds to the low `size_of::<T>()` bytes of the mask.
+    let mask = if cfg!(target_endian = "little") {
+        &mask[..size_of::<T>()]
+    } else {
+        &mask[mask.len() - size_of::<T>()..]
+    };
+    for ((byte, reserved), mask) in value
+        .as_mut_bytes()
+        .iter_mut()
+        .zip(reserved.as_bytes())
+        .zip(mask)
+    {
+        *byte = (*byte & mask) | (reserved & !mask);
+    }
+    value
+}
+
+/// Returns `MASK` as a `T`.
+fn mask<T: AtomicAccess, const MASK: u64>() -> T {
+    let mut ones = T::new_zeroed();
+    ones.as_mut_bytes().fill(0xff);
+    merge(ones, T::new_zeroed(), MASK)
+}
+
+impl<T: AtomicAccess, const MASK: u64, R: ReservedPolicy>
+    UniqueMmioPointer<'_, Masked<ReadWrite<T>, MASK, R>>
+{
+    /// Returns a pointer to the wrapped register, which isn't masked.
+    fn inner(&mut self) -> UniqueMmioPointer<'_, ReadWrite<T>> {
+        let regs = self.ptr_nonnull().cast();
+        // SAFETY: `Masked<W, MASK, R>` is a transparent wrapper around `W`, so the child pointer is
+        // exactly the same allocation.
+        unsafe { self.child(regs) }
+    }
+
+    /// Performs an MMIO read of the entire `T`.
+    pub fn read(&mut self) -> T {
+        self.inner().read()
+    }
+
+    /// Performs an MMIO write of the bits of `value` in `MASK`, and of the reserved bits according
+    /// to `R`.
+    ///
+    /// With [`PreserveReserved`] this reads the register first, which may cause side-effects.
+    pub fn write(&mut self, value: T) {
+        let reserved = match R::RESERVED {
+            ReservedBits::Preserve => self.read(),
+            ReservedBits::Zero => T::new_zeroed(),
+        };
+        self.inner().write(merge(value, reserved, MASK));
+    }
+
+    /// Performs an MMIO read of the entire `T`, applies the given function to it, and then performs
+    /// an MMIO write of the bits of the result in `MASK`, and of the reserved bits according to `R`.
+    pub fn modify(&mut self, f: impl FnOnce(T) -> T) {
+        let current = self.read();
+        let reserved = match R::RESERVED {
+            ReservedBits::Preserve => current,
+            ReservedBits::Zero => T::new_zeroed(),
+        };
+        self.inner().write(merge(f(current), reserved, MASK));
+    }
+}
+
+impl<T: AtomicAccess, const MASK: u64, R> SharedMmioPointer<'_, Masked<ReadPureWrite<T>, MASK, R>> {
+    /// Performs an MMIO read of the entire `T`.
+    pub fn read(&self) -> T {
+        // SAFETY: `Masked<W, MASK, R>` is a transparent wrapper around `W`, so the child pointer is
+        // exactly the same allocation.
+        unsafe { self.child(self.regs.cast::<ReadPureWrite<T>>()) }.read()
+    }
+}
+
+impl<T: AtomicAccess, const MASK: u64, R: ReservedPolicy>
+    UniqueMmioPointer<'_, Masked<ReadPureWrite<T>, MASK, R>>
+{
+    /// Returns a pointer to the wrapped register, which isn't masked.
+    fn inner(&mut self) -> UniqueMmioPointer<'_, ReadPureWrite<T>> {
+        let regs = self.ptr_nonnull().cast();
+        // SAFETY: `Masked<W, MASK, R>` is a transparent wrapper around `W`, so the child pointer is
+        // exactly the same allocation.
+        unsafe { self.child(regs) }
+    }
+
+    /// Performs an MMIO write of the bits of `value` in `MASK`, and of the reserved bits according
+    /// to `R`.
+    ///
+    /// With [`PreserveReserved`] this reads the register first. With the `readback` feature
+    /// enabled and a mismatch hook set, the bits in `MASK` are then read back and checked.
+    pub fn write(&mut self, value: T) {
+        let reserved = match R::RESERVED {
+            ReservedBits::Preserve => self.read(),
+            ReservedBits::Zero => T::new_zeroed(),
+        };
+        self.inner()
+            .write_checked(merge(value, reserved, MASK), mask::<T, MASK>());
+    }
+
+    /// Performs an MMIO read of the entire `T`, applies the given function to it, and then performs
+    /// an MMIO write of the bits of the result in `MASK`, and of the reserved bits according to `R`.
+    pub fn modify(&mut self, f: impl FnOnce(T) -> T) {
+        let current = self.read();
+        let reserved = match R::RESERVED {
+            ReservedBits::Preserve => current,
+            ReservedBits::Zero => T::new_zeroed(),
+        };
+        self.inner()
+            .write_checked(merge(f(current), reserved, MASK), mask::<T, MASK>());
+    }
+}
+
+impl<T: AtomicAccess, const MASK: u64>
+    UniqueMmioPointer<'_, Masked<WriteOnly<T>, MASK, ZeroReserved>>
+{
+    /// Performs an MMIO write of the bits of `value` in `MASK`, with zeroes in the reserved bits.
+    pub fn write(&mut self, value: T) {
+        let regs = self.ptr_nonnull().cast::<WriteOnly<T>>();
+        // SAFETY: `Masked<W, MASK, R>` is a transparent wrapper around `W`, so the child pointer is
+        // exactly the same allocation.
+        unsafe { self.child(regs) }.write(merge(value, T::new_zeroed(), MASK));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field;
+    use core::marker::PhantomData;
+
+    #[repr(C)]
+    struct Registers {
+        control: Masked<ReadWrite<u32>, 0xff8f>,
+        levels: Masked<ReadPureWrite<u16>, 0x3f, ZeroReserved>,
+        command: Masked<WriteOnly<u8>, 0x0f, ZeroReserved>,
+    }
+
+    #[test]
+    fn preserve() {
+        let mut fake = Registers {
+            control: Masked(ReadWrite(0x30), PhantomData),
+            levels: Masked(ReadPureWrite(0), PhantomData),
+            command: Masked(WriteOnly(0), PhantomData),
+        };
+        {
+            let mut regs = UniqueMmioPointer::from(&mut fake);
+            field!(regs, control).write(0xffff_ffff);
+            assert_eq!(field!(regs, control).read(), 0xffbf);
+            field!(regs, control).modify(|control| control & !0xff);
+            assert_eq!(field!(regs, control).read(), 0xff30);
+        }
+        assert_eq!(fake.control.0.0, 0xff30);
+    }
+
+    #[test]
+    fn zero() {
+        let mut fake = Registers {
+            control: Masked(ReadWrite(0), PhantomData),
+            levels: Masked(ReadPureWrite(0xc0), PhantomData),
+            command: Masked(WriteOnly(0), PhantomData),
+        };
+        {
+            let mut regs = UniqueMmioPointer::from(&mut fake);
+            field!(regs, levels).modify(|levels| levels + 1);
+            assert_eq!(field!(regs, levels).read(), 0x01);
+            field!(regs, levels).write(0xffff);
+            field!(regs, command).write(0xff);
+        }
+        assert_eq!(fake.levels.0.0, 0x3f);
+        assert_eq!(fake.command.0.0, 0x0f);
+    }
+}
@@ -0,0 +1,437 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! A minimal register console for bring-up images, built on [`RegisterMap`] tables.
+//!
+//! [`execute`] parses a single command line and runs it against a set of [`MonitorDevice`]s,
+//! writing the result to any [`core::fmt::Write`] such as a UART. It needs neither `std` nor
+//! `alloc`, so all an image needs to provide is a loop reading lines. The commands are:
+//!
+//! - `list <device>` lists the registers of a device.
+//! - `read <device>.<register>` reads a register.
+//! - `write <device>.<register> <value>` writes a register.
+//!
+//! A register may be given by name, by name and array index such as `irq[2]`, or by byte offset
+//! such as `0x10`. Values may be decimal or hexadecimal with a `0x` prefix.
+//!
+//! Only the accesses which a register's field wrapper allows are made, so for example reading a
+//! [`WriteOnly`](crate::fields::WriteOnly) register is refused.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_mmio::{
+//!     UniqueMmioPointer,
+//!     fields::{ReadPure, ReadWrite},
+//!     monitor::{MonitorDevice, execute},
+//!     register_map,
+//! };
+//!
+//! register_map! {
+//!     #[repr(C)]
+//!     pub struct UartRegisters {
+//!         pub data: ReadWrite<u32>,
+//!         pub status: ReadPure<u32>,
+//!     }
+//! }
+//!
+//! let uart: UniqueMmioPointer<UartRegisters>;
+//! # let mut fake = UartRegisters { data: ReadWrite(0), status: ReadPure(0x90) };
+//! # uart = UniqueMmioPointer::from(&mut fake);
+//! let mut devices = [MonitorDevice::new("uart0", uart)];
+//!
+//! let mut output = String::new();
+//! execute(&mut devices, "read uart0.status", &mut output).unwrap();
+//! assert_eq!(output, "uart0.status (0x4) = 0x00000090\n");
+//! execute(&mut devices, "write uart0.data 0x41", &mut output).unwrap();
+//! ```
+
+use crate::{
+    UniqueMmioPointer,
+    register_map::{
+        RegisterError, RegisterInfo, RegisterMap, read_register_info, write_register_masked,
+    },
+};
+use core::{
+    fmt::{self, Display, Formatter, Write},
+    ptr::NonNull,
+};
+
+/// An error parsing or running a monitor command.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MonitorError {
+    /// The command was empty or not recognised.
+    UnknownCommand,
+    /// The command was missing an argument, or had too many.
+    WrongArguments,
+    /// No device with the given name was registered.
+    UnknownDevice,
+    /// The device has no register with the given name or offset.
+    UnknownRegister,
+    /// The array index was out of range, or was given for a register which isn't an array.
+    IndexOutOfRange,
+    /// The value to write couldn't be parsed, or doesn't fit in the register.
+    InvalidValue,
+    /// The register can't safely be read.
+    NotReadable,
+    /// The register can't safely be written.
+    NotWritable,
+    /// The register isn't aligned for the accesses used to read and write it.
+    Misaligned,
+    /// Writing the output failed.
+    Output,
+}
+
+impl Display for MonitorError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownCommand => write!(f, "Unknown command"),
+            Self::WrongArguments => write!(f, "Wrong number of arguments"),
+            Self::UnknownDevice => write!(f, "Unknown device"),
+            Self::UnknownRegister => write!(f, "Unknown register"),
+            Self::IndexOutOfRange => write!(f, "Register index out of range"),
+            Self::InvalidValue => write!(f, "Invalid value"),
+            Self::NotReadable => write!(f, "Register can't be read"),
+            Self::NotWritable => write!(f, "Register can't be written"),
+            Self::Misaligned => write!(f, "Register is misaligned"),
+            Self::Output => write!(f, "Error writing output"),
+        }
+    }
+}
+
+impl core::error::Error for MonitorError {}
+
+impl From<RegisterError> for MonitorError {
+    fn from(e: RegisterError) -> Self {
+        match e {
+            RegisterError::UnknownRegister => Self::UnknownRegister,
+            RegisterError::InvalidValue => Self::InvalidValue,
+            RegisterError::NotReadable => Self::NotReadable,
+            RegisterError::NotWritable => Self::NotWritable,
+            RegisterError::Misaligned => Self::Misaligned,
+        }
+    }
+}
+
+impl From<fmt::Error> for MonitorError {
+    fn from(_: fmt::Error) -> Self {
+        Self::Output
+    }
+}
+
+/// A device whose registers can be accessed by monitor commands.
+#[derive(Debug)]
+pub struct MonitorDevice<'a> {
+    name: &'static str,
+    registers: &'static [RegisterInfo],
+    regs: UniqueMmioPointer<'a, [u8]>,
+}
+
+impl<'a> MonitorDevice<'a> {
+    /// Makes the given device registers available to monitor commands under the given name.
+    pub fn new<T: RegisterMap>(name: &'static str, mut regs: UniqueMmioPointer<'a, T>) -> Self {
+        let bytes = NonNull::slice_from_raw_parts(regs.ptr_nonnull().cast::<u8>(), size_of::<T>());
+        Self {
+            name,
+            registers: T::REGISTERS,
+            // SAFETY: `bytes` covers exactly the same memory as `regs`.
+            regs: unsafe { regs.split_child(bytes) },
+        }
+    }
+
+    /// Returns the name of the device.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Finds the register with the given name, name and index, or offset, and returns its
+    /// description and the offset of the particular element.
+    fn find(&self, register: &str) -> Result<(&'static RegisterInfo, usize), MonitorError> {
+        if let Some(offset) = parse_number(register) {
+            let info = self
+                .registers
+                .iter()
+                .find(|info| offset.wrapping_sub(info.offset as u64) < info.size as u64)
+                .ok_or(MonitorError::UnknownRegister)?;
+            let offset = offset as usize;
+            if (offset - info.offset) % info.width != 0 {
+                return Err(MonitorError::UnknownRegister);
+            }
+            if !info.is_aligned(offset) {
+                return Err(MonitorError::Misaligned);
+            }
+            return Ok((info, offset));
+        }
+        let (name, index) = match register.split_once('[') {
+            Some((name, index)) => {
+                let index = index
+                    .strip_suffix(']')
+                    .and_then(parse_number)
+                    .ok_or(MonitorError::UnknownRegister)?;
+                (name, Some(index))
+            }
+            None => (register, None),
+        };
+        let info = self
+            .registers
+            .iter()
+            .find(|info| info.name == name)
+            .ok_or(MonitorError::UnknownRegister)?;
+        let is_array = info.size != info.width;
+        let offset = match index {
+            None if !is_array => info.offset,
+            Some(index) if is_array && index < (info.size / info.width) as u64 => {
+                info.offset + index as usize * info.width
+            }
+            _ => return Err(MonitorError::IndexOutOfRange),
+        };
+        if !info.is_aligned(offset) {
+            return Err(MonitorError::Misaligned);
+        }
+        Ok((info, offset))
+    }
+
+    /// Reads the register with the given description at the given offset.
+    fn read(&mut self, info: &RegisterInfo, offset: usize) -> Result<u64, MonitorError> {
+        // SAFETY: The register info and offset came from the register map of the type which `regs`
+        // originally pointed to.
+        Ok(unsafe { read_register_info(&mut self.regs, info, offset)? })
+    }
+
+    /// Writes the register with the given description at the given offset, leaving its reserved
+    /// bits alone.
+    fn write(
+        &mut self,
+        info: &RegisterInfo,
+        offset: usize,
+        value: u64,
+    ) -> Result<(), MonitorError> {
+        // SAFETY: The register info and offset came from the register map of the type which `regs`
+        // originally pointed to.
+        unsafe { write_register_masked(&mut self.regs, info, offset, value)? };
+        Ok(())
+    }
+}
+
+/// Parses and runs a single monitor command against the given devices, writing any result to
+/// `out`.
+pub fn execute(
+    devices: &mut [MonitorDevice],
+    command: &str,
+    out: &mut impl Write,
+) -> Result<(), MonitorError> {
+    let mut words = command.split_whitespace();
+    let command = words.next().ok_or(MonitorError::UnknownCommand)?;
+    let target = words.next().ok_or(MonitorError::WrongArguments)?;
+    let value = words.next();
+    if words.next().is_some() {
+        return Err(MonitorError::WrongArguments);
+    }
+
+    if command == "list" {
+        if value.is_some() {
+            return Err(MonitorError::WrongArguments);
+        }
+        let device = find_device(devices, target)?;
+        for info in device.registers {
+            writeln!(
+                out,
+                "{:#06x} {} ({} x {} bytes, {:?})",
+                info.offset,
+                info.name,
+                info.size / info.width.max(1),
+                info.width,
+                info.access
+            )?;
+        }
+        return Ok(());
+    }
+
+    let (device, register) = target.split_once('.').ok_or(MonitorError::WrongArguments)?;
+    let device = find_device(devices, device)?;
+    let (info, offset) = device.find(register)?;
+    match (command, value) {
+        ("read", None) => {
+            let value = device.read(info, offset)?;
+            writeln!(
+                out,
+                "{}.{} ({:#x}) = {:#0width$x}",
+                device.name,
+                register,
+                offset,
+                value,
+                width = info.width * 2 + 2
+            )?;
+        }
+        ("write", Some(value)) => {
+            let value = parse_number(value).ok_or(MonitorError::InvalidValue)?;
+            device.write(info, offset, value)?;
+        }
+        ("read" | "write", _) => return Err(MonitorError::WrongArguments),
+        _ => return Err(MonitorError::UnknownCommand),
+    }
+    Ok(())
+}
+
+fn find_device<'b, 'a>(
+    devices: &'b mut [MonitorDevice<'a>],
+    name: &str,
+) -> Result<&'b mut MonitorDevice<'a>, MonitorError> {
+    devices
+        .iter_mut()
+        .find(|device| device.name == name)
+        .ok_or(MonitorError::UnknownDevice)
+}
+
+/// Parses a decimal number, or a hexadecimal number with a `0x` prefix.
+fn parse_number(s: &str) -> Option<u64> {
+    match s.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => s.parse().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fields::{ReadOnly, ReadPure, ReadWrite64LoHi, WriteOnly},
+        register_map,
+    };
+
+    register_map! {
+        #[repr(C)]
+        struct Registers {
+            id: ReadPure<u16>,
+            flags: ReadOnly<u8>,
+            reserved: u8,
+            irq: [WriteOnly<u32>; 2],
+            counter: ReadWrite64LoHi,
+        }
+    }
+
+    /// A `fmt::Write` into a fixed-size buffer.
+    struct Buffer {
+        bytes: [u8; 256],
+        len: usize,
+    }
+
+    impl Buffer {
+        fn new() -> Self {
+            Self {
+                bytes: [0; 256],
+                len: 0,
+            }
+        }
+
+        fn take(&mut self) -> &str {
+            let len = self.len;
+            self.len = 0;
+            str::from_utf8(&self.bytes[..len]).unwrap()
+        }
+    }
+
+    impl Write for Buffer {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let end = self.len + s.len();
+            self.bytes
+                .get_mut(self.len..end)
+                .ok_or(fmt::Error)?
+                .copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    fn fake() -> Registers {
+        Registers {
+            id: ReadPure(0x1234),
+            flags: ReadOnly(5),
+            reserved: 0,
+            irq: [WriteOnly(0), WriteOnly(0)],
+            counter: ReadWrite64LoHi(0),
+        }
+    }
+
+    #[test]
+    fn read_write() {
+        let mut fake = fake();
+        let mut out = Buffer::new();
+        {
+            let mut devices = [MonitorDevice::new(
+                "dev",
+                UniqueMmioPointer::from(&mut fake),
+            )];
+            execute(&mut devices, "read dev.id", &mut out).unwrap();
+            assert_eq!(out.take(), "dev.id (0x0) = 0x1234\n");
+            execute(&mut devices, "  read   dev.0x2 ", &mut out).unwrap();
+            assert_eq!(out.take(), "dev.0x2 (0x2) = 0x05\n");
+            execute(&mut devices, "write dev.irq[1] 0xabcd", &mut out).unwrap();
+            execute(&mut devices, "write dev.0x4 42", &mut out).unwrap();
+            execute(&mut devices, "write dev.counter 0x1_0000_0002", &mut out).unwrap_err();
+            execute(&mut devices, "write dev.counter 0x100000002", &mut out).unwrap();
+            execute(&mut devices, "read dev.counter", &mut out).unwrap();
+            assert_eq!(out.take(), "dev.counter (0x10) = 0x0000000100000002\n");
+        }
+        assert_eq!(fake.irq[0].0, 42);
+        assert_eq!(fake.irq[1].0, 0xabcd);
+        assert_eq!(fake.counter.0, 0x1_0000_0002);
+    }
+
+    #[test]
+    fn list() {
+        let mut fake = fake();
+        let mut devices = [MonitorDevice::new(
+            "dev",
+            UniqueMmioPointer::from(&mut fake),
+        )];
+        let mut out = Buffer::new();
+        execute(&mut devices, "list dev", &mut out).unwrap();
+        assert_eq!(
+            out.take(),
+            "0x0000 id (1 x 2 bytes, ReadPure)\n\
+             0x0002 flags (1 x 1 bytes, ReadOnly)\n\
+             0x0003 reserved (1 x 1 bytes, Unsafe)\n\
+             0x0004 irq (2 x 4 bytes, WriteOnly)\n\
+             0x0010 counter (1 x 8 bytes, ReadWrite64LoHi)\n"
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let mut fake = fake();
+        let mut devices = [MonitorDevice::new(
+            "dev",
+            UniqueMmioPointer::from(&mut fake),
+        )];
+        let mut out = Buffer::new();
+        let mut run = |command| execute(&mut devices, command, &mut out);
+        assert_eq!(run(""), Err(MonitorError::UnknownCommand));
+        assert_eq!(run("poke dev.id"), Err(MonitorError::UnknownCommand));
+        assert_eq!(run("read"), Err(MonitorError::WrongArguments));
+        assert_eq!(run("read dev.id 1"), Err(MonitorError::WrongArguments));
+        assert_eq!(run("write dev.irq[0]"), Err(MonitorError::WrongArguments));
+        assert_eq!(run("read other.id"), Err(MonitorError::UnknownDevice));
+        assert_eq!(run("read dev.nothing"), Err(MonitorError::UnknownRegister));
+        assert_eq!(run("read dev.0x1"), Err(MonitorError::UnknownRegister));
+        assert_eq!(run("read dev.0x40"), Err(MonitorError::UnknownRegister));
+        assert_eq!(run("read dev.id[0]"), Err(MonitorError::IndexOutOfRange));
+        assert_eq!(run("write dev.irq 1"), Err(MonitorError::IndexOutOfRange));
+        assert_eq!(
+            run("write dev.irq[2] 1"),
+            Err(MonitorError::IndexOutOfRange)
+        );
+        assert_eq!(run("read dev.irq[0]"), Err(MonitorError::NotReadable));
+        assert_eq!(run("read dev.reserved"), Err(MonitorError::NotReadable));
+        assert_eq!(run("write dev.id 1"), Err(MonitorError::NotWritable));
+        assert_eq!(
+            run("write dev.irq[0] 0x100000000"),
+            Err(MonitorError::InvalidValue)
+        );
+        assert_eq!(
+            run("write dev.irq[0] nope"),
+            Err(MonitorError::InvalidValue)
+        );
+    }
+}
@@ -0,0 +1,152 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Helpers for [`Overlay`] fields, whose register layout depends on the mode of the device.
+//!
+//! Some devices reuse the same address range for different registers depending on a mode bit. For
+//! example, a DMA channel may have different configuration registers in memory-to-memory and
+//! peripheral modes. `Overlay<A, B>` covers both layouts, and
+//! [`as_a`](UniqueMmioPointer::as_a) and [`as_b`](UniqueMmioPointer::as_b) project to whichever
+//! one the driver knows the device is using.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_mmio::{
+//!     UniqueMmioPointer, field,
+//!     fields::{Overlay, ReadPureWrite},
+//! };
+//!
+//! #[repr(C)]
+//! struct MemoryConfig {
+//!     source: ReadPureWrite<u32>,
+//!     destination: ReadPureWrite<u32>,
+//! }
+//!
+//! #[repr(C)]
+//! struct PeripheralConfig {
+//!     address: ReadPureWrite<u32>,
+//!     request_line: ReadPureWrite<u8>,
+//! }
+//!
+//! #[repr(C)]
+//! struct DmaChannel {
+//!     mode: ReadPureWrite<u32>,
+//!     config: Overlay<MemoryConfig, PeripheralConfig>,
+//! }
+//!
+//! const MODE_PERIPHERAL: u32 = 1;
+//!
+//! fn set_peripheral(channel: &mut UniqueMmioPointer<DmaChannel>, address: u32, request_line: u8) {
+//!     field!(*channel, mode).write(MODE_PERIPHERAL);
+//!     let mut config = field!(*channel, config);
+//!     let mut config = config.as_b();
+//!     field!(config, address).write(address);
+//!     field!(config, request_line).write(request_line);
+//! }
+//! # let mut fake = DmaChannel {
+//! #     mode: ReadPureWrite(0),
+//! #     config: Overlay::a(MemoryConfig {
+//! #         source: ReadPureWrite(0),
+//! #         destination: ReadPureWrite(0),
+//! #     }),
+//! # };
+//! # set_peripheral(&mut UniqueMmioPointer::from(&mut fake), 0x1000, 3);
+//! ```
+
+use crate::{SharedMmioPointer, UniqueMmioPointer, fields::Overlay};
+use core::mem::ManuallyDrop;
+
+impl<A, B> Overlay<A, B> {
+    /// Creates an overlay holding the `A` layout, for fakes in tests.
+    pub const fn a(a: A) -> Self {
+        Self {
+            a: ManuallyDrop::new(a),
+        }
+    }
+
+    /// Creates an overlay holding the `B` layout, for fakes in tests.
+    pub const fn b(b: B) -> Self {
+        Self {
+            b: ManuallyDrop::new(b),
+        }
+    }
+}
+
+impl<'a, A, B> UniqueMmioPointer<'a, Overlay<A, B>> {
+    /// Returns a pointer to the registers with the `A` layout.
+    pub fn as_a(&mut self) -> UniqueMmioPointer<'_, A> {
+        let regs = self.regs.cast::<A>();
+        // SAFETY: `Overlay<A, B>` is a `repr(C)` union, so `A` starts at the same address and is
+        // within the same allocation.
+        unsafe { self.child(regs) }
+    }
+
+    /// Returns a pointer to the registers with the `B` layout.
+    pub fn as_b(&mut self) -> UniqueMmioPointer<'_, B> {
+        let regs = self.regs.cast::<B>();
+        // SAFETY: `Overlay<A, B>` is a `repr(C)` union, so `B` starts at the same address and is
+        // within the same allocation.
+        unsafe { self.child(regs) }
+    }
+
+    /// Converts this into a pointer to the registers with the `A` layout.
+    pub fn into_a(mut self) -> UniqueMmioPointer<'a, A> {
+        let regs = self.regs.cast::<A>();
+        // SAFETY: `Overlay<A, B>` is a `repr(C)` union, so `A` starts at the same address and is
+        // within the same allocation.
+        unsafe { self.split_child(regs) }
+    }
+
+    /// Converts this into a pointer to the registers with the `B` layout.
+    pub fn into_b(mut self) -> UniqueMmioPointer<'a, B> {
+        let regs = self.regs.cast::<B>();
+        // SAFETY: `Overlay<A, B>` is a `repr(C)` union, so `B` starts at the same address and is
+        // within the same allocation.
+        unsafe { self.split_child(regs) }
+    }
+}
+
+impl<A, B> SharedMmioPointer<'_, Overlay<A, B>> {
+    /// Returns a pointer to the registers with the `A` layout.
+    pub fn as_a(&self) -> SharedMmioPointer<'_, A> {
+        // SAFETY: `Overlay<A, B>` is a `repr(C)` union, so `A` starts at the same address and is
+        // within the same allocation.
+        unsafe { self.child(self.regs.cast::<A>()) }
+    }
+
+    /// Returns a pointer to the registers with the `B` layout.
+    pub fn as_b(&self) -> SharedMmioPointer<'_, B> {
+        // SAFETY: `Overlay<A, B>` is a `repr(C)` union, so `B` starts at the same address and is
+        // within the same allocation.
+        unsafe { self.child(self.regs.cast::<B>()) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fields::{ReadPure, ReadPureWrite};
+
+    #[test]
+    fn layout() {
+        assert_eq!(size_of::<Overlay<u8, [u32; 3]>>(), 12);
+        assert_eq!(align_of::<Overlay<u8, [u32; 3]>>(), 4);
+        assert_eq!(size_of::<Overlay<u64, u16>>(), 8);
+    }
+
+    #[test]
+    fn views() {
+        let mut fake = Overlay::<ReadPureWrite<u32>, [ReadPure<u16>; 2]>::a(ReadPureWrite(0));
+        let mut overlay = UniqueMmioPointer::from(&mut fake);
+        overlay.as_a().write(0x1234_5678);
+        let mut b = overlay.as_b();
+        assert_eq!(b.get(0).unwrap().read(), 0x5678);
+        assert_eq!(b.get(1).unwrap().read(), 0x1234);
+        assert_eq!(overlay.as_a().read(), 0x1234_5678);
+        assert_eq!(overlay.as_b().get(1).unwrap().read(), 0x1234);
+        assert_eq!(SharedMmioPointer::as_a(&overlay).read(), 0x1234_5678);
+        assert_eq!(overlay.into_b().get(0).unwrap().read(), 0x5678);
+    }
+}
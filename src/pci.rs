@@ -0,0 +1,762 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Helpers for PCI devices.
+//!
+//! These don't access PCI configuration space themselves; the caller is expected to read the BAR
+//! values with whatever configuration space accessor their platform provides.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_mmio::{
+//!     fields::ReadWrite,
+//!     pci::{MemoryBar, physical_instance_from_bar},
+//! };
+//!
+//! #[repr(C)]
+//! struct NvmeRegisters {
+//!     capabilities: ReadWrite<u64>,
+//!     version: ReadWrite<u32>,
+//! }
+//!
+//! // BARs as read from configuration space. BAR 0 is a 64-bit prefetchable memory BAR.
+//! let bars = [0x1000_000c, 0x0000_0001, 0, 0, 0, 0];
+//! let bar = MemoryBar::from_bars(&bars, 0).unwrap();
+//! assert!(bar.is_64bit());
+//! assert!(bar.prefetchable());
+//! assert_eq!(bar.address(), 0x1_1000_0000);
+//!
+//! // SAFETY: The BAR has been assigned, with a size of 16 KiB which was found by sizing it, and
+//! // we only create one `PhysicalInstance` for it.
+//! let instance = unsafe { physical_instance_from_bar::<NvmeRegisters>(&bar, 0x4000) };
+//! ```
+
+use crate::{
+    PhysicalInstance, SharedMmioPointer, UniqueMmioPointer,
+    access::AtomicAccess,
+    field,
+    fields::{ReadPure, ReadPureWrite},
+};
+use core::{
+    fmt::{self, Display, Formatter},
+    ptr::NonNull,
+};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+const BAR_IO_SPACE: u32 = 1 << 0;
+const BAR_TYPE_MASK: u32 = 0b11 << 1;
+const BAR_TYPE_32BIT: u32 = 0b00 << 1;
+const BAR_TYPE_64BIT: u32 = 0b10 << 1;
+const BAR_PREFETCHABLE: u32 = 1 << 3;
+const BAR_MEMORY_ADDRESS_MASK: u32 = !0xf;
+
+/// An error decoding a PCI BAR or constructing a `PhysicalInstance` from it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BarError {
+    /// The BAR is an I/O space BAR rather than a memory BAR.
+    IoSpace,
+    /// The BAR has a reserved memory type.
+    ReservedType,
+    /// The BAR index is out of range, or it is a 64-bit BAR in the last slot so has no upper half.
+    IndexOutOfRange,
+    /// The end of the BAR region overflows.
+    TooLarge,
+    /// The BAR region is smaller than the type being requested.
+    TooSmall {
+        /// The size in bytes of the type being requested.
+        expected: usize,
+        /// The size in bytes of the BAR region.
+        actual: u64,
+    },
+}
+
+impl Display for BarError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::IoSpace => write!(f, "BAR is an I/O space BAR"),
+            Self::ReservedType => write!(f, "BAR has a reserved memory type"),
+            Self::IndexOutOfRange => write!(f, "BAR index out of range"),
+            Self::TooLarge => write!(f, "BAR region end overflows"),
+            Self::TooSmall { expected, actual } => write!(
+                f,
+                "BAR size {actual:#x} is smaller than requested size {expected:#x}"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for BarError {}
+
+/// A decoded PCI memory BAR.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MemoryBar {
+    address: u64,
+    is_64bit: bool,
+    prefetchable: bool,
+}
+
+impl MemoryBar {
+    /// Decodes a memory BAR from the value of its BAR register, and the value of the following BAR
+    /// register in case it is a 64-bit BAR.
+    ///
+    /// `upper` is ignored for 32-bit BARs, and treated as 0 if it is `None` for a 64-bit BAR.
+    pub const fn decode(lower: u32, upper: Option<u32>) -> Result<Self, BarError> {
+        if lower & BAR_IO_SPACE != 0 {
+            return Err(BarError::IoSpace);
+        }
+        let is_64bit = match lower & BAR_TYPE_MASK {
+            BAR_TYPE_32BIT => false,
+            BAR_TYPE_64BIT => true,
+            _ => return Err(BarError::ReservedType),
+        };
+        let mut address = (lower & BAR_MEMORY_ADDRESS_MASK) as u64;
+        if is_64bit {
+            if let Some(upper) = upper {
+                address |= (upper as u64) << 32;
+            }
+        }
+        Ok(Self {
+            address,
+            is_64bit,
+            prefetchable: lower & BAR_PREFETCHABLE != 0,
+        })
+    }
+
+    /// Decodes the memory BAR with the given index from the values of a device's BAR registers,
+    /// combining it with the following register if it is a 64-bit BAR.
+    pub const fn from_bars(bars: &[u32], index: usize) -> Result<Self, BarError> {
+        if index >= bars.len() {
+            return Err(BarError::IndexOutOfRange);
+        }
+        let bar = match Self::decode(bars[index], None) {
+            Ok(bar) => bar,
+            Err(e) => return Err(e),
+        };
+        if !bar.is_64bit {
+            return Ok(bar);
+        }
+        if index + 1 >= bars.len() {
+            return Err(BarError::IndexOutOfRange);
+        }
+        Self::decode(bars[index], Some(bars[index + 1]))
+    }
+
+    /// Returns the base address of the BAR.
+    pub const fn address(&self) -> u64 {
+        self.address
+    }
+
+    /// Returns whether this is a 64-bit BAR, which uses the following BAR register for the upper
+    /// half of its address.
+    pub const fn is_64bit(&self) -> bool {
+        self.is_64bit
+    }
+
+    /// Returns whether the BAR is marked as prefetchable.
+    pub const fn prefetchable(&self) -> bool {
+        self.prefetchable
+    }
+}
+
+/// Calculates the size of a memory BAR from the value read back from it after writing all ones to
+/// it, and to the following register in case it is a 64-bit BAR.
+///
+/// Returns 0 if the BAR is not implemented.
+pub const fn memory_bar_size(lower: u32, upper: Option<u32>) -> u64 {
+    let lower_mask = lower & BAR_MEMORY_ADDRESS_MASK;
+    if lower & BAR_TYPE_MASK == BAR_TYPE_64BIT {
+        let upper_mask = match upper {
+            Some(upper) => upper,
+            None => 0,
+        };
+        let mask = ((upper_mask as u64) << 32) | lower_mask as u64;
+        if mask == 0 {
+            0
+        } else {
+            (!mask).wrapping_add(1)
+        }
+    } else if lower_mask == 0 {
+        0
+    } else {
+        (!lower_mask).wrapping_add(1) as u64
+    }
+}
+
+/// Constructs a `PhysicalInstance` for the MMIO region of the given memory BAR.
+///
+/// `size` is the size of the BAR in bytes, as found by sizing it, e.g. with [`memory_bar_size`].
+/// Returns an error if this is smaller than `T`, or if the end of the region overflows.
+///
+/// # Safety
+///
+/// The BAR must have been assigned an address which maps to the device's registers, which must
+/// have type `T`, and there must only ever be a single `PhysicalInstance` created for those device
+/// registers.
+pub unsafe fn physical_instance_from_bar<T>(
+    bar: &MemoryBar,
+    size: u64,
+) -> Result<PhysicalInstance<T>, BarError> {
+    if size < size_of::<T>() as u64 {
+        return Err(BarError::TooSmall {
+            expected: size_of::<T>(),
+            actual: size,
+        });
+    }
+    if bar.address.checked_add(size).is_none() {
+        return Err(BarError::TooLarge);
+    }
+    // SAFETY: The caller promised that the BAR maps to device registers of type `T`, and that no
+    // other `PhysicalInstance` exists for them.
+    Ok(unsafe { PhysicalInstance::new_u64(bar.address) })
+}
+
+/// The per-vector mask bit in the vector control field of an MSI-X table entry.
+const MSIX_VECTOR_MASKED: u32 = 1 << 0;
+
+/// An entry in an MSI-X table.
+#[derive(Clone, Debug, Default, Eq, FromBytes, Immutable, IntoBytes, KnownLayout, PartialEq)]
+#[repr(C, align(16))]
+pub struct MsixTableEntry {
+    /// The lower 32 bits of the message address.
+    pub message_address_lower: ReadPureWrite<u32>,
+    /// The upper 32 bits of the message address.
+    pub message_address_upper: ReadPureWrite<u32>,
+    /// The message data.
+    pub message_data: ReadPureWrite<u32>,
+    /// The vector control field, bit 0 of which masks the vector.
+    pub vector_control: ReadPureWrite<u32>,
+}
+
+/// A PCI device's MSI-X table.
+///
+/// # Example
+///
+/// ```
+/// use safe_mmio::{
+///     UniqueMmioPointer,
+///     pci::{MsixTable, MsixTableEntry},
+/// };
+///
+/// let table: UniqueMmioPointer<[MsixTableEntry]>;
+/// # let mut fake = [MsixTableEntry::default(), MsixTableEntry::default()];
+/// # table = UniqueMmioPointer::from(fake.as_mut_slice());
+/// let mut msix = MsixTable::new(table);
+/// msix.set_entry(1, 0xfee0_0000, 42);
+/// msix.unmask(1);
+/// assert!(!msix.is_masked(1));
+/// ```
+#[derive(Debug)]
+pub struct MsixTable<'a> {
+    entries: UniqueMmioPointer<'a, [MsixTableEntry]>,
+}
+
+impl<'a> MsixTable<'a> {
+    /// Creates a new `MsixTable` from a pointer to the table entries.
+    pub const fn new(entries: UniqueMmioPointer<'a, [MsixTableEntry]>) -> Self {
+        Self { entries }
+    }
+
+    /// Returns the number of entries in the table.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether the table has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Sets the message address and data of the given entry.
+    ///
+    /// The entry is masked while it is updated, so that the device never sends a message with a
+    /// partially updated address and data. Afterwards it is restored to its previous mask state.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set_entry(&mut self, index: usize, address: u64, data: u32) {
+        let mut entry = self.entry(index);
+        let vector_control = field!(entry, vector_control).read();
+        field!(entry, vector_control).write(vector_control | MSIX_VECTOR_MASKED);
+        field!(entry, message_address_lower).write(address as u32);
+        field!(entry, message_address_upper).write((address >> 32) as u32);
+        field!(entry, message_data).write(data);
+        if vector_control & MSIX_VECTOR_MASKED == 0 {
+            field!(entry, vector_control).write(vector_control);
+        }
+    }
+
+    /// Masks the given entry, so the device won't send its message.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn mask(&mut self, index: usize) {
+        let mut entry = self.entry(index);
+        field!(entry, vector_control).modify(|control| control | MSIX_VECTOR_MASKED);
+    }
+
+    /// Unmasks the given entry, so the device may send its message.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn unmask(&mut self, index: usize) {
+        let mut entry = self.entry(index);
+        field!(entry, vector_control).modify(|control| control & !MSIX_VECTOR_MASKED);
+    }
+
+    /// Returns whether the given entry is masked.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn is_masked(&mut self, index: usize) -> bool {
+        let mut entry = self.entry(index);
+        field!(entry, vector_control).read() & MSIX_VECTOR_MASKED != 0
+    }
+
+    fn entry(&mut self, index: usize) -> UniqueMmioPointer<'_, MsixTableEntry> {
+        self.entries
+            .get(index)
+            .expect("MSI-X table index out of bounds")
+    }
+}
+
+/// The size of the configuration space of a single PCIe function in ECAM.
+const ECAM_FUNCTION_SIZE: usize = 1 << 12;
+
+/// The address of a PCI function.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct PciAddress {
+    /// The bus number.
+    pub bus: u8,
+    /// The device number, which must be less than 32.
+    pub device: u8,
+    /// The function number, which must be less than 8.
+    pub function: u8,
+}
+
+/// The header common to the configuration space of all PCI functions.
+#[derive(Clone, Debug, Default, Eq, FromBytes, Immutable, IntoBytes, KnownLayout, PartialEq)]
+#[repr(C, align(4))]
+pub struct ConfigHeader {
+    /// The vendor ID, or 0xffff if there is no function at this address.
+    pub vendor_id: ReadPure<u16>,
+    /// The device ID.
+    pub device_id: ReadPure<u16>,
+    /// The command register.
+    ///
+    /// This can enable bus mastering and so DMA, so it can only be written with the unsafe
+    /// [`Ecam::write_u16`].
+    pub command: ReadPure<u16>,
+    /// The status register.
+    pub status: ReadPureWrite<u16>,
+    /// The revision ID.
+    pub revision_id: ReadPure<u8>,
+    /// The programming interface.
+    pub prog_if: ReadPure<u8>,
+    /// The subclass.
+    pub subclass: ReadPure<u8>,
+    /// The class code.
+    pub class_code: ReadPure<u8>,
+    /// The cache line size.
+    pub cache_line_size: ReadPureWrite<u8>,
+    /// The latency timer.
+    pub latency_timer: ReadPureWrite<u8>,
+    /// The header type, including the multi-function bit.
+    pub header_type: ReadPure<u8>,
+    /// The built-in self test register.
+    ///
+    /// Writing this can start a self test, so it can only be written with the unsafe
+    /// [`Ecam::write_u8`].
+    pub bist: ReadPure<u8>,
+}
+
+/// A PCIe Enhanced Configuration Access Mechanism (ECAM) window.
+///
+/// Each bus has 1 MiB of configuration space in the window, and each function on a bus has 4 KiB.
+///
+/// # Example
+///
+/// ```
+/// use safe_mmio::{
+///     UniqueMmioPointer, field_shared,
+///     pci::{Ecam, PciAddress},
+/// };
+///
+/// let window: UniqueMmioPointer<[u8]>;
+/// # let mut fake = [0xffff_ffffu32; 2048];
+/// # fake[0] = 0x1234_1af4;
+/// # window = UniqueMmioPointer::from(zerocopy::IntoBytes::as_mut_bytes(fake.as_mut_slice()));
+/// let mut ecam = Ecam::new(window, 0);
+/// let address = PciAddress { bus: 0, device: 0, function: 0 };
+/// assert_eq!(ecam.read_u16(address, 0), Some(0x1af4));
+/// let header = ecam.header(address).unwrap();
+/// assert_eq!(field_shared!(header, device_id).read(), 0x1234);
+/// ```
+#[derive(Debug)]
+pub struct Ecam<'a> {
+    window: UniqueMmioPointer<'a, [u8]>,
+    start_bus: u8,
+}
+
+impl<'a> Ecam<'a> {
+    /// Creates a new `Ecam` for the given window, the start of which corresponds to `start_bus`.
+    ///
+    /// Panics if the window isn't aligned to 4 bytes.
+    pub fn new(window: UniqueMmioPointer<'a, [u8]>, start_bus: u8) -> Self {
+        assert!(
+            window.ptr().cast::<u8>().align_offset(4) == 0,
+            "ECAM window must be aligned to 4 bytes"
+        );
+        Self { window, start_bus }
+    }
+
+    /// Returns a pointer to the common configuration header of the given function, or `None` if
+    /// it is outside the window.
+    pub fn header(&mut self, address: PciAddress) -> Option<UniqueMmioPointer<'_, ConfigHeader>> {
+        let regs = self.config_pointer::<ConfigHeader>(address, 0)?;
+        // SAFETY: config_pointer checked that the pointer is within the window, and it is aligned
+        // because the window is aligned and function offsets are multiples of 4 KiB.
+        Some(unsafe { self.window.child(regs) })
+    }
+
+    /// Reads a byte from the configuration space of the given function, or returns `None` if the
+    /// offset is outside the window.
+    pub fn read_u8(&self, address: PciAddress, offset: u16) -> Option<u8> {
+        self.read(address, offset)
+    }
+
+    /// Reads a 16-bit value from the configuration space of the given function, or returns `None`
+    /// if the offset is outside the window or not aligned.
+    pub fn read_u16(&self, address: PciAddress, offset: u16) -> Option<u16> {
+        self.read(address, offset)
+    }
+
+    /// Reads a 32-bit value from the configuration space of the given function, or returns `None`
+    /// if the offset is outside the window or not aligned.
+    pub fn read_u32(&self, address: PciAddress, offset: u16) -> Option<u32> {
+        self.read(address, offset)
+    }
+
+    /// Writes a byte to the configuration space of the given function, or returns `None` if the
+    /// offset is outside the window.
+    ///
+    /// # Safety
+    ///
+    /// The write must not have any side-effects which violate memory safety, such as remapping a
+    /// BAR over memory which is in use.
+    pub unsafe fn write_u8(&mut self, address: PciAddress, offset: u16, value: u8) -> Option<()> {
+        // SAFETY: The caller promised that the write is safe.
+        unsafe { self.write(address, offset, value) }
+    }
+
+    /// Writes a 16-bit value to the configuration space of the given function, or returns `None`
+    /// if the offset is outside the window or not aligned.
+    ///
+    /// # Safety
+    ///
+    /// The write must not have any side-effects which violate memory safety, such as remapping a
+    /// BAR over memory which is in use.
+    pub unsafe fn write_u16(&mut self, address: PciAddress, offset: u16, value: u16) -> Option<()> {
+        // SAFETY: The caller promised that the write is safe.
+        unsafe { self.write(address, offset, value) }
+    }
+
+    /// Writes a 32-bit value to the configuration space of the given function, or returns `None`
+    /// if the offset is outside the window or not aligned.
+    ///
+    /// # Safety
+    ///
+    /// The write must not have any side-effects which violate memory safety, such as remapping a
+    /// BAR over memory which is in use.
+    pub unsafe fn write_u32(&mut self, address: PciAddress, offset: u16, value: u32) -> Option<()> {
+        // SAFETY: The caller promised that the write is safe.
+        unsafe { self.write(address, offset, value) }
+    }
+
+    fn read<T: AtomicAccess>(&self, address: PciAddress, offset: u16) -> Option<T> {
+        let regs = self.config_pointer::<ReadPure<T>>(address, offset)?;
+        // SAFETY: config_pointer checked that the pointer is within the window and aligned.
+        let regs = unsafe { SharedMmioPointer::child(&self.window, regs) };
+        Some(regs.read())
+    }
+
+    /// # Safety
+    ///
+    /// The write must not have any side-effects which violate memory safety.
+    unsafe fn write<T: AtomicAccess>(
+        &mut self,
+        address: PciAddress,
+        offset: u16,
+        value: T,
+    ) -> Option<()> {
+        let regs = self.config_pointer::<T>(address, offset)?;
+        // SAFETY: config_pointer checked that the pointer is within the window and aligned.
+        let mut regs = unsafe { self.window.child(regs) };
+        // SAFETY: The caller promised that the write is safe.
+        unsafe { regs.write_unsafe(value) };
+        Some(())
+    }
+
+    /// Returns a pointer to a `T` at the given offset in the configuration space of the given
+    /// function, or `None` if any part of it is outside the window or it is not aligned.
+    fn config_pointer<T>(&self, address: PciAddress, offset: u16) -> Option<NonNull<T>> {
+        if address.device >= 32
+            || address.function >= 8
+            || usize::from(offset) + size_of::<T>() > ECAM_FUNCTION_SIZE
+            || usize::from(offset) % align_of::<T>() != 0
+        {
+            return None;
+        }
+        let bus = address.bus.checked_sub(self.start_bus)?;
+        let window_offset = (usize::from(bus) << 20)
+            | (usize::from(address.device) << 15)
+            | (usize::from(address.function) << 12)
+            | usize::from(offset);
+        if window_offset + size_of::<T>() > self.window.len() {
+            return None;
+        }
+        NonNull::new(
+            self.window
+                .ptr()
+                .cast::<u8>()
+                .wrapping_add(window_offset)
+                .cast_mut(),
+        )
+        .map(NonNull::cast)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_32bit() {
+        let bar = MemoryBar::decode(0xfebf_0000, None).unwrap();
+        assert_eq!(bar.address(), 0xfebf_0000);
+        assert!(!bar.is_64bit());
+        assert!(!bar.prefetchable());
+
+        let bar = MemoryBar::decode(0xfebf_0008, Some(0x1234)).unwrap();
+        assert_eq!(bar.address(), 0xfebf_0000);
+        assert!(bar.prefetchable());
+    }
+
+    #[test]
+    fn decode_64bit() {
+        let bar = MemoryBar::decode(0x8000_000c, Some(0x80)).unwrap();
+        assert_eq!(bar.address(), 0x80_8000_0000);
+        assert!(bar.is_64bit());
+        assert!(bar.prefetchable());
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(MemoryBar::decode(0xc001, None), Err(BarError::IoSpace));
+        assert_eq!(
+            MemoryBar::decode(0x8000_0002, None),
+            Err(BarError::ReservedType)
+        );
+        assert_eq!(
+            MemoryBar::decode(0x8000_0006, None),
+            Err(BarError::ReservedType)
+        );
+    }
+
+    #[test]
+    fn from_bars() {
+        let bars = [0x1000_0004, 0x2, 0x3000_0000, 0xc001, 0, 0x4000_0004];
+        assert_eq!(
+            MemoryBar::from_bars(&bars, 0).unwrap().address(),
+            0x2_1000_0000
+        );
+        assert_eq!(
+            MemoryBar::from_bars(&bars, 2).unwrap().address(),
+            0x3000_0000
+        );
+        assert_eq!(MemoryBar::from_bars(&bars, 3), Err(BarError::IoSpace));
+        assert_eq!(
+            MemoryBar::from_bars(&bars, 5),
+            Err(BarError::IndexOutOfRange)
+        );
+        assert_eq!(
+            MemoryBar::from_bars(&bars, 6),
+            Err(BarError::IndexOutOfRange)
+        );
+    }
+
+    #[test]
+    fn size() {
+        assert_eq!(memory_bar_size(0xffff_f000, None), 0x1000);
+        assert_eq!(memory_bar_size(0xfff0_0008, None), 0x10_0000);
+        assert_eq!(memory_bar_size(0xfff0_000c, Some(0xffff_ffff)), 0x10_0000);
+        assert_eq!(
+            memory_bar_size(0x0000_000c, Some(0xffff_fff0)),
+            0x10_0000_0000
+        );
+        assert_eq!(memory_bar_size(0, None), 0);
+        assert_eq!(memory_bar_size(0x4, Some(0)), 0);
+    }
+
+    #[test]
+    fn physical_instance() {
+        let bar = MemoryBar::decode(0xfebf_0000, None).unwrap();
+        // SAFETY: The instance is never used to access any device.
+        unsafe {
+            let instance = physical_instance_from_bar::<[u32; 4]>(&bar, 0x1000).unwrap();
+            assert_eq!(instance.pa(), 0xfebf_0000);
+            assert_eq!(
+                physical_instance_from_bar::<[u32; 4]>(&bar, 8),
+                Err(BarError::TooSmall {
+                    expected: 16,
+                    actual: 8
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn msix_table() {
+        let mut entries = [const {
+            MsixTableEntry {
+                message_address_lower: ReadPureWrite(0),
+                message_address_upper: ReadPureWrite(0),
+                message_data: ReadPureWrite(0),
+                vector_control: ReadPureWrite(MSIX_VECTOR_MASKED | 0x100),
+            }
+        }; 3];
+
+        {
+            let mut table = MsixTable::new(UniqueMmioPointer::from(entries.as_mut_slice()));
+            assert_eq!(table.len(), 3);
+            assert!(table.is_masked(0));
+
+            table.set_entry(0, 0x1_fee0_1000, 42);
+            assert!(table.is_masked(0));
+
+            table.unmask(1);
+            assert!(!table.is_masked(1));
+            table.set_entry(1, 0xfee0_2000, 43);
+            assert!(!table.is_masked(1));
+
+            table.unmask(2);
+            table.mask(2);
+            assert!(table.is_masked(2));
+        }
+
+        assert_eq!(entries[0].message_address_lower.0, 0xfee0_1000);
+        assert_eq!(entries[0].message_address_upper.0, 0x1);
+        assert_eq!(entries[0].message_data.0, 42);
+        assert_eq!(entries[0].vector_control.0, 0x101);
+        assert_eq!(entries[1].message_address_lower.0, 0xfee0_2000);
+        assert_eq!(entries[1].message_data.0, 43);
+        assert_eq!(entries[1].vector_control.0, 0x100);
+        assert_eq!(entries[2].vector_control.0, 0x101);
+    }
+
+    #[test]
+    #[should_panic]
+    fn msix_table_out_of_bounds() {
+        let mut entries = [MsixTableEntry::default()];
+        let mut table = MsixTable::new(UniqueMmioPointer::from(entries.as_mut_slice()));
+        table.mask(1);
+    }
+
+    #[test]
+    fn ecam() {
+        // Two functions' worth of configuration space.
+        let mut config = [0u32; 2048];
+        config[0] = 0x1234_1af4;
+        config[2] = 0x0c03_3001;
+        config[1024] = 0xffff_ffff;
+
+        {
+            let window = UniqueMmioPointer::from(config.as_mut_bytes());
+            let mut ecam = Ecam::new(window, 1);
+            let function0 = PciAddress {
+                bus: 1,
+                device: 0,
+                function: 0,
+            };
+            let function1 = PciAddress {
+                function: 1,
+                ..function0
+            };
+
+            assert_eq!(ecam.read_u32(function0, 0), Some(0x1234_1af4));
+            assert_eq!(ecam.read_u16(function0, 2), Some(0x1234));
+            assert_eq!(ecam.read_u8(function0, 11), Some(0x0c));
+            assert_eq!(ecam.read_u16(function1, 0), Some(0xffff));
+
+            let mut header = ecam.header(function0).unwrap();
+            assert_eq!(field!(header, vendor_id).read(), 0x1af4);
+            assert_eq!(field!(header, class_code).read(), 0x0c);
+            assert_eq!(field!(header, subclass).read(), 0x03);
+            assert_eq!(field!(header, command).read(), 0);
+
+            // SAFETY: This is just a fake, so writes have no side-effects.
+            unsafe {
+                assert_eq!(ecam.write_u16(function0, 0x04, 0x0006), Some(()));
+                assert_eq!(ecam.write_u32(function1, 0x10, 0xfebf_0000), Some(()));
+                assert_eq!(ecam.write_u8(function1, 0x3c, 0x2a), Some(()));
+                assert_eq!(ecam.write_u16(function1, 0x3d, 0), None);
+            }
+
+            // Misaligned.
+            assert_eq!(ecam.read_u32(function0, 2), None);
+            // Past the end of the function's configuration space.
+            assert_eq!(ecam.read_u32(function0, 0x1000), None);
+            // Outside the window.
+            assert_eq!(
+                ecam.read_u8(
+                    PciAddress {
+                        function: 2,
+                        ..function0
+                    },
+                    0
+                ),
+                None
+            );
+            assert!(
+                ecam.header(PciAddress {
+                    device: 1,
+                    ..function0
+                })
+                .is_none()
+            );
+            // Before the start bus.
+            assert_eq!(
+                ecam.read_u8(
+                    PciAddress {
+                        bus: 0,
+                        ..function0
+                    },
+                    0
+                ),
+                None
+            );
+            // Invalid device and function numbers.
+            assert_eq!(
+                ecam.read_u8(
+                    PciAddress {
+                        device: 32,
+                        ..function0
+                    },
+                    0
+                ),
+                None
+            );
+            assert_eq!(
+                ecam.read_u8(
+                    PciAddress {
+                        function: 8,
+                        ..function0
+                    },
+                    0
+                ),
+                None
+            );
+        }
+
+        assert_eq!(config[1], 0x0006);
+        assert_eq!(config[1024 + 4], 0xfebf_0000);
+        assert_eq!(config[1024 + 15], 0x2a);
+    }
+}
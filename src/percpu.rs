@@ -0,0 +1,176 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Support for devices which have a separate register frame for each CPU.
+
+use crate::UniqueMmioPointer;
+use core::marker::PhantomData;
+
+/// Provides the index of the CPU which the caller is running on.
+pub trait CurrentCpu {
+    /// Returns the index of the current CPU.
+    ///
+    /// On aarch64 this would usually be derived from the affinity fields of `MPIDR_EL1`.
+    fn current_cpu() -> usize;
+}
+
+/// A container of one register frame for each of `N` CPUs, which only gives access to the frame for
+/// the current CPU.
+///
+/// The caller must make sure that it isn't migrated to a different CPU while using the pointer
+/// returned by [`current`](Self::current), e.g. by disabling preemption.
+///
+/// # Example
+///
+/// ```
+/// use safe_mmio::{
+///     UniqueMmioPointer,
+///     fields::ReadWrite,
+///     percpu::{CurrentCpu, PerCpu},
+/// };
+///
+/// struct Mpidr;
+///
+/// impl CurrentCpu for Mpidr {
+///     fn current_cpu() -> usize {
+///         // Read MPIDR_EL1 here.
+///         # 1
+///     }
+/// }
+///
+/// let frames: UniqueMmioPointer<[ReadWrite<u32>; 4]>;
+/// # let mut fake = [const { ReadWrite(0) }; 4];
+/// # frames = UniqueMmioPointer::from(&mut fake);
+/// let mut timers = PerCpu::<_, Mpidr, 4>::new(frames.split());
+/// let mut timer = timers.current().unwrap();
+/// // SAFETY: The pointer is only used on this CPU.
+/// unsafe { timer.regs() }.write(1000);
+/// ```
+#[derive(Debug)]
+pub struct PerCpu<'a, T, C: CurrentCpu, const N: usize> {
+    frames: [UniqueMmioPointer<'a, T>; N],
+    _current_cpu: PhantomData<C>,
+}
+
+impl<'a, T, C: CurrentCpu, const N: usize> PerCpu<'a, T, C, N> {
+    /// Creates a new `PerCpu` from the register frames for each CPU, in order of CPU index.
+    pub const fn new(frames: [UniqueMmioPointer<'a, T>; N]) -> Self {
+        Self {
+            frames,
+            _current_cpu: PhantomData,
+        }
+    }
+
+    /// Returns a pointer to the register frame for the current CPU, or `None` if the current CPU
+    /// index is out of range.
+    ///
+    /// The pointer is wrapped in [`NotSendable`] so that it can't be sent to another CPU.
+    pub fn current(&mut self) -> Option<NotSendable<'_, T>> {
+        let frame = self.frames.get_mut(C::current_cpu())?;
+        Some(NotSendable::new(frame.reborrow()))
+    }
+
+    /// Returns the register frames for all CPUs.
+    pub fn into_inner(self) -> [UniqueMmioPointer<'a, T>; N] {
+        self.frames
+    }
+}
+
+/// A pointer to device registers which must only be accessed from the CPU which created it, such
+/// as a GIC redistributor frame.
+///
+/// Unlike `UniqueMmioPointer`, this is neither `Send` nor `Sync`, so it can't be moved to or shared
+/// with another thread which might be running on a different CPU. Getting the underlying
+/// `UniqueMmioPointer` back out is `unsafe`, as that is `Send`.
+///
+/// ```compile_fail
+/// use safe_mmio::{UniqueMmioPointer, fields::ReadWrite, percpu::NotSendable};
+///
+/// fn assert_send<T: Send>(_: T) {}
+///
+/// let mut fake = ReadWrite(0u32);
+/// assert_send(NotSendable::new(UniqueMmioPointer::from(&mut fake)));
+/// ```
+#[derive(Debug)]
+pub struct NotSendable<'a, T: ?Sized> {
+    regs: UniqueMmioPointer<'a, T>,
+    _not_send: PhantomData<*mut ()>,
+}
+
+impl<'a, T: ?Sized> NotSendable<'a, T> {
+    /// Wraps the given pointer so that it can't be sent to another thread.
+    pub const fn new(regs: UniqueMmioPointer<'a, T>) -> Self {
+        Self {
+            regs,
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Returns a pointer to the device registers.
+    ///
+    /// # Safety
+    ///
+    /// The returned pointer, and any pointers derived from it, must only be used on the CPU which
+    /// created this `NotSendable`.
+    pub const unsafe fn regs(&mut self) -> UniqueMmioPointer<'_, T> {
+        self.regs.reborrow()
+    }
+
+    /// Returns the original pointer.
+    ///
+    /// # Safety
+    ///
+    /// The returned pointer, and any pointers derived from it, must only be used on the CPU which
+    /// created this `NotSendable`.
+    pub unsafe fn into_inner(self) -> UniqueMmioPointer<'a, T> {
+        self.regs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fields::ReadWrite;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static FAKE_CPU: AtomicUsize = AtomicUsize::new(0);
+
+    struct FakeCpu;
+
+    impl CurrentCpu for FakeCpu {
+        fn current_cpu() -> usize {
+            FAKE_CPU.load(Ordering::Relaxed)
+        }
+    }
+
+    #[test]
+    fn per_cpu() {
+        let mut fake = [ReadWrite(0u32), ReadWrite(0)];
+        {
+            let frames = UniqueMmioPointer::from(&mut fake);
+            let mut per_cpu = PerCpu::<_, FakeCpu, 2>::new(frames.split());
+            FAKE_CPU.store(1, Ordering::Relaxed);
+            // SAFETY: The test only uses the pointer on the current thread.
+            unsafe { per_cpu.current().unwrap().regs().write(11) };
+            FAKE_CPU.store(0, Ordering::Relaxed);
+            // SAFETY: The test only uses the pointer on the current thread.
+            unsafe { per_cpu.current().unwrap().regs().write(10) };
+            FAKE_CPU.store(2, Ordering::Relaxed);
+            assert!(per_cpu.current().is_none());
+        }
+        assert_eq!(fake[0].0, 10);
+        assert_eq!(fake[1].0, 11);
+    }
+
+    #[test]
+    fn not_sendable() {
+        let mut fake = ReadWrite(0u32);
+        let mut regs = NotSendable::new(UniqueMmioPointer::from(&mut fake));
+        // SAFETY: The test only uses the pointers on the current thread.
+        unsafe {
+            regs.regs().write(42);
+            assert_eq!(regs.into_inner().read(), 42);
+        }
+    }
+}
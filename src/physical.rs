@@ -3,31 +3,107 @@
 // See LICENSE-APACHE and LICENSE-MIT for details.
 
 use core::{
+    cmp::Ordering,
     fmt::{self, Debug, Formatter},
     marker::PhantomData,
 };
 
-/// The physical instance of some device's MMIO space.
-pub struct PhysicalInstance<T> {
-    pa: usize,
-    _phantom: PhantomData<T>,
+/// A physical address space in which a device's MMIO region may be found, such as the Secure or
+/// Non-secure world.
+///
+/// Firmware which maps devices for several worlds can take a `PhysicalInstance` tagged with a
+/// particular world in its mapping functions, so that an instance for the wrong world is rejected
+/// at compile time.
+pub trait World {
+    /// The name of the world, for debug output.
+    const NAME: &'static str;
 }
 
-impl<T> Debug for PhysicalInstance<T> {
+/// The Non-secure physical address space. This is the default for [`PhysicalInstance`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct NonSecure;
+
+/// The Secure physical address space.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Secure;
+
+/// The Realm physical address space of the Arm Realm Management Extension.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Realm;
+
+impl World for NonSecure {
+    const NAME: &'static str = "NonSecure";
+}
+
+impl World for Secure {
+    const NAME: &'static str = "Secure";
+}
+
+impl World for Realm {
+    const NAME: &'static str = "Realm";
+}
+
+/// The physical instance of some device's MMIO space, in the physical address space of world `W`.
+pub struct PhysicalInstance<T, W: World = NonSecure> {
+    pa: u64,
+    _phantom: PhantomData<(T, W)>,
+}
+
+impl<T, W: World> Debug for PhysicalInstance<T, W> {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         f.debug_struct("PhysicalInstance")
             .field("pa", &self.pa)
             .field("size", &size_of::<T>())
+            .field("world", &W::NAME)
             .finish()
     }
 }
 
-impl<T> PhysicalInstance<T> {
+// Implement comparison traits manually rather than deriving to avoid an unneccessary bound on T.
+
+impl<T, W: World> PartialEq for PhysicalInstance<T, W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.pa == other.pa
+    }
+}
+
+impl<T, W: World> Eq for PhysicalInstance<T, W> {}
+
+impl<T, W: World> PartialOrd for PhysicalInstance<T, W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// `PhysicalInstance`s are ordered by their physical base address.
+impl<T, W: World> Ord for PhysicalInstance<T, W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.pa.cmp(&other.pa)
+    }
+}
+
+impl<T, W: World> PhysicalInstance<T, W> {
     /// # Safety
     ///
-    /// This must refer to the physical address of a real set of device registers of type `T`, and
-    /// there must only ever be a single `PhysicalInstance` created for those device registers.
+    /// This must refer to the physical address of a real set of device registers of type `T` in
+    /// the physical address space of world `W`, and there must only ever be a single
+    /// `PhysicalInstance` created for those device registers.
     pub const unsafe fn new(pa: usize) -> Self {
+        Self {
+            pa: pa as u64,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Creates a `PhysicalInstance` for registers at a physical address which may not fit in a
+    /// `usize`, such as a peripheral above 4 GiB on a 32-bit target with LPAE.
+    ///
+    /// # Safety
+    ///
+    /// This must refer to the physical address of a real set of device registers of type `T` in
+    /// the physical address space of world `W`, and there must only ever be a single
+    /// `PhysicalInstance` created for those device registers.
+    pub const unsafe fn new_u64(pa: u64) -> Self {
         Self {
             pa,
             _phantom: PhantomData,
@@ -35,7 +111,136 @@ impl<T> PhysicalInstance<T> {
     }
 
     /// Returns the physical base address of the device's registers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the address doesn't fit in a `usize`. This can only happen on targets with
+    /// pointers narrower than 64 bits, for instances created with [`new_u64`](Self::new_u64). Use
+    /// [`pa_u64`](Self::pa_u64) if this is possible.
     pub const fn pa(&self) -> usize {
+        assert!(
+            self.pa <= usize::MAX as u64,
+            "Physical address doesn't fit in usize"
+        );
+        self.pa as usize
+    }
+
+    /// Returns the physical base address of the device's registers, as a `u64`.
+    pub const fn pa_u64(&self) -> u64 {
         self.pa
     }
+
+    /// Returns whether the given physical address is within the device's MMIO region.
+    pub const fn contains(&self, pa: usize) -> bool {
+        self.contains_u64(pa as u64)
+    }
+
+    /// Returns whether the given physical address, which may not fit in a `usize`, is within the
+    /// device's MMIO region.
+    pub const fn contains_u64(&self, pa: u64) -> bool {
+        pa.wrapping_sub(self.pa) < size_of::<T>() as u64
+    }
+
+    /// Returns whether the device's MMIO region overlaps with that of the given other device in the
+    /// same world.
+    ///
+    /// Zero-sized regions never overlap anything.
+    pub const fn overlaps<U>(&self, other: &PhysicalInstance<U, W>) -> bool {
+        if size_of::<T>() == 0 || size_of::<U>() == 0 {
+            return false;
+        }
+        self.contains_u64(other.pa) || other.contains_u64(self.pa)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contains() {
+        // SAFETY: These instances are never used to access any device.
+        let instance = unsafe { PhysicalInstance::<[u32; 4]>::new(0x1000) };
+        assert!(!instance.contains(0xfff));
+        assert!(instance.contains(0x1000));
+        assert!(instance.contains(0x100f));
+        assert!(!instance.contains(0x1010));
+
+        // SAFETY: These instances are never used to access any device.
+        let top = unsafe { PhysicalInstance::<u64>::new(usize::MAX - 7) };
+        assert!(top.contains(usize::MAX));
+        assert!(!top.contains(0));
+
+        // SAFETY: These instances are never used to access any device.
+        let empty = unsafe { PhysicalInstance::<()>::new(0x1000) };
+        assert!(!empty.contains(0x1000));
+    }
+
+    #[test]
+    fn above_4_gib() {
+        // SAFETY: This instance is never used to access any device.
+        let instance = unsafe { PhysicalInstance::<[u32; 4]>::new_u64(0x1_0000_1000) };
+        assert_eq!(instance.pa_u64(), 0x1_0000_1000);
+        assert!(instance.contains_u64(0x1_0000_100f));
+        assert!(!instance.contains_u64(0x1000));
+        assert!(!instance.contains(0x1000));
+    }
+
+    #[test]
+    fn overlaps() {
+        // SAFETY: These instances are never used to access any device.
+        let (a, b, c, d, empty) = unsafe {
+            (
+                PhysicalInstance::<[u32; 4]>::new(0x1000),
+                PhysicalInstance::<[u32; 4]>::new(0x1010),
+                PhysicalInstance::<u64>::new(0x100c),
+                PhysicalInstance::<[u8; 0x100]>::new(0x0f00),
+                PhysicalInstance::<()>::new(0x1004),
+            )
+        };
+        assert!(!a.overlaps(&b));
+        assert!(!b.overlaps(&a));
+        assert!(a.overlaps(&c));
+        assert!(c.overlaps(&a));
+        assert!(b.overlaps(&c));
+        assert!(a.overlaps(&a));
+        assert!(!a.overlaps(&d));
+        assert!(!a.overlaps(&empty));
+        assert!(!empty.overlaps(&a));
+    }
+
+    #[test]
+    fn worlds() {
+        // SAFETY: These instances are never used to access any device.
+        let (non_secure, secure) = unsafe {
+            (
+                PhysicalInstance::<u32>::new(0x1000),
+                PhysicalInstance::<u32, Secure>::new(0x1000),
+            )
+        };
+        fn map_secure<T>(instance: PhysicalInstance<T, Secure>) -> usize {
+            instance.pa()
+        }
+        // `map_secure(non_secure)` wouldn't compile.
+        assert_eq!(map_secure(secure), non_secure.pa());
+    }
+
+    #[test]
+    fn ordering() {
+        // SAFETY: These instances are never used to access any device.
+        let mut instances = unsafe {
+            [
+                PhysicalInstance::<u32>::new(0x3000),
+                PhysicalInstance::<u32>::new(0x1000),
+                PhysicalInstance::<u32>::new(0x2000),
+            ]
+        };
+        assert!(instances[1] < instances[2]);
+        assert_ne!(instances[0], instances[1]);
+        instances.sort();
+        assert_eq!(
+            instances.map(|instance| instance.pa()),
+            [0x1000, 0x2000, 0x3000]
+        );
+    }
 }
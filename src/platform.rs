@@ -0,0 +1,132 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! The unsafe entry points for creating MMIO pointers, for use by platform and board code.
+//!
+//! Creating a [`UniqueMmioPointer`] to real device memory is inherently unsafe, as only the platform
+//! knows where devices are mapped and that nothing else is accessing them. Everything after that is
+//! safe: projecting to fields with [`field!`](crate::field), [`field_shared!`](crate::field_shared)
+//! and [`split_fields_checked!`](crate::split_fields_checked), indexing and splitting arrays, and
+//! reading and writing registers whose wrappers permit it.
+//!
+//! So a system can be split into a platform crate, which uses the functions in this module to
+//! create pointers to each device, and driver crates, which accept those pointers and only use the
+//! safe API. The driver crates can then be `#![forbid(unsafe_code)]`.
+//!
+//! With the `alias-check` feature enabled, the functions in this module return
+//! `alias_check::Checked` pointers instead, which panic if they overlap another device pointer
+//! created by the platform. Either way, the platform lends drivers a `UniqueMmioPointer` with
+//! `reborrow`, as in the example below.
+//!
+//! # Example
+//!
+//! ```
+//! mod uart_driver {
+//!     #![forbid(unsafe_code)]
+//!
+//!     use safe_mmio::prelude::*;
+//!
+//!     #[repr(C)]
+//!     pub struct Registers {
+//!         data: ReadWrite<u32>,
+//!         status: ReadPure<u32>,
+//!     }
+//!
+//!     pub struct Uart<'a> {
+//!         regs: UniqueMmioPointer<'a, Registers>,
+//!     }
+//!
+//!     impl<'a> Uart<'a> {
+//!         pub fn new(regs: UniqueMmioPointer<'a, Registers>) -> Self {
+//!             Self { regs }
+//!         }
+//!
+//!         pub fn write_byte(&mut self, byte: u8) {
+//!             while field_shared!(self.regs, status).read() & 0x20 != 0 {}
+//!             field!(self.regs, data).write(byte.into());
+//!         }
+//!     }
+//! }
+//!
+//! # fn platform(address: usize) {
+//! // In the platform crate:
+//! // SAFETY: The UART is mapped as device memory at this address, and nothing else accesses it.
+//! let mut regs = unsafe { safe_mmio::platform::from_exposed_addr(address) }.unwrap();
+//! let mut uart = uart_driver::Uart::new(regs.reborrow());
+//! uart.write_byte(b'x');
+//! # }
+//! ```
+
+use crate::UniqueMmioPointer;
+#[cfg(feature = "alias-check")]
+use crate::alias_check::{ByteLen, Checked};
+use core::ptr::NonNull;
+
+/// Creates a new `UniqueMmioPointer` from a non-null raw pointer.
+///
+/// This is the same as [`UniqueMmioPointer::new`].
+///
+/// # Safety
+///
+/// `regs` must satisfy the requirements of [`UniqueMmioPointer::new`].
+#[cfg(not(feature = "alias-check"))]
+pub const unsafe fn unique<'a, T: ?Sized>(regs: NonNull<T>) -> UniqueMmioPointer<'a, T> {
+    // SAFETY: Our caller promised that `regs` satisfies the requirements of `new`.
+    unsafe { UniqueMmioPointer::new(regs) }
+}
+
+/// Creates a new `UniqueMmioPointer` from a non-null raw pointer, and registers its address range
+/// until it is dropped.
+///
+/// This is the same as [`UniqueMmioPointer::new`] followed by [`Checked::new`].
+///
+/// # Safety
+///
+/// `regs` must satisfy the requirements of [`UniqueMmioPointer::new`].
+///
+/// # Panics
+///
+/// Panics if the address range overlaps that of another `Checked` pointer which still exists.
+#[cfg(feature = "alias-check")]
+#[track_caller]
+pub unsafe fn unique<'a, T: ?Sized + ByteLen>(regs: NonNull<T>) -> Checked<'a, T> {
+    // SAFETY: Our caller promised that `regs` satisfies the requirements of `new`.
+    Checked::new(unsafe { UniqueMmioPointer::new(regs) })
+}
+
+/// Creates a new `UniqueMmioPointer` from a virtual address using exposed provenance, or returns
+/// `None` if `addr` is 0.
+///
+/// This is the same as [`UniqueMmioPointer::try_from_exposed_addr`].
+///
+/// # Safety
+///
+/// If `addr` is not 0 then it must satisfy the requirements of
+/// [`UniqueMmioPointer::from_exposed_addr`].
+#[cfg(not(feature = "alias-check"))]
+pub unsafe fn from_exposed_addr<'a, T>(addr: usize) -> Option<UniqueMmioPointer<'a, T>> {
+    // SAFETY: Our caller promised that `addr` satisfies the requirements of `from_exposed_addr`.
+    unsafe { UniqueMmioPointer::try_from_exposed_addr(addr) }
+}
+
+/// Creates a new `UniqueMmioPointer` from a virtual address using exposed provenance, and registers
+/// its address range until it is dropped, or returns `None` if `addr` is 0.
+///
+/// This is the same as [`UniqueMmioPointer::try_from_exposed_addr`] followed by [`Checked::new`].
+///
+/// # Safety
+///
+/// If `addr` is not 0 then it must satisfy the requirements of
+/// [`UniqueMmioPointer::from_exposed_addr`].
+///
+/// # Panics
+///
+/// Panics if the address range overlaps that of another `Checked` pointer which still exists.
+#[cfg(feature = "alias-check")]
+#[track_caller]
+pub unsafe fn from_exposed_addr<'a, T>(addr: usize) -> Option<Checked<'a, T>> {
+    // SAFETY: Our caller promised that `addr` satisfies the requirements of `from_exposed_addr`.
+    let pointer = unsafe { UniqueMmioPointer::try_from_exposed_addr(addr) }?;
+    Some(Checked::new(pointer))
+}
@@ -0,0 +1,35 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Re-exports of the types, traits and macros which most drivers need.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_mmio::prelude::*;
+//!
+//! #[repr(C)]
+//! struct UartRegisters {
+//!     data: ReadWrite<u32>,
+//!     status: ReadPure<u32>,
+//! }
+//!
+//! fn write_byte(regs: &mut UniqueMmioPointer<UartRegisters>, byte: u8) {
+//!     while field_shared!(*regs, status).read() & 0x20 != 0 {}
+//!     field!(*regs, data).write(byte.into());
+//! }
+//! # let mut fake = UartRegisters { data: ReadWrite(0), status: ReadPure(0) };
+//! # write_byte(&mut UniqueMmioPointer::from(&mut fake), 42);
+//! ```
+
+pub use crate::{
+    PhysicalInstance, SharedMmioPointer, UniqueMmioPointer,
+    access::{PureReadable, SideEffectReadable, Writable},
+    field, field_shared,
+    fields::{
+        ReadConstant, ReadOnly, ReadPure, ReadPureWrite, ReadWrite, ReadWrite64HiLo,
+        ReadWrite64LoHi, WriteOnly,
+    },
+    split_fields, split_fields_checked,
+};
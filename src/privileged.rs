@@ -0,0 +1,116 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Helpers for [`Privileged`] registers, which can only be written by code holding a capability
+//! token.
+//!
+//! In a large firmware codebase it is useful to restrict which modules can change
+//! security-sensitive configuration, such as TrustZone address space controllers, even though
+//! other code needs to read it. Wrapping such registers in `Privileged<T, Token>` means that
+//! writing them needs a reference to a `Token`. If `Token` is a zero-sized type with a private
+//! field, only the module defining it can construct one, so this is enforced at compile time with
+//! no runtime cost.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_mmio::{UniqueMmioPointer, field, field_shared, fields::Privileged};
+//!
+//! #[repr(C)]
+//! pub struct TzcRegisters {
+//!     region_attributes: Privileged<u32, platform::SecureConfig>,
+//! }
+//!
+//! mod platform {
+//!     use super::TzcRegisters;
+//!     use safe_mmio::{UniqueMmioPointer, field};
+//!
+//!     /// Capability to change the TrustZone configuration.
+//!     pub struct SecureConfig(());
+//!
+//!     pub fn configure(tzc: &mut UniqueMmioPointer<TzcRegisters>) {
+//!         let token = SecureConfig(());
+//!         field!(*tzc, region_attributes).write(0xc000_0001, &token);
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let mut tzc: UniqueMmioPointer<TzcRegisters>;
+//! # let mut fake = TzcRegisters { region_attributes: Privileged(0, Default::default()) };
+//! # tzc = UniqueMmioPointer::from(&mut fake);
+//! platform::configure(&mut tzc);
+//! // Any code can read the register, but only `platform` can write it.
+//! assert_eq!(field_shared!(tzc, region_attributes).read(), 0xc000_0001);
+//! # }
+//! ```
+//!
+//! Code outside `platform` can't construct a `SecureConfig`, so can't write the register:
+//!
+//! ```compile_fail,E0423
+//! # mod platform {
+//! #     pub struct SecureConfig(());
+//! # }
+//! # use safe_mmio::{UniqueMmioPointer, fields::Privileged};
+//! # let mut fake = Privileged::<u32, platform::SecureConfig>(0, Default::default());
+//! let mut register = UniqueMmioPointer::from(&mut fake);
+//! register.write(0, &platform::SecureConfig(()));
+//! ```
+
+use crate::{SharedMmioPointer, UniqueMmioPointer, access::AtomicAccess, fields::Privileged};
+
+impl<T: AtomicAccess, Token> SharedMmioPointer<'_, Privileged<T, Token>> {
+    /// Performs an MMIO read of the entire `T`.
+    #[inline(always)]
+    pub fn read(&self) -> T {
+        // SAFETY: self.regs is always a valid pointer to MMIO address space, and the field being
+        // wrapped in `Privileged` implies that it is safe to read from a shared reference because
+        // doing so has no side-effects. `Privileged<T, Token>` is a transparent wrapper around `T`,
+        // so the child pointer is exactly the same allocation.
+        unsafe { self.child(self.regs.cast::<T>()).read_unsafe() }
+    }
+}
+
+impl<T: AtomicAccess, Token> UniqueMmioPointer<'_, Privileged<T, Token>> {
+    /// Performs an MMIO write of the entire `T`, given proof that the caller holds the capability
+    /// to do so.
+    #[inline(always)]
+    pub fn write(&mut self, value: T, _token: &Token) {
+        let regs = self.regs.cast::<T>();
+        // SAFETY: `Privileged<T, Token>` is a transparent wrapper around `T`, so the child pointer
+        // is exactly the same allocation. The field being wrapped in `Privileged` implies that it
+        // is safe to write by anyone holding a `Token`.
+        unsafe { self.child(regs).write_unsafe(value) }
+    }
+
+    /// Performs an MMIO read of the entire `T`, applies the given function to it, and then performs
+    /// an MMIO write of the resulting value.
+    ///
+    /// This is equivalent to calling [`read`](SharedMmioPointer::read) then
+    /// [`write`](Self::write).
+    pub fn modify(&mut self, token: &Token, f: impl FnOnce(T) -> T) {
+        let value = self.read();
+        self.write(f(value), token);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::marker::PhantomData;
+
+    struct Token;
+
+    #[test]
+    fn read_write() {
+        let mut fake = Privileged(0x10u32, PhantomData::<Token>);
+        {
+            let mut register = UniqueMmioPointer::from(&mut fake);
+            assert_eq!(register.read(), 0x10);
+            register.write(0x20, &Token);
+            register.modify(&Token, |value| value | 1);
+            assert_eq!(register.read(), 0x21);
+        }
+        assert_eq!(fake.0, 0x21);
+    }
+}
@@ -0,0 +1,156 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Kani proof harnesses for the pointer projection methods and macros.
+//!
+//! Run these with `cargo kani --features verification`.
+
+use crate::{
+    SharedMmioPointer, UniqueMmioPointer,
+    fields::{ReadOnly, ReadPure, ReadWrite},
+};
+use core::mem::size_of;
+
+const LEN: usize = 4;
+const ELEMENT_SIZE: usize = size_of::<ReadWrite<u32>>();
+
+#[repr(C)]
+struct Registers {
+    control: ReadWrite<u32>,
+    status: ReadPure<u16>,
+    data: ReadOnly<u64>,
+}
+
+/// Checks that the given child lies entirely within the parent.
+fn assert_within<T: ?Sized, U>(
+    parent: &SharedMmioPointer<T>,
+    parent_size: usize,
+    child: &SharedMmioPointer<U>,
+) {
+    assert!(child.addr() >= parent.addr());
+    assert!(child.addr() - parent.addr() + size_of::<U>() <= parent_size);
+}
+
+/// Checks that the two given pointers don't overlap.
+fn assert_disjoint<T, U>(a: &SharedMmioPointer<T>, b: &SharedMmioPointer<U>) {
+    assert!(a.addr() + size_of::<T>() <= b.addr() || b.addr() + size_of::<U>() <= a.addr());
+}
+
+#[kani::proof]
+fn slice_get_in_bounds() {
+    let mut fake = [const { ReadWrite(0u32) }; LEN];
+    let len: usize = kani::any();
+    kani::assume(len <= LEN);
+    let index: usize = kani::any();
+
+    let mut regs = UniqueMmioPointer::from(&mut fake[..len]);
+    let parent = *regs;
+    match regs.get(index) {
+        Some(child) => {
+            assert!(index < len);
+            assert_within(&parent, len * ELEMENT_SIZE, &child);
+        }
+        None => assert!(index >= len),
+    }
+}
+
+#[kani::proof]
+fn array_get_in_bounds() {
+    let mut fake = [const { ReadWrite(0u32) }; LEN];
+    let index: usize = kani::any();
+
+    let mut regs = UniqueMmioPointer::from(&mut fake);
+    let parent = *regs;
+    match regs.get(index) {
+        Some(child) => {
+            assert!(index < LEN);
+            assert_within(&parent, LEN * ELEMENT_SIZE, &child);
+        }
+        None => assert!(index >= LEN),
+    }
+}
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn split_disjoint() {
+    let mut fake = [const { ReadWrite(0u32) }; LEN];
+    let a: usize = kani::any();
+    let b: usize = kani::any();
+    kani::assume(a < LEN && b < LEN && a != b);
+
+    let regs = UniqueMmioPointer::from(&mut fake);
+    let parent = *regs;
+    let children = regs.split();
+    assert_within(&parent, LEN * ELEMENT_SIZE, &children[a]);
+    assert_disjoint(&children[a], &children[b]);
+}
+
+#[kani::proof]
+#[kani::unwind(3)]
+fn split_some_disjoint() {
+    let mut fake = [const { ReadWrite(0u32) }; LEN];
+    let a: usize = kani::any();
+    let b: usize = kani::any();
+    kani::assume(a < LEN && b < LEN && a != b);
+
+    let regs = UniqueMmioPointer::from(&mut fake[..]);
+    let parent = *regs;
+    let [first, second] = regs.split_some([a, b]);
+    assert_within(&parent, LEN * ELEMENT_SIZE, &first);
+    assert_within(&parent, LEN * ELEMENT_SIZE, &second);
+    assert_disjoint(&first, &second);
+}
+
+#[kani::proof]
+#[kani::should_panic]
+#[kani::unwind(3)]
+fn split_some_rejects_duplicates() {
+    let mut fake = [const { ReadWrite(0u32) }; LEN];
+    let index: usize = kani::any();
+    kani::assume(index < LEN);
+
+    UniqueMmioPointer::from(&mut fake[..]).split_some([index, index]);
+}
+
+#[kani::proof]
+fn field_in_bounds() {
+    let mut fake = Registers {
+        control: ReadWrite(0),
+        status: ReadPure(0),
+        data: ReadOnly(0),
+    };
+    let mut regs = UniqueMmioPointer::from(&mut fake);
+    let parent = *regs;
+    let data = field!(regs, data);
+    assert_within(&parent, size_of::<Registers>(), &data);
+    let status = field_shared!(regs, status);
+    assert_within(&parent, size_of::<Registers>(), &status);
+}
+
+#[kani::proof]
+fn split_fields_disjoint() {
+    let mut fake = Registers {
+        control: ReadWrite(0),
+        status: ReadPure(0),
+        data: ReadOnly(0),
+    };
+    let regs = UniqueMmioPointer::from(&mut fake);
+    let parent = *regs;
+    // SAFETY: Each field name is only passed once.
+    let (control, status, data) = unsafe { split_fields!(regs, control, status, data) };
+    assert_within(&parent, size_of::<Registers>(), &control);
+    assert_within(&parent, size_of::<Registers>(), &status);
+    assert_within(&parent, size_of::<Registers>(), &data);
+    assert_disjoint(&control, &status);
+    assert_disjoint(&control, &data);
+    assert_disjoint(&status, &data);
+}
+
+#[kani::proof]
+fn writes_recorded() {
+    let mut fake = ReadWrite(0u32);
+    let value: u32 = kani::any();
+    UniqueMmioPointer::from(&mut fake).write(value);
+    assert_eq!(fake.0, value);
+}
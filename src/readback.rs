@@ -0,0 +1,196 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Read-back verification of writes, for catching writes which the device ignored.
+//!
+//! A write to a register of a device whose clock or power domain isn't enabled, or which is locked,
+//! is often silently dropped, and the driver only notices much later when it hangs waiting for
+//! something which will never happen. With the `readback` feature enabled and a hook set with
+//! [`set_mismatch_hook`], every write to a [`ReadPureWrite`] register reads the register back
+//! afterwards, and calls the hook if any writable bit doesn't have the value which was written.
+//!
+//! By default all bits are compared. Registers with read-only or self-clearing bits can be written
+//! with [`write_checked`](crate::UniqueMmioPointer::write_checked) to give a mask of the bits which
+//! should read back as written. Writes by offset through a
+//! [`RegisterMap`](crate::register_map::RegisterMap) use the `#[writable(mask)]` of the register,
+//! if it has one.
+//!
+//! Without the feature, or if no hook is set, no extra reads are made.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_mmio::{UniqueMmioPointer, field, fields::ReadPureWrite, readback};
+//!
+//! #[repr(C)]
+//! struct Registers {
+//!     control: ReadPureWrite<u32>,
+//! }
+//!
+//! fn report(mismatch: &readback::Mismatch) {
+//!     panic!("{mismatch}");
+//! }
+//!
+//! readback::set_mismatch_hook(report);
+//!
+//! let mut regs: UniqueMmioPointer<Registers>;
+//! # let mut fake = Registers { control: ReadPureWrite(0) };
+//! # regs = UniqueMmioPointer::from(&mut fake);
+//! // Bit 31 is a read-only busy flag, so don't check it.
+//! field!(regs, control).write_checked(0x8000_0001, 0x7fff_ffff);
+//! ```
+//!
+//! [`ReadPureWrite`]: crate::fields::ReadPureWrite
+
+use crate::{SharedMmioPointer, access::AtomicAccess, fields::ReadPureWrite};
+use core::{
+    fmt::{self, Display, Formatter},
+    ptr::null_mut,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+static MISMATCH_HOOK: AtomicPtr<()> = AtomicPtr::new(null_mut());
+
+/// A register which didn't read back the value which was written to it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Mismatch {
+    /// The virtual address of the register.
+    pub address: usize,
+    /// The width of the register in bytes.
+    pub width: usize,
+    /// The value which was written, zero-extended.
+    pub written: u64,
+    /// The value which was read back, zero-extended.
+    pub read: u64,
+    /// The mask of bits which were compared.
+    pub mask: u64,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Register at {:#x} read back {:#x} after writing {:#x} (mask {:#x})",
+            self.address, self.read, self.written, self.mask
+        )
+    }
+}
+
+/// Sets a function to be called when a write to a [`ReadPureWrite`] register doesn't read back as
+/// written.
+///
+/// [`ReadPureWrite`]: crate::fields::ReadPureWrite
+pub fn set_mismatch_hook(hook: fn(&Mismatch)) {
+    MISMATCH_HOOK.store(hook as *mut (), Ordering::Release);
+}
+
+/// Removes the mismatch hook, if any, so that writes are no longer read back.
+pub fn clear_mismatch_hook() {
+    MISMATCH_HOOK.store(null_mut(), Ordering::Release);
+}
+
+/// Reads back the given register after `written` was written to it, and calls the mismatch hook if
+/// any of the bits in `mask` differ, or any bits at all if there is no mask.
+pub(crate) fn verify<T: AtomicAccess>(
+    register: &SharedMmioPointer<ReadPureWrite<T>>,
+    written: T,
+    mask: Option<T>,
+) {
+    if !is_enabled() {
+        return;
+    }
+    check(
+        register.addr(),
+        size_of::<T>(),
+        to_u64(written),
+        to_u64(register.read()),
+        mask.map(to_u64),
+    );
+}
+
+/// Returns whether a mismatch hook is set, so that registers should be read back after writes.
+pub(crate) fn is_enabled() -> bool {
+    !MISMATCH_HOOK.load(Ordering::Acquire).is_null()
+}
+
+/// Calls the mismatch hook if any of the bits in `mask` of the value read back from the register
+/// of the given width at `address` differ from those written, or any bits at all if there is no
+/// mask.
+pub(crate) fn check(address: usize, width: usize, written: u64, read: u64, mask: Option<u64>) {
+    let hook = MISMATCH_HOOK.load(Ordering::Acquire);
+    if hook.is_null() {
+        return;
+    }
+    let mask = mask.unwrap_or(u64::MAX >> (64 - 8 * width));
+    if (read ^ written) & mask != 0 {
+        // SAFETY: The only non-null values ever stored in `MISMATCH_HOOK` are `fn(&Mismatch)`
+        // pointers, by `set_mismatch_hook`.
+        let hook = unsafe { core::mem::transmute::<*mut (), fn(&Mismatch)>(hook) };
+        hook(&Mismatch {
+            address,
+            width,
+            written,
+            read,
+            mask,
+        });
+    }
+}
+
+/// Zero-extends the given value to a `u64`.
+fn to_u64<T: AtomicAccess>(value: T) -> u64 {
+    let mut bytes = [0; 8];
+    let bytes_len = size_of::<T>();
+    if cfg!(target_endian = "little") {
+        bytes[..bytes_len].copy_from_slice(value.as_bytes());
+    } else {
+        bytes[8 - bytes_len..].copy_from_slice(value.as_bytes());
+    }
+    u64::from_ne_bytes(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UniqueMmioPointer;
+    use core::sync::atomic::AtomicUsize;
+
+    static MISMATCHES: AtomicUsize = AtomicUsize::new(0);
+
+    fn count(mismatch: &Mismatch) {
+        assert_eq!(mismatch.width, 2);
+        assert_eq!(mismatch.written, 0x1234);
+        assert_eq!(mismatch.read, 0x1230);
+        MISMATCHES.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[test]
+    fn mismatch() {
+        set_mismatch_hook(count);
+        let mut fake = ReadPureWrite(0u16);
+        // Writes to fake registers always read back correctly.
+        UniqueMmioPointer::from(&mut fake).write(0x1234);
+        assert_eq!(MISMATCHES.load(Ordering::Relaxed), 0);
+        // Simulate a register which ignores writes to the bottom 4 bits.
+        fake.0 = 0x1230;
+        let register = UniqueMmioPointer::from(&mut fake);
+        verify(&register, 0x1234, Some(0xfff0));
+        assert_eq!(MISMATCHES.load(Ordering::Relaxed), 0);
+        verify(&register, 0x1234, None);
+        assert_eq!(MISMATCHES.load(Ordering::Relaxed), 1);
+        check(register.addr(), 2, 0x1234, 0x1230, Some(0xfff0));
+        assert_eq!(MISMATCHES.load(Ordering::Relaxed), 1);
+        check(register.addr(), 2, 0x1234, 0x1230, None);
+        assert_eq!(MISMATCHES.load(Ordering::Relaxed), 2);
+        clear_mismatch_hook();
+        verify(&register, 0x1234, None);
+        assert_eq!(MISMATCHES.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn zero_extend() {
+        assert_eq!(to_u64(0xffu8), 0xff);
+        assert_eq!(to_u64(-1i16), 0xffff);
+        assert_eq!(to_u64(0x1234_5678u32), 0x1234_5678);
+    }
+}
@@ -0,0 +1,1132 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Tables of register names and offsets, for debug monitors and panic handlers.
+//!
+//! Wrapping the definition of a register struct in [`register_map!`](macro@crate::register_map)
+//! implements [`RegisterMap`] for it, with a static table describing each field. Debug shells can
+//! then print symbolic names when dumping or poking a device by offset.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_mmio::{
+//!     fields::{ReadPure, ReadWrite, WriteOnly},
+//!     register_map,
+//!     register_map::{Access, RegisterMap},
+//! };
+//!
+//! register_map! {
+//!     #[repr(C)]
+//!     pub struct UartRegisters {
+//!         pub data: ReadWrite<u32>,
+//!         pub status: ReadPure<u32>,
+//!         reserved: [u32; 2],
+//!         pub interrupt_clear: [WriteOnly<u32>; 4],
+//!     }
+//! }
+//!
+//! let register = UartRegisters::lookup(0x14).unwrap();
+//! assert_eq!(register.name, "interrupt_clear");
+//! assert_eq!(register.offset, 0x10);
+//! assert_eq!(register.width, 4);
+//! assert_eq!(register.access, Access::WriteOnly);
+//! assert!(UartRegisters::lookup(0x20).is_none());
+//! ```
+//!
+//! Fields may also be given the value they have after the device is reset, to check that the
+//! device really has been reset before configuring it:
+//!
+//! ```
+//! use safe_mmio::{
+//!     SharedMmioPointer,
+//!     fields::{ReadPure, ReadPureWrite},
+//!     register_map,
+//!     register_map::RegisterMap,
+//! };
+//!
+//! register_map! {
+//!     #[repr(C)]
+//!     pub struct TimerRegisters {
+//!         /// Control register.
+//!         #[reset(0x0000_0301)]
+//!         pub control: ReadPureWrite<u32>,
+//!         pub count: ReadPure<u32>,
+//!     }
+//! }
+//!
+//! # let fake = TimerRegisters {
+//! #     control: ReadPureWrite(0x300),
+//! #     count: ReadPure(42),
+//! # };
+//! let timer: SharedMmioPointer<TimerRegisters>;
+//! # timer = SharedMmioPointer::from(&fake);
+//! if let Err(mismatch) = TimerRegisters::verify_reset(&timer) {
+//!     println!("{mismatch}");
+//! }
+//! # assert_eq!(
+//! #     TimerRegisters::verify_reset(&timer).unwrap_err().to_string(),
+//! #     "Register control at offset 0x0 is 0x300 rather than its reset value 0x301"
+//! # );
+//! ```
+
+use crate::{
+    SharedMmioPointer, UniqueMmioPointer,
+    access::AtomicAccess,
+    fields::{
+        Command, Masked, Overlay, Privileged, Protected, ReadConstant, ReadOnly, ReadPure,
+        ReadPureCommand, ReadPureWrite, ReadWrite, ReadWrite64HiLo, ReadWrite64LoHi, WriteOnly,
+    },
+    masked::ReservedPolicy,
+};
+use core::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    marker::PhantomData,
+    ptr::NonNull,
+};
+
+/// The safe accesses which a register allows, according to its field wrapper.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Access {
+    /// A [`ReadOnly`] register.
+    ReadOnly,
+    /// A [`ReadPure`] or [`ReadConstant`] register, a [`Protected`] register which can only be
+    /// written after an unlock sequence, or a [`Privileged`] register which can only be written
+    /// with a capability token.
+    ReadPure,
+    /// A [`WriteOnly`] register.
+    WriteOnly,
+    /// A [`ReadWrite`] register.
+    ReadWrite,
+    /// A [`ReadPureWrite`] register.
+    ReadPureWrite,
+    /// A [`ReadWrite64LoHi`] register.
+    ReadWrite64LoHi,
+    /// A [`ReadWrite64HiLo`] register.
+    ReadWrite64HiLo,
+    /// A field with no field wrapper, such as reserved space, a [`Command`] or [`ReadPureCommand`]
+    /// which can't safely be accessed with arbitrary integers, an [`Overlay`] whose layout depends
+    /// on the mode of the device, or a field wrapper around a type which isn't [`AtomicAccess`] so
+    /// can't be accessed with a single instruction.
+    Unsafe,
+}
+
+/// A description of a single field of a register struct.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RegisterInfo {
+    /// The offset of the field in bytes from the start of the struct.
+    pub offset: usize,
+    /// The name of the field.
+    pub name: &'static str,
+    /// The size of the whole field in bytes. This is larger than `width` for arrays.
+    pub size: usize,
+    /// The width in bytes of each register in the field.
+    pub width: usize,
+    /// The safe accesses which the register allows.
+    pub access: Access,
+    /// The value of each register in the field after the device is reset, if known.
+    pub reset: Option<u64>,
+    /// The bits of each register in the field which may be written, if known. The other bits are
+    /// reserved.
+    pub writable: Option<u64>,
+    /// What [`RegisterMap::write_masked`] and [`RegisterMap::modify`] write to the reserved bits of
+    /// the register.
+    pub reserved: ReservedBits,
+}
+
+/// What to write to the reserved bits of a register, outside its writable bits mask.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ReservedBits {
+    /// Write back the value read from the register. This needs an extra read for
+    /// [`RegisterMap::write_masked`].
+    #[default]
+    Preserve,
+    /// Write zeroes.
+    Zero,
+}
+
+/// An error accessing a register by offset through a [`RegisterMap`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RegisterError {
+    /// There is no register at the given offset.
+    UnknownRegister,
+    /// The value doesn't fit in the register.
+    InvalidValue,
+    /// The register can't safely be read.
+    NotReadable,
+    /// The register can't safely be written.
+    NotWritable,
+    /// The register isn't aligned for the accesses used to read and write it.
+    Misaligned,
+}
+
+impl Display for RegisterError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownRegister => write!(f, "Unknown register"),
+            Self::InvalidValue => write!(f, "Invalid value"),
+            Self::NotReadable => write!(f, "Register can't be read"),
+            Self::NotWritable => write!(f, "Register can't be written"),
+            Self::Misaligned => write!(f, "Register is misaligned"),
+        }
+    }
+}
+
+impl Error for RegisterError {}
+
+/// A register struct with a table describing its fields, implemented by
+/// [`register_map!`](macro@crate::register_map).
+///
+/// # Safety
+///
+/// Each entry in `REGISTERS` must correctly describe the offset, size and access of a field of the
+/// struct, as code such as the [`monitor`](crate::monitor) relies on it to make MMIO accesses.
+pub unsafe trait RegisterMap {
+    /// The fields of the struct, in declaration order.
+    const REGISTERS: &'static [RegisterInfo];
+
+    /// Returns the description of the field containing the given byte offset, if any.
+    fn lookup(offset: usize) -> Option<&'static RegisterInfo> {
+        Self::REGISTERS
+            .iter()
+            .find(|register| offset.wrapping_sub(register.offset) < register.size)
+    }
+
+    /// Checks that every register with a known reset value which can be read without side effects
+    /// currently has its reset value.
+    ///
+    /// This is a useful sanity check after resetting a device and before configuring it, to catch
+    /// a device which didn't actually reset or a wrong base address.
+    ///
+    /// Returns the first register which doesn't have its reset value, in declaration order.
+    fn verify_reset(regs: &SharedMmioPointer<Self>) -> Result<(), Mismatch>
+    where
+        Self: Sized,
+    {
+        for register in Self::REGISTERS {
+            let Some(expected) = register.reset else {
+                continue;
+            };
+            if !matches!(register.access, Access::ReadPure | Access::ReadPureWrite) {
+                continue;
+            }
+            let is_array = register.size != register.width;
+            for index in 0..register.size.checked_div(register.width).unwrap_or(0) {
+                let offset = register.offset + index * register.width;
+                if !register.is_aligned(offset) {
+                    continue;
+                }
+                // SAFETY: The offset, width and access come from the register map of `Self`, which
+                // says that the register can be read without side effects.
+                let Some(actual) = (unsafe { read_register(regs, offset, register.width) }) else {
+                    continue;
+                };
+                if actual != expected {
+                    return Err(Mismatch {
+                        name: register.name,
+                        index: is_array.then_some(index),
+                        offset,
+                        expected,
+                        actual,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the given value to the register at the given byte offset, leaving its reserved bits
+    /// alone.
+    ///
+    /// Bits of `value` outside the register's writable bits mask are ignored, and the reserved bits
+    /// are written according to its [`ReservedBits`] policy. Registers without a mask are written
+    /// as is.
+    fn write_masked(
+        regs: &mut UniqueMmioPointer<Self>,
+        offset: usize,
+        value: u64,
+    ) -> Result<(), RegisterError>
+    where
+        Self: Sized,
+    {
+        let info = find_register::<Self>(offset)?;
+        // SAFETY: The register info and offset came from the register map of `Self`.
+        unsafe { write_register_masked(regs, info, offset, value) }
+    }
+
+    /// Reads the register at the given byte offset, calls `f` with its value, and writes back the
+    /// result, leaving its reserved bits alone.
+    ///
+    /// Bits of the result outside the register's writable bits mask are ignored, and the reserved
+    /// bits are written according to its [`ReservedBits`] policy.
+    fn modify(
+        regs: &mut UniqueMmioPointer<Self>,
+        offset: usize,
+        f: impl FnOnce(u64) -> u64,
+    ) -> Result<(), RegisterError>
+    where
+        Self: Sized,
+    {
+        let info = find_register::<Self>(offset)?;
+        if !info.access.is_writable() {
+            return Err(RegisterError::NotWritable);
+        }
+        // SAFETY: The register info and offset came from the register map of `Self`.
+        unsafe {
+            let old = read_register_info(regs, info, offset)?;
+            let new = f(old);
+            let value = match info.writable {
+                None => new,
+                Some(mask) => (new & mask) | info.reserved.value(old, mask),
+            };
+            write_register_info(regs, info, offset, value)
+        }
+    }
+}
+
+/// Returns the description of the register at the given offset, which must be the offset of an
+/// individual register rather than somewhere within one.
+fn find_register<R: RegisterMap>(offset: usize) -> Result<&'static RegisterInfo, RegisterError> {
+    let info = R::lookup(offset).ok_or(RegisterError::UnknownRegister)?;
+    if (offset - info.offset) % info.width != 0 {
+        return Err(RegisterError::UnknownRegister);
+    }
+    if !info.is_aligned(offset) {
+        return Err(RegisterError::Misaligned);
+    }
+    Ok(info)
+}
+
+impl RegisterInfo {
+    /// Returns whether a register of this field at the given offset is aligned for the accesses
+    /// used to read and write it.
+    ///
+    /// Fields in a `repr(C)` struct are always aligned, but those in a `repr(packed)` struct may
+    /// not be.
+    pub(crate) const fn is_aligned(&self, offset: usize) -> bool {
+        let alignment = match self.access {
+            // These are accessed as two 32-bit halves.
+            Access::ReadWrite64LoHi | Access::ReadWrite64HiLo => 4,
+            _ => self.width,
+        };
+        matches!(offset.checked_rem(alignment), Some(0))
+    }
+}
+
+impl Access {
+    /// Returns whether the field wrapper allows the register to be written.
+    const fn is_writable(self) -> bool {
+        matches!(
+            self,
+            Self::WriteOnly
+                | Self::ReadWrite
+                | Self::ReadPureWrite
+                | Self::ReadWrite64LoHi
+                | Self::ReadWrite64HiLo
+        )
+    }
+}
+
+impl ReservedBits {
+    /// Returns the reserved bits to write, given the current value of the register and its
+    /// writable bits mask.
+    const fn value(self, current: u64, writable: u64) -> u64 {
+        match self {
+            Self::Preserve => current & !writable,
+            Self::Zero => 0,
+        }
+    }
+}
+
+/// Returns a pointer to a `U` at the given byte offset from `regs`.
+///
+/// # Safety
+///
+/// The offset must be in bounds and aligned for `U`, and `U` must correctly describe the register
+/// there.
+unsafe fn register<'b, U, R: ?Sized>(
+    regs: &'b mut UniqueMmioPointer<R>,
+    offset: usize,
+) -> UniqueMmioPointer<'b, U> {
+    let ptr = regs.ptr_nonnull().cast::<u8>();
+    // SAFETY: Our caller promised that the offset is in bounds.
+    unsafe { regs.child(ptr.add(offset).cast::<U>()) }
+}
+
+/// Reads the register with the given description at the given byte offset from `regs`, if its field
+/// wrapper allows it.
+///
+/// # Safety
+///
+/// `info` must describe the register at `offset`.
+pub(crate) unsafe fn read_register_info<R: ?Sized>(
+    regs: &mut UniqueMmioPointer<R>,
+    info: &RegisterInfo,
+    offset: usize,
+) -> Result<u64, RegisterError> {
+    // SAFETY: Our caller promised that `info` describes the register at the offset, and we only
+    // read registers whose field wrapper allows it, with their width.
+    unsafe {
+        Ok(match (info.access, info.width) {
+            (Access::ReadWrite64LoHi, _) => register::<ReadWrite64LoHi, _>(regs, offset).read(),
+            (Access::ReadWrite64HiLo, _) => register::<ReadWrite64HiLo, _>(regs, offset).read(),
+            (Access::WriteOnly | Access::Unsafe, _) => return Err(RegisterError::NotReadable),
+            (_, 1) => register::<u8, _>(regs, offset).read_unsafe().into(),
+            (_, 2) => register::<u16, _>(regs, offset).read_unsafe().into(),
+            (_, 4) => register::<u32, _>(regs, offset).read_unsafe().into(),
+            (_, 8) => register::<u64, _>(regs, offset).read_unsafe(),
+            _ => return Err(RegisterError::NotReadable),
+        })
+    }
+}
+
+/// Writes the register with the given description at the given byte offset from `regs`, if its
+/// field wrapper allows it, ignoring its writable bits mask.
+///
+/// With the `readback` feature enabled and a mismatch hook set, a [`ReadPureWrite`] register is
+/// then read back and the bits in its writable bits mask checked, or all bits if it doesn't have
+/// one.
+///
+/// # Safety
+///
+/// `info` must describe the register at `offset`.
+unsafe fn write_register_info<R: ?Sized>(
+    regs: &mut UniqueMmioPointer<R>,
+    info: &RegisterInfo,
+    offset: usize,
+    value: u64,
+) -> Result<(), RegisterError> {
+    let invalid = |_| RegisterError::InvalidValue;
+    // SAFETY: Our caller promised that `info` describes the register at the offset, and we only
+    // write registers whose field wrapper allows it, with their width.
+    unsafe {
+        match (info.access, info.width) {
+            (Access::ReadWrite64LoHi, _) => {
+                register::<ReadWrite64LoHi, _>(regs, offset).write(value)
+            }
+            (Access::ReadWrite64HiLo, _) => {
+                register::<ReadWrite64HiLo, _>(regs, offset).write(value)
+            }
+            (Access::ReadOnly | Access::ReadPure | Access::Unsafe, _) => {
+                return Err(RegisterError::NotWritable);
+            }
+            (_, 1) => {
+                register::<u8, _>(regs, offset).write_unsafe(value.try_into().map_err(invalid)?)
+            }
+            (_, 2) => {
+                register::<u16, _>(regs, offset).write_unsafe(value.try_into().map_err(invalid)?)
+            }
+            (_, 4) => {
+                register::<u32, _>(regs, offset).write_unsafe(value.try_into().map_err(invalid)?)
+            }
+            (_, 8) => register::<u64, _>(regs, offset).write_unsafe(value),
+            _ => return Err(RegisterError::NotWritable),
+        }
+    }
+    #[cfg(feature = "readback")]
+    if info.access == Access::ReadPureWrite && crate::readback::is_enabled() {
+        // SAFETY: Our caller promised that `info` describes the register at the offset, and
+        // `ReadPureWrite` registers can be read without side effects.
+        let read = unsafe { read_register_info(regs, info, offset)? };
+        crate::readback::check(regs.addr() + offset, info.width, value, read, info.writable);
+    }
+    Ok(())
+}
+
+/// Writes the register with the given description at the given byte offset from `regs`, leaving
+/// its reserved bits alone according to its writable bits mask and [`ReservedBits`] policy.
+///
+/// # Safety
+///
+/// `info` must describe the register at `offset`.
+pub(crate) unsafe fn write_register_masked<R: ?Sized>(
+    regs: &mut UniqueMmioPointer<R>,
+    info: &RegisterInfo,
+    offset: usize,
+    value: u64,
+) -> Result<(), RegisterError> {
+    if !info.access.is_writable() {
+        return Err(RegisterError::NotWritable);
+    }
+    let value = match (info.writable, info.reserved) {
+        (None, _) => value,
+        (Some(mask), ReservedBits::Zero) => value & mask,
+        (Some(mask), ReservedBits::Preserve) => {
+            // SAFETY: Our caller promised that `info` describes the register at the offset.
+            let current = unsafe { read_register_info(regs, info, offset)? };
+            (value & mask) | (current & !mask)
+        }
+    };
+    // SAFETY: Our caller promised that `info` describes the register at the offset.
+    unsafe { write_register_info(regs, info, offset, value) }
+}
+
+/// Reads the register of the given width at the given byte offset from `regs`, or returns `None` if
+/// the width isn't 1, 2, 4 or 8 bytes.
+///
+/// # Safety
+///
+/// There must be a register of the given width at the offset which can be read without side
+/// effects.
+pub(crate) unsafe fn read_register<R>(
+    regs: &SharedMmioPointer<R>,
+    offset: usize,
+    width: usize,
+) -> Option<u64> {
+    // SAFETY: Our caller promised that the offset is within the registers.
+    let register =
+        unsafe { NonNull::new_unchecked(regs.ptr().cast::<u8>().add(offset).cast_mut()) };
+    // SAFETY: Our caller promised that there is a register of the given width at the offset which
+    // can be read without side effects.
+    unsafe {
+        Some(match width {
+            1 => regs.child(register).read_unsafe().into(),
+            2 => regs.child(register.cast::<u16>()).read_unsafe().into(),
+            4 => regs.child(register.cast::<u32>()).read_unsafe().into(),
+            8 => regs.child(register.cast::<u64>()).read_unsafe(),
+            _ => return None,
+        })
+    }
+}
+
+/// A register which didn't have its expected reset value, returned by [`RegisterMap::verify_reset`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Mismatch {
+    /// The name of the field containing the register.
+    pub name: &'static str,
+    /// The index of the register within the field, if the field is an array.
+    pub index: Option<usize>,
+    /// The offset of the register in bytes from the start of the device.
+    pub offset: usize,
+    /// The reset value of the register.
+    pub expected: u64,
+    /// The value which was read from the register.
+    pub actual: u64,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Register {}", self.name)?;
+        if let Some(index) = self.index {
+            write!(f, "[{index}]")?;
+        }
+        write!(
+            f,
+            " at offset {:#x} is {:#x} rather than its reset value {:#x}",
+            self.offset, self.actual, self.expected
+        )
+    }
+}
+
+impl Error for Mismatch {}
+
+/// The type of a field in a struct passed to [`register_map!`](macro@crate::register_map).
+///
+/// Field wrappers only implement this when the type they wrap is [`AtomicAccess`], so that the
+/// width of the register is one which can be accessed with a single instruction. Fields of other
+/// types are described as [`Access::Unsafe`].
+///
+/// This trait is sealed, so can't be implemented outside this crate.
+pub trait Field: sealed::Sealed {
+    /// The safe accesses which the field allows.
+    const ACCESS: Access;
+    /// The width in bytes of each register in the field.
+    const WIDTH: usize;
+    /// The bits of each register in the field which may be written, if the type says.
+    const WRITABLE: Option<u64> = None;
+    /// What to write to the reserved bits of each register in the field.
+    const RESERVED: ReservedBits = ReservedBits::Preserve;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_field_for_wrapper {
+    ($($wrapper:ident => $access:ident),*) => {
+        $(
+            impl<T: AtomicAccess> sealed::Sealed for $wrapper<T> {}
+            impl<T: AtomicAccess> Field for $wrapper<T> {
+                const ACCESS: Access = Access::$access;
+                const WIDTH: usize = size_of::<T>();
+            }
+        )*
+    };
+}
+
+impl_field_for_wrapper!(
+    ReadOnly => ReadOnly,
+    ReadPure => ReadPure,
+    ReadConstant => ReadPure,
+    WriteOnly => WriteOnly,
+    ReadWrite => ReadWrite,
+    ReadPureWrite => ReadPureWrite
+);
+
+impl<T> sealed::Sealed for Command<T> {}
+
+impl<T> Field for Command<T> {
+    const ACCESS: Access = Access::Unsafe;
+    const WIDTH: usize = size_of::<T>();
+}
+
+impl<T> sealed::Sealed for ReadPureCommand<T> {}
+
+impl<T> Field for ReadPureCommand<T> {
+    const ACCESS: Access = Access::Unsafe;
+    const WIDTH: usize = size_of::<T>();
+}
+
+impl sealed::Sealed for ReadWrite64LoHi {}
+impl sealed::Sealed for ReadWrite64HiLo {}
+impl<T: AtomicAccess, U> sealed::Sealed for Protected<T, U> {}
+
+impl<T: AtomicAccess, U> Field for Protected<T, U> {
+    const ACCESS: Access = Access::ReadPure;
+    const WIDTH: usize = size_of::<T>();
+}
+
+impl<A, B> sealed::Sealed for Overlay<A, B> {}
+
+impl<A, B> Field for Overlay<A, B> {
+    const ACCESS: Access = Access::Unsafe;
+    const WIDTH: usize = size_of::<Self>();
+}
+
+impl<T: AtomicAccess, Token> sealed::Sealed for Privileged<T, Token> {}
+
+impl<T: AtomicAccess, Token> Field for Privileged<T, Token> {
+    const ACCESS: Access = Access::ReadPure;
+    const WIDTH: usize = size_of::<T>();
+}
+
+impl<W: Field, const MASK: u64, R: ReservedPolicy> sealed::Sealed for Masked<W, MASK, R> {}
+
+impl<W: Field, const MASK: u64, R: ReservedPolicy> Field for Masked<W, MASK, R> {
+    const ACCESS: Access = W::ACCESS;
+    const WIDTH: usize = W::WIDTH;
+    const WRITABLE: Option<u64> = Some(MASK);
+    const RESERVED: ReservedBits = R::RESERVED;
+}
+
+impl Field for ReadWrite64LoHi {
+    const ACCESS: Access = Access::ReadWrite64LoHi;
+    const WIDTH: usize = 8;
+}
+
+impl Field for ReadWrite64HiLo {
+    const ACCESS: Access = Access::ReadWrite64HiLo;
+    const WIDTH: usize = 8;
+}
+
+macro_rules! impl_field_for_int {
+    ($($t:ty),*) => {
+        $(
+            impl sealed::Sealed for $t {}
+            impl Field for $t {
+                const ACCESS: Access = Access::Unsafe;
+                const WIDTH: usize = size_of::<$t>();
+            }
+        )*
+    };
+}
+
+impl_field_for_int!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+impl<F: Field, const N: usize> sealed::Sealed for [F; N] {}
+
+impl<F: Field, const N: usize> Field for [F; N] {
+    const ACCESS: Access = F::ACCESS;
+    const WIDTH: usize = F::WIDTH;
+    const WRITABLE: Option<u64> = F::WRITABLE;
+    const RESERVED: ReservedBits = F::RESERVED;
+}
+
+/// Gets the access, width and writable bits of a field type for [`register_map!`](macro@crate::register_map),
+/// from its [`Field`] implementation if it has one or from [`NotField`] otherwise.
+///
+/// The inherent constants take precedence over those of the trait when `F: Field`, so this works
+/// for any concrete field type without needing specialisation.
+#[doc(hidden)]
+pub struct FieldInfo<F>(PhantomData<F>);
+
+impl<F: Field> FieldInfo<F> {
+    pub const ACCESS: Access = F::ACCESS;
+    pub const WIDTH: usize = F::WIDTH;
+    pub const WRITABLE: Option<u64> = F::WRITABLE;
+    pub const RESERVED: ReservedBits = F::RESERVED;
+}
+
+/// The fallback for [`FieldInfo`] of a field type which doesn't implement [`Field`], such as a
+/// field wrapper around a struct, which is treated as a single unsafe register.
+#[doc(hidden)]
+pub trait NotField {
+    const ACCESS: Access = Access::Unsafe;
+    const WIDTH: usize;
+    const WRITABLE: Option<u64> = None;
+    const RESERVED: ReservedBits = ReservedBits::Preserve;
+}
+
+impl<F> NotField for FieldInfo<F> {
+    const WIDTH: usize = size_of::<F>();
+}
+
+/// Defines a register struct, and implements [`RegisterMap`](crate::register_map::RegisterMap) for it
+/// with a table describing each of its fields.
+///
+/// Fields with a field wrapper type around an [`AtomicAccess`](crate::access::AtomicAccess) type,
+/// or an array of them, are described with the accesses which the wrapper allows. Fields of any
+/// other type, such as reserved space or a field wrapper around a struct or array, are described
+/// as [`Access::Unsafe`](crate::register_map::Access::Unsafe).
+///
+/// A field may have a `#[reset(value)]` attribute giving the value of the register (or of each
+/// register, for an array) after the device is reset, which
+/// [`RegisterMap::verify_reset`](crate::register_map::RegisterMap::verify_reset) checks.
+///
+/// A field may also have a `#[writable(mask)]` attribute giving the bits of the register which may
+/// be written, and a `#[reserved(preserve)]` or `#[reserved(zero)]` attribute saying whether
+/// [`RegisterMap::write_masked`](crate::register_map::RegisterMap::write_masked) and
+/// [`RegisterMap::modify`](crate::register_map::RegisterMap::modify) write back the current value
+/// of the other, reserved, bits or write zeroes to them. The default is to preserve them.
+///
+/// The attributes only apply to accesses by offset through the
+/// [`RegisterMap`](crate::register_map::RegisterMap) methods and the [`monitor`](crate::monitor).
+/// To apply the mask and policy to accesses through typed field pointers too, such as
+/// `field!(regs, control).modify(..)`, give the field a [`Masked`](crate::fields::Masked) type
+/// instead. The table then takes the mask and policy from the type, unless the attributes are also
+/// given.
+///
+/// See the [`register_map`](mod@crate::register_map) module for an example.
+#[macro_export]
+macro_rules! register_map {
+    (
+        $(#[$attribute:meta])*
+        $struct_vis:vis struct $name:ident {
+            $($fields:tt)*
+        }
+    ) => {
+        $crate::__register_map_fields! {
+            [$(#[$attribute])* $struct_vis struct $name]
+            []
+            []
+            [] [] []
+            $($fields)*
+        }
+    };
+}
+
+/// Separates the `#[reset(..)]`, `#[writable(..)]` and `#[reserved(..)]` attributes of the fields
+/// passed to [`register_map!`](macro@crate::register_map) from their other attributes, one field at
+/// a time.
+///
+/// The first group is the struct header, the second the fields which have been processed so far, and
+/// the rest the other attributes, reset value, writable bits mask and reserved bits policy of the
+/// current field.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register_map_fields {
+    (
+        [$($header:tt)*] [$($done:tt)*] [$($attributes:tt)*]
+        [] [$($writable:expr)?] [$($reserved:ident)?]
+        #[reset($reset:expr)]
+        $($rest:tt)*
+    ) => {
+        $crate::__register_map_fields! {
+            [$($header)*] [$($done)*] [$($attributes)*]
+            [$reset] [$($writable)?] [$($reserved)?]
+            $($rest)*
+        }
+    };
+    (
+        [$($header:tt)*] [$($done:tt)*] [$($attributes:tt)*]
+        [$($reset:expr)?] [] [$($reserved:ident)?]
+        #[writable($writable:expr)]
+        $($rest:tt)*
+    ) => {
+        $crate::__register_map_fields! {
+            [$($header)*] [$($done)*] [$($attributes)*]
+            [$($reset)?] [$writable] [$($reserved)?]
+            $($rest)*
+        }
+    };
+    (
+        [$($header:tt)*] [$($done:tt)*] [$($attributes:tt)*]
+        [$($reset:expr)?] [$($writable:expr)?] []
+        #[reserved(preserve)]
+        $($rest:tt)*
+    ) => {
+        $crate::__register_map_fields! {
+            [$($header)*] [$($done)*] [$($attributes)*]
+            [$($reset)?] [$($writable)?] [Preserve]
+            $($rest)*
+        }
+    };
+    (
+        [$($header:tt)*] [$($done:tt)*] [$($attributes:tt)*]
+        [$($reset:expr)?] [$($writable:expr)?] []
+        #[reserved(zero)]
+        $($rest:tt)*
+    ) => {
+        $crate::__register_map_fields! {
+            [$($header)*] [$($done)*] [$($attributes)*]
+            [$($reset)?] [$($writable)?] [Zero]
+            $($rest)*
+        }
+    };
+    (
+        [$($header:tt)*] [$($done:tt)*] [$($attributes:tt)*]
+        [$($reset:expr)?] [$($writable:expr)?] [$($reserved:ident)?]
+        #[$attribute:meta]
+        $($rest:tt)*
+    ) => {
+        $crate::__register_map_fields! {
+            [$($header)*] [$($done)*] [$($attributes)* #[$attribute]]
+            [$($reset)?] [$($writable)?] [$($reserved)?]
+            $($rest)*
+        }
+    };
+    (
+        [$($header:tt)*] [$($done:tt)*] [$($attributes:tt)*]
+        [$($reset:expr)?] [$($writable:expr)?] [$($reserved:ident)?]
+        $field_vis:vis $field:ident: $type:ty
+        $(, $($rest:tt)*)?
+    ) => {
+        $crate::__register_map_fields! {
+            [$($header)*]
+            [
+                $($done)*
+                {
+                    [$($attributes)*] $field_vis $field: $type
+                    [$($reset)?] [$($writable)?] [$($reserved)?]
+                }
+            ]
+            []
+            [] [] []
+            $($($rest)*)?
+        }
+    };
+    (
+        [
+            $(#[$attribute:meta])*
+            $struct_vis:vis struct $name:ident
+        ]
+        [$({
+            [$(#[$field_attribute:meta])*]
+            $field_vis:vis $field:ident: $type:ty
+            [$($reset:expr)?] [$($writable:expr)?] [$($reserved:ident)?]
+        })*]
+        []
+        [] [] []
+    ) => {
+        $(#[$attribute])*
+        $struct_vis struct $name {
+            $(
+                $(#[$field_attribute])*
+                $field_vis $field: $type,
+            )*
+        }
+
+        const _: () = {
+            #[allow(unused_imports)]
+            use $crate::register_map::NotField as _;
+
+            // SAFETY: Each entry is generated from the declaration of the corresponding field.
+            unsafe impl $crate::register_map::RegisterMap for $name {
+                const REGISTERS: &'static [$crate::register_map::RegisterInfo] = &[
+                    $(
+                        $crate::register_map::RegisterInfo {
+                            offset: core::mem::offset_of!($name, $field),
+                            name: stringify!($field),
+                            size: size_of::<$type>(),
+                            width: <$crate::register_map::FieldInfo<$type>>::WIDTH,
+                            access: <$crate::register_map::FieldInfo<$type>>::ACCESS,
+                            reset: [$(Some($reset),)? None][0],
+                            writable: [
+                                $(Some($writable),)?
+                                <$crate::register_map::FieldInfo<$type>>::WRITABLE,
+                            ][0],
+                            reserved: [
+                                $($crate::register_map::ReservedBits::$reserved,)?
+                                <$crate::register_map::FieldInfo<$type>>::RESERVED,
+                            ][0],
+                        },
+                    )*
+                ];
+            }
+        };
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fields::ZeroReserved;
+
+    register_map! {
+        #[repr(C)]
+        struct Registers {
+            #[reset(0x01)]
+            control: ReadWrite<u8>,
+            status: ReadOnly<u8>,
+            reserved: u16,
+            counter: ReadWrite64LoHi,
+            empty: [ReadPure<u32>; 0],
+            /// Identification registers.
+            #[reset(0x12)]
+            #[allow(unused)]
+            ids: [ReadPureWrite<u16>; 2],
+        }
+    }
+
+    #[test]
+    fn table() {
+        assert_eq!(
+            Registers::REGISTERS,
+            [
+                RegisterInfo {
+                    offset: 0,
+                    name: "control",
+                    size: 1,
+                    width: 1,
+                    access: Access::ReadWrite,
+                    reset: Some(0x01),
+                    writable: None,
+                    reserved: ReservedBits::Preserve,
+                },
+                RegisterInfo {
+                    offset: 1,
+                    name: "status",
+                    size: 1,
+                    width: 1,
+                    access: Access::ReadOnly,
+                    reset: None,
+                    writable: None,
+                    reserved: ReservedBits::Preserve,
+                },
+                RegisterInfo {
+                    offset: 2,
+                    name: "reserved",
+                    size: 2,
+                    width: 2,
+                    access: Access::Unsafe,
+                    reset: None,
+                    writable: None,
+                    reserved: ReservedBits::Preserve,
+                },
+                RegisterInfo {
+                    offset: 8,
+                    name: "counter",
+                    size: 8,
+                    width: 8,
+                    access: Access::ReadWrite64LoHi,
+                    reset: None,
+                    writable: None,
+                    reserved: ReservedBits::Preserve,
+                },
+                RegisterInfo {
+                    offset: 16,
+                    name: "empty",
+                    size: 0,
+                    width: 4,
+                    access: Access::ReadPure,
+                    reset: None,
+                    writable: None,
+                    reserved: ReservedBits::Preserve,
+                },
+                RegisterInfo {
+                    offset: 16,
+                    name: "ids",
+                    size: 4,
+                    width: 2,
+                    access: Access::ReadPureWrite,
+                    reset: Some(0x12),
+                    writable: None,
+                    reserved: ReservedBits::Preserve,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn lookup() {
+        assert_eq!(Registers::lookup(1).unwrap().name, "status");
+        assert_eq!(Registers::lookup(3).unwrap().name, "reserved");
+        assert!(Registers::lookup(4).is_none());
+        assert_eq!(Registers::lookup(15).unwrap().name, "counter");
+        assert_eq!(Registers::lookup(16).unwrap().name, "ids");
+        assert_eq!(Registers::lookup(19).unwrap().name, "ids");
+        assert!(Registers::lookup(20).is_none());
+    }
+
+    #[test]
+    fn verify_reset() {
+        let mut fake = Registers {
+            control: ReadWrite(0),
+            status: ReadOnly(0),
+            reserved: 0,
+            counter: ReadWrite64LoHi(0),
+            empty: [],
+            ids: [ReadPureWrite(0x12), ReadPureWrite(0x12)],
+        };
+        // `control` doesn't have its reset value, but can't be read without side effects.
+        assert_eq!(Registers::verify_reset(&(&fake).into()), Ok(()));
+
+        fake.ids[1] = ReadPureWrite(0x34);
+        assert_eq!(
+            Registers::verify_reset(&(&fake).into()),
+            Err(Mismatch {
+                name: "ids",
+                index: Some(1),
+                offset: 18,
+                expected: 0x12,
+                actual: 0x34,
+            })
+        );
+    }
+
+    register_map! {
+        #[repr(C)]
+        struct MaskedRegisters {
+            #[writable(0xff87)]
+            control: ReadPureWrite<u32>,
+            #[writable(0x3f)]
+            #[reserved(zero)]
+            levels: ReadPureWrite<u32>,
+            plain: ReadPureWrite<u32>,
+            status: ReadPure<u32>,
+        }
+    }
+
+    #[test]
+    fn write_masked() {
+        let mut fake = MaskedRegisters {
+            control: ReadPureWrite(0x48),
+            levels: ReadPureWrite(0xc0),
+            plain: ReadPureWrite(0),
+            status: ReadPure(0),
+        };
+        {
+            let mut regs = UniqueMmioPointer::from(&mut fake);
+            MaskedRegisters::write_masked(&mut regs, 0, 0xffff_ffff).unwrap();
+            MaskedRegisters::write_masked(&mut regs, 4, 0x1ff).unwrap();
+            MaskedRegisters::write_masked(&mut regs, 8, 0x1234_5678).unwrap();
+            assert_eq!(
+                MaskedRegisters::write_masked(&mut regs, 8, 0x1_0000_0000),
+                Err(RegisterError::InvalidValue)
+            );
+            assert_eq!(
+                MaskedRegisters::write_masked(&mut regs, 12, 0),
+                Err(RegisterError::NotWritable)
+            );
+            assert_eq!(
+                MaskedRegisters::write_masked(&mut regs, 2, 0),
+                Err(RegisterError::UnknownRegister)
+            );
+        }
+        assert_eq!(fake.control.0, 0xff87 | 0x48);
+        assert_eq!(fake.levels.0, 0x3f);
+        assert_eq!(fake.plain.0, 0x1234_5678);
+    }
+
+    #[test]
+    fn modify() {
+        let mut fake = MaskedRegisters {
+            control: ReadPureWrite(0x48),
+            levels: ReadPureWrite(0xc5),
+            plain: ReadPureWrite(0),
+            status: ReadPure(0),
+        };
+        {
+            let mut regs = UniqueMmioPointer::from(&mut fake);
+            MaskedRegisters::modify(&mut regs, 0, |value| value ^ 0xffff).unwrap();
+            MaskedRegisters::modify(&mut regs, 4, |value| value + 1).unwrap();
+            assert_eq!(
+                MaskedRegisters::modify(&mut regs, 12, |value| value),
+                Err(RegisterError::NotWritable)
+            );
+        }
+        assert_eq!(fake.control.0, 0xff87 | 0x48);
+        assert_eq!(fake.levels.0, 0x06);
+    }
+
+    register_map! {
+        #[repr(C)]
+        struct TypedMaskRegisters {
+            control: Masked<ReadPureWrite<u32>, 0xff87>,
+            levels: [Masked<ReadWrite<u16>, 0x3f, ZeroReserved>; 2],
+            #[writable(0xf)]
+            overridden: Masked<ReadPureWrite<u32>, 0xff, ZeroReserved>,
+        }
+    }
+
+    #[test]
+    fn typed_mask() {
+        let [control, levels, overridden] = TypedMaskRegisters::REGISTERS else {
+            panic!("Wrong number of registers");
+        };
+        assert_eq!(control.access, Access::ReadPureWrite);
+        assert_eq!(control.writable, Some(0xff87));
+        assert_eq!(control.reserved, ReservedBits::Preserve);
+        assert_eq!(levels.width, 2);
+        assert_eq!(levels.writable, Some(0x3f));
+        assert_eq!(levels.reserved, ReservedBits::Zero);
+        assert_eq!(overridden.writable, Some(0xf));
+        assert_eq!(overridden.reserved, ReservedBits::Zero);
+
+        let mut fake = TypedMaskRegisters {
+            control: Masked(ReadPureWrite(0x48), PhantomData),
+            levels: [
+                Masked(ReadWrite(0), PhantomData),
+                Masked(ReadWrite(0), PhantomData),
+            ],
+            overridden: Masked(ReadPureWrite(0), PhantomData),
+        };
+        {
+            let mut regs = UniqueMmioPointer::from(&mut fake);
+            TypedMaskRegisters::write_masked(&mut regs, 0, 0xffff_ffff).unwrap();
+            TypedMaskRegisters::write_masked(&mut regs, 6, 0xffff).unwrap();
+        }
+        assert_eq!(fake.control.0.0, 0xff87 | 0x48);
+        assert_eq!(fake.levels[1].0.0, 0x3f);
+    }
+
+    register_map! {
+        #[repr(C, packed)]
+        struct PackedRegisters {
+            flags: ReadPureWrite<u8>,
+            control: ReadPureWrite<u32>,
+            halves: ReadPure<[u16; 2]>,
+        }
+    }
+
+    #[test]
+    fn misaligned() {
+        let halves = PackedRegisters::REGISTERS[2];
+        assert_eq!(halves.access, Access::Unsafe);
+        assert_eq!(halves.width, 4);
+
+        let mut fake = PackedRegisters {
+            flags: ReadPureWrite(0),
+            control: ReadPureWrite(0),
+            halves: ReadPure([1, 2]),
+        };
+        {
+            let mut regs = UniqueMmioPointer::from(&mut fake);
+            PackedRegisters::write_masked(&mut regs, 0, 3).unwrap();
+            assert_eq!(
+                PackedRegisters::write_masked(&mut regs, 1, 42),
+                Err(RegisterError::Misaligned)
+            );
+            assert_eq!(
+                PackedRegisters::modify(&mut regs, 5, |value| value),
+                Err(RegisterError::Misaligned)
+            );
+        }
+        assert_eq!({ fake.flags }.0, 3);
+        assert_eq!({ fake.control }.0, 0);
+    }
+}
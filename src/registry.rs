@@ -0,0 +1,330 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! A registry of device MMIO regions, to hand out each [`PhysicalInstance`] at most once.
+//!
+//! Platform code registers the MMIO region of every device when it boots, and drivers then take
+//! the `PhysicalInstance` for the device they want. The registry rejects overlapping regions, and
+//! only hands out a `PhysicalInstance` for each region once, so the uniqueness invariant of
+//! `PhysicalInstance` is checked at runtime rather than relying on every caller of
+//! [`PhysicalInstance::new`] getting it right.
+//!
+//! The registry doesn't do any locking itself. To use it as a global, wrap it in whatever mutex
+//! type your platform provides.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_mmio::{
+//!     fields::ReadWrite,
+//!     registry::{DeviceRegistry, RegistryError},
+//! };
+//!
+//! #[repr(C)]
+//! struct UartRegisters {
+//!     data: ReadWrite<u32>,
+//!     status: ReadWrite<u32>,
+//! }
+//!
+//! let mut registry = DeviceRegistry::<4>::new();
+//! // SAFETY: These are the MMIO regions of real devices on our platform.
+//! unsafe {
+//!     registry.register(0x900_0000, 0x1000).unwrap();
+//!     assert_eq!(
+//!         registry.register(0x900_0800, 0x1000),
+//!         Err(RegistryError::Overlap { pa: 0x900_0000, size: 0x1000 })
+//!     );
+//! }
+//!
+//! let uart = registry.take::<UartRegisters>(0x900_0000).unwrap();
+//! assert_eq!(
+//!     registry.take::<UartRegisters>(0x900_0000),
+//!     Err(RegistryError::AlreadyTaken)
+//! );
+//! ```
+
+use crate::PhysicalInstance;
+use core::fmt::{self, Display, Formatter};
+
+/// An error registering a device region or taking a `PhysicalInstance` from a [`DeviceRegistry`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RegistryError {
+    /// The registry has no space for any more regions.
+    Full,
+    /// The region being registered overlaps with the existing region at the given address.
+    Overlap {
+        /// The physical base address of the existing region.
+        pa: u64,
+        /// The size in bytes of the existing region.
+        size: u64,
+    },
+    /// The region being registered extends past the end of the address space.
+    Overflow,
+    /// No region has been registered at the given address.
+    NotRegistered,
+    /// The registered region is smaller than the type being requested.
+    TooSmall {
+        /// The size in bytes of the type being requested.
+        expected: u64,
+        /// The size in bytes of the registered region.
+        actual: u64,
+    },
+    /// The `PhysicalInstance` for the region has already been taken.
+    AlreadyTaken,
+}
+
+impl Display for RegistryError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Full => write!(f, "Device registry is full"),
+            Self::Overlap { pa, size } => write!(
+                f,
+                "Region overlaps existing region at {pa:#x} with size {size:#x}"
+            ),
+            Self::Overflow => write!(f, "Region extends past the end of the address space"),
+            Self::NotRegistered => write!(f, "No region registered at the given address"),
+            Self::TooSmall { expected, actual } => write!(
+                f,
+                "Registered region size {actual:#x} is smaller than requested size {expected:#x}"
+            ),
+            Self::AlreadyTaken => write!(f, "Region has already been taken"),
+        }
+    }
+}
+
+impl core::error::Error for RegistryError {}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Region {
+    pa: u64,
+    size: u64,
+    taken: bool,
+}
+
+impl Region {
+    /// Returns whether the given physical address is within this region.
+    const fn contains(&self, pa: u64) -> bool {
+        pa.wrapping_sub(self.pa) < self.size
+    }
+
+    /// Returns whether this region overlaps the given other region.
+    const fn overlaps(&self, other: &Region) -> bool {
+        self.size != 0 && other.size != 0 && (self.contains(other.pa) || other.contains(self.pa))
+    }
+}
+
+/// A registry of up to `N` device MMIO regions.
+#[derive(Clone, Debug)]
+pub struct DeviceRegistry<const N: usize> {
+    regions: [Option<Region>; N],
+}
+
+impl<const N: usize> DeviceRegistry<N> {
+    /// Creates a new empty registry.
+    pub const fn new() -> Self {
+        Self { regions: [None; N] }
+    }
+
+    /// Registers the MMIO region of a device, of `size` bytes starting at physical address `pa`.
+    ///
+    /// Addresses are 64 bits even on 32-bit targets, so that devices above 4 GiB can be registered
+    /// on targets with a larger physical address space.
+    ///
+    /// Returns an error if the region overlaps any region which has already been registered,
+    /// extends past the end of the physical address space, or if the registry is full.
+    ///
+    /// # Safety
+    ///
+    /// The region must be the MMIO region of a real device, and there must not be any
+    /// `PhysicalInstance` for any part of it other than those taken from this registry.
+    pub unsafe fn register(&mut self, pa: u64, size: u64) -> Result<(), RegistryError> {
+        // A region may end exactly at the top of the address space, so check its last byte.
+        if size != 0 && pa.checked_add(size - 1).is_none() {
+            return Err(RegistryError::Overflow);
+        }
+        let new_region = Region {
+            pa,
+            size,
+            taken: false,
+        };
+        let mut free_slot = None;
+        for (i, slot) in self.regions.iter().enumerate() {
+            match slot {
+                Some(region) if region.overlaps(&new_region) || region.pa == pa => {
+                    return Err(RegistryError::Overlap {
+                        pa: region.pa,
+                        size: region.size,
+                    });
+                }
+                Some(_) => {}
+                None => {
+                    free_slot.get_or_insert(i);
+                }
+            }
+        }
+        let free_slot = free_slot.ok_or(RegistryError::Full)?;
+        self.regions[free_slot] = Some(new_region);
+        Ok(())
+    }
+
+    /// Takes the `PhysicalInstance` for the device region registered at physical address `pa`.
+    ///
+    /// Returns an error if no region was registered at exactly `pa`, if the registered region is
+    /// smaller than `T`, or if the region has already been taken.
+    pub fn take<T>(&mut self, pa: u64) -> Result<PhysicalInstance<T>, RegistryError> {
+        let region = self
+            .regions
+            .iter_mut()
+            .flatten()
+            .find(|region| region.pa == pa)
+            .ok_or(RegistryError::NotRegistered)?;
+        let expected = size_of::<T>() as u64;
+        if region.size < expected {
+            return Err(RegistryError::TooSmall {
+                expected,
+                actual: region.size,
+            });
+        }
+        if region.taken {
+            return Err(RegistryError::AlreadyTaken);
+        }
+        region.taken = true;
+        // SAFETY: The caller of `register` promised that this is a real device region with no
+        // other `PhysicalInstance`s, we checked that it doesn't overlap any other registered
+        // region, and we've just marked it as taken so we will never return another
+        // `PhysicalInstance` for it.
+        Ok(unsafe { PhysicalInstance::new_u64(pa) })
+    }
+
+    /// Returns whether the region registered at physical address `pa` has been taken, or `None` if
+    /// there is no such region.
+    pub fn is_taken(&self, pa: u64) -> Option<bool> {
+        self.regions
+            .iter()
+            .flatten()
+            .find(|region| region.pa == pa)
+            .map(|region| region.taken)
+    }
+
+    /// Returns the physical base address and size of the registered region containing the given
+    /// physical address, if any.
+    pub fn find(&self, pa: u64) -> Option<(u64, u64)> {
+        self.regions
+            .iter()
+            .flatten()
+            .find(|region| region.contains(pa))
+            .map(|region| (region.pa, region.size))
+    }
+}
+
+impl<const N: usize> Default for DeviceRegistry<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register_and_take() {
+        let mut registry = DeviceRegistry::<4>::new();
+        // SAFETY: The instances are never used to access any device.
+        unsafe {
+            registry.register(0x1000, 0x100).unwrap();
+            registry.register(0x2000, 0x100).unwrap();
+        }
+
+        assert_eq!(registry.is_taken(0x1000), Some(false));
+        let instance = registry.take::<[u32; 4]>(0x1000).unwrap();
+        assert_eq!(instance.pa_u64(), 0x1000);
+        assert_eq!(registry.is_taken(0x1000), Some(true));
+        assert_eq!(
+            registry.take::<[u32; 4]>(0x1000),
+            Err(RegistryError::AlreadyTaken)
+        );
+
+        assert_eq!(
+            registry.take::<[u8; 0x101]>(0x2000),
+            Err(RegistryError::TooSmall {
+                expected: 0x101,
+                actual: 0x100
+            })
+        );
+        assert!(registry.take::<[u8; 0x100]>(0x2000).is_ok());
+
+        assert_eq!(
+            registry.take::<u32>(0x3000),
+            Err(RegistryError::NotRegistered)
+        );
+        assert_eq!(
+            registry.take::<u32>(0x1004),
+            Err(RegistryError::NotRegistered)
+        );
+        assert_eq!(registry.is_taken(0x3000), None);
+    }
+
+    #[test]
+    fn reject_overlap() {
+        let mut registry = DeviceRegistry::<4>::new();
+        // SAFETY: The instances are never used to access any device.
+        unsafe {
+            registry.register(0x1000, 0x100).unwrap();
+            assert_eq!(
+                registry.register(0x10ff, 0x10),
+                Err(RegistryError::Overlap {
+                    pa: 0x1000,
+                    size: 0x100
+                })
+            );
+            assert_eq!(
+                registry.register(0xf00, 0x101),
+                Err(RegistryError::Overlap {
+                    pa: 0x1000,
+                    size: 0x100
+                })
+            );
+            assert_eq!(
+                registry.register(0x1000, 0),
+                Err(RegistryError::Overlap {
+                    pa: 0x1000,
+                    size: 0x100
+                })
+            );
+            registry.register(0xf00, 0x100).unwrap();
+            registry.register(0x1100, 0x100).unwrap();
+            assert_eq!(registry.register(u64::MAX, 2), Err(RegistryError::Overflow));
+        }
+        assert_eq!(registry.find(0x10ff), Some((0x1000, 0x100)));
+        assert_eq!(registry.find(0x1200), None);
+    }
+
+    #[test]
+    fn high_addresses() {
+        let mut registry = DeviceRegistry::<4>::new();
+        // SAFETY: The instances are never used to access any device.
+        unsafe {
+            registry.register(0x1_0000_0000, 0x1000).unwrap();
+            registry.register(u64::MAX - 0xfff, 0x1000).unwrap();
+            assert_eq!(
+                registry.register(u64::MAX - 0xfff, 0x1001),
+                Err(RegistryError::Overflow)
+            );
+        }
+        let instance = registry.take::<u32>(0x1_0000_0000).unwrap();
+        assert_eq!(instance.pa_u64(), 0x1_0000_0000);
+        assert_eq!(registry.find(u64::MAX), Some((u64::MAX - 0xfff, 0x1000)));
+    }
+
+    #[test]
+    fn full() {
+        let mut registry = DeviceRegistry::<1>::new();
+        // SAFETY: The instances are never used to access any device.
+        unsafe {
+            registry.register(0x1000, 0x100).unwrap();
+            assert_eq!(registry.register(0x2000, 0x100), Err(RegistryError::Full));
+        }
+    }
+}
@@ -0,0 +1,231 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Detection of stale pointers into device registers after the device has been reset.
+
+use crate::UniqueMmioPointer;
+use core::{fmt::Debug, marker::PhantomData, ptr::NonNull};
+
+/// A pointer to the registers of a device which may be reset, along with a generation counter
+/// which is incremented on each reset.
+///
+/// After a reset, previously projected child pointers may no longer be meaningful, for example if
+/// the device has moved its queue doorbells. Rather than holding on to child pointers, drivers can
+/// keep [`ChildHandle`]s, which record the generation in which they were created. In debug builds,
+/// using a handle from before the most recent reset panics.
+///
+/// # Example
+///
+/// ```
+/// use safe_mmio::{
+///     UniqueMmioPointer, field, fields::ReadWrite, resettable::ResettableMmio, split_fields,
+/// };
+///
+/// #[repr(C)]
+/// struct Registers {
+///     reset: ReadWrite<u32>,
+///     doorbell: ReadWrite<u32>,
+/// }
+///
+/// let regs: UniqueMmioPointer<Registers>;
+/// # let mut fake = Registers { reset: ReadWrite(0), doorbell: ReadWrite(0) };
+/// # regs = UniqueMmioPointer::from(&mut fake);
+/// let mut device = ResettableMmio::new(regs);
+/// // SAFETY: Only one field is split.
+/// let doorbell = device.handle(|regs| unsafe { split_fields!(regs, doorbell) });
+/// device.get(&doorbell).write(1);
+///
+/// device.reset(|mut regs| field!(regs, reset).write(1));
+/// // `device.get(&doorbell)` would now panic in a debug build, so get a new handle.
+/// // SAFETY: Only one field is split.
+/// let doorbell = device.handle(|regs| unsafe { split_fields!(regs, doorbell) });
+/// device.get(&doorbell).write(1);
+/// ```
+#[derive(Debug)]
+pub struct ResettableMmio<'a, T> {
+    regs: UniqueMmioPointer<'a, T>,
+    generation: u64,
+}
+
+impl<'a, T> ResettableMmio<'a, T> {
+    /// Wraps the given pointer to device registers, starting at generation 0.
+    pub const fn new(regs: UniqueMmioPointer<'a, T>) -> Self {
+        Self {
+            regs,
+            generation: 0,
+        }
+    }
+
+    /// Returns the current generation, i.e. the number of times the device has been reset through
+    /// this wrapper.
+    pub const fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Returns a pointer to the device registers.
+    pub const fn regs(&mut self) -> UniqueMmioPointer<'_, T> {
+        self.regs.reborrow()
+    }
+
+    /// Calls `reset` with a pointer to the device registers to reset the device, and then
+    /// increments the generation so that all existing [`ChildHandle`]s become stale.
+    pub fn reset(&mut self, reset: impl FnOnce(UniqueMmioPointer<'_, T>)) {
+        reset(self.regs.reborrow());
+        self.generation += 1;
+    }
+
+    /// Calls `project` to get a pointer to some part of the device registers, and returns a handle
+    /// which can later be used to get that pointer again in the current generation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pointer returned by `project` isn't within the device registers, or if `U`
+    /// needs a greater alignment than `T` or isn't aligned within it, as then the handle might not
+    /// give an aligned pointer for another instance of `T`.
+    pub fn handle<U>(
+        &mut self,
+        project: impl FnOnce(UniqueMmioPointer<'_, T>) -> UniqueMmioPointer<'_, U>,
+    ) -> ChildHandle<T, U> {
+        let base = self.regs.ptr_nonnull().addr().get();
+        let child = project(self.regs.reborrow()).ptr_nonnull().addr().get();
+        let offset = child
+            .checked_sub(base)
+            .filter(|offset| {
+                offset
+                    .checked_add(size_of::<U>())
+                    .is_some_and(|end| end <= size_of::<T>())
+            })
+            .expect("Child pointer is not within the device registers");
+        assert!(
+            align_of::<U>() <= align_of::<T>() && offset % align_of::<U>() == 0,
+            "Child pointer is not aligned within the device registers"
+        );
+        ChildHandle {
+            offset,
+            generation: self.generation,
+            _type: PhantomData,
+        }
+    }
+
+    /// Returns the pointer for the given handle.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if the device has been reset since the handle was created.
+    pub fn get<U>(&mut self, handle: &ChildHandle<T, U>) -> UniqueMmioPointer<'_, U> {
+        debug_assert_eq!(
+            handle.generation, self.generation,
+            "Used child handle from before device reset"
+        );
+        // SAFETY: The handle was created by `handle` for a `ResettableMmio<T>`, which checked that
+        // its offset and size are within `T` and that its offset is a multiple of the alignment of
+        // `U`, which is no greater than that of `T`. So the resulting pointer is valid for MMIO and
+        // properly aligned. It borrows `self` mutably, so there can't be any other pointers to it
+        // in use.
+        unsafe {
+            let child = NonNull::new_unchecked(self.regs.ptr_mut().byte_add(handle.offset).cast());
+            self.regs.child(child)
+        }
+    }
+}
+
+/// A handle to a `U` within the registers of a [`ResettableMmio<T>`], which is only valid until the
+/// device is next reset.
+///
+/// It can only be used with a `ResettableMmio` for the same `T` as it was created from, so that its
+/// offset is always within the registers.
+///
+/// ```compile_fail,E0308
+/// use safe_mmio::{UniqueMmioPointer, fields::ReadWrite, resettable::ResettableMmio};
+///
+/// let mut large = [const { ReadWrite(0u32) }; 100];
+/// let mut small = ReadWrite(0u32);
+/// let mut large = ResettableMmio::new(UniqueMmioPointer::from(&mut large));
+/// let mut small = ResettableMmio::new(UniqueMmioPointer::from(&mut small));
+/// let handle = large.handle(|regs| regs.take(99).unwrap());
+/// small.get(&handle);
+/// ```
+pub struct ChildHandle<T, U> {
+    offset: usize,
+    generation: u64,
+    _type: PhantomData<fn() -> (T, U)>,
+}
+
+impl<T, U> ChildHandle<T, U> {
+    /// Returns the generation of the `ResettableMmio` in which this handle was created.
+    pub const fn generation(&self) -> u64 {
+        self.generation
+    }
+}
+
+impl<T, U> Clone for ChildHandle<T, U> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, U> Copy for ChildHandle<T, U> {}
+
+impl<T, U> Debug for ChildHandle<T, U> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("ChildHandle")
+            .field("offset", &self.offset)
+            .field("generation", &self.generation)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fields::ReadWrite;
+
+    #[test]
+    fn handle_and_get() {
+        let mut fake = [ReadWrite(0u32), ReadWrite(0), ReadWrite(0)];
+        let mut device = ResettableMmio::new(UniqueMmioPointer::from(&mut fake));
+        let handle = device.handle(|regs| regs.take(2).unwrap());
+        assert_eq!(handle.generation(), 0);
+        device.get(&handle).write(42);
+
+        device.reset(|mut regs| regs.get(0).unwrap().write(1));
+        assert_eq!(device.generation(), 1);
+        let handle = device.handle(|regs| regs.take(2).unwrap());
+        assert_eq!(handle.generation(), 1);
+        assert_eq!(device.get(&handle).read(), 42);
+        assert_eq!(fake[0].0, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Child pointer is not within the device registers")]
+    fn handle_outside_registers() {
+        let mut fake = [ReadWrite(0u32), ReadWrite(0)];
+        let mut device = ResettableMmio::new(UniqueMmioPointer::from(&mut fake));
+        // SAFETY: The pointer is never used to access anything.
+        device.handle(|_| unsafe { UniqueMmioPointer::new(NonNull::<ReadWrite<u32>>::dangling()) });
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "Used child handle from before device reset")]
+    fn stale_handle() {
+        let mut fake = [ReadWrite(0u32), ReadWrite(0)];
+        let mut device = ResettableMmio::new(UniqueMmioPointer::from(&mut fake));
+        let handle = device.handle(|regs| regs.take(1).unwrap());
+        device.reset(|_| {});
+        device.get(&handle);
+    }
+
+    #[test]
+    #[should_panic(expected = "Child pointer is not aligned within the device registers")]
+    fn handle_more_aligned_than_registers() {
+        let mut fake = [ReadWrite(0u32), ReadWrite(0)];
+        let mut device = ResettableMmio::new(UniqueMmioPointer::from(&mut fake));
+        device.handle(|mut regs| {
+            let child = regs.ptr_nonnull().cast::<ReadWrite<u64>>();
+            // SAFETY: `fake` is 8 bytes long, and the pointer is never used to access anything.
+            unsafe { regs.split_child(child) }
+        });
+    }
+}
@@ -0,0 +1,312 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Helper for DMA descriptor rings with MMIO head and tail index registers.
+
+use crate::{UniqueMmioPointer, access::AtomicAccess};
+use core::{marker::PhantomData, ptr::NonNull};
+
+/// A ring of DMA descriptors in normal memory, which the driver produces and the device consumes.
+///
+/// The driver adds descriptors at the tail of the ring with [`try_push`](Self::try_push), and then
+/// tells the device about them by writing the tail index register with
+/// [`publish`](Self::publish). The device processes descriptors from the head of the ring, and
+/// advances the head index register as it does so. The driver then gets back each descriptor which
+/// the device has finished with from [`reclaim`](Self::reclaim), e.g. to check its status or free
+/// its buffer, after which its slot can be reused.
+///
+/// Indices in the registers are always in the range `0..len`, and one slot is always kept free so
+/// that a full ring can be told apart from an empty one.
+///
+/// # Example
+///
+/// ```
+/// use safe_mmio::{UniqueMmioPointer, ring::DescriptorRing};
+///
+/// #[derive(Clone, Copy, Debug, Default)]
+/// #[repr(C)]
+/// struct Descriptor {
+///     address: u64,
+///     length: u32,
+///     flags: u32,
+/// }
+///
+/// let head: UniqueMmioPointer<u32>;
+/// let tail: UniqueMmioPointer<u32>;
+/// # let mut fake_head = 0;
+/// # let mut fake_tail = 0;
+/// # head = UniqueMmioPointer::from(&mut fake_head);
+/// # tail = UniqueMmioPointer::from(&mut fake_tail);
+/// let mut descriptors = [Descriptor::default(); 64];
+/// // SAFETY: The device has been told the address and length of `descriptors`, and has been reset
+/// // so it is idle with both indices at 0.
+/// let mut ring = unsafe { DescriptorRing::new(&mut descriptors, head, tail) };
+/// ring.try_push(Descriptor { address: 0x8000_0000, length: 1500, flags: 0 }).unwrap();
+/// ring.publish();
+/// // Later, e.g. from the completion interrupt handler:
+/// ring.reclaim(|descriptor| {
+///     // Free the buffer at descriptor.address.
+/// });
+/// ```
+#[derive(Debug)]
+pub struct DescriptorRing<'a, D, I> {
+    descriptors: NonNull<[D]>,
+    phantom: PhantomData<&'a mut [D]>,
+    head: UniqueMmioPointer<'a, I>,
+    tail: UniqueMmioPointer<'a, I>,
+    /// The index at which the next descriptor will be added.
+    next: usize,
+    /// The index last written to the tail register.
+    published: usize,
+    /// The index of the oldest descriptor which hasn't yet been reclaimed.
+    reclaimed: usize,
+}
+
+impl<'a, D: Copy, I: AtomicAccess + TryFrom<usize> + TryInto<usize>> DescriptorRing<'a, D, I> {
+    /// Creates a new `DescriptorRing` from the descriptor array and pointers to the head and tail
+    /// index registers.
+    ///
+    /// The ring starts out empty, at the index currently in the head register.
+    ///
+    /// # Safety
+    ///
+    /// The device must have been configured to use `descriptors` as its ring, and must not access
+    /// any descriptor other than those between the head and tail indices. It must be idle, with the
+    /// tail register equal to the head register. It must be safe to read the head register and to
+    /// write any index in the range `0..descriptors.len()` to the tail register at any time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are fewer than 2 descriptors, or the largest index doesn't fit in `I`.
+    pub unsafe fn new(
+        descriptors: &'a mut [D],
+        mut head: UniqueMmioPointer<'a, I>,
+        tail: UniqueMmioPointer<'a, I>,
+    ) -> Self {
+        assert!(
+            descriptors.len() >= 2,
+            "Ring must have at least 2 descriptors"
+        );
+        assert!(
+            I::try_from(descriptors.len() - 1).is_ok(),
+            "Ring is too long for its index registers"
+        );
+        let len = descriptors.len();
+        // SAFETY: Our caller promised that it is safe to read the head register.
+        let start = index_to_usize(unsafe { head.read_unsafe() }, len);
+        Self {
+            descriptors: NonNull::from(descriptors),
+            phantom: PhantomData,
+            head,
+            tail,
+            next: start,
+            published: start,
+            reclaimed: start,
+        }
+    }
+
+    /// Returns the number of descriptors in the ring, including the one which is always kept free.
+    pub fn len(&self) -> usize {
+        self.descriptors.len()
+    }
+
+    /// Returns whether there are no descriptors in the ring which haven't been reclaimed.
+    pub fn is_empty(&self) -> bool {
+        self.next == self.reclaimed
+    }
+
+    /// Returns the number of descriptors which can be added before the ring is full.
+    ///
+    /// This doesn't read the head register, so descriptors which the device has finished with
+    /// aren't counted as free until they have been reclaimed.
+    pub fn free(&self) -> usize {
+        (self.reclaimed + self.len() - self.next - 1) % self.len()
+    }
+
+    /// Writes the given descriptor to the tail of the ring, unless it is full in which case the
+    /// descriptor is returned as an error.
+    ///
+    /// The device won't see the descriptor until [`publish`](Self::publish) is called.
+    pub fn try_push(&mut self, descriptor: D) -> Result<(), D> {
+        if self.free() == 0 {
+            return Err(descriptor);
+        }
+        // SAFETY: `next` is always less than the length of `descriptors`, and the device won't
+        // access it until the tail register is updated.
+        unsafe {
+            self.descriptors
+                .cast::<D>()
+                .add(self.next)
+                .write_volatile(descriptor);
+        }
+        self.next = (self.next + 1) % self.len();
+        Ok(())
+    }
+
+    /// Tells the device about all descriptors added since the last call, by writing the tail index
+    /// register.
+    ///
+    /// A barrier is issued first, so the device sees the descriptor writes before the new index.
+    pub fn publish(&mut self) {
+        if self.next == self.published {
+            return;
+        }
+        dma_write_barrier();
+        let Ok(next) = I::try_from(self.next) else {
+            unreachable!("Index {} doesn't fit in the index register", self.next);
+        };
+        // SAFETY: The caller of `new` promised that writing any index within the ring to the tail
+        // register is safe.
+        unsafe {
+            self.tail.write_unsafe(next);
+        }
+        self.published = self.next;
+    }
+
+    /// Reads the head index register, and then calls `f` with each descriptor which the device has
+    /// finished with since the last call, in order.
+    ///
+    /// Returns the number of descriptors reclaimed. Their slots can then be reused.
+    pub fn reclaim(&mut self, mut f: impl FnMut(D)) -> usize {
+        // SAFETY: The caller of `new` promised that reading the head register is safe.
+        let head = index_to_usize(unsafe { self.head.read_unsafe() }, self.len());
+        // Make sure that we don't read any descriptors before the device has finished writing them.
+        dma_read_barrier();
+        let mut count = 0;
+        while self.reclaimed != head && self.reclaimed != self.published {
+            // SAFETY: `reclaimed` is always less than the length of `descriptors`, and the device
+            // has finished with it.
+            f(unsafe {
+                self.descriptors
+                    .cast::<D>()
+                    .add(self.reclaimed)
+                    .read_volatile()
+            });
+            self.reclaimed = (self.reclaimed + 1) % self.len();
+            count += 1;
+        }
+        count
+    }
+
+    /// Returns the original pointers to the head and tail index registers.
+    pub fn into_inner(self) -> (UniqueMmioPointer<'a, I>, UniqueMmioPointer<'a, I>) {
+        (self.head, self.tail)
+    }
+}
+
+/// Converts an index read from an index register to a `usize`.
+///
+/// # Panics
+///
+/// Panics if the index is out of range for a ring of the given length, which means that the device
+/// is misbehaving.
+fn index_to_usize<I: TryInto<usize>>(index: I, len: usize) -> usize {
+    match index.try_into() {
+        Ok(index) if index < len => index,
+        _ => panic!("Device returned an index out of range for ring of length {len}"),
+    }
+}
+
+/// Makes sure that writes to normal memory are visible to devices before any later writes to MMIO
+/// registers.
+#[inline(always)]
+fn dma_write_barrier() {
+    #[cfg(all(target_arch = "aarch64", not(miri)))]
+    // SAFETY: A barrier has no effect on memory safety.
+    unsafe {
+        core::arch::asm!("dmb oshst", options(nostack, preserves_flags));
+    }
+    #[cfg(any(not(target_arch = "aarch64"), miri))]
+    core::sync::atomic::fence(core::sync::atomic::Ordering::SeqCst);
+}
+
+/// Makes sure that later reads from normal memory see any writes made by a device before an earlier
+/// read from an MMIO register.
+#[inline(always)]
+fn dma_read_barrier() {
+    #[cfg(all(target_arch = "aarch64", not(miri)))]
+    // SAFETY: A barrier has no effect on memory safety.
+    unsafe {
+        core::arch::asm!("dmb oshld", options(nostack, preserves_flags));
+    }
+    #[cfg(any(not(target_arch = "aarch64"), miri))]
+    core::sync::atomic::fence(core::sync::atomic::Ordering::SeqCst);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_publish_reclaim() {
+        let mut descriptors = [0u64; 4];
+        let mut head = 2u16;
+        let mut tail = 2u16;
+        {
+            // SAFETY: The registers are just fakes, and there is no device.
+            let mut ring = unsafe {
+                DescriptorRing::new(
+                    &mut descriptors,
+                    UniqueMmioPointer::from(&mut head),
+                    UniqueMmioPointer::from(&mut tail),
+                )
+            };
+            assert_eq!(ring.len(), 4);
+            assert!(ring.is_empty());
+            assert_eq!(ring.free(), 3);
+            assert_eq!(ring.try_push(10), Ok(()));
+            assert_eq!(ring.try_push(11), Ok(()));
+            assert_eq!(ring.try_push(12), Ok(()));
+            assert_eq!(ring.try_push(13), Err(13));
+            assert_eq!(ring.free(), 0);
+            // Nothing has been published, so nothing can be reclaimed.
+            assert_eq!(ring.reclaim(|_| panic!()), 0);
+            ring.publish();
+            // SAFETY: This is just a fake.
+            assert_eq!(unsafe { ring.tail.read_unsafe() }, 1);
+
+            // Pretend that the device has consumed two descriptors.
+            // SAFETY: This is just a fake.
+            unsafe { ring.head.write_unsafe(0) };
+            let mut reclaimed = [0; 4];
+            let mut count = 0;
+            assert_eq!(
+                ring.reclaim(|descriptor| {
+                    reclaimed[count] = descriptor;
+                    count += 1;
+                }),
+                2
+            );
+            assert_eq!(reclaimed, [10, 11, 0, 0]);
+            assert_eq!(ring.free(), 2);
+            assert!(!ring.is_empty());
+
+            assert_eq!(ring.try_push(20), Ok(()));
+            ring.publish();
+            // Pretend that the device has consumed the rest.
+            // SAFETY: This is just a fake.
+            unsafe { ring.head.write_unsafe(2) };
+            assert_eq!(ring.reclaim(|_| {}), 2);
+            assert!(ring.is_empty());
+        }
+        assert_eq!(tail, 2);
+        assert_eq!(descriptors, [12, 20, 10, 11]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Device returned an index out of range for ring of length 4")]
+    fn index_out_of_range() {
+        let mut descriptors = [0u32; 4];
+        let mut head = 4u32;
+        let mut tail = 0u32;
+        // SAFETY: The registers are just fakes, and there is no device.
+        unsafe {
+            DescriptorRing::new(
+                &mut descriptors,
+                UniqueMmioPointer::from(&mut head),
+                UniqueMmioPointer::from(&mut tail),
+            );
+        }
+    }
+}
@@ -0,0 +1,92 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Shadow copies of write-only registers.
+
+use crate::{UniqueMmioPointer, access::AtomicAccess, fields::WriteOnly};
+
+/// A pointer to a write-only register, along with a copy of the value last written to it.
+///
+/// All writes to the register should go through this, so that the shadow copy stays in sync with
+/// the hardware.
+///
+/// # Example
+///
+/// ```
+/// use safe_mmio::{UniqueMmioPointer, fields::WriteOnly, shadow::Shadowed};
+///
+/// let control: UniqueMmioPointer<WriteOnly<u32>>;
+/// # let mut fake = WriteOnly(0);
+/// # control = UniqueMmioPointer::from(&mut fake);
+/// // The control register is 0 after reset.
+/// let mut control = Shadowed::new(control, 0);
+/// control.modify(|value| value | 0x1);
+/// control.modify(|value| value | 0x4);
+/// assert_eq!(control.read_shadow(), 0x5);
+/// ```
+#[derive(Debug)]
+pub struct Shadowed<'a, T> {
+    register: UniqueMmioPointer<'a, WriteOnly<T>>,
+    shadow: T,
+}
+
+impl<'a, T: AtomicAccess> Shadowed<'a, T> {
+    /// Wraps the given pointer to a write-only register, which currently has the given value.
+    ///
+    /// This doesn't write to the register.
+    pub const fn new(register: UniqueMmioPointer<'a, WriteOnly<T>>, current: T) -> Self {
+        Self {
+            register,
+            shadow: current,
+        }
+    }
+
+    /// Returns the value last written to the register.
+    pub const fn read_shadow(&self) -> T {
+        self.shadow
+    }
+
+    /// Performs an MMIO write of the entire `T`, and updates the shadow copy.
+    pub fn write(&mut self, value: T) {
+        self.register.write(value);
+        self.shadow = value;
+    }
+
+    /// Applies the given function to the shadow copy, and then writes the resulting value.
+    pub fn modify(&mut self, f: impl FnOnce(T) -> T) {
+        self.write(f(self.shadow));
+    }
+
+    /// Calls the given function to modify a copy of the shadow value, and then writes the resulting
+    /// value.
+    pub fn modify_mut(&mut self, f: impl FnOnce(&mut T)) {
+        let mut value = self.shadow;
+        f(&mut value);
+        self.write(value);
+    }
+
+    /// Returns the original pointer to the register.
+    pub fn into_inner(self) -> UniqueMmioPointer<'a, WriteOnly<T>> {
+        self.register
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shadow_in_sync() {
+        let mut fake = WriteOnly(0u32);
+        {
+            let mut shadowed = Shadowed::new(UniqueMmioPointer::from(&mut fake), 0x10);
+            assert_eq!(shadowed.read_shadow(), 0x10);
+            shadowed.modify(|value| value | 0x1);
+            assert_eq!(shadowed.read_shadow(), 0x11);
+            shadowed.modify_mut(|value| *value &= !0x10);
+            assert_eq!(shadowed.read_shadow(), 0x1);
+        }
+        assert_eq!(fake.0, 0x1);
+    }
+}
@@ -0,0 +1,388 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! An MMIO backend which routes accesses to behavioural models of devices, for testing drivers on
+//! the host.
+//!
+//! With the `simulated` feature enabled, [`SimulatedBackend`] can be registered as the
+//! [`custom_mmio`] backend. Each [`Simulation`] reserves a range of addresses for a
+//! [`DeviceModel`], and hands out pointers to it. Every MMIO access through those pointers is
+//! then turned into a call to the model's [`mmio_read`](DeviceModel::mmio_read) or
+//! [`mmio_write`](DeviceModel::mmio_write) method with the offset and width of the access, so a
+//! driver's whole state machine can be tested against a model of how the device behaves.
+//!
+//! Accesses to addresses outside every simulation panic.
+//!
+//! To check that concurrent accesses through [`SharedMmioPointer`]s are correctly synchronised,
+//! [`set_yield_hook`] can register a function to be called before every simulated access. Pointing
+//! it at the `yield_now` function of a model checker such as `shuttle` or `loom` (or just
+//! [`std::thread::yield_now`] for a stress test) lets the checker switch threads at each access, so
+//! it can explore the different orders in which the device model sees them.
+//!
+//! [`custom_mmio`]: crate::custom_mmio
+//!
+//! # Example
+//!
+//! ```standalone_crate
+//! use safe_mmio::{
+//!     field,
+//!     fields::{ReadPure, WriteOnly},
+//!     set_mmio_ops,
+//!     simulated::{DeviceModel, SimulatedBackend, Simulation},
+//! };
+//!
+//! set_mmio_ops!(SimulatedBackend);
+//!
+//! #[repr(C)]
+//! struct Registers {
+//!     data: WriteOnly<u32>,
+//!     count: ReadPure<u32>,
+//! }
+//!
+//! /// A model of a device which counts the bytes written to it.
+//! #[derive(Default)]
+//! struct Counter {
+//!     written: Vec<u8>,
+//! }
+//!
+//! impl DeviceModel for Counter {
+//!     fn mmio_read(&mut self, offset: usize, _width: usize) -> u64 {
+//!         match offset {
+//!             4 => self.written.len() as u64,
+//!             _ => panic!("Unexpected read at {offset:#x}"),
+//!         }
+//!     }
+//!
+//!     fn mmio_write(&mut self, offset: usize, _width: usize, value: u64) {
+//!         match offset {
+//!             0 => self.written.push(value as u8),
+//!             _ => panic!("Unexpected write at {offset:#x}"),
+//!         }
+//!     }
+//! }
+//!
+//! let mut simulation = Simulation::new(Counter::default(), size_of::<Registers>());
+//! let mut regs = simulation.pointer::<Registers>();
+//! for byte in b"hi" {
+//!     field!(regs, data).write((*byte).into());
+//! }
+//! assert_eq!(field!(regs, count).read(), 2);
+//! assert_eq!(simulation.model().written, b"hi");
+//! ```
+
+use crate::{SharedMmioPointer, UniqueMmioPointer, custom_mmio::MmioOps};
+use core::{
+    mem::transmute,
+    ptr::{NonNull, null_mut},
+    sync::atomic::{AtomicPtr, Ordering},
+};
+use std::{
+    boxed::Box,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    vec,
+    vec::Vec,
+};
+
+/// A behavioural model of a device, which handles MMIO accesses to a [`Simulation`].
+pub trait DeviceModel: Send {
+    /// Handles an MMIO read of `width` bytes at the given byte offset from the start of the
+    /// simulated region, and returns the value read.
+    ///
+    /// Only the low `width` bytes of the returned value are used.
+    fn mmio_read(&mut self, offset: usize, width: usize) -> u64;
+
+    /// Handles an MMIO write of the low `width` bytes of `value` at the given byte offset from the
+    /// start of the simulated region.
+    fn mmio_write(&mut self, offset: usize, width: usize, value: u64);
+}
+
+/// A range of addresses which accesses are routed to a device model.
+struct Region {
+    start: usize,
+    len: usize,
+    model: Arc<Mutex<dyn DeviceModel>>,
+}
+
+static REGIONS: Mutex<Vec<Region>> = Mutex::new(Vec::new());
+static YIELD_HOOK: AtomicPtr<()> = AtomicPtr::new(null_mut());
+
+/// A function called before each simulated access, which may switch to another thread.
+pub type YieldHook = fn();
+
+/// Sets a function to be called before every access routed by [`SimulatedBackend`], replacing any
+/// previous one.
+///
+/// The hook is called before any locks are taken, so it may block or switch to another thread. This
+/// is intended to give a model checker such as `shuttle` or `loom` a scheduling point at each MMIO
+/// access, e.g. `set_yield_hook(shuttle::thread::yield_now)`.
+pub fn set_yield_hook(hook: YieldHook) {
+    YIELD_HOOK.store(hook as *mut (), Ordering::Release);
+}
+
+/// Removes the yield hook, if any.
+pub fn clear_yield_hook() {
+    YIELD_HOOK.store(null_mut(), Ordering::Release);
+}
+
+/// Calls the yield hook, if one is set.
+fn yield_point() {
+    let hook = YIELD_HOOK.load(Ordering::Acquire);
+    if !hook.is_null() {
+        // SAFETY: The only non-null values ever stored in `YIELD_HOOK` are `YieldHook` pointers, by
+        // `set_yield_hook`.
+        let hook = unsafe { transmute::<*mut (), YieldHook>(hook) };
+        hook();
+    }
+}
+
+/// A device model attached to a range of addresses, through which [`SimulatedBackend`] routes
+/// accesses to it.
+///
+/// The addresses are reserved by an allocation of normal memory, so they can't overlap any other
+/// simulation, but the memory itself is never accessed by the backend. The model is detached when
+/// the `Simulation` is dropped.
+pub struct Simulation<M> {
+    memory: Box<[u64]>,
+    len: usize,
+    model: Arc<Mutex<M>>,
+}
+
+impl<M: DeviceModel + 'static> Simulation<M> {
+    /// Attaches the given model to a new range of `len` bytes of addresses.
+    pub fn new(model: M, len: usize) -> Self {
+        let memory = vec![0; len.div_ceil(size_of::<u64>()).max(1)].into_boxed_slice();
+        let model = Arc::new(Mutex::new(model));
+        lock(&REGIONS).push(Region {
+            start: memory.as_ptr().addr(),
+            len,
+            model: model.clone(),
+        });
+        Self { memory, len, model }
+    }
+}
+
+impl<M> Simulation<M> {
+    /// Returns a pointer to registers of type `T` at the start of the simulated region.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` is larger than the simulated region or needs more than 8-byte alignment.
+    pub fn pointer<T>(&mut self) -> UniqueMmioPointer<'_, T> {
+        let regs = self.regs::<T>();
+        // SAFETY: The region is reserved by `memory`, which we borrow mutably for the lifetime of
+        // the pointer. Accesses through it are handled by the model rather than touching memory.
+        unsafe { UniqueMmioPointer::new(regs) }
+    }
+
+    /// Returns a shared pointer to registers of type `T` at the start of the simulated region,
+    /// e.g. to access it from several threads at once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` is larger than the simulated region or needs more than 8-byte alignment.
+    pub fn shared_pointer<T>(&self) -> SharedMmioPointer<'_, T> {
+        let regs = self.regs::<T>();
+        // SAFETY: The region is reserved by `memory`, which we borrow for the lifetime of the
+        // pointer. Accesses through it are handled by the model rather than touching memory.
+        unsafe { UniqueMmioPointer::new(regs) }.into()
+    }
+
+    /// Locks and returns the device model, e.g. to inspect its state.
+    ///
+    /// Any MMIO access to the simulation will block while the returned guard is held.
+    pub fn model(&self) -> MutexGuard<'_, M> {
+        lock(&self.model)
+    }
+
+    fn regs<T>(&self) -> NonNull<T> {
+        assert!(
+            size_of::<T>() <= self.len,
+            "Registers are larger than the simulated region"
+        );
+        assert!(
+            align_of::<T>() <= align_of::<u64>(),
+            "Registers need more than 8-byte alignment"
+        );
+        NonNull::from(&*self.memory).cast()
+    }
+}
+
+impl<M> Drop for Simulation<M> {
+    fn drop(&mut self) {
+        let start = self.memory.as_ptr().addr();
+        lock(&REGIONS).retain(|region| region.start != start);
+    }
+}
+
+/// Locks the given mutex, ignoring poisoning so that one failed test doesn't break others.
+fn lock<T: ?Sized>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Finds the simulation containing an access of `width` bytes at `address`, and returns its model
+/// and the offset of the access within it.
+///
+/// # Panics
+///
+/// Panics if the access isn't entirely within a single simulation.
+fn find_model(address: usize, width: usize) -> (Arc<Mutex<dyn DeviceModel>>, usize) {
+    let regions = lock(&REGIONS);
+    let Some(region) = regions
+        .iter()
+        .find(|region| address >= region.start && address - region.start + width <= region.len)
+    else {
+        panic!("MMIO access of {width} bytes at {address:#x} is outside every simulation");
+    };
+    (region.model.clone(), address - region.start)
+}
+
+fn read(address: usize, width: usize) -> u64 {
+    yield_point();
+    let (model, offset) = find_model(address, width);
+    lock(&model).mmio_read(offset, width)
+}
+
+fn write(address: usize, width: usize, value: u64) {
+    yield_point();
+    let (model, offset) = find_model(address, width);
+    lock(&model).mmio_write(offset, width, value);
+}
+
+/// An [`MmioOps`] implementation which routes each access to the [`DeviceModel`] of the
+/// [`Simulation`] containing its address.
+///
+/// Register it with [`set_mmio_ops!`](crate::set_mmio_ops) in the test binary.
+#[derive(Debug)]
+pub struct SimulatedBackend;
+
+// SAFETY: None of the methods access memory through the given pointers, so they are trivially
+// sound for any pointer.
+unsafe impl MmioOps for SimulatedBackend {
+    unsafe fn read_u8(src: *const u8) -> u8 {
+        read(src.addr(), 1) as u8
+    }
+
+    unsafe fn read_u16(src: *const u16) -> u16 {
+        read(src.addr(), 2) as u16
+    }
+
+    unsafe fn read_u32(src: *const u32) -> u32 {
+        read(src.addr(), 4) as u32
+    }
+
+    unsafe fn read_u64(src: *const u64) -> u64 {
+        read(src.addr(), 8)
+    }
+
+    unsafe fn write_u8(dst: *mut u8, value: u8) {
+        write(dst.addr(), 1, value.into());
+    }
+
+    unsafe fn write_u16(dst: *mut u16, value: u16) {
+        write(dst.addr(), 2, value.into());
+    }
+
+    unsafe fn write_u32(dst: *mut u32, value: u32) {
+        write(dst.addr(), 4, value.into());
+    }
+
+    unsafe fn write_u64(dst: *mut u64, value: u64) {
+        write(dst.addr(), 8, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fields::ReadWrite;
+    use core::sync::atomic::AtomicUsize;
+    use std::thread;
+
+    /// A model of a register file of four 32-bit registers, which records the last access.
+    #[derive(Default)]
+    struct Registers {
+        values: [u32; 4],
+        last: Option<(usize, usize)>,
+    }
+
+    impl DeviceModel for Registers {
+        fn mmio_read(&mut self, offset: usize, width: usize) -> u64 {
+            self.last = Some((offset, width));
+            self.values[offset / 4].into()
+        }
+
+        fn mmio_write(&mut self, offset: usize, width: usize, value: u64) {
+            self.last = Some((offset, width));
+            self.values[offset / 4] = value as u32;
+        }
+    }
+
+    #[test]
+    fn routes_to_model() {
+        let mut simulation = Simulation::new(Registers::default(), 16);
+        let mut regs = simulation.pointer::<[u32; 4]>();
+        let address = regs.ptr_mut().cast::<u32>();
+        // SAFETY: SimulatedBackend doesn't access memory.
+        unsafe {
+            SimulatedBackend::write_u32(address.add(2), 42);
+            assert_eq!(SimulatedBackend::read_u32(address.add(2)), 42);
+            assert_eq!(SimulatedBackend::read_u8(address.add(1).cast()), 0);
+        }
+        assert_eq!(simulation.model().values, [0, 0, 42, 0]);
+        assert_eq!(simulation.model().last, Some((4, 1)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn outside_simulation() {
+        let _simulation = Simulation::new(Registers::default(), 16);
+        // SAFETY: SimulatedBackend doesn't access memory.
+        unsafe {
+            SimulatedBackend::write_u32(null_mut(), 42);
+        }
+    }
+
+    #[test]
+    fn detached_on_drop() {
+        let mut simulation = Simulation::new(Registers::default(), 4);
+        let address = simulation.pointer::<u32>().ptr_mut().addr();
+        assert_eq!(read(address, 4), 0);
+        drop(simulation);
+        assert!(lock(&REGIONS).iter().all(|region| region.start != address));
+    }
+
+    #[test]
+    fn concurrent_shared_access() {
+        static YIELDS: AtomicUsize = AtomicUsize::new(0);
+
+        fn count_and_yield() {
+            YIELDS.fetch_add(1, Ordering::Relaxed);
+            thread::yield_now();
+        }
+
+        let simulation = Simulation::new(Registers::default(), 16);
+        let counter = simulation.shared_pointer::<ReadWrite<u32>>();
+        // The read-modify-write below isn't atomic, so it needs a lock to avoid lost updates.
+        let lock = Mutex::new(());
+        set_yield_hook(count_and_yield);
+        thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    for _ in 0..100 {
+                        let _guard = lock.lock().unwrap();
+                        // SAFETY: SimulatedBackend doesn't access memory.
+                        unsafe {
+                            let address = counter.ptr().cast::<u32>().cast_mut();
+                            let value = SimulatedBackend::read_u32(address);
+                            SimulatedBackend::write_u32(address, value + 1);
+                        }
+                    }
+                });
+            }
+        });
+        clear_yield_hook();
+        assert_eq!(simulation.model().values[0], 400);
+        assert!(YIELDS.load(Ordering::Relaxed) >= 800);
+    }
+}
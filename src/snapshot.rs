@@ -0,0 +1,278 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Snapshots of the registers of a device, and the differences between them.
+//!
+//! A [`Snapshot`] records the value of every register of a [`RegisterMap`] struct which can be
+//! read without side effects. Taking one before and after a firmware call or an errata workaround,
+//! and logging their [`diff`](Snapshot::diff), shows exactly which registers it changed.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_mmio::{
+//!     SharedMmioPointer,
+//!     fields::{ReadPure, ReadPureWrite, WriteOnly},
+//!     register_map,
+//!     snapshot::{Snapshot, register_count},
+//! };
+//!
+//! register_map! {
+//!     #[repr(C)]
+//!     pub struct UartRegisters {
+//!         pub data: WriteOnly<u32>,
+//!         pub status: ReadPure<u32>,
+//!         pub control: [ReadPureWrite<u32>; 2],
+//!     }
+//! }
+//!
+//! type UartSnapshot = Snapshot<UartRegisters, { register_count::<UartRegisters>() }>;
+//!
+//! # let mut fake = UartRegisters {
+//! #     data: WriteOnly(0),
+//! #     status: ReadPure(0x90),
+//! #     control: [ReadPureWrite(0), ReadPureWrite(0x301)],
+//! # };
+//! let uart: SharedMmioPointer<UartRegisters>;
+//! # uart = SharedMmioPointer::from(&fake);
+//! let before = UartSnapshot::take(&uart);
+//! // Some firmware call changes `control[1]`.
+//! # fake.control[1] = ReadPureWrite(0x300);
+//! # let uart = SharedMmioPointer::from(&fake);
+//! let after = UartSnapshot::take(&uart);
+//! for change in before.diff(&after) {
+//!     println!("{change}");
+//! }
+//! # let mut changes = before.diff(&after);
+//! # assert_eq!(changes.next().unwrap().to_string(), "control[1] (0xc): 0x00000301 -> 0x00000300");
+//! # assert!(changes.next().is_none());
+//! ```
+
+use crate::{
+    SharedMmioPointer,
+    register_map::{Access, RegisterInfo, RegisterMap, read_register},
+};
+use core::{
+    fmt::{self, Debug, Display, Formatter},
+    marker::PhantomData,
+};
+
+/// Returns the number of individual registers in the given register struct, counting each element
+/// of an array separately.
+///
+/// This is the length which a [`Snapshot`] of the struct must have.
+pub const fn register_count<R: RegisterMap>() -> usize {
+    let mut count = 0;
+    let mut i = 0;
+    while i < R::REGISTERS.len() {
+        count += elements(&R::REGISTERS[i]);
+        i += 1;
+    }
+    count
+}
+
+/// Returns the number of registers in the given field.
+const fn elements(info: &RegisterInfo) -> usize {
+    match info.size.checked_div(info.width) {
+        Some(elements) => elements,
+        None => 0,
+    }
+}
+
+/// Returns each register of `R` in order, along with its index within its field if the field is an
+/// array.
+fn registers<R: RegisterMap>() -> impl Iterator<Item = (&'static RegisterInfo, Option<usize>)> {
+    R::REGISTERS.iter().flat_map(|info| {
+        let is_array = info.size != info.width;
+        (0..elements(info)).map(move |index| (info, is_array.then_some(index)))
+    })
+}
+
+/// The values of the registers of a device of type `R` at some point in time.
+///
+/// `N` must be the number of registers in `R`, as returned by [`register_count`]. Registers which
+/// can't be read without side effects aren't read, and have no value in the snapshot.
+pub struct Snapshot<R, const N: usize> {
+    values: [Option<u64>; N],
+    registers: PhantomData<fn() -> R>,
+}
+
+impl<R: RegisterMap, const N: usize> Snapshot<R, N> {
+    /// Performs an MMIO read of each [`ReadPure`](crate::fields::ReadPure) and
+    /// [`ReadPureWrite`](crate::fields::ReadPureWrite) register of the device, and returns their
+    /// values.
+    pub fn take(regs: &SharedMmioPointer<R>) -> Self {
+        const {
+            assert!(
+                N == register_count::<R>(),
+                "Snapshot length must be the number of registers"
+            )
+        };
+        let mut values = [None; N];
+        for (value, (info, index)) in values.iter_mut().zip(registers::<R>()) {
+            if matches!(info.access, Access::ReadPure | Access::ReadPureWrite) {
+                let offset = info.offset + index.unwrap_or(0) * info.width;
+                // SAFETY: The offset and width came from the register map of `R`, and the register
+                // map says that the register can be read without side effects.
+                *value = unsafe { read_register(regs, offset, info.width) };
+            }
+        }
+        Self {
+            values,
+            registers: PhantomData,
+        }
+    }
+
+    /// Returns the value of the register at the given byte offset, or `None` if there is no
+    /// register there or it wasn't read.
+    pub fn value(&self, offset: usize) -> Option<u64> {
+        registers::<R>()
+            .zip(self.values)
+            .find(|((info, index), _)| info.offset + index.unwrap_or(0) * info.width == offset)
+            .and_then(|(_, value)| value)
+    }
+
+    /// Returns an iterator over the registers whose values differ between this snapshot and
+    /// `other`, in the order they are declared.
+    pub fn diff<'s>(&'s self, other: &'s Self) -> impl Iterator<Item = Change> + 's {
+        registers::<R>()
+            .zip(self.values.iter().zip(&other.values))
+            .filter_map(|((info, index), values)| match values {
+                (Some(old), Some(new)) if old != new => Some(Change {
+                    name: info.name,
+                    index,
+                    offset: info.offset + index.unwrap_or(0) * info.width,
+                    width: info.width,
+                    old: *old,
+                    new: *new,
+                }),
+                _ => None,
+            })
+    }
+}
+
+impl<R, const N: usize> Clone for Snapshot<R, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<R, const N: usize> Copy for Snapshot<R, N> {}
+
+impl<R, const N: usize> Debug for Snapshot<R, N> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Snapshot")
+            .field("values", &self.values)
+            .finish()
+    }
+}
+
+/// A register whose value differs between two snapshots.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Change {
+    /// The name of the field containing the register.
+    pub name: &'static str,
+    /// The index of the register within the field, if the field is an array.
+    pub index: Option<usize>,
+    /// The offset of the register in bytes from the start of the device.
+    pub offset: usize,
+    /// The width of the register in bytes.
+    pub width: usize,
+    /// The value of the register in the first snapshot.
+    pub old: u64,
+    /// The value of the register in the second snapshot.
+    pub new: u64,
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if let Some(index) = self.index {
+            write!(f, "[{index}]")?;
+        }
+        let digits = 2 + self.width * 2;
+        write!(
+            f,
+            " ({:#x}): {:#0digits$x} -> {:#0digits$x}",
+            self.offset, self.old, self.new
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fields::{ReadOnly, ReadPure, ReadPureWrite, ReadWrite},
+        register_map,
+    };
+
+    register_map! {
+        #[repr(C)]
+        struct Registers {
+            control: ReadPureWrite<u8>,
+            status: ReadOnly<u8>,
+            id: ReadPure<u16>,
+            data: ReadWrite<u32>,
+            counters: [ReadPure<u64>; 2],
+        }
+    }
+
+    type RegistersSnapshot = Snapshot<Registers, { register_count::<Registers>() }>;
+
+    #[test]
+    fn count() {
+        assert_eq!(register_count::<Registers>(), 6);
+    }
+
+    #[test]
+    fn take_and_diff() {
+        let mut fake = Registers {
+            control: ReadPureWrite(1),
+            status: ReadOnly(2),
+            id: ReadPure(3),
+            data: ReadWrite(4),
+            counters: [ReadPure(5), ReadPure(6)],
+        };
+        let before = RegistersSnapshot::take(&SharedMmioPointer::from(&fake));
+        assert_eq!(before.value(0), Some(1));
+        // Registers with side effects aren't read.
+        assert_eq!(before.value(1), None);
+        assert_eq!(before.value(2), Some(3));
+        assert_eq!(before.value(4), None);
+        assert_eq!(before.value(16), Some(6));
+        assert_eq!(before.value(24), None);
+        assert_eq!(before.diff(&before).count(), 0);
+
+        fake.control = ReadPureWrite(0x11);
+        fake.status = ReadOnly(0x12);
+        fake.data = ReadWrite(0x14);
+        fake.counters[1] = ReadPure(0x16);
+        let after = RegistersSnapshot::take(&SharedMmioPointer::from(&fake));
+        let mut changes = before.diff(&after);
+        assert_eq!(
+            changes.next(),
+            Some(Change {
+                name: "control",
+                index: None,
+                offset: 0,
+                width: 1,
+                old: 1,
+                new: 0x11,
+            })
+        );
+        assert_eq!(
+            changes.next(),
+            Some(Change {
+                name: "counters",
+                index: Some(1),
+                offset: 16,
+                width: 8,
+                old: 6,
+                new: 0x16,
+            })
+        );
+        assert_eq!(changes.next(), None);
+    }
+}
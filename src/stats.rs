@@ -0,0 +1,129 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Counters of MMIO accesses, for tuning drivers to minimise expensive MMIO round-trips.
+//!
+//! With the `stats` feature enabled, a pointer can be given an [`AccessStats`] with
+//! [`UniqueMmioPointer::with_stats`] or [`SharedMmioPointer::with_stats`], and then every MMIO read
+//! or write through it or any pointer derived from it will be counted. Without the feature the
+//! counting code is not compiled at all, so has no cost.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_mmio::{UniqueMmioPointer, field, fields::ReadWrite, stats::AccessStats};
+//!
+//! #[repr(C)]
+//! struct Registers {
+//!     control: ReadWrite<u32>,
+//!     data: ReadWrite<u32>,
+//! }
+//!
+//! static STATS: AccessStats = AccessStats::new();
+//!
+//! let regs: UniqueMmioPointer<Registers>;
+//! # let mut fake = Registers { control: ReadWrite(0), data: ReadWrite(0) };
+//! # regs = UniqueMmioPointer::from(&mut fake);
+//! let mut regs = regs.with_stats(&STATS);
+//! field!(regs, control).modify(|control| control | 1);
+//! field!(regs, data).write(42);
+//! assert_eq!(STATS.reads(), 1);
+//! assert_eq!(STATS.writes(), 2);
+//! ```
+//!
+//! [`UniqueMmioPointer::with_stats`]: crate::UniqueMmioPointer::with_stats
+//! [`SharedMmioPointer::with_stats`]: crate::SharedMmioPointer::with_stats
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// Counts of MMIO reads and writes made through some set of pointers.
+#[derive(Debug, Default)]
+pub struct AccessStats {
+    reads: AtomicUsize,
+    writes: AtomicUsize,
+}
+
+impl AccessStats {
+    /// Creates a new `AccessStats` with all counts zero.
+    pub const fn new() -> Self {
+        Self {
+            reads: AtomicUsize::new(0),
+            writes: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the number of MMIO reads counted so far.
+    pub fn reads(&self) -> usize {
+        self.reads.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of MMIO writes counted so far.
+    pub fn writes(&self) -> usize {
+        self.writes.load(Ordering::Relaxed)
+    }
+
+    /// Resets all counts to zero.
+    pub fn reset(&self) {
+        self.reads.store(0, Ordering::Relaxed);
+        self.writes.store(0, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_read(&self) {
+        self.reads.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_write(&self) {
+        self.writes.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SharedMmioPointer, UniqueMmioPointer, fields::ReadPure, fields::ReadWrite};
+
+    #[test]
+    fn counts_derived_pointers() {
+        let stats = AccessStats::new();
+        let mut fake = [ReadWrite(1u32), ReadWrite(2), ReadWrite(3)];
+        let mut regs = UniqueMmioPointer::from(&mut fake).with_stats(&stats);
+        regs.get(0).unwrap().write(10);
+        for mut element in regs.split() {
+            element.read();
+        }
+        assert_eq!(stats.reads(), 3);
+        assert_eq!(stats.writes(), 1);
+        stats.reset();
+        assert_eq!(stats.reads(), 0);
+        assert_eq!(stats.writes(), 0);
+    }
+
+    #[test]
+    fn counts_converted_pointers() {
+        let stats = AccessStats::new();
+        let mut fake = [ReadWrite(1u32), ReadWrite(2), ReadWrite(3)];
+        for mut element in UniqueMmioPointer::from(&mut fake).with_stats(&stats) {
+            element.read();
+        }
+        let (mut first, mut rest) = UniqueMmioPointer::from(&mut fake)
+            .with_stats(&stats)
+            .split_first()
+            .unwrap();
+        first.write(10);
+        rest.get(1).unwrap().write(30);
+        assert_eq!(stats.reads(), 3);
+        assert_eq!(stats.writes(), 2);
+    }
+
+    #[test]
+    fn shared() {
+        let stats = AccessStats::new();
+        let fake = [ReadPure(1u8), ReadPure(2)];
+        let regs = SharedMmioPointer::from(&fake).with_stats(&stats);
+        assert_eq!(regs.stats().map(AccessStats::reads), Some(0));
+        assert_eq!(regs.read_array(), [1, 2]);
+        assert_eq!(stats.reads(), 2);
+        assert!(SharedMmioPointer::from(&fake).stats().is_none());
+    }
+}
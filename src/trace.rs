@@ -0,0 +1,587 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! A cyclic buffer of the most recent MMIO accesses, for post-mortem debugging.
+//!
+//! With the `trace` feature enabled, every MMIO read or write made through this crate is recorded
+//! in a fixed-size global ring buffer holding the last [`CAPACITY`] accesses. A panic handler can
+//! then [`dump`] the buffer to show what the driver was doing just before things went wrong, which
+//! is often the most useful thing to know on a board without a debugger attached. Without the
+//! feature the recording code is not compiled at all, so has no cost.
+//!
+//! Entries are timestamped if a hook has been set with [`set_timestamp_hook`], e.g. to read the
+//! system counter.
+//!
+//! In hosted environments and tests, the `log` feature also emits a `log::trace!` record for each
+//! access as it is recorded, so that MMIO activity can be captured with existing logging
+//! infrastructure. With the `debug-path` feature, each record starts with the device name and path
+//! of the pointer through which the access was made. For other logging frameworks, a hook set with
+//! [`set_record_hook`] is called with each access and its path.
+//!
+//! In debug builds, the trace also remembers which registers have been accessed through
+//! [`ReadOnly`](crate::fields::ReadOnly), [`ReadPure`](crate::fields::ReadPure) or
+//! [`WriteOnly`](crate::fields::WriteOnly) fields, and marks any later access in the wrong direction
+//! as [`misdirected`](TraceEntry::misdirected). This can only happen through `read_unsafe` or
+//! `write_unsafe`, so helps to audit unsafe escape hatches in drivers.
+//!
+//! Recording doesn't take any locks, so accesses from multiple CPUs are interleaved in the buffer
+//! in roughly the order they happened. An entry which is overwritten while it is being read is
+//! skipped rather than returned in a torn state.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_mmio::{UniqueMmioPointer, field, fields::ReadWrite, trace};
+//!
+//! #[repr(C)]
+//! struct Registers {
+//!     control: ReadWrite<u32>,
+//!     data: ReadWrite<u32>,
+//! }
+//!
+//! fn read_cntpct() -> u64 {
+//!     // Read the system counter here.
+//!     # 42
+//! }
+//!
+//! trace::set_timestamp_hook(read_cntpct);
+//!
+//! let mut regs: UniqueMmioPointer<Registers>;
+//! # let mut fake = Registers { control: ReadWrite(0), data: ReadWrite(0) };
+//! # regs = UniqueMmioPointer::from(&mut fake);
+//! field!(regs, data).write(0x1234);
+//!
+//! // In the panic handler:
+//! trace::set_enabled(false);
+//! let last = trace::entries().last().unwrap();
+//! assert_eq!(last.kind, trace::AccessKind::Write);
+//! assert_eq!(last.width, 4);
+//! assert_eq!(last.value, 0x1234);
+//! assert_eq!(last.timestamp, Some(42));
+//! ```
+
+use core::{
+    fmt::{self, Display, Formatter},
+    mem::transmute,
+    ptr::null_mut,
+    sync::atomic::{AtomicBool, AtomicPtr, AtomicU32, AtomicU64, AtomicUsize, Ordering, fence},
+};
+
+/// The number of accesses which the trace buffer holds.
+pub const CAPACITY: usize = 64;
+
+static ENABLED: AtomicBool = AtomicBool::new(true);
+static TIMESTAMP_HOOK: AtomicPtr<()> = AtomicPtr::new(null_mut());
+static RECORD_HOOK: AtomicPtr<()> = AtomicPtr::new(null_mut());
+static NEXT: AtomicUsize = AtomicUsize::new(0);
+static CLEARED: AtomicUsize = AtomicUsize::new(0);
+static SLOTS: [Slot; CAPACITY] = [const { Slot::new() }; CAPACITY];
+static DIRECTIONS: [Direction; DIRECTION_CAPACITY] =
+    [const { Direction::new() }; DIRECTION_CAPACITY];
+
+/// The number of read-only or write-only registers which the trace remembers, to check raw accesses
+/// against.
+const DIRECTION_CAPACITY: usize = 64;
+
+/// Whether an access was a read or a write.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AccessKind {
+    /// An MMIO read.
+    Read,
+    /// An MMIO write.
+    Write,
+}
+
+/// A single recorded MMIO access.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TraceEntry {
+    /// The virtual address of the start of the access.
+    pub address: usize,
+    /// The size of the access in bytes.
+    ///
+    /// This is the size of the whole type being read or written, even if it is wider than a single
+    /// bus access.
+    pub width: usize,
+    /// Whether the access was a read or a write.
+    pub kind: AccessKind,
+    /// The value read or written, zero-extended.
+    ///
+    /// For accesses wider than 8 bytes this is only the first 8 bytes, in native byte order.
+    pub value: u64,
+    /// The value returned by the timestamp hook when the access was recorded, if one was set.
+    pub timestamp: Option<u64>,
+    /// Whether this was a read of a register which was previously written through a `WriteOnly`
+    /// field, or a write to a register which was previously read through a `ReadOnly` or
+    /// `ReadPure` field. This is only checked in debug builds.
+    pub misdirected: bool,
+}
+
+impl Display for TraceEntry {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if let Some(timestamp) = self.timestamp {
+            write!(f, "[{timestamp}] ")?;
+        }
+        let kind = match self.kind {
+            AccessKind::Read => "read",
+            AccessKind::Write => "write",
+        };
+        write!(
+            f,
+            "{kind} {:#x} ({} bytes): {:#x}",
+            self.address, self.width, self.value
+        )?;
+        if self.misdirected {
+            match self.kind {
+                AccessKind::Read => write!(f, " (read of write-only register)")?,
+                AccessKind::Write => write!(f, " (write to read-only register)")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Storage for a single entry in the ring buffer.
+///
+/// `sequence` is one more than the index of the access stored in the slot, or 0 while the slot is
+/// empty or being written.
+struct Slot {
+    sequence: AtomicUsize,
+    address: AtomicUsize,
+    width: AtomicU32,
+    value: AtomicU64,
+    timestamp: AtomicU64,
+}
+
+/// Flag in `Slot::width` for a write rather than a read.
+const WRITE_FLAG: u32 = 1 << 31;
+/// Flag in `Slot::width` indicating that there is a timestamp.
+const TIMESTAMP_FLAG: u32 = 1 << 30;
+/// Flag in `Slot::width` for an access in the wrong direction.
+const MISDIRECTED_FLAG: u32 = 1 << 29;
+/// All the flags in `Slot::width`.
+const FLAGS: u32 = WRITE_FLAG | TIMESTAMP_FLAG | MISDIRECTED_FLAG;
+
+/// A register which has been accessed through a read-only or write-only field.
+///
+/// `info` is the width of the register, with `WRITE_FLAG` set if it is write-only, or 0 while the
+/// slot is empty or being written.
+struct Direction {
+    address: AtomicUsize,
+    info: AtomicU32,
+}
+
+impl Direction {
+    const fn new() -> Self {
+        Self {
+            address: AtomicUsize::new(0),
+            info: AtomicU32::new(0),
+        }
+    }
+}
+
+impl Slot {
+    const fn new() -> Self {
+        Self {
+            sequence: AtomicUsize::new(0),
+            address: AtomicUsize::new(0),
+            width: AtomicU32::new(0),
+            value: AtomicU64::new(0),
+            timestamp: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the entry in the slot if it holds the access with the given index.
+    fn load(&self, index: usize) -> Option<TraceEntry> {
+        if self.sequence.load(Ordering::Acquire) != index + 1 {
+            return None;
+        }
+        let address = self.address.load(Ordering::Relaxed);
+        let width = self.width.load(Ordering::Relaxed);
+        let value = self.value.load(Ordering::Relaxed);
+        let timestamp = self.timestamp.load(Ordering::Relaxed);
+        // Check that the slot wasn't overwritten while we were reading it. The fence stops the loads
+        // above from being reordered after this check.
+        fence(Ordering::Acquire);
+        if self.sequence.load(Ordering::Relaxed) != index + 1 {
+            return None;
+        }
+        Some(TraceEntry {
+            address,
+            width: (width & !FLAGS) as usize,
+            kind: if width & WRITE_FLAG == 0 {
+                AccessKind::Read
+            } else {
+                AccessKind::Write
+            },
+            value,
+            timestamp: (width & TIMESTAMP_FLAG != 0).then_some(timestamp),
+            misdirected: width & MISDIRECTED_FLAG != 0,
+        })
+    }
+}
+
+/// Sets a function to be called to timestamp each access as it is recorded.
+///
+/// The hook must not itself make any MMIO accesses through this crate.
+pub fn set_timestamp_hook(hook: fn() -> u64) {
+    TIMESTAMP_HOOK.store(hook as *mut (), Ordering::Release);
+}
+
+/// Removes the timestamp hook, if any. Accesses recorded after this won't have a timestamp.
+pub fn clear_timestamp_hook() {
+    TIMESTAMP_HOOK.store(null_mut(), Ordering::Release);
+}
+
+/// A function called with each access as it is recorded, and the path of the pointer it was made
+/// through if known.
+pub type RecordHook = fn(entry: &TraceEntry, path: Option<&dyn Display>);
+
+/// Sets a function to be called with each access as it is recorded, e.g. to pass it on to a
+/// logging framework other than `log`, which is supported directly by the `log` feature.
+///
+/// The path is the device name and fields through which the access was made, such as
+/// `pl011@9000000.dr`, if the `debug-path` feature is enabled and the pointer has a name or was
+/// projected with a field macro. The hook is called after the entry has been added to the buffer,
+/// and not at all while recording is disabled. It must not itself make any MMIO accesses through
+/// this crate.
+///
+/// # Example
+///
+/// ```
+/// use core::fmt::Display;
+/// use safe_mmio::trace::{self, TraceEntry};
+///
+/// fn log_access(entry: &TraceEntry, path: Option<&dyn Display>) {
+///     match path {
+///         Some(path) => println!("{path}: {entry}"),
+///         None => println!("{entry}"),
+///     }
+/// }
+///
+/// trace::set_record_hook(log_access);
+/// ```
+pub fn set_record_hook(hook: RecordHook) {
+    RECORD_HOOK.store(hook as *mut (), Ordering::Release);
+}
+
+/// Removes the record hook, if any.
+pub fn clear_record_hook() {
+    RECORD_HOOK.store(null_mut(), Ordering::Release);
+}
+
+/// Enables or disables recording of accesses. Recording is enabled by default.
+///
+/// A panic handler should disable recording before dumping the trace, so that the accesses made to
+/// print it (e.g. to a UART) don't overwrite the entries being printed.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Release);
+}
+
+/// Discards all entries currently in the buffer, and forgets which registers have been accessed
+/// through read-only or write-only fields.
+pub fn clear() {
+    CLEARED.store(NEXT.load(Ordering::Acquire), Ordering::Release);
+    for direction in &DIRECTIONS {
+        direction.info.store(0, Ordering::Release);
+        direction.address.store(0, Ordering::Release);
+    }
+}
+
+/// Returns the entries currently in the buffer, from oldest to newest.
+pub fn entries() -> impl Iterator<Item = TraceEntry> {
+    let end = NEXT.load(Ordering::Acquire);
+    let start = end
+        .saturating_sub(CAPACITY)
+        .max(CLEARED.load(Ordering::Acquire));
+    (start..end).filter_map(|index| SLOTS[index % CAPACITY].load(index))
+}
+
+/// Writes the entries currently in the buffer to the given writer, one per line, from oldest to
+/// newest.
+pub fn dump(out: &mut impl fmt::Write) -> fmt::Result {
+    for entry in entries() {
+        writeln!(out, "{entry}")?;
+    }
+    Ok(())
+}
+
+/// Remembers that the `width` bytes at `address` have been accessed through a field which only
+/// allows accesses of the given kind, in debug builds.
+///
+/// If the table of registers is full then the register is not remembered.
+pub(crate) fn record_direction(address: usize, width: usize, only: AccessKind) {
+    if !cfg!(debug_assertions) || !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let mut info = width as u32 & !FLAGS;
+    if only == AccessKind::Write {
+        info |= WRITE_FLAG;
+    }
+    for direction in &DIRECTIONS {
+        match direction
+            .address
+            .compare_exchange(0, address, Ordering::AcqRel, Ordering::Acquire)
+        {
+            Ok(_) => {
+                direction.info.store(info, Ordering::Release);
+                return;
+            }
+            Err(existing) if existing == address => return,
+            Err(_) => {}
+        }
+    }
+}
+
+/// Returns whether an access of the given kind to the `width` bytes at `address` overlaps a
+/// register which has only been accessed in the other direction.
+fn is_misdirected(address: usize, width: usize, kind: AccessKind) -> bool {
+    DIRECTIONS.iter().any(|direction| {
+        let info = direction.info.load(Ordering::Acquire);
+        let start = direction.address.load(Ordering::Relaxed);
+        let len = (info & !FLAGS) as usize;
+        info != 0
+            && address < start.wrapping_add(len)
+            && start < address.wrapping_add(width)
+            && (info & WRITE_FLAG != 0) != (kind == AccessKind::Write)
+    })
+}
+
+/// Records an access of the given value at the given address, made through a pointer with the given
+/// path if known.
+pub(crate) fn record(address: usize, value: &[u8], kind: AccessKind, path: Option<&dyn Display>) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let hook = TIMESTAMP_HOOK.load(Ordering::Acquire);
+    let timestamp = if hook.is_null() {
+        None
+    } else {
+        // SAFETY: The only non-null values ever stored in `TIMESTAMP_HOOK` are `fn() -> u64`
+        // pointers, by `set_timestamp_hook`.
+        let hook = unsafe { transmute::<*mut (), fn() -> u64>(hook) };
+        Some(hook())
+    };
+    let mut bytes = [0; 8];
+    let len = value.len().min(8);
+    bytes[..len].copy_from_slice(&value[..len]);
+    let misdirected = cfg!(debug_assertions) && is_misdirected(address, value.len(), kind);
+    let mut width = value.len() as u32 & !FLAGS;
+    if kind == AccessKind::Write {
+        width |= WRITE_FLAG;
+    }
+    if timestamp.is_some() {
+        width |= TIMESTAMP_FLAG;
+    }
+    if misdirected {
+        width |= MISDIRECTED_FLAG;
+    }
+
+    let index = NEXT.fetch_add(1, Ordering::AcqRel);
+    let slot = &SLOTS[index % CAPACITY];
+    slot.sequence.store(0, Ordering::Relaxed);
+    // Stop the stores below from being reordered before the slot is marked as being written, so
+    // that a reader which sees any of them also sees the sequence change.
+    fence(Ordering::Release);
+    slot.address.store(address, Ordering::Relaxed);
+    slot.width.store(width, Ordering::Relaxed);
+    slot.value
+        .store(u64::from_ne_bytes(bytes), Ordering::Relaxed);
+    slot.timestamp
+        .store(timestamp.unwrap_or_default(), Ordering::Relaxed);
+    slot.sequence.store(index + 1, Ordering::Release);
+
+    let entry = TraceEntry {
+        address,
+        width: value.len(),
+        kind,
+        value: u64::from_ne_bytes(bytes),
+        timestamp,
+        misdirected,
+    };
+    #[cfg(feature = "log")]
+    match path {
+        Some(path) => log::trace!("{path}: {entry}"),
+        None => log::trace!("{entry}"),
+    }
+    let hook = RECORD_HOOK.load(Ordering::Acquire);
+    if !hook.is_null() {
+        // SAFETY: The only non-null values ever stored in `RECORD_HOOK` are `RecordHook` pointers,
+        // by `set_record_hook`.
+        let hook = unsafe { transmute::<*mut (), RecordHook>(hook) };
+        hook(&entry, path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        UniqueMmioPointer,
+        fields::{ReadWrite, WriteOnly},
+    };
+    use core::fmt::Write;
+
+    /// A fixed-size buffer to format into.
+    struct Buffer {
+        bytes: [u8; 64],
+        len: usize,
+    }
+
+    impl Write for Buffer {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let end = self.len + s.len();
+            self.bytes
+                .get_mut(self.len..end)
+                .ok_or(fmt::Error)?
+                .copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn records_accesses() {
+        clear();
+        let mut fake = [ReadWrite(1u32), ReadWrite(2)];
+        let mut regs = UniqueMmioPointer::from(&mut fake);
+        let address = regs.ptr() as usize;
+        regs.get(1).unwrap().write(0xabcd);
+        assert_eq!(regs.get(0).unwrap().read(), 1);
+
+        let mut recorded = entries().filter(|entry| entry.address.wrapping_sub(address) < 8);
+        assert_eq!(
+            recorded.next(),
+            Some(TraceEntry {
+                address: address + 4,
+                width: 4,
+                kind: AccessKind::Write,
+                value: 0xabcd,
+                timestamp: None,
+                misdirected: false,
+            })
+        );
+        assert_eq!(
+            recorded.next(),
+            Some(TraceEntry {
+                address,
+                width: 4,
+                kind: AccessKind::Read,
+                value: 1,
+                timestamp: None,
+                misdirected: false,
+            })
+        );
+        assert_eq!(recorded.next(), None);
+    }
+
+    #[test]
+    fn display() {
+        let entry = TraceEntry {
+            address: 0x900_0000,
+            width: 4,
+            kind: AccessKind::Read,
+            value: 0x42,
+            timestamp: Some(1000),
+            misdirected: false,
+        };
+        let mut buffer = Buffer {
+            bytes: [0; 64],
+            len: 0,
+        };
+        write!(buffer, "{entry}").unwrap();
+        assert_eq!(
+            str::from_utf8(&buffer.bytes[..buffer.len]).unwrap(),
+            "[1000] read 0x9000000 (4 bytes): 0x42"
+        );
+    }
+
+    #[test]
+    fn record_hook() {
+        static ADDRESS: AtomicUsize = AtomicUsize::new(0);
+        static VALUE: AtomicU64 = AtomicU64::new(0);
+        static HAD_PATH: AtomicBool = AtomicBool::new(false);
+
+        fn hook(entry: &TraceEntry, path: Option<&dyn Display>) {
+            if entry.address == ADDRESS.load(Ordering::SeqCst) && entry.kind == AccessKind::Write {
+                VALUE.store(entry.value, Ordering::SeqCst);
+                HAD_PATH.store(path.is_some(), Ordering::SeqCst);
+            }
+        }
+
+        #[repr(C)]
+        struct Registers {
+            control: ReadWrite<u32>,
+        }
+
+        let mut fake = Registers {
+            control: ReadWrite(0),
+        };
+        let mut regs = UniqueMmioPointer::from(&mut fake);
+        ADDRESS.store(regs.ptr() as usize, Ordering::SeqCst);
+        set_record_hook(hook);
+        crate::field!(regs, control).write(0x1234);
+        clear_record_hook();
+        crate::field!(regs, control).write(0x5678);
+        assert_eq!(VALUE.load(Ordering::SeqCst), 0x1234);
+        assert_eq!(
+            HAD_PATH.load(Ordering::SeqCst),
+            cfg!(feature = "debug-path")
+        );
+    }
+    #[test]
+    fn misdirected() {
+        clear();
+        let mut fake = [WriteOnly(0u32), WriteOnly(0)];
+        let mut regs = UniqueMmioPointer::from(&mut fake);
+        let address = regs.ptr() as usize;
+        regs.get(0).unwrap().write(42);
+        // SAFETY: The fake is ordinary memory, so it is safe to read.
+        let value = unsafe { regs.get(0).unwrap().read_unsafe() }.0;
+        assert_eq!(value, 42);
+        // The other element hasn't been accessed as write-only, so reading it is fine.
+        // SAFETY: As above.
+        unsafe { regs.get(1).unwrap().read_unsafe() };
+
+        let mut recorded = entries().filter(|entry| entry.address.wrapping_sub(address) < 8);
+        assert!(!recorded.next().unwrap().misdirected);
+        assert_eq!(recorded.next().unwrap().misdirected, cfg!(debug_assertions));
+        assert!(!recorded.next().unwrap().misdirected);
+        assert_eq!(recorded.next(), None);
+    }
+
+    #[cfg(feature = "log")]
+    #[test]
+    fn log_records() {
+        static LOGGED: AtomicBool = AtomicBool::new(false);
+
+        struct Logger;
+
+        impl log::Log for Logger {
+            fn enabled(&self, _metadata: &log::Metadata) -> bool {
+                true
+            }
+
+            fn log(&self, record: &log::Record) {
+                let mut buffer = Buffer {
+                    bytes: [0; 64],
+                    len: 0,
+                };
+                // Records from other tests may not fit in the buffer, but they aren't needed.
+                if write!(buffer, "{}", record.args()).is_ok()
+                    && buffer.bytes[..buffer.len].ends_with(b"(4 bytes): 0x5a5a5a5a")
+                {
+                    LOGGED.store(true, Ordering::SeqCst);
+                }
+            }
+
+            fn flush(&self) {}
+        }
+
+        log::set_logger(&Logger).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+        let mut fake = ReadWrite(0u32);
+        UniqueMmioPointer::from(&mut fake).write(0x5a5a_5a5a);
+        assert!(LOGGED.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,149 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Typestate wrappers to track the initialisation phase of a device at compile time.
+//!
+//! A [`Device`] wraps a [`UniqueMmioPointer`] along with a marker type for the state the device is
+//! in. Drivers define the transitions between states by implementing [`Transition`], and can then
+//! only call their functions which need the device to be in a given state on a `Device` in that
+//! state. For example, a driver can make sure that DMA is never enabled before the clock has been
+//! enabled.
+//!
+//! A device whose registers haven't been mapped yet is represented by a
+//! [`PhysicalInstance`](crate::PhysicalInstance), so there is no separate state for it.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_mmio::{
+//!     UniqueMmioPointer, field,
+//!     fields::ReadWrite,
+//!     typestate::{Device, Ready, Reset, Transition},
+//! };
+//!
+//! #[repr(C)]
+//! struct DmaRegisters {
+//!     clock_enable: ReadWrite<u32>,
+//!     dma_enable: ReadWrite<u32>,
+//! }
+//!
+//! impl Transition<DmaRegisters, Reset> for Ready {
+//!     fn transition(regs: &mut UniqueMmioPointer<DmaRegisters>) {
+//!         field!(*regs, clock_enable).write(1);
+//!     }
+//! }
+//!
+//! fn enable_dma(device: &mut Device<Ready, DmaRegisters>) {
+//!     let mut regs = device.regs();
+//!     field!(regs, dma_enable).write(1);
+//! }
+//!
+//! let regs: UniqueMmioPointer<DmaRegisters>;
+//! # let mut fake = DmaRegisters { clock_enable: ReadWrite(0), dma_enable: ReadWrite(0) };
+//! # regs = UniqueMmioPointer::from(&mut fake);
+//! let device = Device::new(regs);
+//! // `enable_dma(&mut device)` wouldn't compile here.
+//! let mut device = device.transition::<Ready>();
+//! enable_dma(&mut device);
+//! ```
+//!
+//! A `Device` can only be created in the [`Reset`] state, so the transitions can't be skipped:
+//!
+//! ```compile_fail,E0599
+//! use safe_mmio::{UniqueMmioPointer, fields::ReadWrite, typestate::{Device, Ready}};
+//!
+//! let mut fake = ReadWrite(0u32);
+//! let device = Device::<Ready, _>::new(UniqueMmioPointer::from(&mut fake));
+//! ```
+
+use crate::UniqueMmioPointer;
+use core::marker::PhantomData;
+
+/// Marker state for a device which has just been mapped or reset, and not yet initialised.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Reset;
+
+/// Marker state for a device which has been initialised and is ready to use.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Ready;
+
+/// A transition of a device with registers of type `T` from state `From` to the state implementing
+/// this trait.
+pub trait Transition<T: ?Sized, From> {
+    /// Makes whatever register accesses are needed to move the device from state `From` to this
+    /// state.
+    fn transition(regs: &mut UniqueMmioPointer<T>);
+}
+
+/// A pointer to the registers of a device of type `T`, which is in state `S`.
+#[derive(Debug)]
+pub struct Device<'a, S, T: ?Sized> {
+    regs: UniqueMmioPointer<'a, T>,
+    state: PhantomData<S>,
+}
+
+impl<'a, T: ?Sized> Device<'a, Reset, T> {
+    /// Wraps the given pointer to the registers of a device which has just been mapped or reset.
+    pub const fn new(regs: UniqueMmioPointer<'a, T>) -> Self {
+        Self::with_state(regs)
+    }
+}
+
+impl<'a, S, T: ?Sized> Device<'a, S, T> {
+    /// Wraps the given pointer to the registers of a device which is in state `S`.
+    const fn with_state(regs: UniqueMmioPointer<'a, T>) -> Self {
+        Self {
+            regs,
+            state: PhantomData,
+        }
+    }
+
+    /// Returns a pointer to the device registers.
+    pub const fn regs(&mut self) -> UniqueMmioPointer<'_, T> {
+        self.regs.reborrow()
+    }
+
+    /// Moves the device to state `To`, by making the register accesses defined by the
+    /// corresponding [`Transition`] implementation.
+    pub fn transition<To: Transition<T, S>>(mut self) -> Device<'a, To, T> {
+        To::transition(&mut self.regs);
+        Device::with_state(self.regs)
+    }
+
+    /// Returns the pointer to the device registers, discarding the state.
+    pub fn into_inner(self) -> UniqueMmioPointer<'a, T> {
+        self.regs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fields::ReadWrite;
+
+    struct ClockEnabled;
+
+    impl Transition<ReadWrite<u32>, Reset> for ClockEnabled {
+        fn transition(regs: &mut UniqueMmioPointer<ReadWrite<u32>>) {
+            regs.write(1);
+        }
+    }
+
+    impl Transition<ReadWrite<u32>, ClockEnabled> for Ready {
+        fn transition(regs: &mut UniqueMmioPointer<ReadWrite<u32>>) {
+            regs.modify(|value| value | 2);
+        }
+    }
+
+    #[test]
+    fn transitions() {
+        let mut fake = ReadWrite(0);
+        let device = Device::new(UniqueMmioPointer::from(&mut fake));
+        let mut device = device.transition::<ClockEnabled>();
+        assert_eq!(device.regs().read(), 1);
+        let device = device.transition::<Ready>();
+        let mut regs = device.into_inner();
+        assert_eq!(regs.read(), 3);
+    }
+}
@@ -0,0 +1,339 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Helpers for [`Protected`] registers, which only accept writes after an unlock sequence.
+//!
+//! Many flash, watchdog and clock controllers ignore writes to some registers unless a sequence of
+//! magic keys has first been written to an unlock register, to protect against stray writes.
+//! [`unlock_with`](UniqueMmioPointer::unlock_with) writes the keys, and returns an [`Unlocked`]
+//! guard through which the protected register can be written. When the guard is dropped the
+//! register is locked again.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_mmio::{
+//!     UniqueMmioPointer,
+//!     fields::{Protected, ReadPureWrite},
+//!     split_fields,
+//! };
+//!
+//! #[repr(C)]
+//! struct WatchdogRegisters {
+//!     load: Protected<u32, u32>,
+//!     lock: ReadPureWrite<u32>,
+//! }
+//!
+//! const UNLOCK_KEY: u32 = 0x1acc_e551;
+//! const LOCK_KEY: u32 = 0;
+//!
+//! let mut regs: UniqueMmioPointer<WatchdogRegisters>;
+//! # let mut fake = WatchdogRegisters {
+//! #     load: Protected(0, Default::default()),
+//! #     lock: ReadPureWrite(0),
+//! # };
+//! # regs = UniqueMmioPointer::from(&mut fake);
+//! // SAFETY: Each field is only split out once.
+//! let (mut load, mut lock) = unsafe { split_fields!(regs, load, lock) };
+//! {
+//!     let mut load = load.unlock_with(&mut lock, &[UNLOCK_KEY], Some(LOCK_KEY));
+//!     load.write(1_000_000);
+//! }
+//! assert_eq!(load.read(), 1_000_000);
+//! assert_eq!(lock.read(), LOCK_KEY);
+//! ```
+//!
+//! Watchdogs often also need a sequence of magic values to be written to refresh them. A
+//! [`Watchdog`] always writes the whole sequence given by its [`WatchdogLayout`], and can check in
+//! debug builds that it is refreshed often enough.
+
+use crate::{
+    SharedMmioPointer, UniqueMmioPointer,
+    access::{AtomicAccess, Writable},
+    counter::{CounterValue, FreeRunningCounter},
+    fields::{Protected, WriteOnly},
+};
+use core::fmt::Debug;
+
+impl<T: AtomicAccess, U> SharedMmioPointer<'_, Protected<T, U>> {
+    /// Performs an MMIO read of the entire `T`.
+    #[inline(always)]
+    pub fn read(&self) -> T {
+        // SAFETY: self.regs is always a valid pointer to MMIO address space, and the field being
+        // wrapped in `Protected` implies that it is safe to read from a shared reference because
+        // doing so has no side-effects. `Protected<T, U>` is a transparent wrapper around `T`, so
+        // the child pointer is exactly the same allocation.
+        unsafe { self.child(self.regs.cast::<T>()).read_unsafe() }
+    }
+}
+
+impl<T: AtomicAccess, U: Copy> UniqueMmioPointer<'_, Protected<T, U>> {
+    /// Writes each of the given keys to the `unlock` register in turn, and returns a guard through
+    /// which this register can be written.
+    ///
+    /// When the guard is dropped, `lock` is written to the `unlock` register if it is not `None`.
+    /// Use `None` for devices which lock the register again automatically after each write.
+    pub fn unlock_with<'b, L: Writable<Value = U>>(
+        &'b mut self,
+        unlock: &'b mut L,
+        keys: &[U],
+        lock: Option<U>,
+    ) -> Unlocked<'b, T, L> {
+        for key in keys {
+            unlock.write(*key);
+        }
+        let regs = self.regs.cast::<T>();
+        Unlocked {
+            // SAFETY: `Protected<T, U>` is a transparent wrapper around `T`, so the child pointer
+            // is exactly the same allocation. Writing to it is safe once unlocked, as implied by
+            // the field being wrapped in `Protected`.
+            regs: unsafe { self.child(regs) },
+            unlock,
+            lock,
+        }
+    }
+}
+
+/// A guard through which a [`Protected`] register can be written after it has been unlocked, which
+/// locks it again when it is dropped.
+#[derive(Debug)]
+pub struct Unlocked<'b, T: AtomicAccess, L: Writable> {
+    regs: UniqueMmioPointer<'b, T>,
+    unlock: &'b mut L,
+    lock: Option<L::Value>,
+}
+
+impl<T: AtomicAccess, L: Writable> Unlocked<'_, T, L> {
+    /// Performs an MMIO read of the protected register.
+    #[inline(always)]
+    pub fn read(&self) -> T {
+        // SAFETY: The register was wrapped in `Protected`, which implies that reading it has no
+        // side-effects.
+        unsafe { SharedMmioPointer::read_unsafe(&self.regs) }
+    }
+
+    /// Performs an MMIO write of the protected register.
+    #[inline(always)]
+    pub fn write(&mut self, value: T) {
+        // SAFETY: The register was wrapped in `Protected`, which implies that writing it is safe
+        // once it has been unlocked.
+        unsafe { self.regs.write_unsafe(value) }
+    }
+}
+
+impl<T: AtomicAccess, L: Writable> Drop for Unlocked<'_, T, L> {
+    fn drop(&mut self) {
+        if let Some(lock) = self.lock.take() {
+            self.unlock.write(lock);
+        }
+    }
+}
+
+/// The registers of a watchdog, and the sequence of values which must be written to refresh it.
+pub trait WatchdogLayout {
+    /// The type of the refresh register.
+    type Value: AtomicAccess + 'static;
+
+    /// The values to write to the refresh register, in order, to refresh the watchdog.
+    const REFRESH_SEQUENCE: &'static [Self::Value];
+
+    /// Returns a pointer to the refresh register within the given registers.
+    fn refresh_register<'r>(
+        regs: &'r mut UniqueMmioPointer<'_, Self>,
+    ) -> UniqueMmioPointer<'r, WriteOnly<Self::Value>>;
+}
+
+/// A watchdog which is refreshed by writing the sequence of values given by its layout `W`.
+///
+/// # Example
+///
+/// ```
+/// use safe_mmio::{
+///     UniqueMmioPointer, field,
+///     fields::{ReadPureWrite, WriteOnly},
+///     unlock::{Watchdog, WatchdogLayout},
+/// };
+///
+/// #[repr(C)]
+/// struct WatchdogRegisters {
+///     control: ReadPureWrite<u16>,
+///     refresh: WriteOnly<u16>,
+/// }
+///
+/// impl WatchdogLayout for WatchdogRegisters {
+///     type Value = u16;
+///
+///     const REFRESH_SEQUENCE: &[u16] = &[0xa602, 0xb480];
+///
+///     fn refresh_register<'r>(
+///         regs: &'r mut UniqueMmioPointer<'_, Self>,
+///     ) -> UniqueMmioPointer<'r, WriteOnly<u16>> {
+///         field!(*regs, refresh)
+///     }
+/// }
+///
+/// let regs: UniqueMmioPointer<WatchdogRegisters>;
+/// # let mut fake = WatchdogRegisters { control: ReadPureWrite(0), refresh: WriteOnly(0) };
+/// # regs = UniqueMmioPointer::from(&mut fake);
+/// let mut watchdog = Watchdog::new(regs);
+/// watchdog.refresh();
+/// ```
+#[derive(Debug)]
+pub struct Watchdog<'a, W, C = u32> {
+    regs: UniqueMmioPointer<'a, W>,
+    interval: Option<Interval<'a, C>>,
+}
+
+/// The maximum interval between refreshes of a [`Watchdog`], and the time of the last refresh.
+#[derive(Debug)]
+struct Interval<'a, C> {
+    counter: FreeRunningCounter<'a, C>,
+    max: C,
+    last: C,
+}
+
+impl<'a, W: WatchdogLayout> Watchdog<'a, W> {
+    /// Creates a new `Watchdog` for the given registers.
+    pub const fn new(regs: UniqueMmioPointer<'a, W>) -> Self {
+        Self {
+            regs,
+            interval: None,
+        }
+    }
+}
+
+impl<'a, W: WatchdogLayout, C> Watchdog<'a, W, C> {
+    /// Returns this watchdog, changed to check in debug builds that no more than `max` ticks of
+    /// `counter` elapse between refreshes, starting from now.
+    pub fn with_max_interval<D: CounterValue>(
+        self,
+        counter: FreeRunningCounter<'a, D>,
+        max: D,
+    ) -> Watchdog<'a, W, D> {
+        Watchdog {
+            regs: self.regs,
+            interval: Some(Interval {
+                counter,
+                max,
+                last: counter.read(),
+            }),
+        }
+    }
+
+    /// Returns the original pointer to the watchdog registers.
+    pub fn into_inner(self) -> UniqueMmioPointer<'a, W> {
+        self.regs
+    }
+}
+
+impl<W: WatchdogLayout, C: CounterValue + Debug + PartialOrd> Watchdog<'_, W, C> {
+    /// Refreshes the watchdog by writing each value of the refresh sequence in turn.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if a maximum interval has been set with
+    /// [`with_max_interval`](Self::with_max_interval) and more than that has elapsed since the
+    /// previous refresh.
+    pub fn refresh(&mut self) {
+        if cfg!(debug_assertions) {
+            if let Some(interval) = &self.interval {
+                let elapsed = interval.counter.elapsed_since(interval.last);
+                assert!(
+                    elapsed <= interval.max,
+                    "Watchdog refreshed {elapsed:?} ticks after the previous refresh, more than \
+                     the maximum of {:?}",
+                    interval.max
+                );
+            }
+        }
+        let mut refresh = W::refresh_register(&mut self.regs);
+        for value in W::REFRESH_SEQUENCE {
+            refresh.write(*value);
+        }
+        if cfg!(debug_assertions) {
+            if let Some(interval) = &mut self.interval {
+                interval.last = interval.counter.read();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fields::ReadPure;
+    use core::{marker::PhantomData, ptr::NonNull};
+
+    #[test]
+    fn unlock_and_lock() {
+        let mut fake = Protected(0u16, PhantomData::<u8>);
+        let mut fake_unlock = WriteOnly(0u8);
+        {
+            let mut protected = UniqueMmioPointer::from(&mut fake);
+            let mut unlock = UniqueMmioPointer::from(&mut fake_unlock);
+            assert_eq!(protected.read(), 0);
+            {
+                let mut unlocked = protected.unlock_with(&mut unlock, &[0x12, 0x34], None);
+                unlocked.write(42);
+                assert_eq!(unlocked.read(), 42);
+            }
+            {
+                let mut unlocked = protected.unlock_with(&mut unlock, &[0x56], Some(0xff));
+                unlocked.write(43);
+            }
+        }
+        assert_eq!(fake.0, 43);
+        assert_eq!(fake_unlock.0, 0xff);
+    }
+
+    #[repr(C)]
+    struct FakeWatchdog {
+        refresh: WriteOnly<u32>,
+    }
+
+    impl WatchdogLayout for FakeWatchdog {
+        type Value = u32;
+
+        const REFRESH_SEQUENCE: &[u32] = &[0xaaaa, 0x5555];
+
+        fn refresh_register<'r>(
+            regs: &'r mut UniqueMmioPointer<'_, Self>,
+        ) -> UniqueMmioPointer<'r, WriteOnly<u32>> {
+            crate::field!(*regs, refresh)
+        }
+    }
+
+    #[test]
+    fn watchdog_refresh() {
+        let mut fake = FakeWatchdog {
+            refresh: WriteOnly(0),
+        };
+        let mut watchdog = Watchdog::new(UniqueMmioPointer::from(&mut fake));
+        watchdog.refresh();
+        assert_eq!(fake.refresh.0, 0x5555);
+    }
+
+    #[test]
+    #[cfg_attr(
+        debug_assertions,
+        should_panic(expected = "Watchdog refreshed 11 ticks after the previous refresh")
+    )]
+    fn watchdog_max_interval() {
+        let count = core::cell::UnsafeCell::new(ReadPure(100u32));
+        let count_ptr = NonNull::new(count.get()).unwrap();
+        // SAFETY: `count` is valid, and is only otherwise accessed through the raw pointer below.
+        let counter = FreeRunningCounter::new(unsafe { UniqueMmioPointer::new(count_ptr) }.into());
+        let mut fake = FakeWatchdog {
+            refresh: WriteOnly(0),
+        };
+        let mut watchdog =
+            Watchdog::new(UniqueMmioPointer::from(&mut fake)).with_max_interval(counter, 10);
+        // SAFETY: `count_ptr` is valid, and no reference to `count` exists.
+        unsafe { count_ptr.write(ReadPure(110)) };
+        watchdog.refresh();
+        // SAFETY: As above.
+        unsafe { count_ptr.write(ReadPure(121)) };
+        watchdog.refresh();
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Traits and errors for typed register values, as generated by the `MmioValue` and `MmioEnum`
+//! derive macros behind the `derive` feature.
+
+use core::fmt::{self, Display, Formatter};
+
+/// A fieldless enum which can be stored in a multi-bit field of a register value.
+///
+/// This is usually derived with `#[derive(MmioEnum)]`, and then used as the type of a field
+/// declared in the `#[bits(...)]` attribute of an `MmioValue` type, e.g. `mode = 4..6: Mode`.
+pub trait FieldEnum: Copy {
+    /// The largest value of any variant, used to check at compile time that every variant fits in
+    /// the field.
+    const MAX: u64;
+
+    /// Returns the variant with the given value, or `None` if there is no such variant.
+    fn from_bits(bits: u64) -> Option<Self>;
+
+    /// Returns the value of the variant.
+    fn into_bits(self) -> u64;
+}
+
+/// A field of a register value contained a value which doesn't correspond to any variant of its
+/// enum type.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct InvalidFieldValue(pub u64);
+
+impl Display for InvalidFieldValue {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Invalid field value {:#x}", self.0)
+    }
+}
+
+impl core::error::Error for InvalidFieldValue {}
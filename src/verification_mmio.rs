@@ -0,0 +1,92 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Model MMIO backend for formal verification, enabled by the `verification` feature.
+//!
+//! When built by Kani (i.e. with `cfg(kani)`), every MMIO read returns a nondeterministic value, so
+//! proofs hold for whatever the hardware might return. Writes are recorded in the memory which the
+//! pointer refers to, so harnesses can check what a driver wrote by inspecting their fake
+//! registers. Outside of Kani reads come from that memory too, so ordinary tests still work.
+
+use crate::{SharedMmioPointer, UniqueMmioPointer};
+use core::ptr::NonNull;
+use zerocopy::{FromBytes, Immutable, IntoBytes};
+
+impl<T: FromBytes + IntoBytes> UniqueMmioPointer<'_, T> {
+    /// Performs an MMIO read of the entire `T`.
+    ///
+    /// Note that this takes `&mut self` rather than `&self` because an MMIO read may cause
+    /// side-effects that change the state of the device.
+    ///
+    /// # Safety
+    ///
+    /// This field must be safe to perform an MMIO read from.
+    #[inline(always)]
+    pub unsafe fn read_unsafe(&mut self) -> T {
+        // SAFETY: self.regs is always a valid and unique pointer to MMIO address space.
+        let mut value = unsafe { model_read(self.regs) };
+        self.record_read(value.as_mut_bytes());
+        value
+    }
+}
+
+impl<T: Immutable + IntoBytes> UniqueMmioPointer<'_, T> {
+    /// Performs an MMIO write of the entire `T`.
+    ///
+    /// # Safety
+    ///
+    /// This field must be safe to perform an MMIO write to.
+    #[inline(always)]
+    pub unsafe fn write_unsafe(&mut self, value: T) {
+        self.record_write(value.as_bytes());
+        // SAFETY: self.regs is always a valid and unique pointer to MMIO address space.
+        unsafe {
+            self.regs.write_volatile(value);
+        }
+    }
+}
+
+impl<T: FromBytes + IntoBytes> SharedMmioPointer<'_, T> {
+    /// Performs an MMIO read of the entire `T`.
+    ///
+    /// # Safety
+    ///
+    /// This field must be safe to perform an MMIO read from, and doing so must not cause any
+    /// side-effects.
+    #[inline(always)]
+    pub unsafe fn read_unsafe(&self) -> T {
+        // SAFETY: self.regs is always a valid pointer to MMIO address space.
+        let mut value = unsafe { model_read(self.regs) };
+        self.record_read(value.as_mut_bytes());
+        value
+    }
+}
+
+/// Returns a nondeterministic value when verifying with Kani, or otherwise reads from `src`.
+///
+/// # Safety
+///
+/// `src` must be a valid, aligned pointer to MMIO address space.
+#[cfg(kani)]
+#[inline(always)]
+unsafe fn model_read<T: FromBytes + IntoBytes>(src: NonNull<T>) -> T {
+    let _ = src;
+    let mut value = T::new_zeroed();
+    for byte in value.as_mut_bytes() {
+        *byte = kani::any();
+    }
+    value
+}
+
+/// Returns a nondeterministic value when verifying with Kani, or otherwise reads from `src`.
+///
+/// # Safety
+///
+/// `src` must be a valid, aligned pointer to MMIO address space.
+#[cfg(not(kani))]
+#[inline(always)]
+unsafe fn model_read<T: FromBytes + IntoBytes>(src: NonNull<T>) -> T {
+    // SAFETY: Our caller promises that src is a valid pointer to MMIO address space.
+    unsafe { src.read_volatile() }
+}
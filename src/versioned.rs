@@ -0,0 +1,159 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Support for devices whose register layout depends on the hardware revision.
+
+use crate::UniqueMmioPointer;
+
+/// Which of the layouts of a [`VersionedMmio`] a device uses.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Revision {
+    /// The device registers are described by the first layout.
+    V1,
+    /// The device registers are described by the second layout.
+    V2,
+}
+
+/// A pointer to the registers of a device, which have one of two layouts depending on the
+/// revision of the device.
+///
+/// The revision is selected at probe time, usually by reading an ID register which is at the same
+/// place in both layouts. Drivers can then match on this to access registers which only exist or
+/// have moved in one of the revisions, while keeping a single API for their users.
+///
+/// # Example
+///
+/// ```
+/// use safe_mmio::{
+///     UniqueMmioPointer, field,
+///     fields::{ReadPure, ReadWrite},
+///     versioned::{Revision, VersionedMmio},
+/// };
+///
+/// #[repr(C)]
+/// struct IdRegisters {
+///     id: ReadPure<u32>,
+/// }
+///
+/// #[repr(C)]
+/// struct V1Registers {
+///     id: ReadPure<u32>,
+///     control: ReadWrite<u32>,
+/// }
+///
+/// #[repr(C)]
+/// struct V2Registers {
+///     id: ReadPure<u32>,
+///     status: ReadPure<u32>,
+///     control: ReadWrite<u32>,
+/// }
+///
+/// fn enable(regs: &mut VersionedMmio<V1Registers, V2Registers>) {
+///     match regs {
+///         VersionedMmio::V1(regs) => field!(*regs, control).write(1),
+///         VersionedMmio::V2(regs) => field!(*regs, control).write(1),
+///     }
+/// }
+///
+/// let regs: UniqueMmioPointer<IdRegisters>;
+/// # let mut fake = [0x200, 0, 0];
+/// # regs = unsafe { UniqueMmioPointer::new(core::ptr::NonNull::from(&mut fake).cast()) };
+/// // SAFETY: The device registers are described by `V1Registers` for revision 1 and by
+/// // `V2Registers` for revision 2.
+/// let mut regs = unsafe {
+///     VersionedMmio::<V1Registers, V2Registers>::probe(regs, |mut regs| {
+///         match field!(regs, id).read() >> 8 {
+///             1 => Some(Revision::V1),
+///             2 => Some(Revision::V2),
+///             _ => None,
+///         }
+///     })
+/// }
+/// .unwrap();
+/// assert_eq!(regs.revision(), Revision::V2);
+/// enable(&mut regs);
+/// ```
+#[derive(Debug)]
+pub enum VersionedMmio<'a, V1, V2> {
+    /// The device registers have the first layout.
+    V1(UniqueMmioPointer<'a, V1>),
+    /// The device registers have the second layout.
+    V2(UniqueMmioPointer<'a, V2>),
+}
+
+impl<'a, V1, V2> VersionedMmio<'a, V1, V2> {
+    /// Calls `select` with a pointer to the registers to determine the revision of the device, and
+    /// then returns a pointer to them with the corresponding layout.
+    ///
+    /// Returns `None` if `select` does, e.g. because the device is a revision which the driver
+    /// doesn't support.
+    ///
+    /// # Safety
+    ///
+    /// If `select` returns `Some(Revision::V1)` then the device registers must be valid MMIO
+    /// registers described by `V1`, and likewise for `V2`, including the safety requirements of any
+    /// field wrappers they contain. Both `V1` and `V2` must have an alignment no greater than `R`.
+    pub unsafe fn probe<R>(
+        mut regs: UniqueMmioPointer<'a, R>,
+        select: impl FnOnce(UniqueMmioPointer<'_, R>) -> Option<Revision>,
+    ) -> Option<Self> {
+        let revision = select(regs.reborrow())?;
+        let ptr = regs.ptr_nonnull();
+        // SAFETY: The caller promised that the registers are described by the layout for the
+        // revision which `select` returned. We consume the original pointer, so the new one has
+        // unique access for the same lifetime, and keeps its name, path and stats.
+        Some(unsafe {
+            match revision {
+                Revision::V1 => Self::V1(regs.split_child(ptr.cast())),
+                Revision::V2 => Self::V2(regs.split_child(ptr.cast())),
+            }
+        })
+    }
+
+    /// Returns the revision of the layout which the device registers have.
+    pub const fn revision(&self) -> Revision {
+        match self {
+            Self::V1(_) => Revision::V1,
+            Self::V2(_) => Revision::V2,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{field, fields::ReadPure};
+    use core::ptr::NonNull;
+
+    #[repr(C)]
+    struct V2Registers {
+        id: ReadPure<u32>,
+        data: ReadPure<u32>,
+    }
+
+    #[test]
+    fn probe() {
+        let mut fake = [1u32, 42];
+        for (id, expected) in [(1, Some(Revision::V1)), (2, Some(Revision::V2)), (3, None)] {
+            fake[0] = id;
+            // SAFETY: `fake` is valid for both layouts.
+            let versioned = unsafe {
+                VersionedMmio::<[ReadPure<u32>; 2], V2Registers>::probe(
+                    UniqueMmioPointer::new(NonNull::from(&mut fake).cast::<ReadPure<u32>>()),
+                    |regs| match regs.read() {
+                        1 => Some(Revision::V1),
+                        2 => Some(Revision::V2),
+                        _ => None,
+                    },
+                )
+            };
+            assert_eq!(versioned.as_ref().map(VersionedMmio::revision), expected);
+            match versioned {
+                Some(VersionedMmio::V1(mut regs)) => assert_eq!(regs.get(1).unwrap().read(), 42),
+                Some(VersionedMmio::V2(mut regs)) => assert_eq!(field!(regs, data).read(), 42),
+                None => {}
+            }
+        }
+    }
+}
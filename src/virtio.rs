@@ -0,0 +1,257 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! The register layout of the virtio-mmio transport, from section 4.2.2 of the
+//! [virtio specification](https://docs.oasis-open.org/virtio/virtio/v1.3/virtio-v1.3.html), and
+//! helpers to probe for devices.
+//!
+//! Only the modern (version 2) layout is described. Legacy (version 1) devices use some of the
+//! reserved registers differently.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_mmio::{
+//!     UniqueMmioPointer, field,
+//!     virtio::{VirtioMmioRegisters, device_status, probe},
+//! };
+//!
+//! let mut regs: UniqueMmioPointer<VirtioMmioRegisters>;
+//! # let mut fake: VirtioMmioRegisters = zerocopy::FromZeros::new_zeroed();
+//! # fake.magic_value.0 = VirtioMmioRegisters::MAGIC;
+//! # fake.version.0 = 2;
+//! # fake.device_id.0 = 2;
+//! # regs = UniqueMmioPointer::from(&mut fake);
+//! let info = probe(&regs).unwrap();
+//! if info.device_id == 2 {
+//!     // It's a block device, so reset it and start initialisation.
+//!     field!(regs, status).write(0);
+//!     field!(regs, status).write(device_status::ACKNOWLEDGE | device_status::DRIVER);
+//! }
+//! ```
+
+use crate::{
+    SharedMmioPointer,
+    fields::{ReadPure, ReadPureWrite, WriteOnly},
+    register_map,
+};
+use core::fmt::{self, Display, Formatter};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+register_map! {
+    /// The registers of a virtio-mmio device, up to the start of the device-specific configuration
+    /// space at offset `0x100`.
+    #[derive(Debug, FromBytes, Immutable, IntoBytes, KnownLayout)]
+    #[repr(C)]
+    pub struct VirtioMmioRegisters {
+        /// The magic value `0x74726976` ("virt" in little-endian ASCII).
+        pub magic_value: ReadPure<u32>,
+        /// The version of the device, 2 for a modern device.
+        pub version: ReadPure<u32>,
+        /// The virtio device ID, or 0 if there is no device.
+        pub device_id: ReadPure<u32>,
+        /// The vendor ID of the device.
+        pub vendor_id: ReadPure<u32>,
+        /// The 32 feature bits selected by `device_features_sel`.
+        pub device_features: ReadPure<u32>,
+        /// Selects which 32 feature bits are returned by `device_features`.
+        pub device_features_sel: WriteOnly<u32>,
+        reserved_18: [u32; 2],
+        /// The 32 feature bits selected by `driver_features_sel` which the driver accepts.
+        pub driver_features: WriteOnly<u32>,
+        /// Selects which 32 feature bits are written by `driver_features`.
+        pub driver_features_sel: WriteOnly<u32>,
+        reserved_28: [u32; 2],
+        /// Selects the virtqueue which the queue registers refer to.
+        pub queue_sel: WriteOnly<u32>,
+        /// The maximum size of the selected queue.
+        pub queue_num_max: ReadPure<u32>,
+        /// The size of the selected queue.
+        pub queue_num: WriteOnly<u32>,
+        reserved_3c: [u32; 2],
+        /// Whether the selected queue is ready.
+        pub queue_ready: ReadPureWrite<u32>,
+        reserved_48: [u32; 2],
+        /// Notifies the device that a queue has new buffers. The value written is the queue index.
+        pub queue_notify: WriteOnly<u32>,
+        reserved_54: [u32; 3],
+        /// The reasons for a pending interrupt, a combination of [`interrupt_status`] bits.
+        pub interrupt_status: ReadPure<u32>,
+        /// Acknowledges the [`interrupt_status`] bits written.
+        pub interrupt_ack: WriteOnly<u32>,
+        reserved_68: [u32; 2],
+        /// The device status, a combination of [`device_status`] bits. Writing 0 resets the device.
+        pub status: ReadPureWrite<u32>,
+        reserved_74: [u32; 3],
+        /// The low 32 bits of the physical address of the selected queue's descriptor area.
+        pub queue_desc_low: WriteOnly<u32>,
+        /// The high 32 bits of the physical address of the selected queue's descriptor area.
+        pub queue_desc_high: WriteOnly<u32>,
+        reserved_88: [u32; 2],
+        /// The low 32 bits of the physical address of the selected queue's driver area.
+        pub queue_driver_low: WriteOnly<u32>,
+        /// The high 32 bits of the physical address of the selected queue's driver area.
+        pub queue_driver_high: WriteOnly<u32>,
+        reserved_98: [u32; 2],
+        /// The low 32 bits of the physical address of the selected queue's device area.
+        pub queue_device_low: WriteOnly<u32>,
+        /// The high 32 bits of the physical address of the selected queue's device area.
+        pub queue_device_high: WriteOnly<u32>,
+        reserved_a8: u32,
+        /// Selects the shared memory region which the shared memory registers refer to.
+        pub shm_sel: WriteOnly<u32>,
+        /// The low 32 bits of the length of the selected shared memory region.
+        pub shm_len_low: ReadPure<u32>,
+        /// The high 32 bits of the length of the selected shared memory region.
+        pub shm_len_high: ReadPure<u32>,
+        /// The low 32 bits of the base address of the selected shared memory region.
+        pub shm_base_low: ReadPure<u32>,
+        /// The high 32 bits of the base address of the selected shared memory region.
+        pub shm_base_high: ReadPure<u32>,
+        /// Writing 1 resets the selected queue, and it reads as 1 until the reset is complete.
+        pub queue_reset: ReadPureWrite<u32>,
+        reserved_c4: [u32; 14],
+        /// Changes whenever the device-specific configuration space changes, so that drivers can
+        /// tell if they read an inconsistent configuration.
+        pub config_generation: ReadPure<u32>,
+    }
+}
+
+impl VirtioMmioRegisters {
+    /// The value of the `magic_value` register of every virtio-mmio device.
+    pub const MAGIC: u32 = 0x7472_6976;
+}
+
+/// Bits of the virtio-mmio `status` register.
+pub mod device_status {
+    /// The guest has found the device.
+    pub const ACKNOWLEDGE: u32 = 1 << 0;
+    /// The guest knows how to drive the device.
+    pub const DRIVER: u32 = 1 << 1;
+    /// The driver is ready to drive the device.
+    pub const DRIVER_OK: u32 = 1 << 2;
+    /// The driver has acknowledged the features it understands.
+    pub const FEATURES_OK: u32 = 1 << 3;
+    /// The device has hit an error which it can't recover from without a reset.
+    pub const DEVICE_NEEDS_RESET: u32 = 1 << 6;
+    /// The driver has given up on the device.
+    pub const FAILED: u32 = 1 << 7;
+}
+
+/// Bits of the virtio-mmio `interrupt_status` and `interrupt_ack` registers.
+pub mod interrupt_status {
+    /// The device has used a buffer in at least one of its queues.
+    pub const USED_BUFFER: u32 = 1 << 0;
+    /// The device configuration has changed.
+    pub const CONFIG_CHANGE: u32 = 1 << 1;
+}
+
+/// The identity of a virtio-mmio device, found by [`probe`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DeviceInfo {
+    /// The virtio device ID, such as 1 for a network device or 2 for a block device.
+    pub device_id: u32,
+    /// The vendor ID of the device.
+    pub vendor_id: u32,
+}
+
+/// An error probing a virtio-mmio device.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProbeError {
+    /// The magic value register didn't have the expected value, so this isn't a virtio-mmio device.
+    BadMagic(u32),
+    /// The device has a version other than 2, such as a legacy version 1 device.
+    UnsupportedVersion(u32),
+    /// The transport is present, but there is no device behind it.
+    NoDevice,
+}
+
+impl Display for ProbeError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::BadMagic(magic) => write!(f, "Invalid virtio-mmio magic value {magic:#010x}"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "Unsupported virtio-mmio version {version}")
+            }
+            Self::NoDevice => write!(f, "No device behind virtio-mmio transport"),
+        }
+    }
+}
+
+impl core::error::Error for ProbeError {}
+
+/// Checks the magic value and version registers of the given virtio-mmio transport, and returns the
+/// device and vendor IDs of the device behind it if there is one.
+pub fn probe(regs: &SharedMmioPointer<VirtioMmioRegisters>) -> Result<DeviceInfo, ProbeError> {
+    let magic = crate::field_shared!(*regs, magic_value).read();
+    if magic != VirtioMmioRegisters::MAGIC {
+        return Err(ProbeError::BadMagic(magic));
+    }
+    let version = crate::field_shared!(*regs, version).read();
+    if version != 2 {
+        return Err(ProbeError::UnsupportedVersion(version));
+    }
+    let device_id = crate::field_shared!(*regs, device_id).read();
+    if device_id == 0 {
+        return Err(ProbeError::NoDevice);
+    }
+    Ok(DeviceInfo {
+        device_id,
+        vendor_id: crate::field_shared!(*regs, vendor_id).read(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::register_map::{Access, RegisterMap};
+    use core::mem::offset_of;
+    use zerocopy::FromZeros;
+
+    #[test]
+    fn layout() {
+        assert_eq!(size_of::<VirtioMmioRegisters>(), 0x100);
+        assert_eq!(offset_of!(VirtioMmioRegisters, queue_sel), 0x30);
+        assert_eq!(offset_of!(VirtioMmioRegisters, queue_ready), 0x44);
+        assert_eq!(offset_of!(VirtioMmioRegisters, queue_notify), 0x50);
+        assert_eq!(offset_of!(VirtioMmioRegisters, interrupt_status), 0x60);
+        assert_eq!(offset_of!(VirtioMmioRegisters, status), 0x70);
+        assert_eq!(offset_of!(VirtioMmioRegisters, queue_desc_low), 0x80);
+        assert_eq!(offset_of!(VirtioMmioRegisters, queue_driver_low), 0x90);
+        assert_eq!(offset_of!(VirtioMmioRegisters, queue_device_low), 0xa0);
+        assert_eq!(offset_of!(VirtioMmioRegisters, shm_sel), 0xac);
+        assert_eq!(offset_of!(VirtioMmioRegisters, queue_reset), 0xc0);
+        assert_eq!(offset_of!(VirtioMmioRegisters, config_generation), 0xfc);
+        let register = VirtioMmioRegisters::lookup(0x64).unwrap();
+        assert_eq!(register.name, "interrupt_ack");
+        assert_eq!(register.access, Access::WriteOnly);
+    }
+
+    #[test]
+    fn probe_device() {
+        let mut fake = VirtioMmioRegisters::new_zeroed();
+        let regs = SharedMmioPointer::from(&fake);
+        assert_eq!(probe(&regs), Err(ProbeError::BadMagic(0)));
+
+        fake.magic_value.0 = VirtioMmioRegisters::MAGIC;
+        fake.version.0 = 1;
+        let regs = SharedMmioPointer::from(&fake);
+        assert_eq!(probe(&regs), Err(ProbeError::UnsupportedVersion(1)));
+
+        fake.version.0 = 2;
+        let regs = SharedMmioPointer::from(&fake);
+        assert_eq!(probe(&regs), Err(ProbeError::NoDevice));
+
+        fake.device_id.0 = 1;
+        fake.vendor_id.0 = 0x554d_4551;
+        let regs = SharedMmioPointer::from(&fake);
+        assert_eq!(
+            probe(&regs),
+            Ok(DeviceInfo {
+                device_id: 1,
+                vendor_id: 0x554d_4551
+            })
+        );
+    }
+}
@@ -2,44 +2,226 @@
 // This project is dual-licensed under Apache 2.0 and MIT terms.
 // See LICENSE-APACHE and LICENSE-MIT for details.
 
+//! MMIO accesses using volatile reads and writes.
+//!
+//! On aarch64 this is also built for tests, as a reference to check the inline assembly backend
+//! against, but the methods on the pointer types are then provided by that backend instead.
+
+use crate::access_width;
+#[cfg(all(
+    any(not(target_arch = "aarch64"), miri),
+    not(feature = "custom-mmio"),
+    not(feature = "verification")
+))]
 use crate::{SharedMmioPointer, UniqueMmioPointer};
+use core::ptr::NonNull;
+use zerocopy::{FromBytes, Immutable, IntoBytes};
 
-impl<T> UniqueMmioPointer<'_, T> {
+#[cfg(all(
+    any(not(target_arch = "aarch64"), miri),
+    not(feature = "custom-mmio"),
+    not(feature = "verification")
+))]
+impl<T: FromBytes + IntoBytes> UniqueMmioPointer<'_, T> {
     /// Performs an MMIO read of the entire `T`.
     ///
+    /// If `T` is 1, 2, 4 or 8 bytes long and aligned to its size then this will be a single
+    /// operation. Otherwise it will be split into several, in order of increasing address, each as
+    /// wide as the alignment of `T` up to a maximum of 8 bytes.
+    ///
     /// Note that this takes `&mut self` rather than `&self` because an MMIO read may cause
     /// side-effects that change the state of the device.
     ///
     /// # Safety
     ///
     /// This field must be safe to perform an MMIO read from.
+    #[inline(always)]
     pub unsafe fn read_unsafe(&mut self) -> T {
         // SAFETY: self.regs is always a valid and unique pointer to MMIO address space.
-        unsafe { self.regs.read_volatile() }
+        let mut value = unsafe { mmio_read(self.regs) };
+        self.record_read(value.as_mut_bytes());
+        value
     }
+}
 
+#[cfg(all(
+    any(not(target_arch = "aarch64"), miri),
+    not(feature = "custom-mmio"),
+    not(feature = "verification")
+))]
+impl<T: Immutable + IntoBytes> UniqueMmioPointer<'_, T> {
     /// Performs an MMIO write of the entire `T`.
     ///
+    /// If `T` is 1, 2, 4 or 8 bytes long and aligned to its size then this will be a single
+    /// operation. Otherwise it will be split into several, in order of increasing address, each as
+    /// wide as the alignment of `T` up to a maximum of 8 bytes.
+    ///
     /// # Safety
     ///
     /// This field must be safe to perform an MMIO write to.
+    #[inline(always)]
     pub unsafe fn write_unsafe(&mut self, value: T) {
+        self.record_write(value.as_bytes());
         // SAFETY: self.regs is always a valid and unique pointer to MMIO address space.
-        unsafe {
-            self.regs.write_volatile(value);
-        }
+        unsafe { mmio_write(self.regs, value) }
     }
 }
 
-impl<T> SharedMmioPointer<'_, T> {
+#[cfg(all(
+    any(not(target_arch = "aarch64"), miri),
+    not(feature = "custom-mmio"),
+    not(feature = "verification")
+))]
+impl<T: FromBytes + IntoBytes> SharedMmioPointer<'_, T> {
     /// Performs an MMIO read of the entire `T`.
     ///
+    /// If `T` is 1, 2, 4 or 8 bytes long and aligned to its size then this will be a single
+    /// operation. Otherwise it will be split into several, in order of increasing address, each as
+    /// wide as the alignment of `T` up to a maximum of 8 bytes.
+    ///
     /// # Safety
     ///
     /// This field must be safe to perform an MMIO read from, and doing so must not cause any
     /// side-effects.
+    #[inline(always)]
     pub unsafe fn read_unsafe(&self) -> T {
-        // SAFETY: self.regs is always a valid and unique pointer to MMIO address space.
-        unsafe { self.regs.read_volatile() }
+        // SAFETY: self.regs is always a valid pointer to MMIO address space.
+        let mut value = unsafe { mmio_read(self.regs) };
+        self.record_read(value.as_mut_bytes());
+        value
+    }
+}
+
+/// Performs an MMIO read and returns the value.
+///
+/// # Safety
+///
+/// The pointer must be valid to perform an MMIO read from.
+#[inline(always)]
+pub(crate) unsafe fn mmio_read<T: FromBytes + IntoBytes>(ptr: NonNull<T>) -> T {
+    let mut value = T::new_zeroed();
+    // SAFETY: The caller guarantees that ptr is valid for MMIO reads, so each naturally aligned
+    // part of it is too.
+    unsafe {
+        match (size_of::<T>(), access_width::<T>()) {
+            (1, _) => read_part(ptr.cast::<u8>(), value.as_mut_bytes()),
+            (2, 2) => read_part(ptr.cast::<u16>(), value.as_mut_bytes()),
+            (4, 4) => read_part(ptr.cast::<u32>(), value.as_mut_bytes()),
+            (8, 8) => read_part(ptr.cast::<u64>(), value.as_mut_bytes()),
+            (_, 8) => read_parts::<u64>(ptr.cast(), value.as_mut_bytes()),
+            (_, 4) => read_parts::<u32>(ptr.cast(), value.as_mut_bytes()),
+            (_, 2) => read_parts::<u16>(ptr.cast(), value.as_mut_bytes()),
+            (_, _) => read_parts::<u8>(ptr.cast(), value.as_mut_bytes()),
+        }
+    }
+    value
+}
+
+/// Performs an MMIO write of the given value.
+///
+/// # Safety
+///
+/// The pointer must be valid to perform an MMIO write to.
+#[inline(always)]
+pub(crate) unsafe fn mmio_write<T: Immutable + IntoBytes>(ptr: NonNull<T>, value: T) {
+    // SAFETY: The caller guarantees that ptr is valid for MMIO writes, so each naturally aligned
+    // part of it is too.
+    unsafe {
+        match (size_of::<T>(), access_width::<T>()) {
+            (1, _) => write_part(ptr.cast::<u8>(), value.as_bytes()),
+            (2, 2) => write_part(ptr.cast::<u16>(), value.as_bytes()),
+            (4, 4) => write_part(ptr.cast::<u32>(), value.as_bytes()),
+            (8, 8) => write_part(ptr.cast::<u64>(), value.as_bytes()),
+            (_, 8) => write_parts::<u64>(ptr.cast(), value.as_bytes()),
+            (_, 4) => write_parts::<u32>(ptr.cast(), value.as_bytes()),
+            (_, 2) => write_parts::<u16>(ptr.cast(), value.as_bytes()),
+            (_, _) => write_parts::<u8>(ptr.cast(), value.as_bytes()),
+        }
+    }
+}
+
+/// Performs a single volatile read of a `U` into `bytes`, which must be the same size as `U`.
+///
+/// # Safety
+///
+/// `ptr` must be a valid, aligned pointer to MMIO address space.
+#[inline(always)]
+unsafe fn read_part<U: FromBytes + Immutable + IntoBytes>(ptr: NonNull<U>, bytes: &mut [u8]) {
+    // SAFETY: The caller guarantees that ptr is valid and aligned.
+    unsafe { ptr.read_volatile() }.write_to(bytes).unwrap();
+}
+
+/// Performs a single volatile write of a `U` from `bytes`, which must be the same size as `U`.
+///
+/// # Safety
+///
+/// `ptr` must be a valid, aligned pointer to MMIO address space.
+#[inline(always)]
+unsafe fn write_part<U: FromBytes + Immutable + IntoBytes>(ptr: NonNull<U>, bytes: &[u8]) {
+    // SAFETY: The caller guarantees that ptr is valid and aligned.
+    unsafe { ptr.write_volatile(U::read_from_bytes(bytes).unwrap()) };
+}
+
+/// Reads into `bytes` with a volatile read of each `U` in turn.
+///
+/// # Safety
+///
+/// `ptr` must be a pointer aligned for `U`, valid for MMIO reads spanning `bytes.len()` bytes,
+/// which must be a multiple of the size of `U`.
+unsafe fn read_parts<U: FromBytes + Immutable + IntoBytes>(ptr: NonNull<U>, bytes: &mut [u8]) {
+    for (i, chunk) in bytes.chunks_exact_mut(size_of::<U>()).enumerate() {
+        // SAFETY: The caller guarantees that the whole range is valid, and we stay within it.
+        unsafe { read_part(ptr.add(i), chunk) };
+    }
+}
+
+/// Writes `bytes` with a volatile write of each `U` in turn.
+///
+/// # Safety
+///
+/// `ptr` must be a pointer aligned for `U`, valid for MMIO writes spanning `bytes.len()` bytes,
+/// which must be a multiple of the size of `U`.
+unsafe fn write_parts<U: FromBytes + Immutable + IntoBytes>(ptr: NonNull<U>, bytes: &[u8]) {
+    for (i, chunk) in bytes.chunks_exact(size_of::<U>()).enumerate() {
+        // SAFETY: The caller guarantees that the whole range is valid, and we stay within it.
+        unsafe { write_part(ptr.add(i), chunk) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UniqueMmioPointer;
+
+    #[repr(C, align(4))]
+    #[derive(Debug, Eq, FromBytes, Immutable, IntoBytes, PartialEq)]
+    struct Registers {
+        a: u16,
+        b: u16,
+        c: u32,
+    }
+
+    #[test]
+    fn struct_access() {
+        let mut fake = Registers { a: 1, b: 2, c: 3 };
+        let mut regs = UniqueMmioPointer::from(&mut fake);
+        // SAFETY: The fake registers are ordinary memory.
+        unsafe {
+            assert_eq!(regs.read_unsafe(), Registers { a: 1, b: 2, c: 3 });
+            regs.write_unsafe(Registers { a: 4, b: 5, c: 6 });
+        }
+        assert_eq!(fake, Registers { a: 4, b: 5, c: 6 });
+    }
+
+    #[test]
+    fn byte_array_access() {
+        let mut fake = [1u8, 2, 3];
+        let mut regs = UniqueMmioPointer::from(&mut fake);
+        // SAFETY: The fake registers are ordinary memory.
+        unsafe {
+            assert_eq!(regs.read_unsafe(), [1, 2, 3]);
+            regs.write_unsafe([4, 5, 6]);
+        }
+        assert_eq!(fake, [4, 5, 6]);
     }
 }
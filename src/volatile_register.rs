@@ -0,0 +1,141 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Drop-in replacements for the cell types of `volatile-register` and `vcell`, to migrate existing
+//! register block definitions such as those generated for Cortex-M PACs.
+//!
+//! The cell types of those crates are all transparent wrappers around the register value, as are
+//! the replacements in this module, so a register block can be migrated by changing
+//! `use volatile_register::{RO, RW, WO};` to `use safe_mmio::volatile_register::{RO, RW, WO};`
+//! without changing its layout. Each type maps to the field wrapper with the most conservative
+//! safe-mmio semantics for it: reads through [`RO`] and [`RW`] may have side-effects, so they need
+//! a unique pointer. Once a driver has been migrated, the fields can be changed to more specific
+//! wrappers such as [`ReadPure`](crate::fields::ReadPure) where appropriate.
+//!
+//! Writes through [`RW`] and [`WO`] are safe, whereas they are `unsafe` with `volatile-register`.
+//! [`from_pac`] is `unsafe` instead, and its caller must check that every write to the register
+//! block is safe.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_mmio::{
+//!     UniqueMmioPointer, field,
+//!     fields::{ReadOnly, ReadWrite},
+//!     volatile_register::{self, RO, RW},
+//! };
+//!
+//! // Previously used `volatile_register::{RO, RW}`.
+//! #[repr(C)]
+//! pub struct RegisterBlock {
+//!     pub status: RO<u32>,
+//!     pub data: RW<u32>,
+//! }
+//!
+//! # let mut fake = RegisterBlock { status: ReadOnly(1), data: ReadWrite(0) };
+//! let pointer: *const RegisterBlock;
+//! # pointer = &raw mut fake;
+//! // SAFETY: `pointer` is the address of the device's registers, which are mapped as device memory,
+//! // nothing else accesses them, and reading or writing them has no side-effects which could
+//! // violate memory safety.
+//! let mut regs: UniqueMmioPointer<RegisterBlock> =
+//!     unsafe { volatile_register::from_pac(pointer) };
+//! if field!(regs, status).read() & 1 != 0 {
+//!     field!(regs, data).write(42);
+//! }
+//! ```
+
+use crate::{
+    UniqueMmioPointer,
+    fields::{ReadOnly, ReadWrite, WriteOnly},
+};
+use core::ptr::NonNull;
+
+/// A read-only register, in place of `volatile_register::RO`.
+pub type RO<T> = ReadOnly<T>;
+
+/// A read-write register, in place of `volatile_register::RW`.
+pub type RW<T> = ReadWrite<T>;
+
+/// A write-only register, in place of `volatile_register::WO`.
+pub type WO<T> = WriteOnly<T>;
+
+/// A register with no known restrictions, in place of `vcell::VolatileCell`.
+pub type VolatileCell<T> = ReadWrite<T>;
+
+/// Creates a `UniqueMmioPointer` to a register block from the raw pointer which a PAC provides,
+/// such as `pac::UART0::PTR`.
+///
+/// `T` may be a migrated copy of the PAC's register block type `P`, using the types from this
+/// module in place of those from `volatile-register`.
+///
+/// # Safety
+///
+/// `pointer` must satisfy the requirements of [`UniqueMmioPointer::new`] for `T`, including that
+/// nothing else accesses the registers while the returned pointer exists, e.g. through the PAC.
+///
+/// # Panics
+///
+/// Panics if `pointer` is null, or if `T` and `P` have different sizes or `T` needs a greater
+/// alignment than `P`.
+pub unsafe fn from_pac<'a, T, P>(pointer: *const P) -> UniqueMmioPointer<'a, T> {
+    assert!(
+        size_of::<T>() == size_of::<P>() && align_of::<T>() <= align_of::<P>(),
+        "Register block doesn't have the same layout as the PAC's"
+    );
+    let regs = NonNull::new(pointer.cast_mut())
+        .expect("PAC register block pointer is null")
+        .cast();
+    // SAFETY: Our caller promised that the pointer satisfies the requirements of `new`, and we
+    // checked that `T` fits in the same place as `P`.
+    unsafe { UniqueMmioPointer::new(regs) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field;
+
+    /// A stand-in for the cell type of `volatile-register`, which has the same layout.
+    #[repr(transparent)]
+    struct PacCell<T>(T);
+
+    #[repr(C)]
+    struct PacRegisterBlock {
+        status: PacCell<u32>,
+        data: PacCell<u32>,
+        command: PacCell<u16>,
+    }
+
+    #[repr(C)]
+    struct RegisterBlock {
+        status: RO<u32>,
+        data: RW<u32>,
+        command: WO<u16>,
+    }
+
+    #[test]
+    fn migrated_register_block() {
+        let mut fake = PacRegisterBlock {
+            status: PacCell(1),
+            data: PacCell(0),
+            command: PacCell(0),
+        };
+        // SAFETY: `fake` is valid, and only accessed through this pointer while it exists.
+        let mut regs = unsafe { from_pac::<RegisterBlock, _>(&raw mut fake) };
+        assert_eq!(field!(regs, status).read(), 1);
+        field!(regs, data).write(42);
+        field!(regs, command).write(3);
+        assert_eq!(fake.data.0, 42);
+        assert_eq!(fake.command.0, 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Register block doesn't have the same layout as the PAC's")]
+    fn different_size() {
+        let fake = [PacCell(0u16)];
+        // SAFETY: The pointer is rejected before it can be used.
+        unsafe { from_pac::<RegisterBlock, _>(&raw const fake) };
+    }
+}
@@ -0,0 +1,336 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Non-temporal stores for bulk writes to write-combining mappings, enabled by the `wc` feature.
+//!
+//! Frame buffers and large mailbox or descriptor windows are often mapped write-combining (Normal
+//! Non-cacheable on aarch64). Writing them with non-temporal stores (`stnp` on aarch64, `movnti`
+//! on x86-64) avoids polluting the cache, and lets the CPU merge the stores into larger bus
+//! transactions rather than serialising each one.
+//!
+//! Non-temporal stores are weakly ordered, so [`nontemporal_fence`] must be called after them and
+//! before anything which tells the device that the data is ready, such as writing a doorbell
+//! register.
+//!
+//! On other architectures, or when using a custom MMIO backend, Miri or the verification backend,
+//! the `write_nontemporal` methods fall back to ordinary MMIO writes of each element in turn.
+//!
+//! # Example
+//!
+//! ```
+//! use safe_mmio::{
+//!     UniqueMmioPointer,
+//!     fields::WriteOnly,
+//!     wc::nontemporal_fence,
+//! };
+//!
+//! let mut frame_buffer: UniqueMmioPointer<[WriteOnly<u32>]>;
+//! # let mut fake = [const { WriteOnly(0) }; 4];
+//! # frame_buffer = UniqueMmioPointer::from(fake.as_mut_slice());
+//! frame_buffer.write_nontemporal(&[0x00ff_0000, 0x0000_ff00, 0x0000_00ff, 0x00ff_ffff]);
+//! nontemporal_fence();
+//! ```
+
+use crate::{
+    UniqueMmioPointer,
+    access::AtomicAccess,
+    fields::{ReadPureWrite, ReadWrite, WriteOnly},
+};
+
+/// A type which can be written with non-temporal stores.
+///
+/// This is implemented for 32-bit integers, and 64-bit integers on 64-bit targets.
+///
+/// This trait is sealed, so can't be implemented outside this crate.
+pub trait NonTemporal: sealed::Sealed + AtomicAccess {}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_nontemporal {
+    ($($t:ty),*) => {
+        $(
+            impl sealed::Sealed for $t {}
+            impl NonTemporal for $t {}
+        )*
+    };
+}
+
+impl_nontemporal!(u32, i32);
+#[cfg(target_pointer_width = "64")]
+impl_nontemporal!(u64, i64);
+
+/// Waits for all previous non-temporal stores to complete before any subsequent stores.
+///
+/// This must be called after a sequence of `write_nontemporal` calls and before notifying the
+/// device that the data is ready.
+#[inline(always)]
+pub fn nontemporal_fence() {
+    #[cfg(all(target_arch = "aarch64", not(miri)))]
+    // SAFETY: A barrier has no effect on memory safety.
+    unsafe {
+        core::arch::asm!("dmb oshst", options(nostack, preserves_flags));
+    }
+    #[cfg(all(target_arch = "x86_64", not(miri)))]
+    // SAFETY: A barrier has no effect on memory safety.
+    unsafe {
+        core::arch::asm!("sfence", options(nostack, preserves_flags));
+    }
+    #[cfg(any(not(any(target_arch = "aarch64", target_arch = "x86_64")), miri))]
+    core::sync::atomic::fence(core::sync::atomic::Ordering::SeqCst);
+}
+
+macro_rules! impl_write_nontemporal {
+    ($wrapper:ident) => {
+        impl<T: NonTemporal> UniqueMmioPointer<'_, [$wrapper<T>]> {
+            /// Writes each element of the slice in index order with non-temporal stores.
+            ///
+            /// The stores are weakly ordered, so call [`nontemporal_fence`] before notifying the
+            /// device that the data has been written.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `values` has a different length to this slice.
+            pub fn write_nontemporal(&mut self, values: &[T]) {
+                assert_eq!(
+                    values.len(),
+                    self.len(),
+                    "source slice length does not match destination slice length"
+                );
+                // SAFETY: `self` is a valid and unique pointer to MMIO address space which is safe
+                // to write `values.len()` `T`s to, as `$wrapper` allows writes of any `T`.
+                unsafe {
+                    write_nontemporal(self, values);
+                }
+            }
+        }
+    };
+}
+
+impl_write_nontemporal!(WriteOnly);
+impl_write_nontemporal!(ReadWrite);
+impl_write_nontemporal!(ReadPureWrite);
+
+/// Writes `values` to the slice with non-temporal stores.
+///
+/// # Safety
+///
+/// `regs` must be at least as long as `values`, and it must be safe to perform an MMIO write of any
+/// `T` to each element of it.
+#[cfg(all(
+    any(target_arch = "aarch64", target_arch = "x86_64"),
+    not(miri),
+    not(feature = "custom-mmio"),
+    not(feature = "verification")
+))]
+#[inline(always)]
+unsafe fn write_nontemporal<W, T: NonTemporal>(regs: &mut UniqueMmioPointer<[W]>, values: &[T]) {
+    use zerocopy::{FromBytes, IntoBytes};
+
+    let ptr = regs.ptr_mut().cast::<T>();
+    for (i, value) in values.iter().enumerate() {
+        regs.record_write_at(i * size_of::<T>(), value.as_bytes());
+    }
+    // SAFETY: Our caller guarantees that `ptr` is valid for writes of `values.len()` `T`s.
+    unsafe {
+        match size_of::<T>() {
+            4 => arch::store_u32s(
+                ptr.cast(),
+                <[u32]>::ref_from_bytes(values.as_bytes()).unwrap(),
+            ),
+            8 => arch::store_u64s(
+                ptr.cast(),
+                <[u64]>::ref_from_bytes(values.as_bytes()).unwrap(),
+            ),
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Writes `values` to the slice with ordinary MMIO writes.
+///
+/// # Safety
+///
+/// `regs` must be at least as long as `values`, and it must be safe to perform an MMIO write of any
+/// `T` to each element of it.
+#[cfg(not(all(
+    any(target_arch = "aarch64", target_arch = "x86_64"),
+    not(miri),
+    not(feature = "custom-mmio"),
+    not(feature = "verification")
+)))]
+#[inline(always)]
+unsafe fn write_nontemporal<W, T: NonTemporal>(regs: &mut UniqueMmioPointer<[W]>, values: &[T]) {
+    let ptr = regs.ptr_nonnull().cast::<T>();
+    for (i, value) in values.iter().enumerate() {
+        // SAFETY: Our caller guarantees that `regs` is at least as long as `values`, and that
+        // writing any `T` to each element is safe.
+        unsafe {
+            regs.child(ptr.add(i)).write_unsafe(*value);
+        }
+    }
+}
+
+#[cfg(all(
+    target_arch = "aarch64",
+    not(miri),
+    not(feature = "custom-mmio"),
+    not(feature = "verification")
+))]
+mod arch {
+    use core::arch::asm;
+
+    /// Stores `values` to `ptr` with `stnp`, and `str` for any odd element at the end.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be aligned and valid for MMIO writes of `values.len()` elements.
+    #[inline(always)]
+    pub unsafe fn store_u32s(ptr: *mut u32, values: &[u32]) {
+        let mut pairs = values.chunks_exact(2);
+        for (i, pair) in pairs.by_ref().enumerate() {
+            // SAFETY: Our caller guarantees that `ptr` is valid for the whole slice.
+            unsafe {
+                asm!(
+                    "stnp {a:w}, {b:w}, [{ptr}]",
+                    a = in(reg) pair[0],
+                    b = in(reg) pair[1],
+                    ptr = in(reg) ptr.add(i * 2),
+                    options(nostack, preserves_flags),
+                );
+            }
+        }
+        if let [last] = pairs.remainder() {
+            // SAFETY: Our caller guarantees that `ptr` is valid for the whole slice.
+            unsafe {
+                asm!(
+                    "str {value:w}, [{ptr}]",
+                    value = in(reg) *last,
+                    ptr = in(reg) ptr.add(values.len() - 1),
+                    options(nostack, preserves_flags),
+                );
+            }
+        }
+    }
+
+    /// Stores `values` to `ptr` with `stnp`, and `str` for any odd element at the end.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be aligned and valid for MMIO writes of `values.len()` elements.
+    #[inline(always)]
+    pub unsafe fn store_u64s(ptr: *mut u64, values: &[u64]) {
+        let mut pairs = values.chunks_exact(2);
+        for (i, pair) in pairs.by_ref().enumerate() {
+            // SAFETY: Our caller guarantees that `ptr` is valid for the whole slice.
+            unsafe {
+                asm!(
+                    "stnp {a:x}, {b:x}, [{ptr}]",
+                    a = in(reg) pair[0],
+                    b = in(reg) pair[1],
+                    ptr = in(reg) ptr.add(i * 2),
+                    options(nostack, preserves_flags),
+                );
+            }
+        }
+        if let [last] = pairs.remainder() {
+            // SAFETY: Our caller guarantees that `ptr` is valid for the whole slice.
+            unsafe {
+                asm!(
+                    "str {value:x}, [{ptr}]",
+                    value = in(reg) *last,
+                    ptr = in(reg) ptr.add(values.len() - 1),
+                    options(nostack, preserves_flags),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(all(
+    target_arch = "x86_64",
+    not(miri),
+    not(feature = "custom-mmio"),
+    not(feature = "verification")
+))]
+mod arch {
+    use core::arch::asm;
+
+    /// Stores `values` to `ptr` with `movnti`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be aligned and valid for MMIO writes of `values.len()` elements.
+    #[inline(always)]
+    pub unsafe fn store_u32s(ptr: *mut u32, values: &[u32]) {
+        for (i, value) in values.iter().enumerate() {
+            // SAFETY: Our caller guarantees that `ptr` is valid for the whole slice.
+            unsafe {
+                asm!(
+                    "movnti [{ptr}], {value:e}",
+                    value = in(reg) *value,
+                    ptr = in(reg) ptr.add(i),
+                    options(nostack, preserves_flags),
+                );
+            }
+        }
+    }
+
+    /// Stores `values` to `ptr` with `movnti`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be aligned and valid for MMIO writes of `values.len()` elements.
+    #[inline(always)]
+    pub unsafe fn store_u64s(ptr: *mut u64, values: &[u64]) {
+        for (i, value) in values.iter().enumerate() {
+            // SAFETY: Our caller guarantees that `ptr` is valid for the whole slice.
+            unsafe {
+                asm!(
+                    "movnti [{ptr}], {value:r}",
+                    value = in(reg) *value,
+                    ptr = in(reg) ptr.add(i),
+                    options(nostack, preserves_flags),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_nontemporal_u32() {
+        let mut fake = [const { WriteOnly(0u32) }; 5];
+        {
+            let mut regs = UniqueMmioPointer::from(fake.as_mut_slice());
+            regs.write_nontemporal(&[1, 2, 3, 4, 5]);
+            nontemporal_fence();
+        }
+        assert_eq!(fake.map(|register| register.0), [1, 2, 3, 4, 5]);
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn write_nontemporal_u64() {
+        let mut fake = [const { ReadWrite(0u64) }; 3];
+        {
+            let mut regs = UniqueMmioPointer::from(fake.as_mut_slice());
+            regs.write_nontemporal(&[1 << 40, 2, 3]);
+            nontemporal_fence();
+        }
+        assert_eq!(fake.map(|register| register.0), [1 << 40, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn write_nontemporal_wrong_length() {
+        let mut fake = [const { WriteOnly(0u32) }; 2];
+        let mut regs = UniqueMmioPointer::from(fake.as_mut_slice());
+        regs.write_nontemporal(&[1, 2, 3]);
+    }
+}
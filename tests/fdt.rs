@@ -0,0 +1,153 @@
+// Copyright 2026 The safe-mmio Authors.
+// This project is dual-licensed under Apache 2.0 and MIT terms.
+// See LICENSE-APACHE and LICENSE-MIT for details.
+
+//! Tests for constructing `PhysicalInstance`s from a devicetree.
+
+#![cfg(feature = "fdt")]
+
+use fdt::Fdt;
+use safe_mmio::fdt::{FdtRegError, physical_instance_from_reg, reg_entry};
+
+const FDT_BEGIN_NODE: u32 = 1;
+const FDT_END_NODE: u32 = 2;
+const FDT_PROP: u32 = 3;
+const FDT_END: u32 = 9;
+
+/// A minimal flattened devicetree builder, just enough for these tests.
+#[derive(Default)]
+struct FdtBuilder {
+    structs: Vec<u8>,
+    strings: Vec<u8>,
+}
+
+impl FdtBuilder {
+    fn u32(&mut self, value: u32) {
+        self.structs.extend_from_slice(&value.to_be_bytes());
+    }
+
+    fn pad(&mut self) {
+        while self.structs.len() % 4 != 0 {
+            self.structs.push(0);
+        }
+    }
+
+    fn begin_node(&mut self, name: &str) -> &mut Self {
+        self.u32(FDT_BEGIN_NODE);
+        self.structs.extend_from_slice(name.as_bytes());
+        self.structs.push(0);
+        self.pad();
+        self
+    }
+
+    fn end_node(&mut self) -> &mut Self {
+        self.u32(FDT_END_NODE);
+        self
+    }
+
+    fn prop(&mut self, name: &str, value: &[u8]) -> &mut Self {
+        let name_offset = self.strings.len() as u32;
+        self.strings.extend_from_slice(name.as_bytes());
+        self.strings.push(0);
+        self.u32(FDT_PROP);
+        self.u32(value.len() as u32);
+        self.u32(name_offset);
+        self.structs.extend_from_slice(value);
+        self.pad();
+        self
+    }
+
+    fn prop_cells(&mut self, name: &str, cells: &[u32]) -> &mut Self {
+        let value: Vec<u8> = cells.iter().flat_map(|cell| cell.to_be_bytes()).collect();
+        self.prop(name, &value)
+    }
+
+    fn build(&mut self) -> Vec<u8> {
+        self.u32(FDT_END);
+        let header_size = 40;
+        let reserve_size = 16;
+        let off_dt_struct = header_size + reserve_size;
+        let off_dt_strings = off_dt_struct + self.structs.len();
+        let total_size = off_dt_strings + self.strings.len();
+        let header = [
+            0xd00d_feed,
+            total_size as u32,
+            off_dt_struct as u32,
+            off_dt_strings as u32,
+            header_size as u32,
+            17,
+            16,
+            0,
+            self.strings.len() as u32,
+            self.structs.len() as u32,
+        ];
+        let mut fdt: Vec<u8> = header.iter().flat_map(|word| word.to_be_bytes()).collect();
+        fdt.extend_from_slice(&[0; 16]);
+        fdt.extend_from_slice(&self.structs);
+        fdt.extend_from_slice(&self.strings);
+        fdt
+    }
+}
+
+fn test_fdt() -> Vec<u8> {
+    FdtBuilder::default()
+        .begin_node("")
+        .prop_cells("#address-cells", &[2])
+        .prop_cells("#size-cells", &[1])
+        .begin_node("uart@9000000")
+        .prop_cells("reg", &[0, 0x900_0000, 0x1000, 0, 0x900_2000, 0x10])
+        .end_node()
+        .begin_node("noreg")
+        .end_node()
+        .begin_node("high@1000000000")
+        .prop_cells("reg", &[0x10, 0, 0x1000])
+        .end_node()
+        .end_node()
+        .build()
+}
+
+#[test]
+fn reg_entries() {
+    let data = test_fdt();
+    let fdt = Fdt::new(&data).unwrap();
+    let uart = fdt.find_node("/uart@9000000").unwrap();
+
+    assert_eq!(reg_entry(uart, 0), Ok((0x900_0000, 0x1000)));
+    assert_eq!(reg_entry(uart, 1), Ok((0x900_2000, 0x10)));
+    assert_eq!(reg_entry(uart, 2), Err(FdtRegError::IndexOutOfRange));
+    assert_eq!(
+        reg_entry(fdt.find_node("/noreg").unwrap(), 0),
+        Err(FdtRegError::NoReg)
+    );
+
+    let high = reg_entry(fdt.find_node("/high@1000000000").unwrap(), 0);
+    if cfg!(target_pointer_width = "64") {
+        assert_eq!(high, Ok((0x10_0000_0000, 0x1000)));
+    } else {
+        assert_eq!(high, Err(FdtRegError::TooLarge));
+    }
+}
+
+#[test]
+fn physical_instance() {
+    let data = test_fdt();
+    let fdt = Fdt::new(&data).unwrap();
+    let uart = fdt.find_node("/uart@9000000").unwrap();
+
+    // SAFETY: The instances are never used to access any device.
+    unsafe {
+        let instance = physical_instance_from_reg::<[u32; 4]>(uart, 0).unwrap();
+        assert_eq!(instance.pa(), 0x900_0000);
+
+        let instance = physical_instance_from_reg::<[u32; 4]>(uart, 1).unwrap();
+        assert_eq!(instance.pa(), 0x900_2000);
+
+        assert_eq!(
+            physical_instance_from_reg::<[u32; 5]>(uart, 1),
+            Err(FdtRegError::TooSmall {
+                expected: 20,
+                actual: 0x10
+            })
+        );
+    }
+}